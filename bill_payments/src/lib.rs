@@ -1,11 +1,12 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 mod events;
 use events::{EventCategory, EventPriority, RemitwiseEvents};
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, Address, Bytes, Env, Map, String, Symbol, Vec,
 };
 
 // Storage TTL constants
@@ -23,8 +24,14 @@ pub const MAX_PAGE_LIMIT: u32 = 50;
 pub struct Bill {
     pub id: u32,
     pub owner: Address,
+    /// Address that receives the payment
+    pub payee: Address,
     pub name: String,
+    /// e.g. `rent`, `utilities`, `school`, `phone`
+    pub category: Symbol,
     pub amount: i128,
+    /// Running total paid towards `amount`; equals `amount` once paid
+    pub amount_paid: i128,
     pub due_date: u64,
     pub recurring: bool,
     pub frequency_days: u32,
@@ -32,11 +39,23 @@ pub struct Bill {
     pub created_at: u64,
     pub paid_at: Option<u64>,
     pub schedule_id: Option<u32>,
+    pub status: BillStatus,
     /// Intended currency/asset for this bill (e.g. "XLM", "USDC", "NGN").
     /// Defaults to "XLM" for entries created before this field was introduced.
     pub currency: String,
 }
 
+/// Owner-set state of an unpaid bill, alongside the `paid` flag.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillStatus {
+    Open,
+    /// Frozen from auto-pay and left out of overdue counts, with the reason
+    Disputed(String),
+    /// Withdrawn by the owner and no longer payable, with the reason
+    Cancelled(String),
+}
+
 /// Paginated result for bill queries
 #[contracttype]
 #[derive(Clone)]
@@ -60,6 +79,12 @@ pub mod pause_functions {
 
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+/// Longest grace period an owner may set before bills count as overdue
+pub const MAX_GRACE_DAYS: u32 = 30;
+/// Fixed-point scale of `PaymentConversion::rate`
+pub const RATE_SCALE: i128 = 10_000_000;
+/// Runs kept per standing order; older ones are dropped
+pub const MAX_ORDER_RUNS: u32 = 50;
 const STORAGE_UNPAID_TOTALS: Symbol = symbol_short!("UNPD_TOT");
 
 #[contracterror]
@@ -77,6 +102,18 @@ pub enum Error {
     BatchTooLarge = 9,
     BatchValidationFailed = 10,
     InvalidLimit = 11,
+    InsufficientBalance = 12,
+    PayeeNotRegistered = 13,
+    InvalidSplit = 14,
+    BillIsSplit = 15,
+    BillCancelled = 16,
+    InvalidStatus = 17,
+    PaymentNotDue = 18,
+    PastDueDate = 19,
+    ConversionUnavailable = 20,
+    SlippageExceeded = 21,
+    InvalidPlan = 22,
+    TokenMismatch = 23,
 }
 
 #[contracttype]
@@ -84,7 +121,9 @@ pub enum Error {
 pub struct ArchivedBill {
     pub id: u32,
     pub owner: Address,
+    pub payee: Address,
     pub name: String,
+    pub category: Symbol,
     pub amount: i128,
     pub paid_at: u64,
     pub archived_at: u64,
@@ -92,6 +131,26 @@ pub struct ArchivedBill {
     pub currency: String,
 }
 
+/// Compact record kept per owner once a paid bill's full record is pruned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillRecord {
+    pub id: u32,
+    pub amount: i128,
+    pub paid_at: u64,
+    pub payee: Address,
+}
+
+/// One line of a payment statement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatementEntry {
+    pub bill_id: u32,
+    pub payee: Address,
+    pub amount: i128,
+    pub paid_at: u64,
+}
+
 /// Paginated result for archived bill queries
 #[contracttype]
 #[derive(Clone)]
@@ -102,6 +161,188 @@ pub struct ArchivedBillPage {
     pub count: u32,
 }
 
+/// A payee verified by the admin or a family head.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payee {
+    pub address: Address,
+    pub name: String,
+    /// e.g. `utility`, `school`, `rent`
+    pub category: Symbol,
+    pub verified_by: Address,
+    pub verified_at: u64,
+}
+
+/// One bill in a `create_bills` import; fields match `create_bill`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BillInput {
+    pub name: String,
+    pub payee: Address,
+    pub amount: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    /// Blank defaults to "XLM"
+    pub currency: String,
+    pub category: Symbol,
+}
+
+/// Auto-pay settings for a single bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoPay {
+    /// Token paid from the owner's funded balance
+    pub token: Address,
+    /// Bills above this amount are skipped instead of paid
+    pub max_amount: i128,
+}
+
+/// One member's share of a split bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillShare {
+    pub member: Address,
+    pub amount: i128,
+    pub paid: bool,
+    pub paid_at: Option<u64>,
+}
+
+/// Aggregate totals over one owner's bills. Cancelled bills are left out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerBillTotals {
+    pub bill_count: u32,
+    pub paid_count: u32,
+    pub unpaid_count: u32,
+    pub total_amount: i128,
+    pub paid_amount: i128,
+    pub unpaid_amount: i128,
+}
+
+/// Proof that a bill was paid, kept per bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub bill_id: u32,
+    pub payer: Address,
+    pub payee: Address,
+    pub amount: i128,
+    /// Token moved on-chain, or `None` for a bill recorded as paid elsewhere
+    pub token: Option<Address>,
+    pub paid_at: u64,
+    /// Ledger the payment was recorded in
+    pub ledger_sequence: u32,
+    /// Hash of the payee's own receipt, attached by the payee
+    pub external_hash: Option<Bytes>,
+}
+
+/// A future-dated payment with its funds reserved from the owner's balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    pub bill_id: u32,
+    pub owner: Address,
+    pub token: Address,
+    /// Reserved amount, taken out of the funded balance when scheduled
+    pub amount: i128,
+    /// Part of `amount` taken from the earmarked balance
+    pub earmarked: i128,
+    pub execute_at: u64,
+}
+
+/// One dated installment of a bill's payment plan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Installment {
+    pub amount: i128,
+    pub due_date: u64,
+    pub paid: bool,
+    pub paid_at: Option<u64>,
+}
+
+/// Progress of a bill's payment plan, as read by the reporting contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentPlanStatus {
+    pub bill_id: u32,
+    pub installments_paid: u32,
+    pub installments_total: u32,
+    /// No installment is overdue, allowing for the owner's grace period
+    pub on_schedule: bool,
+}
+
+/// A recurring payment that is not a bill, e.g. an allowance to an elder
+/// relative or a tithe, paid from the owner's funded balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingOrder {
+    pub id: u32,
+    pub owner: Address,
+    pub payee: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub interval_days: u32,
+    pub next_run: u64,
+    /// No run is made after this time
+    pub end_date: u64,
+    /// False once cancelled or past `end_date`
+    pub active: bool,
+}
+
+/// One executed run of a standing order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingOrderRun {
+    pub amount: i128,
+    /// The run's scheduled time
+    pub due_at: u64,
+    pub executed_at: u64,
+}
+
+/// Who is alerted when an owner's bills stay overdue.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscalationRule {
+    /// e.g. the family wallet's head
+    pub head: Address,
+    /// Days past the overdue point before a bill is escalated
+    pub after_days: u32,
+    /// Whether the head may pay escalated bills on the owner's behalf
+    pub head_can_pay: bool,
+}
+
+/// Estimated and actual amount of one occurrence of a variable bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmountEstimate {
+    pub estimated: i128,
+    /// Set once the owner or payee posts the billed amount
+    pub actual: Option<i128>,
+    pub posted_by: Option<Address>,
+    pub posted_at: Option<u64>,
+}
+
+/// Estimate-vs-actual totals over an owner's variable bills with a posted
+/// actual amount.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EstimateSummary {
+    pub bill_count: u32,
+    pub estimated_total: i128,
+    pub actual_total: i128,
+}
+
+/// Second authorization an owner requires for large payments.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRule {
+    /// e.g. the family head or the remitter
+    pub approver: Address,
+    /// Payments above this amount need the approver's authorization too
+    pub threshold: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct StorageStats {
@@ -112,6 +353,36 @@ pub struct StorageStats {
     pub last_updated: u64,
 }
 
+/// Conversion behind a bill paid in another asset.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentConversion {
+    pub bill_id: u32,
+    pub source_token: Address,
+    pub target_token: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    /// `amount_out` per unit of `amount_in`, scaled by `RATE_SCALE`
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+// Client trait for the path-payment / AMM router used for cross-asset payments
+
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouterTrait {
+    fn quote(env: Env, token_in: Address, token_out: Address, amount_in: i128) -> i128;
+    fn swap(
+        env: Env,
+        sender: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
 #[contract]
 pub struct BillPayments;
 
@@ -347,6 +618,172 @@ impl BillPayments {
         Ok(())
     }
 
+    // -----------------------------------------------------------------------
+    // Payee registry
+    // -----------------------------------------------------------------------
+
+    /// Allow or revoke a verifier (e.g. a family head) who can register
+    /// payees alongside the upgrade admin.
+    pub fn set_payee_verifier(
+        env: Env,
+        caller: Address,
+        verifier: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+        let mut verifiers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("VERIFIERS"))
+            .unwrap_or_else(|| Map::new(&env));
+        if allowed {
+            verifiers.set(verifier, true);
+        } else {
+            verifiers.remove(verifier);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("VERIFIERS"), &verifiers);
+        Ok(())
+    }
+
+    /// When required, bills can only be created for (and paid to) registered
+    /// payees. Upgrade admin only.
+    pub fn set_payee_registry_required(
+        env: Env,
+        caller: Address,
+        required: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PAYEE_REQ"), &required);
+        Ok(())
+    }
+
+    pub fn is_payee_registry_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PAYEE_REQ"))
+            .unwrap_or(false)
+    }
+
+    /// Register or update a verified payee. Caller must be the upgrade admin
+    /// or an approved verifier.
+    pub fn register_payee(
+        env: Env,
+        caller: Address,
+        address: Address,
+        name: String,
+        category: Symbol,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_verifier(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut payees = Self::load_payees(&env);
+        payees.set(
+            address.clone(),
+            Payee {
+                address: address.clone(),
+                name,
+                category: category.clone(),
+                verified_by: caller,
+                verified_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PAYEES"), &payees);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("payee_reg"),
+            (address, category),
+        );
+        Ok(())
+    }
+
+    /// Remove a payee from the registry. Existing bills keep their payee.
+    pub fn remove_payee(env: Env, caller: Address, address: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_verifier(&env, &caller)?;
+        let mut payees = Self::load_payees(&env);
+        if payees.remove(address.clone()).is_none() {
+            return Err(Error::PayeeNotRegistered);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PAYEES"), &payees);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("payee_rm"),
+            address,
+        );
+        Ok(())
+    }
+
+    pub fn get_payee(env: Env, address: Address) -> Option<Payee> {
+        Self::load_payees(&env).get(address)
+    }
+
+    /// All registered payees in `category`.
+    pub fn get_payees_by_category(env: Env, category: Symbol) -> Vec<Payee> {
+        let mut result = Vec::new(&env);
+        for (_, payee) in Self::load_payees(&env).iter() {
+            if payee.category == category {
+                result.push_back(payee);
+            }
+        }
+        result
+    }
+
+    fn load_payees(env: &Env) -> Map<Address, Payee> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PAYEES"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn require_verifier(env: &Env, caller: &Address) -> Result<(), Error> {
+        if Self::get_upgrade_admin(env).is_some_and(|admin| admin == *caller) {
+            return Ok(());
+        }
+        let allowed = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, bool>>(&symbol_short!("VERIFIERS"))
+            .and_then(|verifiers| verifiers.get(caller.clone()))
+            .unwrap_or(false);
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::Unauthorized)
+        }
+    }
+
+    fn require_registered_payee(env: &Env, payee: &Address) -> Result<(), Error> {
+        if Self::is_payee_registry_required(env.clone())
+            && !Self::load_payees(env).contains_key(payee.clone())
+        {
+            return Err(Error::PayeeNotRegistered);
+        }
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Core bill operations
     // -----------------------------------------------------------------------
@@ -355,11 +792,13 @@ impl BillPayments {
         env: Env,
         owner: Address,
         name: String,
+        payee: Address,
         amount: i128,
         due_date: u64,
         recurring: bool,
         frequency_days: u32,
         currency: String,
+        category: Symbol,
     ) -> Result<u32, Error> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
@@ -370,9 +809,10 @@ impl BillPayments {
         if recurring && frequency_days == 0 {
             return Err(Error::InvalidFrequency);
         }
+        Self::require_registered_payee(&env, &payee)?;
 
         // Resolve default currency: blank input → "XLM"
-        let resolved_currency = if currency.len() == 0 {
+        let resolved_currency = if currency.is_empty() {
             String::from_str(&env, "XLM")
         } else {
             currency
@@ -396,8 +836,11 @@ impl BillPayments {
         let bill = Bill {
             id: next_id,
             owner: owner.clone(),
+            payee,
             name: name.clone(),
+            category,
             amount,
+            amount_paid: 0,
             due_date,
             recurring,
             frequency_days,
@@ -405,6 +848,7 @@ impl BillPayments {
             created_at: current_time,
             paid_at: None,
             schedule_id: None,
+            status: BillStatus::Open,
             currency: resolved_currency,
         };
 
@@ -416,6 +860,7 @@ impl BillPayments {
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
+        Self::index_bill(&env, &bill_owner, next_id);
         Self::adjust_unpaid_total(&env, &bill_owner, amount);
 
         RemitwiseEvents::emit(
@@ -429,9 +874,28 @@ impl BillPayments {
         Ok(next_id)
     }
 
-    pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+    /// Create several bills for one owner in a single transaction, e.g. when
+    /// onboarding a household's existing obligations. Every input is checked
+    /// before any bill is stored. Returns the new bill IDs in input order.
+    pub fn create_bills(
+        env: Env,
+        owner: Address,
+        inputs: Vec<BillInput>,
+    ) -> Result<Vec<u32>, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        if inputs.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+        for input in inputs.iter() {
+            if input.amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if input.recurring && input.frequency_days == 0 {
+                return Err(Error::InvalidFrequency);
+            }
+            Self::require_registered_payee(&env, &input.payee)?;
+        }
 
         Self::extend_instance_ttl(&env);
         let mut bills: Map<u32, Bill> = env
@@ -439,1263 +903,5475 @@ impl BillPayments {
             .instance()
             .get(&symbol_short!("BILLS"))
             .unwrap_or_else(|| Map::new(&env));
-
-        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
-
-        if bill.owner != caller {
-            return Err(Error::Unauthorized);
-        }
-        if bill.paid {
-            return Err(Error::BillAlreadyPaid);
-        }
-
+        let mut next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
         let current_time = env.ledger().timestamp();
-        bill.paid = true;
-        bill.paid_at = Some(current_time);
-
-        if bill.recurring {
-            let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-            let next_id = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("NEXT_ID"))
-                .unwrap_or(0u32)
-                + 1;
-
-            let next_bill = Bill {
-                id: next_id,
-                owner: bill.owner.clone(),
-                name: bill.name.clone(),
-                amount: bill.amount,
-                due_date: next_due_date,
-                recurring: true,
-                frequency_days: bill.frequency_days,
-                paid: false,
-                created_at: current_time,
-                paid_at: None,
-                schedule_id: bill.schedule_id,
-                currency: bill.currency.clone(),
+        let mut ids = Vec::new(&env);
+        let mut total = 0i128;
+        for input in inputs.iter() {
+            next_id = next_id.saturating_add(1);
+            let currency = if input.currency.is_empty() {
+                String::from_str(&env, "XLM")
+            } else {
+                input.currency
             };
-            bills.set(next_id, next_bill);
-            env.storage()
-                .instance()
-                .set(&symbol_short!("NEXT_ID"), &next_id);
+            bills.set(
+                next_id,
+                Bill {
+                    id: next_id,
+                    owner: owner.clone(),
+                    payee: input.payee,
+                    name: input.name,
+                    category: input.category,
+                    amount: input.amount,
+                    amount_paid: 0,
+                    due_date: input.due_date,
+                    recurring: input.recurring,
+                    frequency_days: input.frequency_days,
+                    paid: false,
+                    created_at: current_time,
+                    paid_at: None,
+                    schedule_id: None,
+                    status: BillStatus::Open,
+                    currency,
+                },
+            );
+            Self::index_bill(&env, &owner, next_id);
+            total = total.saturating_add(input.amount);
+            ids.push_back(next_id);
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::State,
+                EventPriority::Medium,
+                symbol_short!("created"),
+                (next_id, owner.clone(), input.amount, input.due_date),
+            );
         }
-
-        let paid_amount = bill.amount;
-        let was_recurring = bill.recurring;
-        bills.set(bill_id, bill);
         env.storage()
             .instance()
             .set(&symbol_short!("BILLS"), &bills);
-        if !was_recurring {
-            Self::adjust_unpaid_total(&env, &caller, -paid_amount);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        if total != 0 {
+            Self::adjust_unpaid_total(&env, &owner, total);
         }
-
         RemitwiseEvents::emit(
             &env,
-            EventCategory::Transaction,
-            EventPriority::High,
-            symbol_short!("paid"),
-            (bill_id, caller, paid_amount),
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("batch_crt"),
+            (ids.len(), owner),
         );
-
-        Ok(())
+        Ok(ids)
     }
 
-    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        bills.get(bill_id)
-    }
+    /// Manually mark a bill as paid without moving tokens, for bills the
+    /// owner settled off-chain. Use `pay_bill` to pay the payee on-chain.
+    pub fn mark_bill_paid(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES
-    // -----------------------------------------------------------------------
+        Self::extend_instance_ttl(&env);
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        Self::mark_paid(&env, &caller, bill_id, None)?;
+        Ok(())
+    }
 
-    /// Get a page of unpaid bills for `owner`.
+    /// Pay a bill in `token`, sending its outstanding amount to the payee and
+    /// marking it paid in the same transaction.
     ///
-    /// # Arguments
-    /// * `owner`  – whose bills to return
-    /// * `cursor` – start after this bill ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// The amount comes from the owner's funded balance for `token` when it
+    /// covers the bill, otherwise directly from the owner's account. When an
+    /// asset is mapped to the bill's currency, `token` must be that asset;
+    /// use `pay_bill_with` to pay from another token.
     ///
     /// # Returns
-    /// `BillPage { items, next_cursor, count }`.
-    /// When `next_cursor == 0` there are no more pages.
-    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// The amount paid
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `TokenMismatch` - If `token` is not the bill currency's asset
+    pub fn pay_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        token: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        Self::extend_instance_ttl(&env);
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        Self::require_registered_payee(&env, &bill.payee)?;
+        if Self::get_currency_asset(env.clone(), bill.currency.clone())
+            .is_some_and(|asset| asset != token)
+        {
+            return Err(Error::TokenMismatch);
         }
 
-        Self::build_page(&env, staging, limit)
+        let amount = bill.amount - bill.amount_paid;
+        Self::require_approval(&env, &caller, amount);
+        let client = TokenClient::new(&env, &token);
+        if Self::bill_funds(&env, &caller, &token) >= amount {
+            Self::debit_bill_funds(&env, &caller, &token, amount);
+            client.transfer(&env.current_contract_address(), &bill.payee, &amount);
+        } else {
+            client.transfer(&caller, &bill.payee, &amount);
+        }
+        Self::mark_paid(&env, &caller, bill_id, Some(token.clone()))?;
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("transfer"),
+            (bill_id, bill.payee, token, amount),
+        );
+        Ok(amount)
     }
 
-    /// Get a page of ALL bills (paid + unpaid) for `owner`.
+    /// Record a partial payment towards a bill, e.g. one remittance's worth
+    /// of a large bill. Like `mark_bill_paid`, funds are not moved on-chain.
     ///
-    /// Same cursor/limit semantics as `get_unpaid_bills`.
-    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
-        owner.require_auth();
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
+    /// The payment that covers the rest of the bill marks it paid.
+    ///
+    /// # Returns
+    /// The amount still outstanding
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `InvalidAmount` - If amount is not positive or exceeds what is outstanding
+    pub fn pay_bill_partial(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+        Self::record_partial(&env, &caller, bill_id, amount)
+    }
+
+    /// Add `amount` to a bill's `amount_paid`, marking it paid once nothing
+    /// is outstanding. Returns the amount still outstanding.
+    fn record_partial(
+        env: &Env,
+        caller: &Address,
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let mut bills: Map<u32, Bill> = env
             .storage()
             .instance()
             .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(env, &bill, caller)?;
+        let outstanding = bill.amount - bill.amount_paid;
+        if amount <= 0 || amount > outstanding {
+            return Err(Error::InvalidAmount);
+        }
+        if amount == outstanding {
+            Self::mark_paid(env, caller, bill_id, None)?;
+            return Ok(0);
+        }
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        bill.amount_paid += amount;
+        let amount_paid = bill.amount_paid;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        Self::adjust_unpaid_total(env, caller, -amount);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("partial"),
+            (bill_id, caller.clone(), amount, amount_paid),
+        );
+        Ok(outstanding - amount)
+    }
+
+    fn check_payable(env: &Env, bill: &Bill, caller: &Address) -> Result<(), Error> {
+        if bill.owner != *caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(bill) {
+            return Err(Error::BillCancelled);
         }
+        if Self::load_splits(env).contains_key(bill.id) {
+            return Err(Error::BillIsSplit);
+        }
+        Ok(())
+    }
 
-        Self::build_page(&env, staging, limit)
+    fn is_cancelled(bill: &Bill) -> bool {
+        matches!(bill.status, BillStatus::Cancelled(_))
     }
 
-    /// Get a page of overdue (unpaid + past due_date) bills across all owners.
-    ///
-    /// Same cursor/limit semantics.
-    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let current_time = env.ledger().timestamp();
-        let bills: Map<u32, Bill> = env
+    /// Whether a bill still counts as owed: unpaid and not cancelled.
+    fn is_outstanding(bill: &Bill) -> bool {
+        !bill.paid && !Self::is_cancelled(bill)
+    }
+
+    /// Flag a bill paid, create the next occurrence of a recurring bill, store
+    /// the receipt and emit the `paid` event. Returns the amount that was
+    /// still outstanding.
+    fn mark_paid(
+        env: &Env,
+        caller: &Address,
+        bill_id: u32,
+        token: Option<Address>,
+    ) -> Result<i128, Error> {
+        let mut bills: Map<u32, Bill> = env
             .storage()
             .instance()
             .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.paid || bill.due_date >= current_time {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        let current_time = env.ledger().timestamp();
+        let outstanding = bill.amount - bill.amount_paid;
+        let mut unpaid_delta = -outstanding;
+        bill.paid = true;
+        bill.paid_at = Some(current_time);
+        bill.amount_paid = bill.amount;
+        Self::store_receipt(env, bill_id, caller, &bill.payee, bill.amount, token);
+
+        if bill.recurring {
+            let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
+            let next_id = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("NEXT_ID"))
+                .unwrap_or(0u32)
+                + 1;
+            let next_amount = Self::carry_estimate(env, bill_id, next_id).unwrap_or(bill.amount);
+            unpaid_delta += next_amount;
+
+            let next_bill = Bill {
+                id: next_id,
+                owner: bill.owner.clone(),
+                payee: bill.payee.clone(),
+                name: bill.name.clone(),
+                category: bill.category.clone(),
+                amount: next_amount,
+                amount_paid: 0,
+                due_date: next_due_date,
+                recurring: true,
+                frequency_days: bill.frequency_days,
+                paid: false,
+                created_at: current_time,
+                paid_at: None,
+                schedule_id: bill.schedule_id,
+                status: BillStatus::Open,
+                currency: bill.currency.clone(),
+            };
+            bills.set(next_id, next_bill);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("NEXT_ID"), &next_id);
+            Self::index_bill(env, &bill.owner, next_id);
+            Self::carry_autopay(env, bill_id, next_id);
+            Self::carry_split(env, bill_id, next_id);
         }
 
-        Self::build_page(&env, staging, limit)
+        let owner = bill.owner.clone();
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        if unpaid_delta != 0 {
+            Self::adjust_unpaid_total(env, &owner, unpaid_delta);
+        }
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("paid"),
+            (bill_id, caller.clone(), outstanding),
+        );
+
+        Ok(outstanding)
     }
 
-    /// Admin-only: get ALL bills (any owner), paginated.
-    pub fn get_all_bills(
+    fn store_receipt(
+        env: &Env,
+        bill_id: u32,
+        payer: &Address,
+        payee: &Address,
+        amount: i128,
+        token: Option<Address>,
+    ) {
+        let mut receipts = Self::load_receipts(env);
+        receipts.set(
+            bill_id,
+            Receipt {
+                bill_id,
+                payer: payer.clone(),
+                payee: payee.clone(),
+                amount,
+                token,
+                paid_at: env.ledger().timestamp(),
+                ledger_sequence: env.ledger().sequence(),
+                external_hash: None,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("RECEIPTS"), &receipts);
+    }
+
+    fn load_receipts(env: &Env) -> Map<u32, Receipt> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("RECEIPTS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Receipt for a paid bill, to show a landlord or school that payment
+    /// happened on-chain.
+    pub fn get_receipt(env: Env, bill_id: u32) -> Option<Receipt> {
+        Self::load_receipts(&env).get(bill_id)
+    }
+
+    /// Attach the hash of the payee's own receipt (e.g. a school's invoice
+    /// receipt) to the on-chain receipt. Only the bill's payee may do this.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If there is no receipt for bill_id
+    /// * `Unauthorized` - If caller is not the receipt's payee
+    pub fn attach_receipt_hash(
         env: Env,
         caller: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> Result<BillPage, Error> {
+        bill_id: u32,
+        hash: Bytes,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
-        if admin != caller {
+        let mut receipts = Self::load_receipts(&env);
+        let mut receipt = receipts.get(bill_id).ok_or(Error::BillNotFound)?;
+        if receipt.payee != caller {
             return Err(Error::Unauthorized);
         }
 
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
+        Self::extend_instance_ttl(&env);
+        receipt.external_hash = Some(hash.clone());
+        receipts.set(bill_id, receipt);
+        env.storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
+            .set(&symbol_short!("RECEIPTS"), &receipts);
 
-        Ok(Self::build_page(&env, staging, limit))
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("receipt"),
+            (bill_id, hash),
+        );
+        Ok(())
     }
 
-    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
-    /// `next_cursor` is set to the last *returned* item's ID so the next call's
-    /// `id <= cursor` filter correctly skips past it.
-    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
-        let n = staging.len();
-        let has_next = n > limit;
-        let mut items = Vec::new(env);
-        let mut next_cursor: u32 = 0;
-
-        // Emit all items, or all-but-last if there is a next page
-        let take = if has_next { n - 1 } else { n };
+    // -----------------------------------------------------------------------
+    // Cross-asset payments
+    // -----------------------------------------------------------------------
 
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
+    /// Set the router used by `pay_bill_with`. Upgrade admin only.
+    pub fn set_payment_router(env: Env, caller: Address, router: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
         }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ROUTER"), &router);
+        Ok(())
+    }
 
-        // next_cursor = last returned item's ID (NOT the first skipped item)
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
-            }
-        }
-
-        let count = items.len();
-        BillPage {
-            items,
-            next_cursor,
-            count,
+    /// Map a bill currency code (e.g. "NGN") to the token payees are paid
+    /// in, typically the local anchor asset. Upgrade admin only.
+    pub fn set_currency_asset(
+        env: Env,
+        caller: Address,
+        currency: String,
+        token: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
         }
-    }
-
-    // -----------------------------------------------------------------------
-    // Backward-compat helpers
-    // -----------------------------------------------------------------------
-
-    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
-    /// Only safe for owners with a small number of bills. Prefer the
-    /// paginated `get_unpaid_bills` for production use.
-    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
+        let mut assets = Self::load_currency_assets(&env);
+        assets.set(currency, token);
+        env.storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                result.push_back(bill);
-            }
-        }
-        result
+            .set(&symbol_short!("CUR_ASSET"), &assets);
+        Ok(())
     }
 
-    // -----------------------------------------------------------------------
-    // Archived bill queries (paginated)
-    // -----------------------------------------------------------------------
+    pub fn get_currency_asset(env: Env, currency: String) -> Option<Address> {
+        Self::load_currency_assets(&env).get(currency)
+    }
 
-    /// Get a page of archived bills for `owner`.
-    pub fn get_archived_bills(
+    /// Pay a bill from `source_token` (e.g. USDC) when the payee wants the
+    /// asset mapped to the bill's currency. The router swaps at most
+    /// `max_spend` from the owner's wallet so the payee receives the full
+    /// outstanding amount, and the effective rate is recorded.
+    ///
+    /// # Returns
+    /// The amount of `source_token` spent
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `PayeeNotRegistered` - If the registry is required and the payee is not in it
+    /// * `ConversionUnavailable` - If no router or no asset for the bill's currency is set
+    /// * `SlippageExceeded` - If `max_spend` cannot buy the outstanding amount
+    pub fn pay_bill_with(
         env: Env,
-        owner: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> ArchivedBillPage {
-        let limit = Self::clamp_limit(limit);
-        let archived: Map<u32, ArchivedBill> = env
+        caller: Address,
+        bill_id: u32,
+        source_token: Address,
+        max_spend: i128,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        Self::require_registered_payee(&env, &bill.payee)?;
+        let router: Address = env
             .storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&symbol_short!("ROUTER"))
+            .ok_or(Error::ConversionUnavailable)?;
+        let target_token = Self::load_currency_assets(&env)
+            .get(bill.currency.clone())
+            .ok_or(Error::ConversionUnavailable)?;
+        if max_spend <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
-        for (id, bill) in archived.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        let amount = bill.amount - bill.amount_paid;
+        Self::require_approval(&env, &caller, amount);
+        let router = SwapRouterClient::new(&env, &router);
+        let max_out = router.quote(&source_token, &target_token, &max_spend);
+        if max_out < amount {
+            return Err(Error::SlippageExceeded);
+        }
+        // Spend only what the quoted rate needs, rounded up.
+        let amount_in = (amount * max_spend + max_out - 1) / max_out;
+        let amount_out = router.swap(
+            &caller,
+            &source_token,
+            &target_token,
+            &amount_in,
+            &amount,
+            &bill.payee,
+        );
+        if amount_out < amount {
+            return Err(Error::SlippageExceeded);
         }
 
-        let has_next = staging.len() > limit;
-        let mut items = Vec::new(&env);
-        let mut next_cursor: u32 = 0;
-        let take = if has_next {
-            staging.len() - 1
-        } else {
-            staging.len()
+        Self::extend_instance_ttl(&env);
+        let conversion = PaymentConversion {
+            bill_id,
+            source_token,
+            target_token: target_token.clone(),
+            amount_in,
+            amount_out,
+            rate: amount_out * RATE_SCALE / amount_in,
+            timestamp: env.ledger().timestamp(),
         };
+        let mut conversions = Self::load_conversions(&env);
+        conversions.set(bill_id, conversion.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONVERTS"), &conversions);
+        Self::mark_paid(&env, &caller, bill_id, Some(target_token))?;
 
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
-        }
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("converted"),
+            conversion,
+        );
+        Ok(amount_in)
+    }
+
+    pub fn get_payment_conversion(env: Env, bill_id: u32) -> Option<PaymentConversion> {
+        Self::load_conversions(&env).get(bill_id)
+    }
+
+    /// Conversions behind `owner`'s active bills, for the compliance report.
+    pub fn get_owner_conversions(env: Env, owner: Address) -> Vec<PaymentConversion> {
+        let conversions = Self::load_conversions(&env);
+        let mut result = Vec::new(&env);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if let Some(conversion) = conversions.get(id) {
+                result.push_back(conversion);
             }
         }
+        result
+    }
 
-        let count = items.len();
-        ArchivedBillPage {
-            items,
-            next_cursor,
-            count,
-        }
+    fn load_currency_assets(env: &Env) -> Map<String, Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CUR_ASSET"))
+            .unwrap_or_else(|| Map::new(env))
     }
 
-    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
+    fn load_conversions(env: &Env) -> Map<u32, PaymentConversion> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        archived.get(bill_id)
+            .get(&symbol_short!("CONVERTS"))
+            .unwrap_or_else(|| Map::new(env))
     }
 
     // -----------------------------------------------------------------------
-    // Remaining operations
+    // Large payment approval
     // -----------------------------------------------------------------------
 
-    pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+    /// Require `approver` to co-authorize any payment by `caller` above
+    /// `threshold`, guarding the bills budget against a compromised key.
+    ///
+    /// Covers `pay_bill`, `schedule_payment`, `set_autopay` caps
+    /// and `withdraw_balance`. Pass `None` to remove the rule. Once a rule is
+    /// set, changing or removing it also needs the current approver.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If threshold is negative
+    pub fn set_payment_approval(
+        env: Env,
+        caller: Address,
+        approver: Option<Address>,
+        threshold: i128,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
-        if bill.owner != caller {
-            return Err(Error::Unauthorized);
+        let mut rules = Self::load_approval_rules(&env);
+        if let Some(current) = rules.get(caller.clone()) {
+            current.approver.require_auth();
+        }
+
+        match approver.clone() {
+            Some(approver) => {
+                if threshold < 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                rules.set(
+                    caller.clone(),
+                    ApprovalRule {
+                        approver,
+                        threshold,
+                    },
+                );
+            }
+            None => {
+                rules.remove(caller.clone());
+            }
         }
-        let removed_unpaid_amount = if bill.paid { 0 } else { bill.amount };
-        bills.remove(bill_id);
+        Self::extend_instance_ttl(&env);
         env.storage()
             .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if removed_unpaid_amount > 0 {
-            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
-        }
+            .set(&symbol_short!("APPROVALS"), &rules);
+
         RemitwiseEvents::emit(
             &env,
-            EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("canceled"),
-            bill_id,
+            EventCategory::Access,
+            EventPriority::High,
+            symbol_short!("approval"),
+            (caller, approver, threshold),
         );
         Ok(())
     }
 
-    pub fn archive_paid_bills(
-        env: Env,
-        caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
-        Self::extend_instance_ttl(&env);
+    pub fn get_payment_approval(env: Env, owner: Address) -> Option<ApprovalRule> {
+        Self::load_approval_rules(&env).get(owner)
+    }
 
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
+    fn load_approval_rules(env: &Env) -> Map<Address, ApprovalRule> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let current_time = env.ledger().timestamp();
-        let mut archived_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
+            .get(&symbol_short!("APPROVALS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
 
-        for (id, bill) in bills.iter() {
-            if let Some(paid_at) = bill.paid_at {
-                if bill.paid && paid_at < before_timestamp {
-                    let archived_bill = ArchivedBill {
-                        id: bill.id,
-                        owner: bill.owner.clone(),
-                        name: bill.name.clone(),
-                        amount: bill.amount,
-                        paid_at,
-                        archived_at: current_time,
-                        currency: bill.currency.clone(),
-                    };
-                    archived.set(id, archived_bill);
-                    to_remove.push_back(id);
-                    archived_count += 1;
-                }
+    fn require_approval(env: &Env, owner: &Address, amount: i128) {
+        if let Some(rule) = Self::load_approval_rules(env).get(owner.clone()) {
+            if amount > rule.threshold {
+                rule.approver.require_auth();
             }
         }
+    }
 
-        for id in to_remove.iter() {
-            bills.remove(id);
+    // -----------------------------------------------------------------------
+    // Funded balances
+    // -----------------------------------------------------------------------
+
+    /// Deposit `amount` of `token` into the owner's funded balance, used by
+    /// `pay_bill`.
+    ///
+    /// # Returns
+    /// The new balance
+    pub fn fund_balance(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
         }
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&owner, &env.current_contract_address(), &amount);
+        let balance = Self::get_balance(env.clone(), owner.clone(), token.clone()) + amount;
+        Self::set_balance(&env, &owner, &token, balance);
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("funded"),
+            (owner, token, amount),
+        );
+        Ok(balance)
+    }
 
-        Self::extend_archive_ttl(&env);
-        Self::update_storage_stats(&env);
+    /// Return `amount` of `token` from the owner's funded balance.
+    ///
+    /// # Returns
+    /// The remaining balance
+    pub fn withdraw_balance(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let balance = Self::get_balance(env.clone(), owner.clone(), token.clone());
+        if amount > balance {
+            return Err(Error::InsufficientBalance);
+        }
+        Self::require_approval(&env, &owner, amount);
+        Self::extend_instance_ttl(&env);
+        Self::set_balance(&env, &owner, &token, balance - amount);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &owner, &amount);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("archived"),
-            archived_count,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("defunded"),
+            (owner, token, amount),
         );
-
-        Ok(archived_count)
+        Ok(balance - amount)
     }
 
-    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::RESTORE)?;
-        Self::extend_instance_ttl(&env);
+    pub fn get_balance(env: Env, owner: Address, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<(Address, Address), i128>>(&symbol_short!("BALANCES"))
+            .and_then(|balances| balances.get((owner, token)))
+            .unwrap_or(0)
+    }
 
-        let mut archived: Map<u32, ArchivedBill> = env
+    fn set_balance(env: &Env, owner: &Address, token: &Address, balance: i128) {
+        let mut balances: Map<(Address, Address), i128> = env
             .storage()
             .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
-
-        if archived_bill.owner != caller {
-            return Err(Error::Unauthorized);
-        }
-
-        let mut bills: Map<u32, Bill> = env
-            .storage()
+            .get(&symbol_short!("BALANCES"))
+            .unwrap_or_else(|| Map::new(env));
+        balances.set((owner.clone(), token.clone()), balance);
+        env.storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .set(&symbol_short!("BALANCES"), &balances);
+    }
 
-        let restored_bill = Bill {
-            id: archived_bill.id,
-            owner: archived_bill.owner.clone(),
-            name: archived_bill.name.clone(),
-            amount: archived_bill.amount,
-            due_date: env.ledger().timestamp() + 2592000,
-            recurring: false,
-            frequency_days: 0,
-            paid: true,
-            created_at: archived_bill.paid_at,
-            paid_at: Some(archived_bill.paid_at),
-            schedule_id: None,
-            currency: archived_bill.currency.clone(),
-        };
+    /// Deposit `amount` of `token` from `payer` into `owner`'s earmarked
+    /// balance, e.g. the bills leg of a remittance split. Earmarked funds are
+    /// spent before the funded balance and can only pay bills, never be
+    /// withdrawn.
+    ///
+    /// # Returns
+    /// The new earmarked balance
+    pub fn deposit_earmarked(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        payer.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        let earmarked =
+            Self::get_earmarked_balance(env.clone(), owner.clone(), token.clone()) + amount;
+        Self::set_earmarked(&env, &owner, &token, earmarked);
 
-        bills.set(bill_id, restored_bill);
-        archived.remove(bill_id);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("earmarked"),
+            (owner, payer, token, amount),
+        );
+        Ok(earmarked)
+    }
 
+    pub fn get_earmarked_balance(env: Env, owner: Address, token: Address) -> i128 {
         env.storage()
             .instance()
-            .set(&symbol_short!("BILLS"), &bills);
+            .get::<_, Map<(Address, Address), i128>>(&symbol_short!("EARMARKED"))
+            .and_then(|balances| balances.get((owner, token)))
+            .unwrap_or(0)
+    }
+
+    fn set_earmarked(env: &Env, owner: &Address, token: &Address, balance: i128) {
+        let mut balances: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("EARMARKED"))
+            .unwrap_or_else(|| Map::new(env));
+        balances.set((owner.clone(), token.clone()), balance);
         env.storage()
             .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
+            .set(&symbol_short!("EARMARKED"), &balances);
+    }
 
-        Self::update_storage_stats(&env);
+    /// Earmarked plus funded balance, i.e. what can pay `owner`'s bills.
+    fn bill_funds(env: &Env, owner: &Address, token: &Address) -> i128 {
+        Self::get_earmarked_balance(env.clone(), owner.clone(), token.clone())
+            + Self::get_balance(env.clone(), owner.clone(), token.clone())
+    }
 
-        RemitwiseEvents::emit(
-            &env,
-            EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("restored"),
-            bill_id,
-        );
-        Ok(())
+    /// Take `amount` for a bill payment, earmarked funds first. The caller
+    /// checks `bill_funds` beforehand. Returns the part that was earmarked.
+    fn debit_bill_funds(env: &Env, owner: &Address, token: &Address, amount: i128) -> i128 {
+        let earmarked = Self::get_earmarked_balance(env.clone(), owner.clone(), token.clone());
+        let from_earmark = earmarked.min(amount);
+        if from_earmark > 0 {
+            Self::set_earmarked(env, owner, token, earmarked - from_earmark);
+        }
+        if amount > from_earmark {
+            let balance = Self::get_balance(env.clone(), owner.clone(), token.clone());
+            Self::set_balance(env, owner, token, balance - (amount - from_earmark));
+        }
+        from_earmark
     }
 
-    pub fn bulk_cleanup_bills(
+    // -----------------------------------------------------------------------
+    // Scheduled payments
+    // -----------------------------------------------------------------------
+
+    /// Schedule a bill to be paid in `token` at or after `execute_at`.
+    ///
+    /// The outstanding amount is reserved from the owner's funded balance
+    /// straight away, and anyone may call `execute_scheduled_payment` once
+    /// the time comes. `cancel_scheduled_payment` returns the reservation.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `InvalidStatus` - If a payment is already scheduled for the bill
+    /// * `InsufficientBalance` - If the funded balance cannot cover the bill
+    pub fn schedule_payment(
         env: Env,
         caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
+        bill_id: u32,
+        token: Address,
+        execute_at: u64,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut deleted_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
 
-        for (id, bill) in archived.iter() {
-            if bill.archived_at < before_timestamp {
-                to_remove.push_back(id);
-                deleted_count += 1;
-            }
+        let mut scheduled = Self::load_scheduled_payments(&env);
+        if scheduled.contains_key(bill_id) {
+            return Err(Error::InvalidStatus);
         }
-
-        for id in to_remove.iter() {
-            archived.remove(id);
+        let amount = bill.amount - bill.amount_paid;
+        if amount > Self::bill_funds(&env, &caller, &token) {
+            return Err(Error::InsufficientBalance);
         }
+        Self::require_approval(&env, &caller, amount);
 
+        Self::extend_instance_ttl(&env);
+        let earmarked = Self::debit_bill_funds(&env, &caller, &token, amount);
+        scheduled.set(
+            bill_id,
+            ScheduledPayment {
+                bill_id,
+                owner: caller.clone(),
+                token,
+                amount,
+                earmarked,
+                execute_at,
+            },
+        );
         env.storage()
             .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-        Self::update_storage_stats(&env);
+            .set(&symbol_short!("SCHED_PAY"), &scheduled);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("cleaned"),
-            deleted_count,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("sched_pay"),
+            (bill_id, amount, execute_at),
         );
-        Ok(deleted_count)
+        Ok(())
     }
 
-    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
-        caller.require_auth();
+    /// Execute a scheduled payment once its time has come. Permissionless.
+    ///
+    /// Pays the payee what is still outstanding from the reservation and
+    /// returns any excess to the owner's balance. If the bill was paid or
+    /// cancelled in the meantime, or has since been split, the whole
+    /// reservation goes back to the balance.
+    ///
+    /// # Returns
+    /// The amount paid to the payee
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If no payment is scheduled for bill_id
+    /// * `PaymentNotDue` - If `execute_at` has not been reached
+    /// * `PayeeNotRegistered` - If the registry is required and the payee is not in it
+    pub fn execute_scheduled_payment(env: Env, bill_id: u32) -> Result<i128, Error> {
         Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
-        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap() {
-            return Err(Error::BatchTooLarge);
-        }
-        let bills_map: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        for id in bill_ids.iter() {
-            let bill = bills_map.get(id).ok_or(Error::BillNotFound)?;
-            if bill.owner != caller {
-                return Err(Error::Unauthorized);
-            }
-            if bill.paid {
-                return Err(Error::BillAlreadyPaid);
-            }
+        let payment = Self::load_scheduled_payments(&env)
+            .get(bill_id)
+            .ok_or(Error::BillNotFound)?;
+        if env.ledger().timestamp() < payment.execute_at {
+            return Err(Error::PaymentNotDue);
         }
+
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let current_time = env.ledger().timestamp();
-        let mut next_id: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-        let mut paid_count = 0u32;
-        let mut unpaid_delta = 0i128;
-        for id in bill_ids.iter() {
-            let mut bill = bills.get(id).ok_or(Error::BillNotFound)?;
-            if bill.owner != caller || bill.paid {
-                return Err(Error::BatchValidationFailed);
-            }
-            let amount = bill.amount;
-            bill.paid = true;
-            bill.paid_at = Some(current_time);
-            if bill.recurring {
-                next_id = next_id.saturating_add(1);
-                let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
-                let next_bill = Bill {
-                    id: next_id,
-                    owner: bill.owner.clone(),
-                    name: bill.name.clone(),
-                    amount: bill.amount,
-                    due_date: next_due_date,
-                    recurring: true,
-                    frequency_days: bill.frequency_days,
-                    paid: false,
-                    created_at: current_time,
-                    paid_at: None,
-                    schedule_id: bill.schedule_id,
-                    currency: bill.currency.clone(),
-                };
-                bills.set(next_id, next_bill);
-            } else {
-                unpaid_delta = unpaid_delta.saturating_sub(amount);
+        Self::release_scheduled_payment(&env, bill_id);
+        let bill = match Self::get_bill(env.clone(), bill_id) {
+            Some(bill)
+                if Self::is_outstanding(&bill)
+                    && !Self::load_splits(&env).contains_key(bill_id) =>
+            {
+                bill
             }
-            bills.set(id, bill);
-            paid_count += 1;
-            RemitwiseEvents::emit(
-                &env,
-                EventCategory::Transaction,
-                EventPriority::High,
-                symbol_short!("paid"),
-                (id, caller.clone(), amount),
-            );
-        }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if unpaid_delta != 0 {
-            Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
-        }
-        Self::update_storage_stats(&env);
+            _ => return Ok(0),
+        };
+        Self::require_registered_payee(&env, &bill.payee)?;
+
+        let amount = (bill.amount - bill.amount_paid).min(payment.amount);
+        Self::debit_bill_funds(&env, &payment.owner, &payment.token, amount);
+        TokenClient::new(&env, &payment.token).transfer(
+            &env.current_contract_address(),
+            &bill.payee,
+            &amount,
+        );
+        Self::mark_paid(&env, &payment.owner, bill_id, Some(payment.token.clone()))?;
+
         RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            EventPriority::Medium,
-            symbol_short!("batch_pay"),
-            (paid_count, caller),
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("sched_pd"),
+            (bill_id, bill.payee, payment.token, amount),
         );
-        Ok(paid_count)
+        Ok(amount)
     }
 
-    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
+    /// Cancel a scheduled payment before it executes, returning the
+    /// reservation to the owner's funded balance.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If no payment is scheduled for bill_id
+    /// * `Unauthorized` - If caller is not the bill owner
+    pub fn cancel_scheduled_payment(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        let payment = Self::load_scheduled_payments(&env)
+            .get(bill_id)
+            .ok_or(Error::BillNotFound)?;
+        if payment.owner != caller {
+            return Err(Error::Unauthorized);
         }
 
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                total += bill.amount;
-            }
-        }
-        total
+        Self::extend_instance_ttl(&env);
+        Self::release_scheduled_payment(&env, bill_id);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("sched_cxl"),
+            (bill_id, payment.amount),
+        );
+        Ok(())
     }
 
-    pub fn get_storage_stats(env: Env) -> StorageStats {
+    pub fn get_scheduled_payment(env: Env, bill_id: u32) -> Option<ScheduledPayment> {
+        Self::load_scheduled_payments(&env).get(bill_id)
+    }
+
+    fn load_scheduled_payments(env: &Env) -> Map<u32, ScheduledPayment> {
         env.storage()
             .instance()
-            .get(&symbol_short!("STOR_STAT"))
-            .unwrap_or(StorageStats {
-                active_bills: 0,
-                archived_bills: 0,
-                total_unpaid_amount: 0,
-                total_archived_amount: 0,
-                last_updated: 0,
-            })
+            .get(&symbol_short!("SCHED_PAY"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Drop a bill's scheduled payment, if any, and credit the reservation
+    /// back to the owner's earmarked and funded balances it came from.
+    fn release_scheduled_payment(env: &Env, bill_id: u32) {
+        let mut scheduled = Self::load_scheduled_payments(env);
+        if let Some(payment) = scheduled.get(bill_id) {
+            scheduled.remove(bill_id);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SCHED_PAY"), &scheduled);
+            let (owner, token) = (&payment.owner, &payment.token);
+            if payment.earmarked > 0 {
+                let earmarked =
+                    Self::get_earmarked_balance(env.clone(), owner.clone(), token.clone());
+                Self::set_earmarked(env, owner, token, earmarked + payment.earmarked);
+            }
+            let balance = Self::get_balance(env.clone(), owner.clone(), token.clone());
+            Self::set_balance(
+                env,
+                owner,
+                token,
+                balance + payment.amount - payment.earmarked,
+            );
+        }
     }
 
     // -----------------------------------------------------------------------
-    // Currency-filter helper queries
+    // Grace periods
     // -----------------------------------------------------------------------
 
-    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
-    ///
-    /// # Arguments
-    /// * `owner`    – whose bills to return
-    /// * `currency` – currency code to filter by, e.g. `"USDC"`, `"XLM"`
-    /// * `cursor`   – start after this bill ID (pass 0 for the first page)
-    /// * `limit`    – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// Set how many days after the due date the caller's bills still count
+    /// as on time, following local biller norms. Applies to
+    /// `get_overdue_bills` and to the reporting contract's overdue counts.
     ///
-    /// # Returns
-    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
-    pub fn get_bills_by_currency(
-        env: Env,
-        owner: Address,
-        currency: String,
-        cursor: u32,
-        limit: u32,
-    ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
+    /// # Errors
+    /// * `InvalidLimit` - If days exceeds `MAX_GRACE_DAYS`
+    pub fn set_grace_period(env: Env, caller: Address, days: u32) -> Result<(), Error> {
+        caller.require_auth();
+        if days > MAX_GRACE_DAYS {
+            return Err(Error::InvalidLimit);
+        }
+        let mut grace_periods = Self::load_grace_periods(&env);
+        if days == 0 {
+            grace_periods.remove(caller.clone());
+        } else {
+            grace_periods.set(caller.clone(), days);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .set(&symbol_short!("GRACE"), &grace_periods);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.currency != currency {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("grace"),
+            (caller, days),
+        );
+        Ok(())
+    }
 
-        Self::build_page(&env, staging, limit)
+    /// Grace period in days for `owner`'s bills; 0 if none is set.
+    pub fn get_grace_period(env: Env, owner: Address) -> u32 {
+        Self::load_grace_periods(&env).get(owner).unwrap_or(0)
     }
 
-    /// Get a page of **unpaid** bills for `owner` that match `currency`.
+    fn load_grace_periods(env: &Env) -> Map<Address, u32> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("GRACE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Standing orders
+    // -----------------------------------------------------------------------
+
+    /// Pay `amount` of `token` to `payee` every `interval_days`, from
+    /// `start_at` until `end_date`, out of the owner's funded balance.
     ///
-    /// Same cursor/limit semantics as `get_bills_by_currency`.
-    pub fn get_unpaid_bills_by_currency(
+    /// # Returns
+    /// The new standing order ID
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InvalidFrequency` - If interval_days is 0
+    /// * `InvalidLimit` - If end_date is before start_at
+    pub fn create_standing_order(
         env: Env,
         owner: Address,
-        currency: String,
-        cursor: u32,
-        limit: u32,
-    ) -> BillPage {
-        let limit = Self::clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
+        payee: Address,
+        token: Address,
+        amount: i128,
+        interval_days: u32,
+        start_at: u64,
+        end_date: u64,
+    ) -> Result<u32, Error> {
+        owner.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if interval_days == 0 {
+            return Err(Error::InvalidFrequency);
+        }
+        if end_date < start_at {
+            return Err(Error::InvalidLimit);
+        }
+        Self::require_approval(&env, &owner, amount);
+
+        Self::extend_instance_ttl(&env);
+        let id = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&symbol_short!("NEXT_SO"))
+            .unwrap_or(0u32)
+            + 1;
+        let mut orders = Self::load_standing_orders(&env);
+        orders.set(
+            id,
+            StandingOrder {
+                id,
+                owner: owner.clone(),
+                payee: payee.clone(),
+                token,
+                amount,
+                interval_days,
+                next_run: start_at,
+                end_date,
+                active: true,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SORDERS"), &orders);
+        env.storage().instance().set(&symbol_short!("NEXT_SO"), &id);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid || bill.currency != currency {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("so_new"),
+            (id, owner, payee, amount, interval_days),
+        );
+        Ok(id)
+    }
+
+    /// Stop a standing order. Its history is kept.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If no standing order has this ID
+    /// * `Unauthorized` - If caller is not the owner
+    pub fn cancel_standing_order(env: Env, caller: Address, order_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        let mut orders = Self::load_standing_orders(&env);
+        let mut order = orders.get(order_id).ok_or(Error::BillNotFound)?;
+        if order.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        order.active = false;
+        orders.set(order_id, order);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SORDERS"), &orders);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("so_cxl"),
+            (order_id, caller),
+        );
+        Ok(())
+    }
+
+    /// Make the next due run of a standing order. Permissionless so a keeper
+    /// can trigger it; each call makes at most one run.
+    ///
+    /// # Returns
+    /// The amount paid
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If no standing order has this ID
+    /// * `InvalidStatus` - If the order is cancelled or has ended
+    /// * `PaymentNotDue` - If the next run is still in the future
+    /// * `InsufficientBalance` - If the funded balance cannot cover the run
+    pub fn execute_standing_order(env: Env, order_id: u32) -> Result<i128, Error> {
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let mut orders = Self::load_standing_orders(&env);
+        let mut order = orders.get(order_id).ok_or(Error::BillNotFound)?;
+        if !order.active || order.next_run > order.end_date {
+            return Err(Error::InvalidStatus);
+        }
+        let now = env.ledger().timestamp();
+        if now < order.next_run {
+            return Err(Error::PaymentNotDue);
+        }
+        let balance = Self::get_balance(env.clone(), order.owner.clone(), order.token.clone());
+        if order.amount > balance {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::set_balance(&env, &order.owner, &order.token, balance - order.amount);
+        TokenClient::new(&env, &order.token).transfer(
+            &env.current_contract_address(),
+            &order.payee,
+            &order.amount,
+        );
+
+        let mut runs = Self::load_order_runs(&env);
+        let mut history = runs.get(order_id).unwrap_or_else(|| Vec::new(&env));
+        if history.len() >= MAX_ORDER_RUNS {
+            history.pop_front();
+        }
+        history.push_back(StandingOrderRun {
+            amount: order.amount,
+            due_at: order.next_run,
+            executed_at: now,
+        });
+        runs.set(order_id, history);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SO_RUNS"), &runs);
+
+        let amount = order.amount;
+        let due_at = order.next_run;
+        order.next_run += order.interval_days as u64 * 86400;
+        order.active = order.next_run <= order.end_date;
+        orders.set(order_id, order);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SORDERS"), &orders);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("so_paid"),
+            (order_id, amount, due_at),
+        );
+        Ok(amount)
+    }
+
+    pub fn get_standing_order(env: Env, order_id: u32) -> Option<StandingOrder> {
+        Self::load_standing_orders(&env).get(order_id)
+    }
+
+    /// The most recent runs of a standing order, oldest first.
+    pub fn get_standing_order_runs(env: Env, order_id: u32) -> Vec<StandingOrderRun> {
+        Self::load_order_runs(&env)
+            .get(order_id)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    fn load_standing_orders(env: &Env) -> Map<u32, StandingOrder> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SORDERS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn load_order_runs(env: &Env) -> Map<u32, Vec<StandingOrderRun>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SO_RUNS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Payment plans
+    // -----------------------------------------------------------------------
+
+    /// Split what is outstanding on a large bill into dated installments of
+    /// `(due_date, amount)`, in due date order. While a plan is on schedule
+    /// the bill is not overdue, whatever its own due date.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `InvalidStatus` - If the bill already has a plan
+    /// * `InvalidPlan` - If there are fewer than two installments, an amount is
+    ///   not positive, due dates do not increase, or the amounts do not add up
+    ///   to the outstanding amount
+    pub fn create_payment_plan(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        installments: Vec<(u64, i128)>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        let mut plans = Self::load_plans(&env);
+        if plans.contains_key(bill_id) {
+            return Err(Error::InvalidStatus);
+        }
+        if installments.len() < 2 || installments.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidPlan);
+        }
+
+        let mut plan = Vec::new(&env);
+        let mut total = 0i128;
+        let mut last_due = 0u64;
+        for (due_date, amount) in installments.iter() {
+            if amount <= 0 || (!plan.is_empty() && due_date <= last_due) {
+                return Err(Error::InvalidPlan);
             }
+            total = total.saturating_add(amount);
+            last_due = due_date;
+            plan.push_back(Installment {
+                amount,
+                due_date,
+                paid: false,
+                paid_at: None,
+            });
+        }
+        if total != bill.amount - bill.amount_paid {
+            return Err(Error::InvalidPlan);
         }
 
-        Self::build_page(&env, staging, limit)
+        Self::extend_instance_ttl(&env);
+        plans.set(bill_id, plan);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PLANS"), &plans);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("plan"),
+            (bill_id, installments.len(), last_due),
+        );
+        Ok(())
     }
 
-    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
+    /// Record the next unpaid installment of a bill's plan as paid, like
+    /// `pay_bill_partial`. The last installment marks the bill paid.
     ///
-    /// # Example
-    /// ```text
-    /// let usdc_owed = client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC"));
-    /// ```
-    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
+    /// # Returns
+    /// The amount still outstanding on the bill
+    ///
+    /// # Errors
+    /// * `InvalidStatus` - If the bill has no plan
+    /// * plus the errors of `pay_bill_partial`
+    pub fn pay_installment(env: Env, caller: Address, bill_id: u32) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        let mut plans = Self::load_plans(&env);
+        let mut plan = plans.get(bill_id).ok_or(Error::InvalidStatus)?;
+        let index = plan
+            .iter()
+            .position(|installment| !installment.paid)
+            .ok_or(Error::InvalidStatus)? as u32;
+
+        Self::extend_instance_ttl(&env);
+        let mut installment = plan.get(index).unwrap();
+        // Earlier partial payments may leave less than the installment owed.
+        let amount = installment.amount.min(bill.amount - bill.amount_paid);
+        installment.paid = true;
+        installment.paid_at = Some(env.ledger().timestamp());
+        plan.set(index, installment);
+        plans.set(bill_id, plan);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PLANS"), &plans);
+        Self::record_partial(&env, &caller, bill_id, amount)
+    }
+
+    pub fn get_payment_plan(env: Env, bill_id: u32) -> Vec<Installment> {
+        Self::load_plans(&env)
+            .get(bill_id)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Plan progress for each of `owner`'s unpaid bills with a plan.
+    pub fn get_owner_plan_statuses(env: Env, owner: Address) -> Vec<PaymentPlanStatus> {
+        let plans = Self::load_plans(&env);
         let bills: Map<u32, Bill> = env
             .storage()
             .instance()
             .get(&symbol_short!("BILLS"))
             .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner && bill.currency == currency {
-                total += bill.amount;
+        let overdue_before = Self::overdue_before(&env, &owner);
+        let mut statuses = Vec::new(&env);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            let plan = match (plans.get(id), bills.get(id)) {
+                (Some(plan), Some(bill)) if Self::is_outstanding(&bill) => plan,
+                _ => continue,
+            };
+            let mut installments_paid = 0u32;
+            for installment in plan.iter() {
+                if installment.paid {
+                    installments_paid += 1;
+                }
             }
+            statuses.push_back(PaymentPlanStatus {
+                bill_id: id,
+                installments_paid,
+                installments_total: plan.len(),
+                on_schedule: Self::plan_on_schedule(&plan, overdue_before),
+            });
         }
-        total
+        statuses
+    }
+
+    fn load_plans(env: &Env) -> Map<u32, Vec<Installment>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PLANS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Whether every installment due before `overdue_before` is paid.
+    fn plan_on_schedule(plan: &Vec<Installment>, overdue_before: u64) -> bool {
+        plan.iter()
+            .all(|installment| installment.paid || installment.due_date >= overdue_before)
+    }
+
+    /// Bills due before this time are overdue, given `owner`'s grace period.
+    fn overdue_before(env: &Env, owner: &Address) -> u64 {
+        env.ledger()
+            .timestamp()
+            .saturating_sub(Self::get_grace_period(env.clone(), owner.clone()) as u64 * 86400)
     }
 
     // -----------------------------------------------------------------------
-    // Internal helpers
+    // Overdue escalation
     // -----------------------------------------------------------------------
 
-    fn extend_instance_ttl(env: &Env) {
+    /// Escalate the caller's bills to `head` once they are overdue (after any
+    /// grace period) by more than `after_days`. `None` removes the rule.
+    pub fn set_escalation(
+        env: Env,
+        caller: Address,
+        head: Option<Address>,
+        after_days: u32,
+        head_can_pay: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let mut rules = Self::load_escalation_rules(&env);
+        match head.clone() {
+            Some(head) => rules.set(
+                caller.clone(),
+                EscalationRule {
+                    head,
+                    after_days,
+                    head_can_pay,
+                },
+            ),
+            None => {
+                rules.remove(caller.clone());
+            }
+        }
+        Self::extend_instance_ttl(&env);
         env.storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .set(&symbol_short!("ESCALATE"), &rules);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("esc_rule"),
+            (caller, head, after_days),
+        );
+        Ok(())
     }
 
-    fn extend_archive_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    pub fn get_escalation(env: Env, owner: Address) -> Option<EscalationRule> {
+        Self::load_escalation_rules(&env).get(owner)
     }
 
-    fn update_storage_stats(env: &Env) {
+    /// Emit an `escalated` alert addressed to the head for each of `owner`'s
+    /// bills past the escalation point, once per bill. Permissionless so a
+    /// keeper can run it. Disputed bills are not escalated.
+    ///
+    /// # Returns
+    /// The IDs of the bills escalated by this call
+    pub fn escalate_overdue(env: Env, owner: Address) -> Vec<u32> {
+        let mut escalated_ids = Vec::new(&env);
+        let rule = match Self::load_escalation_rules(&env).get(owner.clone()) {
+            Some(rule) => rule,
+            None => return escalated_ids,
+        };
         let bills: Map<u32, Bill> = env
             .storage()
             .instance()
             .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(env));
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(env));
-
-        let mut active_count = 0u32;
-        let mut unpaid_amount = 0i128;
-        for (_, bill) in bills.iter() {
-            active_count += 1;
-            if !bill.paid {
-                unpaid_amount = unpaid_amount.saturating_add(bill.amount);
+            .unwrap_or_else(|| Map::new(&env));
+        let now = env.ledger().timestamp();
+        let overdue_from = Self::get_grace_period(env.clone(), owner.clone()) as u64 * 86400;
+        let mut escalated = Self::load_escalated(&env);
+        let plans = Self::load_plans(&env);
+        let overdue_before = Self::overdue_before(&env, &owner);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            let bill = match bills.get(id) {
+                Some(bill) if !bill.paid && bill.status == BillStatus::Open => bill,
+                _ => continue,
+            };
+            if plans
+                .get(id)
+                .is_some_and(|plan| Self::plan_on_schedule(&plan, overdue_before))
+            {
+                continue;
+            }
+            let overdue_at = bill.due_date + overdue_from;
+            if escalated.contains_key(id) || now <= overdue_at + rule.after_days as u64 * 86400 {
+                continue;
             }
+            escalated.set(id, now);
+            escalated_ids.push_back(id);
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Alert,
+                EventPriority::High,
+                symbol_short!("escalated"),
+                (
+                    id,
+                    rule.head.clone(),
+                    owner.clone(),
+                    (now - overdue_at) / 86400,
+                ),
+            );
+        }
+        if !escalated_ids.is_empty() {
+            Self::extend_instance_ttl(&env);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ESCALATED"), &escalated);
+        }
+        escalated_ids
+    }
+
+    /// When the owner's rule allows it, let the head pay an escalated bill
+    /// in `token` on the owner's behalf, from the head's own earmarked and
+    /// funded balances or else the head's wallet.
+    ///
+    /// # Returns
+    /// The amount paid
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the head, or the rule does not let the head pay
+    /// * `InvalidStatus` - If the bill has not been escalated
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `PayeeNotRegistered` - If the registry is required and the payee is not in it
+    pub fn pay_escalated_bill(
+        env: Env,
+        head: Address,
+        bill_id: u32,
+        token: Address,
+    ) -> Result<i128, Error> {
+        head.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        match Self::load_escalation_rules(&env).get(bill.owner.clone()) {
+            Some(rule) if rule.head == head && rule.head_can_pay => {}
+            _ => return Err(Error::Unauthorized),
+        }
+        if !Self::load_escalated(&env).contains_key(bill_id) {
+            return Err(Error::InvalidStatus);
         }
+        Self::check_payable(&env, &bill, &bill.owner)?;
+        Self::require_registered_payee(&env, &bill.payee)?;
+
+        Self::extend_instance_ttl(&env);
+        let amount = bill.amount - bill.amount_paid;
+        let client = TokenClient::new(&env, &token);
+        if Self::bill_funds(&env, &head, &token) >= amount {
+            Self::debit_bill_funds(&env, &head, &token, amount);
+            client.transfer(&env.current_contract_address(), &bill.payee, &amount);
+        } else {
+            client.transfer(&head, &bill.payee, &amount);
+        }
+        Self::mark_paid(&env, &head, bill_id, Some(token.clone()))?;
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("head_paid"),
+            (bill_id, head, bill.owner, amount),
+        );
+        Ok(amount)
+    }
+
+    fn load_escalation_rules(env: &Env) -> Map<Address, EscalationRule> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ESCALATE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Escalated bill IDs with the time they were escalated.
+    fn load_escalated(env: &Env) -> Map<u32, u64> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ESCALATED"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Variable amounts
+    // -----------------------------------------------------------------------
+
+    /// Mark a bill as variable, e.g. electricity, with `estimated` as its
+    /// amount until the actual amount is posted. Later occurrences of a
+    /// recurring bill start again from the estimate.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If estimated is not positive or below what is already paid
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    pub fn set_estimated_amount(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        estimated: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+        if estimated <= 0 || estimated < bill.amount_paid {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut estimates = Self::load_estimates(&env);
+        let estimate = match estimates.get(bill_id) {
+            Some(mut estimate) => {
+                estimate.estimated = estimated;
+                estimate
+            }
+            None => AmountEstimate {
+                estimated,
+                actual: None,
+                posted_by: None,
+                posted_at: None,
+            },
+        };
+        if estimate.actual.is_none() {
+            Self::set_bill_amount(&env, bill_id, estimated);
+        }
+        estimates.set(bill_id, estimate);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ESTIMATES"), &estimates);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("estimate"),
+            (bill_id, estimated),
+        );
+        Ok(())
+    }
+
+    /// Post the actual amount of a variable bill before its due date. The
+    /// owner or the payee may post, and may correct it until the due date.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If actual is not positive or below what is already paid
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is neither the owner nor the payee
+    /// * `InvalidStatus` - If the bill has no estimate, or a scheduled payment
+    ///   reserves less than the new outstanding amount
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `BillIsSplit` - If the bill is split among members
+    /// * `PastDueDate` - If the due date has passed
+    pub fn post_actual_amount(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        actual: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if caller != bill.owner && caller != bill.payee {
+            return Err(Error::Unauthorized);
+        }
+        Self::check_payable(&env, &bill, &bill.owner)?;
+        let mut estimates = Self::load_estimates(&env);
+        let mut estimate = estimates.get(bill_id).ok_or(Error::InvalidStatus)?;
+        if actual <= 0 || actual < bill.amount_paid {
+            return Err(Error::InvalidAmount);
+        }
+        if env.ledger().timestamp() > bill.due_date {
+            return Err(Error::PastDueDate);
+        }
+        if let Some(payment) = Self::load_scheduled_payments(&env).get(bill_id) {
+            if actual - bill.amount_paid > payment.amount {
+                return Err(Error::InvalidStatus);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::set_bill_amount(&env, bill_id, actual);
+        estimate.actual = Some(actual);
+        estimate.posted_by = Some(caller.clone());
+        estimate.posted_at = Some(env.ledger().timestamp());
+        estimates.set(bill_id, estimate);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ESTIMATES"), &estimates);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("actual"),
+            (bill_id, caller, actual),
+        );
+        Ok(())
+    }
+
+    pub fn get_amount_estimate(env: Env, bill_id: u32) -> Option<AmountEstimate> {
+        Self::load_estimates(&env).get(bill_id)
+    }
+
+    /// Estimate-vs-actual totals over `owner`'s bills, for budgeting.
+    pub fn get_estimate_summary(env: Env, owner: Address) -> EstimateSummary {
+        let estimates = Self::load_estimates(&env);
+        let mut summary = EstimateSummary {
+            bill_count: 0,
+            estimated_total: 0,
+            actual_total: 0,
+        };
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if let Some(AmountEstimate {
+                estimated,
+                actual: Some(actual),
+                ..
+            }) = estimates.get(id)
+            {
+                summary.bill_count += 1;
+                summary.estimated_total = summary.estimated_total.saturating_add(estimated);
+                summary.actual_total = summary.actual_total.saturating_add(actual);
+            }
+        }
+        summary
+    }
+
+    fn load_estimates(env: &Env) -> Map<u32, AmountEstimate> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ESTIMATES"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Start the next occurrence of a variable bill from the estimate.
+    /// Returns the estimated amount, or `None` for a fixed-amount bill.
+    fn carry_estimate(env: &Env, from: u32, to: u32) -> Option<i128> {
+        let mut estimates = Self::load_estimates(env);
+        let estimated = estimates.get(from)?.estimated;
+        estimates.set(
+            to,
+            AmountEstimate {
+                estimated,
+                actual: None,
+                posted_by: None,
+                posted_at: None,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ESTIMATES"), &estimates);
+        Some(estimated)
+    }
+
+    fn set_bill_amount(env: &Env, bill_id: u32, amount: i128) {
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(env));
+        if let Some(mut bill) = bills.get(bill_id) {
+            let delta = amount - bill.amount;
+            bill.amount = amount;
+            let owner = bill.owner.clone();
+            bills.set(bill_id, bill);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("BILLS"), &bills);
+            if delta != 0 {
+                Self::adjust_unpaid_total(env, &owner, delta);
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Reminders
+    // -----------------------------------------------------------------------
+
+    /// Emit a `due_soon` reminder event for each unpaid bill of `owner`
+    /// falling due within the next `horizon_days`.
+    ///
+    /// Each event carries `(bill_id, days_until_due, amount)`, where
+    /// `days_until_due` is rounded down and `amount` is what is still
+    /// outstanding. Overdue bills are not included. Anyone may call this,
+    /// typically the notification service on a schedule.
+    ///
+    /// # Returns
+    /// The number of reminders emitted
+    pub fn emit_upcoming_due(env: Env, owner: Address, horizon_days: u32) -> u32 {
+        let now = env.ledger().timestamp();
+        let horizon_end = now.saturating_add(horizon_days as u64 * 86400);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut count = 0u32;
+        for (bill_id, bill) in bills.iter() {
+            if bill.owner != owner || !Self::is_outstanding(&bill) || bill.due_date < now {
+                continue;
+            }
+            if bill.due_date > horizon_end {
+                continue;
+            }
+            let days_until_due = ((bill.due_date - now) / 86400) as u32;
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Alert,
+                EventPriority::Medium,
+                symbol_short!("due_soon"),
+                (bill_id, days_until_due, bill.amount - bill.amount_paid),
+            );
+            count += 1;
+        }
+        count
+    }
+
+    // -----------------------------------------------------------------------
+    // Auto-pay
+    // -----------------------------------------------------------------------
+
+    /// Enable or disable auto-pay for a bill.
+    ///
+    /// With auto-pay enabled, anyone may call `process_due_autopay` to pay the
+    /// bill from the owner's funded balance in `token` once it is due. Bills
+    /// above `max_amount` are skipped. The setting follows a recurring bill
+    /// to its next occurrence. Pass `None` to disable.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `InvalidAmount` - If max_amount is not positive
+    pub fn set_autopay(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        token: Option<Address>,
+        max_amount: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        Self::check_payable(&env, &bill, &caller)?;
+
+        let mut settings = Self::load_autopay(&env);
+        match token.clone() {
+            Some(token) => {
+                if max_amount <= 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                Self::require_approval(&env, &caller, max_amount);
+                settings.set(bill_id, AutoPay { token, max_amount });
+            }
+            None => {
+                settings.remove(bill_id);
+            }
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("AUTOPAY"), &settings);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("autopay"),
+            (bill_id, token, max_amount),
+        );
+        Ok(())
+    }
+
+    pub fn get_autopay(env: Env, bill_id: u32) -> Option<AutoPay> {
+        Self::load_autopay(&env).get(bill_id)
+    }
+
+    /// Pay every due, unpaid bill of `owner` that has auto-pay enabled.
+    ///
+    /// Permissionless: funds only ever move from the owner's funded balance
+    /// to the bill's payee. A bill is skipped, with an `ap_skip` event, when
+    /// it is disputed, split, exceeds its `max_amount`, the balance cannot cover it, or
+    /// its payee is not registered while the registry is required.
+    ///
+    /// # Returns
+    /// The ids of the bills paid
+    pub fn process_due_autopay(env: Env, owner: Address) -> Result<Vec<u32>, Error> {
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let settings = Self::load_autopay(&env);
+        let splits = Self::load_splits(&env);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut paid = Vec::new(&env);
+        for (bill_id, bill) in bills.iter() {
+            if bill.owner != owner || !Self::is_outstanding(&bill) || bill.due_date > now {
+                continue;
+            }
+            let autopay = match settings.get(bill_id) {
+                Some(autopay) => autopay,
+                None => continue,
+            };
+
+            let balance = Self::bill_funds(&env, &owner, &autopay.token);
+            let amount = bill.amount - bill.amount_paid;
+            let reason = if bill.status != BillStatus::Open {
+                Some(symbol_short!("disputed"))
+            } else if splits.contains_key(bill_id) {
+                Some(symbol_short!("split"))
+            } else if amount > autopay.max_amount {
+                Some(symbol_short!("over_cap"))
+            } else if amount > balance {
+                Some(symbol_short!("no_funds"))
+            } else if Self::require_registered_payee(&env, &bill.payee).is_err() {
+                Some(symbol_short!("no_payee"))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                RemitwiseEvents::emit(
+                    &env,
+                    EventCategory::Alert,
+                    EventPriority::Medium,
+                    symbol_short!("ap_skip"),
+                    (bill_id, reason, amount),
+                );
+                continue;
+            }
+
+            Self::debit_bill_funds(&env, &owner, &autopay.token, amount);
+            TokenClient::new(&env, &autopay.token).transfer(
+                &env.current_contract_address(),
+                &bill.payee,
+                &amount,
+            );
+            Self::mark_paid(&env, &owner, bill_id, Some(autopay.token.clone()))?;
+
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Transaction,
+                EventPriority::High,
+                symbol_short!("autopaid"),
+                (bill_id, bill.payee, autopay.token, amount),
+            );
+            paid.push_back(bill_id);
+        }
+        Ok(paid)
+    }
+
+    fn load_autopay(env: &Env) -> Map<u32, AutoPay> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("AUTOPAY"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Move the auto-pay setting of `from` to `to`; `to == 0` just drops it.
+    fn carry_autopay(env: &Env, from: u32, to: u32) {
+        let mut settings = Self::load_autopay(env);
+        if let Some(autopay) = settings.get(from) {
+            settings.remove(from);
+            if to != 0 {
+                settings.set(to, autopay);
+            }
+            env.storage()
+                .instance()
+                .set(&symbol_short!("AUTOPAY"), &settings);
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Bill splitting
+    // -----------------------------------------------------------------------
+
+    /// Divide a bill among members, e.g. family wallet members sharing rent.
+    ///
+    /// Each member settles their own share with `pay_share`; the bill flips
+    /// to paid once every share is settled. A split bill can no longer be
+    /// paid whole. Shares may be changed until the first one is settled, and
+    /// the split repeats, unsettled, on the next occurrence of a recurring
+    /// bill.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `InvalidSplit` - If a share is not positive, a member appears twice,
+    ///   the shares do not add up to the bill amount, or a share or part of
+    ///   the bill is already paid
+    pub fn split_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        shares: Vec<(Address, i128)>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(&bill) {
+            return Err(Error::BillCancelled);
+        }
+        if bill.amount_paid > 0 {
+            return Err(Error::InvalidSplit);
+        }
+
+        let mut splits = Self::load_splits(&env);
+        if let Some(existing) = splits.get(bill_id) {
+            if existing.iter().any(|share| share.paid) {
+                return Err(Error::InvalidSplit);
+            }
+        }
+        if shares.is_empty() || shares.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidSplit);
+        }
+        let mut members: Map<Address, bool> = Map::new(&env);
+        let mut split = Vec::new(&env);
+        let mut total = 0i128;
+        for (member, amount) in shares.iter() {
+            if amount <= 0 || members.contains_key(member.clone()) {
+                return Err(Error::InvalidSplit);
+            }
+            members.set(member.clone(), true);
+            total = total.checked_add(amount).ok_or(Error::InvalidSplit)?;
+            split.push_back(BillShare {
+                member,
+                amount,
+                paid: false,
+                paid_at: None,
+            });
+        }
+        if total != bill.amount {
+            return Err(Error::InvalidSplit);
+        }
+
+        Self::extend_instance_ttl(&env);
+        splits.set(bill_id, split);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLITS"), &splits);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("split"),
+            (bill_id, shares.len()),
+        );
+        Ok(())
+    }
+
+    /// Settle the caller's share of a split bill in `token`, paid directly to
+    /// the bill's payee.
+    ///
+    /// # Returns
+    /// `true` if this was the last outstanding share and the bill is now paid
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist or is not split
+    /// * `Unauthorized` - If member has no share in the bill
+    /// * `BillAlreadyPaid` - If the member's share is already settled
+    pub fn pay_share(
+        env: Env,
+        member: Address,
+        bill_id: u32,
+        token: Address,
+    ) -> Result<bool, Error> {
+        member.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        Self::require_registered_payee(&env, &bill.payee)?;
+        let mut splits = Self::load_splits(&env);
+        let mut split = splits.get(bill_id).ok_or(Error::BillNotFound)?;
+        let index = split
+            .iter()
+            .position(|share| share.member == member)
+            .ok_or(Error::Unauthorized)? as u32;
+        let mut share = split.get(index).unwrap();
+        if share.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&member, &bill.payee, &share.amount);
+        share.paid = true;
+        share.paid_at = Some(env.ledger().timestamp());
+        let amount = share.amount;
+        split.set(index, share);
+        let settled = split.iter().all(|share| share.paid);
+        splits.set(bill_id, split);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLITS"), &splits);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("share_pd"),
+            (bill_id, member, token.clone(), amount),
+        );
+        if settled {
+            Self::mark_paid(&env, &bill.owner, bill_id, Some(token))?;
+        }
+        Ok(settled)
+    }
+
+    pub fn get_bill_shares(env: Env, bill_id: u32) -> Vec<BillShare> {
+        Self::load_splits(&env)
+            .get(bill_id)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    fn load_splits(env: &Env) -> Map<u32, Vec<BillShare>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SPLITS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Move the split of `from` to `to` with every share reset to unpaid;
+    /// `to == 0` just drops it.
+    fn carry_split(env: &Env, from: u32, to: u32) {
+        let mut splits = Self::load_splits(env);
+        if let Some(split) = splits.get(from) {
+            splits.remove(from);
+            if to != 0 {
+                let mut next = Vec::new(env);
+                for mut share in split.iter() {
+                    share.paid = false;
+                    share.paid_at = None;
+                    next.push_back(share);
+                }
+                splits.set(to, next);
+            }
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SPLITS"), &splits);
+        }
+    }
+
+    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        bills.get(bill_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // PAGINATED LIST QUERIES
+    // -----------------------------------------------------------------------
+
+    /// Get a page of unpaid bills for `owner`.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose bills to return
+    /// * `cursor` – start after this bill ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`.
+    /// When `next_cursor == 0` there are no more pages.
+    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if id <= cursor {
+                continue;
+            }
+            let bill = match bills.get(id) {
+                Some(bill) => bill,
+                None => continue,
+            };
+            if !Self::is_outstanding(&bill) {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner`.
+    ///
+    /// Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        owner.require_auth();
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if id <= cursor {
+                continue;
+            }
+            let bill = match bills.get(id) {
+                Some(bill) => bill,
+                None => continue,
+            };
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Get a page of `owner`'s bills by position in the owner index, oldest
+    /// first, without scanning other owners' bills.
+    ///
+    /// Unlike the cursor-based queries, `next_cursor` here is the `offset` to
+    /// pass for the next page (0 when there are no more pages).
+    pub fn get_bills_by_owner(env: Env, owner: Address, offset: u32, limit: u32) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let ids = Self::owner_bill_ids(&env, &owner);
+
+        let mut items = Vec::new(&env);
+        let end = ids.len().min(offset.saturating_add(limit));
+        for i in offset..end {
+            if let Some(bill) = ids.get(i).and_then(|id| bills.get(id)) {
+                items.push_back(bill);
+            }
+        }
+        let next_cursor = if end < ids.len() { end } else { 0 };
+        let count = items.len();
+        BillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    /// Aggregate counts and amounts over `owner`'s active (unarchived) bills.
+    pub fn get_owner_bill_totals(env: Env, owner: Address) -> OwnerBillTotals {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut totals = OwnerBillTotals {
+            bill_count: 0,
+            paid_count: 0,
+            unpaid_count: 0,
+            total_amount: 0,
+            paid_amount: 0,
+            unpaid_amount: 0,
+        };
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            let bill = match bills.get(id) {
+                Some(bill) if !Self::is_cancelled(&bill) => bill,
+                _ => continue,
+            };
+            totals.bill_count += 1;
+            totals.total_amount += bill.amount;
+            totals.paid_amount += bill.amount_paid;
+            if bill.paid {
+                totals.paid_count += 1;
+            } else {
+                totals.unpaid_count += 1;
+                totals.unpaid_amount += bill.amount - bill.amount_paid;
+            }
+        }
+        totals
+    }
+
+    /// Every bill `owner` paid between `period_start` and `period_end`
+    /// (inclusive), ordered by `paid_at`, from active bills, archived bills
+    /// and the compact history alike. For the reporting contract and
+    /// off-chain tax or NGO tooling.
+    pub fn get_payment_statement(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<StatementEntry> {
+        let mut statement = Vec::new(&env);
+        let in_period = |paid_at: u64| paid_at >= period_start && paid_at <= period_end;
+
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if let Some(Bill {
+                paid: true,
+                paid_at: Some(paid_at),
+                payee,
+                amount,
+                ..
+            }) = bills.get(id)
+            {
+                if in_period(paid_at) {
+                    Self::insert_by_paid_at(&mut statement, id, payee, amount, paid_at);
+                }
+            }
+        }
+
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        for (id, bill) in archived.iter() {
+            if bill.owner == owner && in_period(bill.paid_at) {
+                Self::insert_by_paid_at(&mut statement, id, bill.payee, bill.amount, bill.paid_at);
+            }
+        }
+
+        for record in Self::load_history(&env, &owner).iter() {
+            if in_period(record.paid_at) {
+                Self::insert_by_paid_at(
+                    &mut statement,
+                    record.id,
+                    record.payee,
+                    record.amount,
+                    record.paid_at,
+                );
+            }
+        }
+        statement
+    }
+
+    fn insert_by_paid_at(
+        statement: &mut Vec<StatementEntry>,
+        bill_id: u32,
+        payee: Address,
+        amount: i128,
+        paid_at: u64,
+    ) {
+        let pos = statement
+            .iter()
+            .position(|entry| entry.paid_at > paid_at)
+            .unwrap_or(statement.len() as usize);
+        statement.insert(
+            pos as u32,
+            StatementEntry {
+                bill_id,
+                payee,
+                amount,
+                paid_at,
+            },
+        );
+    }
+
+    /// Get a page of overdue bills across all owners: unpaid, not disputed,
+    /// and past due_date plus the owner's grace period.
+    ///
+    /// Same cursor/limit semantics.
+    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let current_time = env.ledger().timestamp();
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let grace_periods = Self::load_grace_periods(&env);
+        let plans = Self::load_plans(&env);
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for (id, bill) in bills.iter() {
+            if id <= cursor {
+                continue;
+            }
+            if bill.paid || bill.status != BillStatus::Open {
+                continue;
+            }
+            let grace = grace_periods.get(bill.owner.clone()).unwrap_or(0) as u64 * 86400;
+            let on_time = match plans.get(id) {
+                Some(plan) => Self::plan_on_schedule(&plan, current_time.saturating_sub(grace)),
+                None => bill.due_date + grace >= current_time,
+            };
+            if on_time {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Admin-only: get ALL bills (any owner), paginated.
+    pub fn get_all_bills(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<BillPage, Error> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for (id, bill) in bills.iter() {
+            if id <= cursor {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Ok(Self::build_page(&env, staging, limit))
+    }
+
+    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
+    /// `next_cursor` is set to the last *returned* item's ID so the next call's
+    /// `id <= cursor` filter correctly skips past it.
+    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
+        let n = staging.len();
+        let has_next = n > limit;
+        let mut items = Vec::new(env);
+        let mut next_cursor: u32 = 0;
+
+        // Emit all items, or all-but-last if there is a next page
+        let take = if has_next { n - 1 } else { n };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+
+        // next_cursor = last returned item's ID (NOT the first skipped item)
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        BillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Backward-compat helpers
+    // -----------------------------------------------------------------------
+
+    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
+    /// Only safe for owners with a small number of bills. Prefer the
+    /// paginated `get_unpaid_bills` for production use.
+    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for (_, bill) in bills.iter() {
+            if Self::is_outstanding(&bill) && bill.owner == owner {
+                result.push_back(bill);
+            }
+        }
+        result
+    }
+
+    // -----------------------------------------------------------------------
+    // Archived bill queries (paginated)
+    // -----------------------------------------------------------------------
+
+    /// Get a page of archived bills for `owner`.
+    pub fn get_archived_bills(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> ArchivedBillPage {
+        let limit = Self::clamp_limit(limit);
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
+        for (id, bill) in archived.iter() {
+            if id <= cursor {
+                continue;
+            }
+            if bill.owner != owner {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        let has_next = staging.len() > limit;
+        let mut items = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let take = if has_next {
+            staging.len() - 1
+        } else {
+            staging.len()
+        };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        ArchivedBillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        archived.get(bill_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Remaining operations
+    // -----------------------------------------------------------------------
+
+    /// Cancel an unpaid bill with a reason. The bill stays on record as
+    /// `Cancelled`, stops recurring and no longer counts as unpaid.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is already cancelled
+    pub fn cancel_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        reason: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(&bill) {
+            return Err(Error::BillCancelled);
+        }
+        let removed_unpaid_amount = bill.amount - bill.amount_paid;
+        bill.status = BillStatus::Cancelled(reason.clone());
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        Self::carry_autopay(&env, bill_id, 0);
+        Self::carry_split(&env, bill_id, 0);
+        Self::release_scheduled_payment(&env, bill_id);
+        if removed_unpaid_amount > 0 {
+            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
+        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("canceled"),
+            (bill_id, reason),
+        );
+        Ok(())
+    }
+
+    /// Start or stop a recurring series.
+    ///
+    /// Paying a recurring bill creates its next occurrence at
+    /// `due_date + frequency_days`, carrying over the payee, amount and
+    /// category. Setting `recurring` to false on the current unpaid bill
+    /// makes it the last one in the series.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `InvalidFrequency` - If enabling a bill with no `frequency_days`
+    pub fn set_recurring(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        recurring: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(&bill) {
+            return Err(Error::BillCancelled);
+        }
+        if recurring && bill.frequency_days == 0 {
+            return Err(Error::InvalidFrequency);
+        }
+
+        Self::extend_instance_ttl(&env);
+        bill.recurring = recurring;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("recurring"),
+            (bill_id, recurring),
+        );
+        Ok(())
+    }
+
+    /// Offer an unpaid bill to `new_owner`, e.g. when the eldest child takes
+    /// over the electricity bill. Nothing changes until the new owner calls
+    /// `accept_bill_transfer`; a later offer replaces an earlier one.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner, or is `new_owner`
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    pub fn transfer_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        new_owner: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller || new_owner == caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(&bill) {
+            return Err(Error::BillCancelled);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut offers = Self::load_transfer_offers(&env);
+        offers.set(bill_id, new_owner.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("XFER_OFR"), &offers);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::Medium,
+            symbol_short!("xfer_ofr"),
+            (bill_id, caller, new_owner),
+        );
+        Ok(())
+    }
+
+    /// Withdraw a pending transfer offer.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `InvalidStatus` - If no transfer is pending
+    pub fn cancel_bill_transfer(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        let bill = Self::get_bill(env.clone(), bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        let mut offers = Self::load_transfer_offers(&env);
+        if offers.remove(bill_id).is_none() {
+            return Err(Error::InvalidStatus);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("XFER_OFR"), &offers);
+        Ok(())
+    }
+
+    /// Accept a bill offered with `transfer_bill`. The bill, its plan, split
+    /// and future occurrences move to the caller; the previous owner's
+    /// auto-pay setting is dropped and any scheduled payment is refunded to
+    /// them.
+    ///
+    /// # Errors
+    /// * `InvalidStatus` - If no transfer is pending for bill_id
+    /// * `Unauthorized` - If the bill was offered to someone else
+    /// * `BillAlreadyPaid` - If the bill was paid in the meantime
+    /// * `BillCancelled` - If the bill was cancelled in the meantime
+    pub fn accept_bill_transfer(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        let mut offers = Self::load_transfer_offers(&env);
+        let new_owner = offers.get(bill_id).ok_or(Error::InvalidStatus)?;
+        if new_owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if Self::is_cancelled(&bill) {
+            return Err(Error::BillCancelled);
+        }
+
+        Self::extend_instance_ttl(&env);
+        offers.remove(bill_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("XFER_OFR"), &offers);
+        Self::carry_autopay(&env, bill_id, 0);
+        Self::release_scheduled_payment(&env, bill_id);
+
+        let previous_owner = bill.owner.clone();
+        let outstanding = bill.amount - bill.amount_paid;
+        bill.owner = caller.clone();
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        Self::unindex_bill(&env, &previous_owner, bill_id);
+        Self::index_bill(&env, &caller, bill_id);
+        Self::adjust_unpaid_total(&env, &previous_owner, -outstanding);
+        Self::adjust_unpaid_total(&env, &caller, outstanding);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Access,
+            EventPriority::High,
+            symbol_short!("xfer"),
+            (bill_id, previous_owner, caller),
+        );
+        Ok(())
+    }
+
+    /// The address a bill has been offered to, if a transfer is pending.
+    pub fn get_bill_transfer(env: Env, bill_id: u32) -> Option<Address> {
+        Self::load_transfer_offers(&env).get(bill_id)
+    }
+
+    fn load_transfer_offers(env: &Env) -> Map<u32, Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("XFER_OFR"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Dispute an unpaid bill, e.g. a wrong amount from the provider.
+    ///
+    /// A disputed bill is skipped by auto-pay and left out of overdue
+    /// queries until `resolve_dispute` reopens it. The owner can still pay
+    /// it manually.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillCancelled` - If the bill is cancelled
+    /// * `InvalidStatus` - If the bill is already disputed
+    pub fn dispute_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        reason: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        match bill.status {
+            BillStatus::Open => {}
+            BillStatus::Disputed(_) => return Err(Error::InvalidStatus),
+            BillStatus::Cancelled(_) => return Err(Error::BillCancelled),
+        }
+
+        Self::extend_instance_ttl(&env);
+        bill.status = BillStatus::Disputed(reason.clone());
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Alert,
+            EventPriority::Medium,
+            symbol_short!("disputed"),
+            (bill_id, reason),
+        );
+        Ok(())
+    }
+
+    /// Reopen a disputed bill.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill_id does not exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `InvalidStatus` - If the bill is not disputed
+    pub fn resolve_dispute(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if !matches!(bill.status, BillStatus::Disputed(_)) {
+            return Err(Error::InvalidStatus);
+        }
+
+        Self::extend_instance_ttl(&env);
+        bill.status = BillStatus::Open;
+        bills.set(bill_id, bill);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("resolved"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    pub fn archive_paid_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let current_time = env.ledger().timestamp();
+        let mut archived_count = 0u32;
+        let mut to_remove: Vec<u32> = Vec::new(&env);
+
+        for (id, bill) in bills.iter() {
+            if let Some(paid_at) = bill.paid_at {
+                if bill.paid && paid_at < before_timestamp {
+                    let archived_bill = ArchivedBill {
+                        id: bill.id,
+                        owner: bill.owner.clone(),
+                        payee: bill.payee.clone(),
+                        name: bill.name.clone(),
+                        category: bill.category.clone(),
+                        amount: bill.amount,
+                        paid_at,
+                        archived_at: current_time,
+                        currency: bill.currency.clone(),
+                    };
+                    archived.set(id, archived_bill);
+                    to_remove.push_back(id);
+                    archived_count += 1;
+                }
+            }
+        }
+
+        let mut index = Self::load_owner_index(&env);
+        for id in to_remove.iter() {
+            if let Some(bill) = bills.get(id) {
+                if let Some(mut ids) = index.get(bill.owner.clone()) {
+                    if let Some(pos) = ids.first_index_of(id) {
+                        ids.remove(pos);
+                    }
+                    index.set(bill.owner, ids);
+                }
+            }
+            bills.remove(id);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("OWN_BILLS"), &index);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+
+        Self::extend_archive_ttl(&env);
+        Self::update_storage_stats(&env);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("archived"),
+            archived_count,
+        );
+
+        Ok(archived_count)
+    }
+
+    /// Move the owner's bills paid before `before_timestamp` into a compact
+    /// per-owner history and prune their full records, receipts and other
+    /// per-bill entries, so active queries stay fast and storage bounded.
+    ///
+    /// # Returns
+    /// The number of bills moved
+    pub fn archive_paid_before(
+        env: Env,
+        owner: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut receipts = Self::load_receipts(&env);
+        let mut estimates = Self::load_estimates(&env);
+        let mut escalated = Self::load_escalated(&env);
+        let mut conversions = Self::load_conversions(&env);
+        let mut plans = Self::load_plans(&env);
+        let mut history = Self::load_history(&env, &owner);
+        let mut kept = Vec::new(&env);
+        let mut moved = 0u32;
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            match bills.get(id) {
+                Some(Bill {
+                    paid: true,
+                    paid_at: Some(paid_at),
+                    amount,
+                    payee,
+                    ..
+                }) if paid_at < before_timestamp => {
+                    history.push_back(BillRecord {
+                        id,
+                        amount,
+                        paid_at,
+                        payee,
+                    });
+                    bills.remove(id);
+                    receipts.remove(id);
+                    estimates.remove(id);
+                    escalated.remove(id);
+                    conversions.remove(id);
+                    plans.remove(id);
+                    moved += 1;
+                }
+                _ => kept.push_back(id),
+            }
+        }
+        if moved == 0 {
+            return Ok(0);
+        }
+
+        let mut index = Self::load_owner_index(&env);
+        index.set(owner.clone(), kept);
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("BILLS"), &bills);
+        storage.set(&symbol_short!("OWN_BILLS"), &index);
+        storage.set(&symbol_short!("RECEIPTS"), &receipts);
+        storage.set(&symbol_short!("ESTIMATES"), &estimates);
+        storage.set(&symbol_short!("ESCALATED"), &escalated);
+        storage.set(&symbol_short!("CONVERTS"), &conversions);
+        storage.set(&symbol_short!("PLANS"), &plans);
+        let key = (symbol_short!("HISTORY"), owner.clone());
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(
+            &key,
+            ARCHIVE_LIFETIME_THRESHOLD,
+            ARCHIVE_BUMP_AMOUNT,
+        );
+        Self::update_storage_stats(&env);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Low,
+            symbol_short!("pruned"),
+            (owner, moved, before_timestamp),
+        );
+        Ok(moved)
+    }
+
+    /// A page of the owner's compact bill history, oldest first.
+    pub fn get_bill_history(env: Env, owner: Address, offset: u32, limit: u32) -> Vec<BillRecord> {
+        let history = Self::load_history(&env, &owner);
+        let end = offset
+            .saturating_add(Self::clamp_limit(limit))
+            .min(history.len());
+        if offset >= end {
+            return Vec::new(&env);
+        }
+        history.slice(offset..end)
+    }
+
+    fn load_history(env: &Env, owner: &Address) -> Vec<BillRecord> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("HISTORY"), owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::RESTORE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
+
+        if archived_bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let restored_bill = Bill {
+            id: archived_bill.id,
+            owner: archived_bill.owner.clone(),
+            payee: archived_bill.payee.clone(),
+            name: archived_bill.name.clone(),
+            category: archived_bill.category.clone(),
+            amount: archived_bill.amount,
+            amount_paid: archived_bill.amount,
+            due_date: env.ledger().timestamp() + 2592000,
+            recurring: false,
+            frequency_days: 0,
+            paid: true,
+            created_at: archived_bill.paid_at,
+            paid_at: Some(archived_bill.paid_at),
+            schedule_id: None,
+            status: BillStatus::Open,
+            currency: archived_bill.currency.clone(),
+        };
+
+        bills.set(bill_id, restored_bill);
+        archived.remove(bill_id);
+        Self::index_bill(&env, &caller, bill_id);
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+
+        Self::update_storage_stats(&env);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("restored"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    pub fn bulk_cleanup_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut deleted_count = 0u32;
+        let mut to_remove: Vec<u32> = Vec::new(&env);
+
+        for (id, bill) in archived.iter() {
+            if bill.archived_at < before_timestamp {
+                to_remove.push_back(id);
+                deleted_count += 1;
+            }
+        }
+
+        for id in to_remove.iter() {
+            archived.remove(id);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCH_BILL"), &archived);
+        Self::update_storage_stats(&env);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("cleaned"),
+            deleted_count,
+        );
+        Ok(deleted_count)
+    }
+
+    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap() {
+            return Err(Error::BatchTooLarge);
+        }
+        let bills_map: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let splits = Self::load_splits(&env);
+        for id in bill_ids.iter() {
+            let bill = bills_map.get(id).ok_or(Error::BillNotFound)?;
+            if bill.owner != caller {
+                return Err(Error::Unauthorized);
+            }
+            if bill.paid {
+                return Err(Error::BillAlreadyPaid);
+            }
+            if Self::is_cancelled(&bill) {
+                return Err(Error::BillCancelled);
+            }
+            if splits.contains_key(id) {
+                return Err(Error::BillIsSplit);
+            }
+        }
+        Self::extend_instance_ttl(&env);
+        let mut bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let current_time = env.ledger().timestamp();
+        let mut next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut paid_count = 0u32;
+        let mut unpaid_delta = 0i128;
+        for id in bill_ids.iter() {
+            let mut bill = bills.get(id).ok_or(Error::BillNotFound)?;
+            if bill.owner != caller || bill.paid {
+                return Err(Error::BatchValidationFailed);
+            }
+            let amount = bill.amount - bill.amount_paid;
+            unpaid_delta = unpaid_delta.saturating_sub(amount);
+            bill.paid = true;
+            bill.paid_at = Some(current_time);
+            bill.amount_paid = bill.amount;
+            if bill.recurring {
+                next_id = next_id.saturating_add(1);
+                let next_amount = Self::carry_estimate(&env, id, next_id).unwrap_or(bill.amount);
+                unpaid_delta = unpaid_delta.saturating_add(next_amount);
+                let next_due_date = bill.due_date + (bill.frequency_days as u64 * 86400);
+                let next_bill = Bill {
+                    id: next_id,
+                    owner: bill.owner.clone(),
+                    payee: bill.payee.clone(),
+                    name: bill.name.clone(),
+                    category: bill.category.clone(),
+                    amount: next_amount,
+                    amount_paid: 0,
+                    due_date: next_due_date,
+                    recurring: true,
+                    frequency_days: bill.frequency_days,
+                    paid: false,
+                    created_at: current_time,
+                    paid_at: None,
+                    schedule_id: bill.schedule_id,
+                    status: BillStatus::Open,
+                    currency: bill.currency.clone(),
+                };
+                bills.set(next_id, next_bill);
+                Self::index_bill(&env, &caller, next_id);
+                Self::carry_autopay(&env, id, next_id);
+                Self::carry_split(&env, id, next_id);
+            }
+            Self::store_receipt(&env, id, &caller, &bill.payee, bill.amount, None);
+            bills.set(id, bill);
+            paid_count += 1;
+            RemitwiseEvents::emit(
+                &env,
+                EventCategory::Transaction,
+                EventPriority::High,
+                symbol_short!("paid"),
+                (id, caller.clone(), amount),
+            );
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILLS"), &bills);
+        if unpaid_delta != 0 {
+            Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
+        }
+        Self::update_storage_stats(&env);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("batch_pay"),
+            (paid_count, caller),
+        );
+        Ok(paid_count)
+    }
+
+    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
+        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
+            if let Some(total) = totals.get(owner.clone()) {
+                return total;
+            }
+        }
+
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut total = 0i128;
+        for (_, bill) in bills.iter() {
+            if Self::is_outstanding(&bill) && bill.owner == owner {
+                total += bill.amount - bill.amount_paid;
+            }
+        }
+        total
+    }
+
+    pub fn get_storage_stats(env: Env) -> StorageStats {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STOR_STAT"))
+            .unwrap_or(StorageStats {
+                active_bills: 0,
+                archived_bills: 0,
+                total_unpaid_amount: 0,
+                total_archived_amount: 0,
+                last_updated: 0,
+            })
+    }
+
+    // -----------------------------------------------------------------------
+    // Currency-filter helper queries
+    // -----------------------------------------------------------------------
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
+    ///
+    /// # Arguments
+    /// * `owner`    – whose bills to return
+    /// * `currency` – currency code to filter by, e.g. `"USDC"`, `"XLM"`
+    /// * `cursor`   – start after this bill ID (pass 0 for the first page)
+    /// * `limit`    – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
+    pub fn get_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for (id, bill) in bills.iter() {
+            if id <= cursor {
+                continue;
+            }
+            if bill.owner != owner || bill.currency != currency {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Get all of `owner`'s active bills in `category`, paid and unpaid.
+    pub fn get_bills_by_category(env: Env, owner: Address, category: Symbol) -> Vec<Bill> {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for id in Self::owner_bill_ids(&env, &owner).iter() {
+            if let Some(bill) = bills.get(id) {
+                if bill.category == category {
+                    result.push_back(bill);
+                }
+            }
+        }
+        result
+    }
+
+    /// Get a page of **unpaid** bills for `owner` that match `currency`.
+    ///
+    /// Same cursor/limit semantics as `get_bills_by_currency`.
+    pub fn get_unpaid_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = Self::clamp_limit(limit);
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
+        for (id, bill) in bills.iter() {
+            if id <= cursor {
+                continue;
+            }
+            if bill.owner != owner || !Self::is_outstanding(&bill) || bill.currency != currency {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        Self::build_page(&env, staging, limit)
+    }
+
+    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
+    ///
+    /// # Example
+    /// ```text
+    /// let usdc_owed = client.get_total_unpaid_by_currency(&owner, &String::from_str(&env, "USDC"));
+    /// ```
+    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut total = 0i128;
+        for (_, bill) in bills.iter() {
+            if Self::is_outstanding(&bill) && bill.owner == owner && bill.currency == currency {
+                total += bill.amount - bill.amount_paid;
+            }
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn load_owner_index(env: &Env) -> Map<Address, Vec<u32>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("OWN_BILLS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn owner_bill_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        Self::load_owner_index(env)
+            .get(owner.clone())
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Add a bill to its owner's index, keeping the ids in ascending order.
+    fn index_bill(env: &Env, owner: &Address, bill_id: u32) {
+        let mut index = Self::load_owner_index(env);
+        let mut ids = index.get(owner.clone()).unwrap_or_else(|| Vec::new(env));
+        match ids.binary_search(bill_id) {
+            Ok(_) => return,
+            Err(pos) => ids.insert(pos, bill_id),
+        }
+        index.set(owner.clone(), ids);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("OWN_BILLS"), &index);
+    }
+
+    fn unindex_bill(env: &Env, owner: &Address, bill_id: u32) {
+        let mut index = Self::load_owner_index(env);
+        if let Some(mut ids) = index.get(owner.clone()) {
+            if let Ok(pos) = ids.binary_search(bill_id) {
+                ids.remove(pos);
+                index.set(owner.clone(), ids);
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("OWN_BILLS"), &index);
+            }
+        }
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    fn extend_archive_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    }
+
+    fn update_storage_stats(env: &Env) {
+        let bills: Map<u32, Bill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILLS"))
+            .unwrap_or_else(|| Map::new(env));
+        let archived: Map<u32, ArchivedBill> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ARCH_BILL"))
+            .unwrap_or_else(|| Map::new(env));
+
+        let mut active_count = 0u32;
+        let mut unpaid_amount = 0i128;
+        for (_, bill) in bills.iter() {
+            active_count += 1;
+            if Self::is_outstanding(&bill) {
+                unpaid_amount = unpaid_amount.saturating_add(bill.amount - bill.amount_paid);
+            }
+        }
+
+        let mut archived_count = 0u32;
+        let mut archived_amount = 0i128;
+        for (_, bill) in archived.iter() {
+            archived_count += 1;
+            archived_amount = archived_amount.saturating_add(bill.amount);
+        }
+
+        let stats = StorageStats {
+            active_bills: active_count,
+            archived_bills: archived_count,
+            total_unpaid_amount: unpaid_amount,
+            total_archived_amount: archived_amount,
+            last_updated: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STOR_STAT"), &stats);
+    }
+    fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
+        env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
+    }
+
+    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let mut totals: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_UNPAID_TOTALS)
+            .unwrap_or_else(|| Map::new(env));
+        let current = totals.get(owner.clone()).unwrap_or(0);
+        let next = if delta >= 0 {
+            current.saturating_add(delta)
+        } else {
+            current.saturating_sub(delta.saturating_abs())
+        };
+        totals.set(owner.clone(), next);
+        env.storage()
+            .instance()
+            .set(&STORAGE_UNPAID_TOTALS, &totals);
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        Env, String,
+    };
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    /// Create `count` bills with a static name. Returns their IDs.
+    /// Due dates are set in the future so they are NOT overdue.
+    fn setup_bills(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        count: u32,
+    ) -> Vec<u32> {
+        let mut ids = Vec::new(env);
+        for i in 0..count {
+            let id = client.create_bill(
+                owner,
+                &String::from_str(env, "Test Bill"),
+                &Address::generate(env),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(env, "XLM"),
+                &symbol_short!("utilities"),
+            );
+            ids.push_back(id);
+        }
+        ids
+    }
+
+    // --- get_unpaid_bills ---
+
+    #[test]
+    fn test_get_unpaid_bills_empty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let page = client.get_unpaid_bills(&owner, &0, &0);
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+        assert_eq!(page.items.len(), 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_single_page() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 5);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_multiple_pages() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 7);
+
+        let page1 = client.get_unpaid_bills(&owner, &0, &3);
+        assert_eq!(page1.count, 3);
+        assert!(page1.next_cursor > 0, "expected a next cursor");
+
+        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
+        assert_eq!(page2.count, 3);
+        assert!(page2.next_cursor > 0);
+
+        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
+        assert_eq!(page3.count, 1);
+        assert_eq!(page3.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 4);
+        let second_id = ids.get(1).unwrap();
+        client.mark_bill_paid(&owner, &second_id);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_other_owner() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        let page = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page.count, 3);
+        for bill in page.items.iter() {
+            assert_eq!(bill.owner, owner_a);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 3);
+
+        // owner_a sees only their own bills
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 2);
+        for bill in page_a.items.iter() {
+            assert_eq!(
+                bill.owner, owner_a,
+                "owner_a page must not contain owner_b bills"
+            );
+        }
+
+        // owner_b sees only their own bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3);
+        for bill in page_b.items.iter() {
+            assert_eq!(
+                bill.owner, owner_b,
+                "owner_b page must not contain owner_a bills"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
+        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays one of their bills
+        client.mark_bill_paid(&owner_a, &ids_a.get(0).unwrap());
+
+        // owner_a now has 1 unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 1);
+        for bill in page_a.items.iter() {
+            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
+            assert!(!bill.paid, "Should only see unpaid bills");
+        }
+
+        // owner_b still has 2 unpaid — unaffected by owner_a's payment
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
+        // owner_b has bills but owner_a has none — owner_a gets empty page
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        // Only owner_b creates bills
+        setup_bills(&env, &client, &owner_b, 3);
+
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should see no bills");
+        assert_eq!(page_a.next_cursor, 0);
+
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
+        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays all their bills
+        for id in ids_a.iter() {
+            client.mark_bill_paid(&owner_a, &id);
+        }
+
+        // owner_a has zero unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+
+        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b);
+            assert!(!bill.paid);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
+        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        // Interleave bills: a, b, a, b, a, b ...
+        for i in 0..4u32 {
+            client.create_bill(
+                &owner_a,
+                &String::from_str(&env, "Bill A"),
+                &Address::generate(&env),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &symbol_short!("utilities"),
+            );
+            client.create_bill(
+                &owner_b,
+                &String::from_str(&env, "Bill B"),
+                &Address::generate(&env),
+                &(200i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &symbol_short!("utilities"),
+            );
+        }
+
+        // Paginate through owner_a with small page size
+        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
+        let mut cursor = 0u32;
+        loop {
+            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
+            for bill in page.items.iter() {
+                assert_eq!(
+                    bill.owner, owner_a,
+                    "Paginated result must never contain owner_b's bill"
+                );
+                all_a_bills.push_back(bill);
+            }
+            if page.next_cursor == 0 {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(
+            all_a_bills.len(),
+            4,
+            "owner_a should have exactly 4 bills across all pages"
+        );
+    }
+
+    // --- get_overdue_bills ---
+
+    #[test]
+    fn test_get_overdue_bills_not_overdue() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 3);
+        let page = client.get_overdue_bills(&0, &10);
+        assert_eq!(page.count, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        for _ in 0..6u32 {
+            client.create_bill(
+                &owner,
+                &String::from_str(&env, "Overdue Bill"),
+                &Address::generate(&env),
+                &100,
+                &0,
+                &false,
+                &0,
+                &String::from_str(&env, "XLM"),
+                &symbol_short!("utilities"),
+            );
+        }
+
+        env.ledger().set_timestamp(1);
+
+        let page1 = client.get_overdue_bills(&0, &4);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    // --- get_all_bills_for_owner ---
+
+    #[test]
+    fn test_get_all_bills_for_owner_includes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 5);
+        let first_id = ids.get(0).unwrap();
+        client.mark_bill_paid(&owner, &first_id);
+
+        let page = client.get_all_bills_for_owner(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+    }
+
+    // --- limit clamping ---
+
+    #[test]
+    fn test_limit_zero_uses_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 3);
+        let page = client.get_unpaid_bills(&owner, &0, &0);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_limit_clamped_to_max() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 55);
+        let page = client.get_unpaid_bills(&owner, &0, &9999);
+        assert_eq!(page.count, MAX_PAGE_LIMIT);
+        assert!(page.next_cursor > 0);
+    }
+
+    // --- archived bill pagination ---
+
+    #[test]
+    fn test_get_archived_bills_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        client.set_pause_admin(&owner, &owner);
+
+        let ids = setup_bills(&env, &client, &owner, 6);
+        for bill_id in ids.iter() {
+            client.mark_bill_paid(&owner, &bill_id);
+        }
+        client.archive_paid_bills(&owner, &u64::MAX);
+
+        let page1 = client.get_archived_bills(&owner, &0, &4);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_archived_bills(&owner, &page1.next_cursor, &4);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    // --- owner index ---
+
+    #[test]
+    fn test_get_bills_by_owner_uses_offsets() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_bills(&env, &client, &other, 2);
+        let ids = setup_bills(&env, &client, &owner, 5);
+        setup_bills(&env, &client, &other, 2);
+
+        let page1 = client.get_bills_by_owner(&owner, &0, &3);
+        assert_eq!(page1.count, 3);
+        assert_eq!(page1.next_cursor, 3);
+        assert_eq!(page1.items.get(0).unwrap().id, ids.get(0).unwrap());
+
+        let page2 = client.get_bills_by_owner(&owner, &page1.next_cursor, &3);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+        assert!(page2.items.iter().all(|bill| bill.owner == owner));
+        assert_eq!(client.get_bills_by_owner(&owner, &10, &3).count, 0);
+    }
+
+    #[test]
+    fn test_owner_bill_totals_and_archive() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        client.set_pause_admin(&owner, &owner);
+
+        // Amounts 100, 200, 300
+        let ids = setup_bills(&env, &client, &owner, 3);
+        client.mark_bill_paid(&owner, &ids.get(0).unwrap());
+        client.pay_bill_partial(&owner, &ids.get(1).unwrap(), &50);
+
+        let totals = client.get_owner_bill_totals(&owner);
+        assert_eq!(totals.bill_count, 3);
+        assert_eq!(totals.paid_count, 1);
+        assert_eq!(totals.unpaid_count, 2);
+        assert_eq!(totals.total_amount, 600);
+        assert_eq!(totals.paid_amount, 150);
+        assert_eq!(totals.unpaid_amount, 450);
+
+        // Archived bills leave the index and come back on restore
+        client.archive_paid_bills(&owner, &u64::MAX);
+        assert_eq!(client.get_bills_by_owner(&owner, &0, &10).count, 2);
+        client.restore_bill(&owner, &ids.get(0).unwrap());
+        let page = client.get_bills_by_owner(&owner, &0, &10);
+        assert_eq!(page.count, 3);
+        assert_eq!(page.items.get(0).unwrap().id, ids.get(0).unwrap());
+    }
+
+    // --- categories ---
+
+    #[test]
+    fn test_get_bills_by_category() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let xlm = String::from_str(&env, "XLM");
+        let name = String::from_str(&env, "Bill");
+
+        let rent = client.create_bill(
+            &owner,
+            &name,
+            &other,
+            &500,
+            &86400,
+            &true,
+            &30,
+            &xlm,
+            &symbol_short!("rent"),
+        );
+        client.create_bill(
+            &owner,
+            &name,
+            &other,
+            &40,
+            &86400,
+            &false,
+            &0,
+            &xlm,
+            &symbol_short!("phone"),
+        );
+        client.create_bill(
+            &other,
+            &name,
+            &owner,
+            &700,
+            &86400,
+            &false,
+            &0,
+            &xlm,
+            &symbol_short!("rent"),
+        );
+
+        // The next occurrence of a recurring bill keeps its category
+        client.mark_bill_paid(&owner, &rent);
+        let bills = client.get_bills_by_category(&owner, &symbol_short!("rent"));
+        assert_eq!(bills.len(), 2);
+        assert!(bills
+            .iter()
+            .all(|bill| bill.owner == owner && bill.amount == 500));
+        assert_eq!(
+            client
+                .get_bills_by_category(&owner, &symbol_short!("school"))
+                .len(),
+            0
+        );
+    }
+
+    // --- token payments ---
+
+    fn setup_token(env: &Env, holder: &Address, amount: i128) -> Address {
+        let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+        soroban_sdk::token::StellarAssetClient::new(env, &token.address()).mint(holder, &amount);
+        token.address()
+    }
+
+    #[test]
+    fn test_pay_bill_transfers_to_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &payee,
+            &300,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(client.pay_bill(&owner, &bill_id, &token), 300);
+        assert_eq!(balances.balance(&payee), 300);
+        assert_eq!(balances.balance(&owner), 700);
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.paid_at, Some(env.ledger().timestamp()));
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(
+            client.try_pay_bill(&owner, &bill_id, &token),
+            Err(Ok(Error::BillAlreadyPaid))
+        );
+    }
+
+    #[test]
+    fn test_pay_bill_uses_funded_balance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let other = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        assert_eq!(client.fund_balance(&owner, &token, &500), 500);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &payee,
+            &200,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_pay_bill(&other, &bill_id, &token),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        client.pay_bill(&owner, &bill_id, &token);
+        assert_eq!(client.get_balance(&owner, &token), 300);
+        assert_eq!(balances.balance(&owner), 500);
+        assert_eq!(balances.balance(&payee), 200);
+
+        assert_eq!(
+            client.try_withdraw_balance(&owner, &token, &301),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        assert_eq!(client.withdraw_balance(&owner, &token, &300), 0);
+        assert_eq!(balances.balance(&owner), 800);
+    }
+
+    // --- payee registry ---
+
+    #[test]
+    fn test_registered_payee_required_for_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let head = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let utility = Address::generate(&env);
+        let lookalike = Address::generate(&env);
+        let name = String::from_str(&env, "Power");
+        let xlm = String::from_str(&env, "XLM");
+
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_payee_registry_required(&admin, &true);
+        assert_eq!(
+            client.try_register_payee(&head, &utility, &name, &symbol_short!("utility")),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.set_payee_verifier(&admin, &head, &true);
+        client.register_payee(&head, &utility, &name, &symbol_short!("utility"));
+
+        let payee = client.get_payee(&utility).unwrap();
+        assert_eq!(payee.verified_by, head);
+        assert_eq!(
+            client
+                .get_payees_by_category(&symbol_short!("utility"))
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            client.try_create_bill(
+                &owner,
+                &name,
+                &lookalike,
+                &100,
+                &86400,
+                &false,
+                &0,
+                &xlm,
+                &symbol_short!("utilities")
+            ),
+            Err(Ok(Error::PayeeNotRegistered))
+        );
+        let bill_id = client.create_bill(
+            &owner,
+            &name,
+            &utility,
+            &100,
+            &86400,
+            &false,
+            &0,
+            &xlm,
+            &symbol_short!("utilities"),
+        );
+
+        client.remove_payee(&admin, &utility);
+        let token = setup_token(&env, &owner, 100);
+        assert_eq!(
+            client.try_pay_bill(&owner, &bill_id, &token),
+            Err(Ok(Error::PayeeNotRegistered))
+        );
+    }
+
+    // --- auto-pay ---
+
+    #[test]
+    fn test_process_due_autopay_pays_from_funded_balance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+        let xlm = String::from_str(&env, "XLM");
+
+        let rent = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &payee,
+            &300,
+            &1000,
+            &true,
+            &30,
+            &xlm,
+            &symbol_short!("utilities"),
+        );
+        let manual = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Gym"),
+            &payee,
+            &50,
+            &1000,
+            &false,
+            &0,
+            &xlm,
+            &symbol_short!("utilities"),
+        );
+        client.set_autopay(&owner, &rent, &Some(token.clone()), &400);
+        client.fund_balance(&owner, &token, &500);
+
+        // Not due yet
+        assert_eq!(client.process_due_autopay(&owner).len(), 0);
+
+        env.ledger().set_timestamp(1000);
+        let paid = client.process_due_autopay(&owner);
+        assert_eq!(paid, soroban_sdk::vec![&env, rent]);
+        assert_eq!(balances.balance(&payee), 300);
+        assert_eq!(client.get_balance(&owner, &token), 200);
+        assert!(client.get_bill(&rent).unwrap().paid);
+        assert!(!client.get_bill(&manual).unwrap().paid);
+
+        // The setting follows the next occurrence
+        let next_id = rent + 2;
+        assert_eq!(
+            client.get_bill(&next_id).unwrap().due_date,
+            1000 + 30 * 86400
+        );
+        assert!(client.get_autopay(&rent).is_none());
+        assert_eq!(client.get_autopay(&next_id).unwrap().max_amount, 400);
+
+        // Balance no longer covers the next occurrence
+        env.ledger().set_timestamp(1000 + 30 * 86400);
+        assert_eq!(client.process_due_autopay(&owner).len(), 0);
+        assert!(!client.get_bill(&next_id).unwrap().paid);
+    }
+
+    #[test]
+    fn test_autopay_skips_bills_over_cap() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let xlm = String::from_str(&env, "XLM");
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &payee,
+            &300,
+            &0,
+            &true,
+            &30,
+            &xlm,
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_set_autopay(&other, &bill_id, &Some(token.clone()), &500),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_set_autopay(&owner, &bill_id, &Some(token.clone()), &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+        client.set_autopay(&owner, &bill_id, &Some(token.clone()), &200);
+        client.fund_balance(&owner, &token, &1000);
+
+        assert_eq!(client.process_due_autopay(&owner).len(), 0);
+        assert_eq!(client.get_balance(&owner, &token), 1000);
+
+        client.set_autopay(&owner, &bill_id, &None, &0);
+        assert!(client.get_autopay(&bill_id).is_none());
+    }
+
+    // --- receipts ---
+
+    #[test]
+    fn test_receipt_recorded_on_payment() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let school = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        env.ledger().set_timestamp(5000);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Term fees"),
+            &school,
+            &600,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &symbol_short!("school"),
+        );
+        assert!(client.get_receipt(&bill_id).is_none());
+        client.pay_bill(&owner, &bill_id, &token);
+
+        let receipt = client.get_receipt(&bill_id).unwrap();
+        assert_eq!(receipt.payer, owner);
+        assert_eq!(receipt.payee, school);
+        assert_eq!(receipt.amount, 600);
+        assert_eq!(receipt.token, Some(token));
+        assert_eq!(receipt.paid_at, 5000);
+        assert_eq!(receipt.ledger_sequence, env.ledger().sequence());
+        assert_eq!(receipt.external_hash, None);
+
+        let hash = Bytes::from_array(&env, &[7; 32]);
+        assert_eq!(
+            client.try_attach_receipt_hash(&owner, &bill_id, &hash),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.attach_receipt_hash(&school, &bill_id, &hash);
+        assert_eq!(
+            client.get_receipt(&bill_id).unwrap().external_hash,
+            Some(hash)
+        );
+    }
+
+    // --- scheduled payments ---
+
+    #[test]
+    fn test_scheduled_payment_executes_after_time() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let landlord = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &landlord,
+            &700,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("rent"),
+        );
+        assert_eq!(
+            client.try_schedule_payment(&owner, &bill_id, &token, &50_000),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        client.fund_balance(&owner, &token, &1000);
+        client.schedule_payment(&owner, &bill_id, &token, &50_000);
+        assert_eq!(client.get_balance(&owner, &token), 300);
+        assert_eq!(
+            client.try_schedule_payment(&owner, &bill_id, &token, &60_000),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        assert_eq!(
+            client.try_execute_scheduled_payment(&bill_id),
+            Err(Ok(Error::PaymentNotDue))
+        );
+        env.ledger().set_timestamp(50_000);
+        assert_eq!(client.execute_scheduled_payment(&bill_id), 700);
+        assert_eq!(balances.balance(&landlord), 700);
+        assert_eq!(client.get_balance(&owner, &token), 300);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert!(client.get_scheduled_payment(&bill_id).is_none());
+    }
+
+    #[test]
+    fn test_cancel_scheduled_payment_releases_funds() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Phone"),
+            &other,
+            &200,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("phone"),
+        );
+        client.fund_balance(&owner, &token, &500);
+        client.schedule_payment(&owner, &bill_id, &token, &10);
+        assert_eq!(
+            client.try_cancel_scheduled_payment(&other, &bill_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.cancel_scheduled_payment(&owner, &bill_id);
+        assert_eq!(client.get_balance(&owner, &token), 500);
+        assert_eq!(
+            client.try_execute_scheduled_payment(&bill_id),
+            Err(Ok(Error::BillNotFound))
+        );
+
+        // Paid elsewhere before execution: the reservation is returned
+        client.schedule_payment(&owner, &bill_id, &token, &10);
+        client.mark_bill_paid(&owner, &bill_id);
+        env.ledger().set_timestamp(10);
+        assert_eq!(client.execute_scheduled_payment(&bill_id), 0);
+        assert_eq!(client.get_balance(&owner, &token), 500);
+    }
 
-        let mut archived_count = 0u32;
-        let mut archived_amount = 0i128;
-        for (_, bill) in archived.iter() {
-            archived_count += 1;
-            archived_amount = archived_amount.saturating_add(bill.amount);
-        }
+    // --- earmarked balance ---
 
-        let stats = StorageStats {
-            active_bills: active_count,
-            archived_bills: archived_count,
-            total_unpaid_amount: unpaid_amount,
-            total_archived_amount: archived_amount,
-            last_updated: env.ledger().timestamp(),
-        };
+    #[test]
+    fn test_earmarked_balance_only_pays_bills() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let splitter = Address::generate(&env);
+        let landlord = Address::generate(&env);
+        let token = setup_token(&env, &splitter, 1000);
+        let balances = TokenClient::new(&env, &token);
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("STOR_STAT"), &stats);
-    }
-    fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
-        env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
-    }
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &landlord,
+            &700,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("rent"),
+        );
+        client.deposit_earmarked(&splitter, &owner, &token, &600);
+        assert_eq!(client.get_earmarked_balance(&owner, &token), 600);
+        assert_eq!(
+            client.try_withdraw_balance(&owner, &token, &100),
+            Err(Ok(Error::InsufficientBalance))
+        );
 
-    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
-        if delta == 0 {
-            return;
-        }
-        let mut totals: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&STORAGE_UNPAID_TOTALS)
-            .unwrap_or_else(|| Map::new(env));
-        let current = totals.get(owner.clone()).unwrap_or(0);
-        let next = if delta >= 0 {
-            current.saturating_add(delta)
-        } else {
-            current.saturating_sub(delta.saturating_abs())
-        };
-        totals.set(owner.clone(), next);
-        env.storage()
-            .instance()
-            .set(&STORAGE_UNPAID_TOTALS, &totals);
+        // Scheduling takes earmarked funds first; cancelling puts them back
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &200);
+        client.fund_balance(&owner, &token, &200);
+        client.schedule_payment(&owner, &bill_id, &token, &10);
+        assert_eq!(client.get_earmarked_balance(&owner, &token), 0);
+        assert_eq!(client.get_balance(&owner, &token), 100);
+        client.cancel_scheduled_payment(&owner, &bill_id);
+        assert_eq!(client.get_earmarked_balance(&owner, &token), 600);
+        assert_eq!(client.get_balance(&owner, &token), 200);
+
+        client.pay_bill(&owner, &bill_id, &token);
+        assert_eq!(balances.balance(&landlord), 700);
+        assert_eq!(client.get_earmarked_balance(&owner, &token), 0);
+        assert_eq!(client.get_balance(&owner, &token), 100);
     }
-}
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Env, String,
-    };
+    // --- cross-asset payments ---
 
-    fn make_env() -> Env {
-        Env::default()
-    }
+    /// Router paying out two units of `token_out` per unit of `token_in`.
+    #[contract]
+    pub struct MockSwapRouter;
 
-    /// Create `count` bills with a static name. Returns their IDs.
-    /// Due dates are set in the future so they are NOT overdue.
-    fn setup_bills(
-        env: &Env,
-        client: &BillPaymentsClient,
-        owner: &Address,
-        count: u32,
-    ) -> Vec<u32> {
-        let mut ids = Vec::new(env);
-        for i in 0..count {
-            let id = client.create_bill(
-                owner,
-                &String::from_str(env, "Test Bill"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-                &String::from_str(&env, "XLM"),
-            );
-            ids.push_back(id);
+    #[contractimpl]
+    impl MockSwapRouter {
+        pub fn quote(_env: Env, _token_in: Address, _token_out: Address, amount_in: i128) -> i128 {
+            amount_in * 2
         }
-        ids
-    }
 
-    // --- get_unpaid_bills ---
+        pub fn swap(
+            env: Env,
+            sender: Address,
+            token_in: Address,
+            token_out: Address,
+            amount_in: i128,
+            _min_out: i128,
+            to: Address,
+        ) -> i128 {
+            let router = env.current_contract_address();
+            TokenClient::new(&env, &token_in).transfer(&sender, &router, &amount_in);
+            TokenClient::new(&env, &token_out).transfer(&router, &to, &(amount_in * 2));
+            amount_in * 2
+        }
+    }
 
     #[test]
-    fn test_get_unpaid_bills_empty() {
+    fn test_pay_bill_with_converts_and_records_rate() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let router = env.register_contract(None, MockSwapRouter);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
+        let utility = Address::generate(&env);
+        let usdc = setup_token(&env, &owner, 1000);
+        let naira = setup_token(&env, &router, 10_000);
 
-        let page = client.get_unpaid_bills(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
-        assert_eq!(page.items.len(), 0);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Electricity"),
+            &utility,
+            &900,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "NGN"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_pay_bill_with(&owner, &bill_id, &usdc, &500),
+            Err(Ok(Error::ConversionUnavailable))
+        );
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_payment_router(&admin, &router);
+        client.set_currency_asset(&admin, &String::from_str(&env, "NGN"), &naira);
+
+        assert_eq!(
+            client.try_pay_bill_with(&owner, &bill_id, &usdc, &400),
+            Err(Ok(Error::SlippageExceeded))
+        );
+        assert_eq!(client.pay_bill_with(&owner, &bill_id, &usdc, &500), 450);
+        assert_eq!(TokenClient::new(&env, &naira).balance(&utility), 900);
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 550);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+
+        let conversion = client.get_payment_conversion(&bill_id).unwrap();
+        assert_eq!(conversion.amount_in, 450);
+        assert_eq!(conversion.rate, 2 * RATE_SCALE);
+        assert_eq!(client.get_owner_conversions(&owner).len(), 1);
     }
 
     #[test]
-    fn test_get_unpaid_bills_single_page() {
+    fn test_pay_bill_requires_currency_asset() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
+        let utility = Address::generate(&env);
+        let usdc = setup_token(&env, &owner, 1000);
+        let naira = setup_token(&env, &owner, 1000);
 
-        setup_bills(&env, &client, &owner, 5);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Electricity"),
+            &utility,
+            &300,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "NGN"),
+            &symbol_short!("utilities"),
+        );
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_currency_asset(&admin, &String::from_str(&env, "NGN"), &naira);
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 5);
-        assert_eq!(page.next_cursor, 0);
+        assert_eq!(
+            client.try_pay_bill(&owner, &bill_id, &usdc),
+            Err(Ok(Error::TokenMismatch))
+        );
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&utility), 0);
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
+
+        assert_eq!(client.pay_bill(&owner, &bill_id, &naira), 300);
+        assert_eq!(TokenClient::new(&env, &naira).balance(&utility), 300);
     }
 
+    // --- large payment approval ---
+
     #[test]
-    fn test_get_unpaid_bills_multiple_pages() {
+    fn test_large_payment_requires_approver() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let head = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let signed = |env: &Env, who: &Address| env.auths().iter().any(|(a, _)| a == who);
 
-        setup_bills(&env, &client, &owner, 7);
+        client.set_payment_approval(&owner, &Some(head.clone()), &100);
+        assert_eq!(client.get_payment_approval(&owner).unwrap().approver, head);
 
-        let page1 = client.get_unpaid_bills(&owner, &0, &3);
-        assert_eq!(page1.count, 3);
-        assert!(page1.next_cursor > 0, "expected a next cursor");
+        let small = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Phone"),
+            &payee,
+            &50,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("phone"),
+        );
+        client.pay_bill(&owner, &small, &token);
+        assert!(!signed(&env, &head));
 
-        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
-        assert_eq!(page2.count, 3);
-        assert!(page2.next_cursor > 0);
+        let large = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &payee,
+            &500,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("rent"),
+        );
+        client.pay_bill(&owner, &large, &token);
+        assert!(signed(&env, &head));
 
-        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
+        // Removing the rule needs the approver as well
+        client.set_payment_approval(&owner, &None, &0);
+        assert!(signed(&env, &head));
+        assert!(client.get_payment_approval(&owner).is_none());
     }
 
+    // --- grace periods ---
+
     #[test]
-    fn test_get_unpaid_bills_excludes_paid() {
+    fn test_grace_period_delays_overdue() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
-        let ids = setup_bills(&env, &client, &owner, 4);
-        let second_id = ids.get(1).unwrap();
-        client.pay_bill(&owner, &second_id);
+        setup_bills(&env, &client, &owner, 1);
+        setup_bills(&env, &client, &other, 1);
+        assert_eq!(
+            client.try_set_grace_period(&owner, &(MAX_GRACE_DAYS + 1)),
+            Err(Ok(Error::InvalidLimit))
+        );
+        client.set_grace_period(&owner, &3);
+        assert_eq!(client.get_grace_period(&owner), 3);
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 3);
+        // One day late: only the bill without a grace period is overdue
+        env.ledger().set_timestamp(2 * 86400);
+        let page = client.get_overdue_bills(&0, &10);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.items.get(0).unwrap().owner, other);
+
+        env.ledger().set_timestamp(5 * 86400);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 2);
     }
 
+    // --- standing orders ---
+
     #[test]
-    fn test_get_unpaid_bills_excludes_other_owner() {
+    fn test_standing_order_runs_until_end_date() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let grandmother = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
 
-        setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
+        assert_eq!(
+            client.try_create_standing_order(&owner, &grandmother, &token, &100, &0, &0, &0),
+            Err(Ok(Error::InvalidFrequency))
+        );
+        let week = 7 * 86400;
+        let order_id = client.create_standing_order(
+            &owner,
+            &grandmother,
+            &token,
+            &100,
+            &7,
+            &week,
+            &(2 * week),
+        );
+        assert_eq!(
+            client.try_execute_standing_order(&order_id),
+            Err(Ok(Error::PaymentNotDue))
+        );
 
-        let page = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page.count, 3);
-        for bill in page.items.iter() {
-            assert_eq!(bill.owner, owner_a);
-        }
+        env.ledger().set_timestamp(week);
+        assert_eq!(
+            client.try_execute_standing_order(&order_id),
+            Err(Ok(Error::InsufficientBalance))
+        );
+        client.fund_balance(&owner, &token, &500);
+        assert_eq!(client.execute_standing_order(&order_id), 100);
+        assert_eq!(
+            client.try_execute_standing_order(&order_id),
+            Err(Ok(Error::PaymentNotDue))
+        );
+
+        env.ledger().set_timestamp(2 * week + 60);
+        client.execute_standing_order(&order_id);
+        assert_eq!(balances.balance(&grandmother), 200);
+        assert!(!client.get_standing_order(&order_id).unwrap().active);
+        assert_eq!(
+            client.try_execute_standing_order(&order_id),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        let runs = client.get_standing_order_runs(&order_id);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs.get(1).unwrap().due_at, 2 * week);
+        assert_eq!(runs.get(1).unwrap().executed_at, 2 * week + 60);
     }
 
+    // --- payment plans ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+    fn test_payment_plan_keeps_bill_on_schedule() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let hospital = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 3);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Hospital"),
+            &hospital,
+            &3000,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("medical"),
+        );
+        let mut bad = Vec::new(&env);
+        bad.push_back((86400u64, 1000i128));
+        bad.push_back((86400u64, 2000i128));
+        assert_eq!(
+            client.try_create_payment_plan(&owner, &bill_id, &bad),
+            Err(Ok(Error::InvalidPlan))
+        );
 
-        // owner_a sees only their own bills
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 2);
-        for bill in page_a.items.iter() {
-            assert_eq!(
-                bill.owner, owner_a,
-                "owner_a page must not contain owner_b bills"
-            );
+        let mut installments = Vec::new(&env);
+        for i in 1..=3u64 {
+            installments.push_back((86400 * 30 * i, 1000i128));
         }
+        client.create_payment_plan(&owner, &bill_id, &installments);
+
+        // Past the bill's own due date but before the first installment
+        env.ledger().set_timestamp(86400 * 10);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+
+        env.ledger().set_timestamp(86400 * 31);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 1);
+        assert!(
+            !client
+                .get_owner_plan_statuses(&owner)
+                .get(0)
+                .unwrap()
+                .on_schedule
+        );
+        assert_eq!(client.pay_installment(&owner, &bill_id), 2000);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+        let status = client.get_owner_plan_statuses(&owner).get(0).unwrap();
+        assert_eq!((status.installments_paid, status.on_schedule), (1, true));
+
+        client.pay_installment(&owner, &bill_id);
+        assert_eq!(client.pay_installment(&owner, &bill_id), 0);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(client.get_owner_plan_statuses(&owner).len(), 0);
+    }
 
-        // owner_b sees only their own bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3);
-        for bill in page_b.items.iter() {
-            assert_eq!(
-                bill.owner, owner_b,
-                "owner_b page must not contain owner_a bills"
-            );
-        }
+    // --- overdue escalation ---
+
+    #[test]
+    fn test_overdue_escalation_lets_head_pay() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let head = Address::generate(&env);
+        let school = Address::generate(&env);
+        let token = setup_token(&env, &head, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School fees"),
+            &school,
+            &400,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("school"),
+        );
+        client.set_escalation(&owner, &Some(head.clone()), &3, &true);
+        client.set_grace_period(&owner, &2);
+
+        // Overdue after the grace period, but not yet by more than 3 days
+        env.ledger().set_timestamp(86400 * 5);
+        assert_eq!(client.escalate_overdue(&owner).len(), 0);
+        assert_eq!(
+            client.try_pay_escalated_bill(&head, &bill_id, &token),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        env.ledger().set_timestamp(86400 * 7);
+        assert_eq!(
+            client.escalate_overdue(&owner),
+            soroban_sdk::vec![&env, bill_id]
+        );
+        assert_eq!(client.escalate_overdue(&owner).len(), 0);
+        assert_eq!(
+            client.try_pay_escalated_bill(&owner, &bill_id, &token),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        assert_eq!(client.pay_escalated_bill(&head, &bill_id, &token), 400);
+        assert_eq!(balances.balance(&school), 400);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(client.get_receipt(&bill_id).unwrap().payer, head);
     }
 
+    // --- bill history ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
-        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+    fn test_archive_paid_before_keeps_compact_history() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
-        let ids_a = setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 2);
+        setup_bills(&env, &client, &owner, 3);
+        setup_bills(&env, &client, &other, 1);
+        env.ledger().set_timestamp(1000);
+        client.mark_bill_paid(&owner, &1);
+        client.mark_bill_paid(&other, &4);
+        env.ledger().set_timestamp(5000);
+        client.mark_bill_paid(&owner, &2);
+
+        assert_eq!(client.archive_paid_before(&owner, &2000), 1);
+        assert!(client.get_bill(&1).is_none());
+        assert!(client.get_receipt(&1).is_none());
+        assert!(client.get_bill(&4).is_some());
+        assert_eq!(client.get_bills_by_owner(&owner, &0, &10).count, 2);
+
+        let history = client.get_bill_history(&owner, &0, &10);
+        assert_eq!(history.len(), 1);
+        let record = history.get(0).unwrap();
+        assert_eq!((record.id, record.amount, record.paid_at), (1, 100, 1000));
+
+        assert_eq!(client.archive_paid_before(&owner, &6000), 1);
+        assert_eq!(client.get_bill_history(&owner, &1, &10).len(), 1);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
+    }
 
-        // owner_a pays one of their bills
-        client.pay_bill(&owner_a, &ids_a.get(0).unwrap());
+    // --- payment statement ---
 
-        // owner_a now has 1 unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 1);
-        for bill in page_a.items.iter() {
-            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
-            assert!(!bill.paid, "Should only see unpaid bills");
-        }
+    #[test]
+    fn test_payment_statement_covers_archived_and_history() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
-        // owner_b still has 2 unpaid — unaffected by owner_a's payment
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
+        setup_bills(&env, &client, &owner, 4);
+        setup_bills(&env, &client, &other, 1);
+        for (id, paid_at) in [(2u32, 1000u64), (1, 2000), (3, 3000), (4, 9000)] {
+            env.ledger().set_timestamp(paid_at);
+            client.mark_bill_paid(&owner, &id);
+        }
+        client.mark_bill_paid(&other, &5);
+        // Bill 2 moves to the compact history, bill 1 stays active
+        client.archive_paid_before(&owner, &1500);
+
+        let statement = client.get_payment_statement(&owner, &0, &5000);
+        assert_eq!(statement.len(), 3);
+        let mut ids = Vec::new(&env);
+        for entry in statement.iter() {
+            ids.push_back(entry.bill_id);
         }
+        assert_eq!(ids, soroban_sdk::vec![&env, 2, 1, 3]);
+        assert_eq!(statement.get(1).unwrap().amount, 100);
+        assert_eq!(client.get_payment_statement(&owner, &2000, &3000).len(), 2);
     }
 
+    // --- bulk import ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
-        // owner_b has bills but owner_a has none — owner_a gets empty page
+    fn test_create_bills_imports_all_or_nothing() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        // Only owner_b creates bills
-        setup_bills(&env, &client, &owner_b, 3);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
 
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should see no bills");
-        assert_eq!(page_a.next_cursor, 0);
+        let input = |name: &str, amount: i128| BillInput {
+            name: String::from_str(&env, name),
+            payee: payee.clone(),
+            amount,
+            due_date: 1_000_000,
+            recurring: false,
+            frequency_days: 0,
+            currency: String::from_str(&env, ""),
+            category: symbol_short!("utilities"),
+        };
 
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+        let mut inputs = Vec::new(&env);
+        inputs.push_back(input("Water", 300));
+        inputs.push_back(input("Power", 0));
+        assert_eq!(
+            client.try_create_bills(&owner, &inputs),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(client.get_bills_by_owner(&owner, &0, &10).count, 0);
+
+        inputs.set(1, input("Power", 500));
+        inputs.push_back(input("School", 1200));
+        let ids = client.create_bills(&owner, &inputs);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(client.get_bills_by_owner(&owner, &0, &10).count, 3);
+        assert_eq!(client.get_total_unpaid(&owner), 2000);
+        let bill = client.get_bill(&ids.get(2).unwrap()).unwrap();
+        assert_eq!(bill.name, String::from_str(&env, "School"));
+        assert_eq!(bill.currency, String::from_str(&env, "XLM"));
     }
 
+    // --- variable amounts ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
-        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+    fn test_variable_bill_tracks_estimate_and_actual() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        let ids_a = setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
-
-        // owner_a pays all their bills
-        for id in ids_a.iter() {
-            client.pay_bill(&owner_a, &id);
-        }
+        let owner = Address::generate(&env);
+        let utility = Address::generate(&env);
 
-        // owner_a has zero unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Electricity"),
+            &utility,
+            &1000,
+            &86400,
+            &true,
+            &30,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_post_actual_amount(&utility, &bill_id, &1200),
+            Err(Ok(Error::InvalidStatus))
+        );
+        client.set_estimated_amount(&owner, &bill_id, &900);
+        assert_eq!(client.get_total_unpaid(&owner), 900);
+
+        client.post_actual_amount(&utility, &bill_id, &1150);
+        assert_eq!(client.get_bill(&bill_id).unwrap().amount, 1150);
+        assert_eq!(client.get_total_unpaid(&owner), 1150);
+        let estimate = client.get_amount_estimate(&bill_id).unwrap();
+        assert_eq!(estimate.estimated, 900);
+        assert_eq!(estimate.posted_by, Some(utility.clone()));
+
+        // The next occurrence starts again from the estimate
+        client.mark_bill_paid(&owner, &bill_id);
+        let next = client
+            .get_bills_by_owner(&owner, &1, &1)
+            .items
+            .get(0)
+            .unwrap();
+        assert_eq!(next.amount, 900);
+        assert_eq!(client.get_amount_estimate(&next.id).unwrap().actual, None);
+
+        env.ledger().set_timestamp(next.due_date + 1);
+        assert_eq!(
+            client.try_post_actual_amount(&owner, &next.id, &950),
+            Err(Ok(Error::PastDueDate))
+        );
 
-        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b);
-            assert!(!bill.paid);
-        }
+        let summary = client.get_estimate_summary(&owner);
+        assert_eq!(summary.bill_count, 1);
+        assert_eq!(summary.estimated_total, 900);
+        assert_eq!(summary.actual_total, 1150);
     }
 
+    // --- ownership transfer ---
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
-        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+    fn test_transfer_bill_needs_acceptance() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        // Interleave bills: a, b, a, b, a, b ...
-        for i in 0..4u32 {
-            client.create_bill(
-                &owner_a,
-                &String::from_str(&env, "Bill A"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-            );
-            client.create_bill(
-                &owner_b,
-                &String::from_str(&env, "Bill B"),
-                &(200i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-            );
-        }
-
-        // Paginate through owner_a with small page size
-        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
-        let mut cursor = 0u32;
-        loop {
-            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
-            for bill in page.items.iter() {
-                assert_eq!(
-                    bill.owner, owner_a,
-                    "Paginated result must never contain owner_b's bill"
-                );
-                all_a_bills.push_back(bill);
-            }
-            if page.next_cursor == 0 {
-                break;
-            }
-            cursor = page.next_cursor;
-        }
+        let parent = Address::generate(&env);
+        let child = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        setup_bills(&env, &client, &parent, 2);
+        client.transfer_bill(&parent, &1, &child);
+        assert_eq!(client.get_bill_transfer(&1), Some(child.clone()));
+        assert_eq!(client.get_bill(&1).unwrap().owner, parent);
+        assert_eq!(
+            client.try_accept_bill_transfer(&stranger, &1),
+            Err(Ok(Error::Unauthorized))
+        );
 
+        client.accept_bill_transfer(&child, &1);
+        assert_eq!(client.get_bill(&1).unwrap().owner, child);
+        assert_eq!(client.get_bill_transfer(&1), None);
+        assert_eq!(client.get_total_unpaid(&parent), 200);
+        assert_eq!(client.get_total_unpaid(&child), 100);
+        assert_eq!(client.get_bills_by_owner(&parent, &0, &10).count, 1);
+        assert_eq!(client.get_bills_by_owner(&child, &0, &10).count, 1);
         assert_eq!(
-            all_a_bills.len(),
-            4,
-            "owner_a should have exactly 4 bills across all pages"
+            client.try_mark_bill_paid(&parent, &1),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.mark_bill_paid(&child, &1);
+        assert_eq!(
+            client.try_accept_bill_transfer(&child, &2),
+            Err(Ok(Error::InvalidStatus))
         );
     }
 
-    // --- get_overdue_bills ---
+    // --- recurring series ---
 
     #[test]
-    fn test_get_overdue_bills_not_overdue() {
+    fn test_set_recurring_stops_series() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let landlord = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner, 3);
-        let page = client.get_overdue_bills(&0, &10);
-        assert_eq!(page.count, 0);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &landlord,
+            &500,
+            &86400,
+            &true,
+            &30,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("rent"),
+        );
+        client.mark_bill_paid(&owner, &bill_id);
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.due_date, 86400 + 30 * 86400);
+        assert_eq!(next.payee, landlord);
+        assert_eq!(next.category, symbol_short!("rent"));
+
+        client.set_recurring(&owner, &next.id, &false);
+        client.mark_bill_paid(&owner, &next.id);
+        assert!(client.get_bill(&(next.id + 1)).is_none());
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+
+        let one_off = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Repair"),
+            &landlord,
+            &80,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("rent"),
+        );
+        assert_eq!(
+            client.try_set_recurring(&owner, &one_off, &true),
+            Err(Ok(Error::InvalidFrequency))
+        );
     }
 
+    // --- disputes and cancellation ---
+
     #[test]
-    fn test_get_overdue_bills_pagination() {
+    fn test_disputed_bill_frozen_from_autopay_and_overdue() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let reason = String::from_str(&env, "Meter misread");
 
-        for _ in 0..6u32 {
-            client.create_bill(
-                &owner,
-                &String::from_str(&env, "Overdue Bill"),
-                &100,
-                &0,
-                &false,
-                &0,
-                &String::from_str(&env, "XLM"),
-            );
-        }
-
-        env.ledger().set_timestamp(1);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &payee,
+            &300,
+            &1000,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
+        );
+        client.set_autopay(&owner, &bill_id, &Some(token.clone()), &500);
+        client.fund_balance(&owner, &token, &500);
+        client.dispute_bill(&owner, &bill_id, &reason);
+        assert_eq!(
+            client.try_dispute_bill(&owner, &bill_id, &reason),
+            Err(Ok(Error::InvalidStatus))
+        );
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().status,
+            BillStatus::Disputed(reason)
+        );
 
-        let page1 = client.get_overdue_bills(&0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
+        env.ledger().set_timestamp(2000);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+        assert_eq!(client.process_due_autopay(&owner).len(), 0);
+        assert_eq!(client.get_total_unpaid(&owner), 300);
 
-        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
-        assert_eq!(page2.count, 2);
-        assert_eq!(page2.next_cursor, 0);
+        client.resolve_dispute(&owner, &bill_id);
+        assert_eq!(
+            client.try_resolve_dispute(&owner, &bill_id),
+            Err(Ok(Error::InvalidStatus))
+        );
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 1);
+        assert_eq!(
+            client.process_due_autopay(&owner),
+            soroban_sdk::vec![&env, bill_id]
+        );
     }
 
-    // --- get_all_bills_for_owner ---
-
     #[test]
-    fn test_get_all_bills_for_owner_includes_paid() {
+    fn test_cancel_bill_keeps_record_with_reason() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let reason = String::from_str(&env, "Moved out");
 
-        let ids = setup_bills(&env, &client, &owner, 5);
-        let first_id = ids.get(0).unwrap();
-        client.pay_bill(&owner, &first_id);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &other,
+            &800,
+            &86400,
+            &true,
+            &30,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_cancel_bill(&other, &bill_id, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.cancel_bill(&owner, &bill_id, &reason);
 
-        let page = client.get_all_bills_for_owner(&owner, &0, &10);
-        assert_eq!(page.count, 5);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.status, BillStatus::Cancelled(reason.clone()));
+        assert!(!bill.paid);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 0);
+        assert_eq!(
+            client.try_mark_bill_paid(&owner, &bill_id),
+            Err(Ok(Error::BillCancelled))
+        );
+        assert_eq!(
+            client.try_cancel_bill(&owner, &bill_id, &reason),
+            Err(Ok(Error::BillCancelled))
+        );
+        assert_eq!(
+            client.try_dispute_bill(&owner, &bill_id, &reason),
+            Err(Ok(Error::BillCancelled))
+        );
     }
 
-    // --- limit clamping ---
+    // --- partial payments ---
 
     #[test]
-    fn test_limit_zero_uses_default() {
+    fn test_pay_bill_partial_tracks_amount_paid() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
 
-        setup_bills(&env, &client, &owner, 3);
-        let page = client.get_unpaid_bills(&owner, &0, &0);
-        assert_eq!(page.count, 3);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "School fees"),
+            &payee,
+            &1000,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "USDC"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &400), 600);
+        assert_eq!(
+            client.try_pay_bill_partial(&owner, &bill_id, &601),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.amount_paid, 400);
+        assert!(!bill.paid);
+        assert_eq!(client.get_total_unpaid(&owner), 600);
+
+        // Paying the rest on-chain only sends what is outstanding
+        assert_eq!(client.pay_bill(&owner, &bill_id, &token), 600);
+        assert_eq!(balances.balance(&payee), 600);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.amount_paid, 1000);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
     }
 
     #[test]
-    fn test_limit_clamped_to_max() {
+    fn test_pay_bill_partial_completes_recurring_bill() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner, 55);
-        let page = client.get_unpaid_bills(&owner, &0, &9999);
-        assert_eq!(page.count, MAX_PAGE_LIMIT);
-        assert!(page.next_cursor > 0);
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &owner,
+            &500,
+            &86400,
+            &true,
+            &30,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
+        );
+        client.pay_bill_partial(&owner, &bill_id, &200);
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &300), 0);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.amount_paid, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 500);
     }
 
-    // --- archived bill pagination ---
+    // --- bill splitting ---
 
     #[test]
-    fn test_get_archived_bills_pagination() {
+    fn test_split_bill_paid_when_all_shares_settled() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let landlord = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sibling, &1000);
+        let balances = TokenClient::new(&env, &token);
 
-        client.set_pause_admin(&owner, &owner);
+        let rent = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Rent"),
+            &landlord,
+            &900,
+            &86400,
+            &true,
+            &30,
+            &String::from_str(&env, "USDC"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_split_bill(
+                &owner,
+                &rent,
+                &soroban_sdk::vec![&env, (owner.clone(), 600), (sibling.clone(), 200)]
+            ),
+            Err(Ok(Error::InvalidSplit))
+        );
+        client.split_bill(
+            &owner,
+            &rent,
+            &soroban_sdk::vec![&env, (owner.clone(), 600), (sibling.clone(), 300)],
+        );
+        assert_eq!(
+            client.try_pay_bill(&owner, &rent, &token),
+            Err(Ok(Error::BillIsSplit))
+        );
 
-        let ids = setup_bills(&env, &client, &owner, 6);
-        for bill_id in ids.iter() {
-            client.pay_bill(&owner, &bill_id);
-        }
-        client.archive_paid_bills(&owner, &u64::MAX);
+        assert!(!client.pay_share(&sibling, &rent, &token));
+        assert_eq!(
+            client.try_pay_share(&sibling, &rent, &token),
+            Err(Ok(Error::BillAlreadyPaid))
+        );
+        assert!(!client.get_bill(&rent).unwrap().paid);
+        assert_eq!(
+            client.try_split_bill(
+                &owner,
+                &rent,
+                &soroban_sdk::vec![&env, (owner.clone(), 900)]
+            ),
+            Err(Ok(Error::InvalidSplit))
+        );
 
-        let page1 = client.get_archived_bills(&owner, &0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
+        assert!(client.pay_share(&owner, &rent, &token));
+        assert!(client.get_bill(&rent).unwrap().paid);
+        assert_eq!(balances.balance(&landlord), 900);
+        assert_eq!(balances.balance(&sibling), 700);
 
-        let page2 = client.get_archived_bills(&owner, &page1.next_cursor, &4);
-        assert_eq!(page2.count, 2);
-        assert_eq!(page2.next_cursor, 0);
+        // The next occurrence is split the same way, unsettled
+        let shares = client.get_bill_shares(&(rent + 1));
+        assert_eq!(shares.len(), 2);
+        assert!(shares.iter().all(|share| !share.paid));
+    }
+
+    #[test]
+    fn test_pay_share_requires_member() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &stranger, 1000);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &payee,
+            &100,
+            &86400,
+            &false,
+            &0,
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
+        );
+        assert_eq!(
+            client.try_pay_share(&stranger, &bill_id, &token),
+            Err(Ok(Error::BillNotFound))
+        );
+        client.split_bill(
+            &owner,
+            &bill_id,
+            &soroban_sdk::vec![&env, (owner.clone(), 100)],
+        );
+        assert_eq!(
+            client.try_pay_share(&stranger, &bill_id, &token),
+            Err(Ok(Error::Unauthorized))
+        );
     }
 
     // -----------------------------------------------------------------------
@@ -1718,15 +6394,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Daily Bill"),
+            &Address::generate(&env),
             &100,
             &base_due_date,
             &true, // recurring
             &1,    // frequency_days = 1
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (1 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1752,15 +6430,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Monthly Bill"),
+            &Address::generate(&env),
             &500,
             &base_due_date,
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (30 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1789,15 +6469,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Annual Bill"),
+            &Address::generate(&env),
             &1200,
             &base_due_date,
             &true, // recurring
             &365,  // frequency_days = 365
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (365 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1829,15 +6511,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Late Payment Test"),
+            &Address::generate(&env),
             &300,
             &base_due_date,
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the bill (at time 1_000_500, which is 500 seconds after due_date)
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify original bill has paid_at set
         let paid_bill = client.get_bill(&bill_id).unwrap();
@@ -1873,15 +6557,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Multi-Cycle Bill"),
+            &Address::generate(&env),
             &250,
             &base_due_date,
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify second bill
         let bill2 = client.get_bill(&2).unwrap();
@@ -1890,7 +6576,7 @@ mod test {
         assert!(!bill2.paid);
 
         // Pay second bill
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify second bill is now paid
         let bill2_paid = client.get_bill(&2).unwrap();
@@ -1922,21 +6608,23 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Three-Cycle Bill"),
+            &Address::generate(&env),
             &150,
             &base_due_date,
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Pay third bill
-        client.pay_bill(&owner, &3);
+        client.mark_bill_paid(&owner, &3);
 
         // Verify third bill is now paid
         let bill3_paid = client.get_bill(&3).unwrap();
@@ -1968,15 +6656,17 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Early Payment Test"),
+            &Address::generate(&env),
             &200,
             &base_due_date,
             &true, // recurring
             &30,   // frequency_days = 30
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the bill early (at time 500_000)
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify original bill has paid_at set to early time
         let paid_bill = client.get_bill(&bill_id).unwrap();
@@ -2006,18 +6696,20 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Weekly Bill"),
+            &Address::generate(&env),
             &50,
             &1_000_000,
             &true,
             &frequency,
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same frequency_days
         let bill1 = client.get_bill(&1).unwrap();
@@ -2042,18 +6734,20 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Fixed Amount Bill"),
+            &Address::generate(&env),
             &amount,
             &1_000_000,
             &true,
             &30,
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same amount
         let bill1 = client.get_bill(&1).unwrap();
@@ -2077,18 +6771,20 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Owner Test"),
+            &Address::generate(&env),
             &100,
             &1_000_000,
             &true,
             &30,
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same owner
         let bill1 = client.get_bill(&1).unwrap();
@@ -2117,14 +6813,16 @@ mod test {
         let bill_id = client.create_bill(
             &owner,
             &String::from_str(&env, "Math Verification"),
+            &Address::generate(&env),
             &100,
             &base_due,
             &true,
             &freq,
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         let next_bill = client.get_bill(&2).unwrap();
         let expected = 1_000_000u64 + (14u64 * 86400);
@@ -2148,18 +6846,22 @@ mod test {
         let frequency: u32 = 30;
         let amount: i128 = 10_000;
         let bill_name = String::from_str(&env, "Rent");
+        let payee = Address::generate(&env);
 
         let bill_id = client.create_bill(
             &owner,
             &bill_name,
+            &payee,
             &amount,
             &original_due_date,
             &true,      // recurring
             &frequency, // frequency_days
+            &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
 
         // Pay the recurring bill – this should create the next occurrence.
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // The next bill should have ID = bill_id + 1.
         let next_id = bill_id + 1;
@@ -2176,10 +6878,7 @@ mod test {
             next_bill.amount, amount,
             "Cloned bill must preserve the original amount"
         );
-        assert_eq!(
-            next_bill.recurring, true,
-            "Cloned bill must remain recurring"
-        );
+        assert!(next_bill.recurring, "Cloned bill must remain recurring");
         assert_eq!(
             next_bill.frequency_days, frequency,
             "Cloned bill must preserve frequency_days"
@@ -2188,7 +6887,11 @@ mod test {
             next_bill.owner, owner,
             "Cloned bill must preserve the original owner"
         );
-        assert_eq!(next_bill.paid, false, "Cloned bill must start as unpaid");
+        assert_eq!(
+            next_bill.payee, payee,
+            "Cloned bill must preserve the original payee"
+        );
+        assert!(!next_bill.paid, "Cloned bill must start as unpaid");
         assert_eq!(
             next_bill.paid_at, None,
             "Cloned bill must have paid_at = None"
@@ -112,7 +112,7 @@ mod testsuit {
     }
 
     #[test]
-    fn test_pay_bill() {
+    fn test_mark_bill_paid() {
         let env = Env::default();
         let contract_id = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &contract_id);
@@ -130,7 +130,7 @@ mod testsuit {
         );
 
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         let bill = client.get_bill(&bill_id).unwrap();
         assert!(bill.paid);
@@ -156,7 +156,7 @@ mod testsuit {
         );
 
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Check original bill is paid
         let bill = client.get_bill(&bill_id).unwrap();
@@ -207,7 +207,7 @@ mod testsuit {
                     &String::from_str(&env, "XLM"),
         );
         env.mock_all_auths();
-        client.pay_bill(&owner, &1);
+        client.mark_bill_paid(&owner, &1);
 
         let unpaid = client.get_unpaid_bills(&owner);
         assert_eq!(unpaid.len(), 2);
@@ -250,7 +250,7 @@ mod testsuit {
                     &String::from_str(&env, "XLM"),
         );
         env.mock_all_auths();
-        client.pay_bill(&owner, &1);
+        client.mark_bill_paid(&owner, &1);
 
         let total = client.get_total_unpaid(&owner);
         assert_eq!(total, 500); // 200 + 300
@@ -264,7 +264,7 @@ mod testsuit {
         let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
 
         env.mock_all_auths();
-        let result = client.try_pay_bill(&owner, &999);
+        let result = client.try_mark_bill_paid(&owner, &999);
         assert_eq!(result, Err(Ok(Error::BillNotFound)));
     }
 
@@ -285,8 +285,8 @@ mod testsuit {
                     &String::from_str(&env, "XLM"),
         );
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
-        let result = client.try_pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
+        let result = client.try_mark_bill_paid(&owner, &bill_id);
         assert_eq!(result, Err(Ok(Error::BillAlreadyPaid)));
     }
 
@@ -447,13 +447,13 @@ mod testsuit {
         );
         env.mock_all_auths();
         // Pay first bill - creates second
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
         let bill2 = client.get_bill(&2).unwrap();
         assert!(!bill2.paid);
         assert_eq!(bill2.due_date, 1000000 + (30 * 86400));
         env.mock_all_auths();
         // Pay second bill - creates third
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
         let bill3 = client.get_bill(&3).unwrap();
         assert!(!bill3.paid);
         assert_eq!(bill3.due_date, 1000000 + (60 * 86400));
@@ -500,14 +500,14 @@ mod testsuit {
             &0,
                     &String::from_str(&env, "XLM"),
         );
-        client.pay_bill(&owner, &1);
+        client.mark_bill_paid(&owner, &1);
 
         // Admin can see all 3 bills
         let all = client.get_all_bills(&admin);
         assert_eq!(all.len(), 3);
     }
     #[test]
-    fn test_pay_bill_unauthorized() {
+    fn test_mark_bill_paid_unauthorized() {
         let env = Env::default();
         let contract_id = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &contract_id);
@@ -525,12 +525,12 @@ mod testsuit {
                     &String::from_str(&env, "XLM"),
         );
 
-        let result = client.try_pay_bill(&other, &bill_id);
+        let result = client.try_mark_bill_paid(&other, &bill_id);
         assert_eq!(result, Err(Ok(Error::Unauthorized)));
     }
 
     #[test]
-    fn test_pay_bill_unauthorized_strict() {
+    fn test_mark_bill_paid_unauthorized_strict() {
         let env = Env::default();
         let contract_id = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &contract_id);
@@ -548,7 +548,7 @@ mod testsuit {
             &0,
         );
 
-        let result = client.try_pay_bill(&owner_b, &bill_id);
+        let result = client.try_mark_bill_paid(&owner_b, &bill_id);
 
         assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
@@ -583,7 +583,7 @@ mod testsuit {
         assert!(bill.is_none());
 
         // Verify paying it fails
-        let result = client.try_pay_bill(&owner, &bill_id);
+        let result = client.try_mark_bill_paid(&owner, &bill_id);
         assert_eq!(result, Err(Ok(Error::BillNotFound)));
     }
 
@@ -611,7 +611,7 @@ mod testsuit {
         assert_eq!(overdue.len(), 1);
 
         // Pay it
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify it's no longer overdue (because it's paid)
         let overdue_after = client.get_overdue_bills(&owner);
@@ -636,7 +636,7 @@ mod testsuit {
                     &String::from_str(&env, "XLM"),
         );
 
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         let next_bill = client.get_bill(&2).unwrap();
         assert_eq!(next_bill.due_date, 1000000 + 86400); // Exactly 1 day later
@@ -771,7 +771,7 @@ mod testsuit {
             &0,
                     &String::from_str(&env, "XLM"),
         );
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         let bills = client.get_all_bills_for_owner(&owner);
         assert_eq!(bills.len(), 1);
@@ -1169,7 +1169,7 @@ mod testsuit {
     /// After create_bill at seq 100 sets TTL to 518,400 (live_until = 518,500),
     /// we must advance past seq 501,220 so TTL drops below 17,280.
     #[test]
-    fn test_instance_ttl_refreshed_on_pay_bill() {
+    fn test_instance_ttl_refreshed_on_mark_bill_paid() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -1213,7 +1213,7 @@ mod testsuit {
         });
 
         // pay_bill calls extend_instance_ttl → re-extends TTL to 518,400
-        client.pay_bill(&owner, &1);
+        client.mark_bill_paid(&owner, &1);
 
         // TTL should be refreshed relative to the new sequence number
         let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -1298,7 +1298,7 @@ mod testsuit {
         });
 
         // Pay second bill to refresh TTL once more
-        client.pay_bill(&owner, &id2);
+        client.mark_bill_paid(&owner, &id2);
 
         // Both bills should still be accessible
         let bill1 = client.get_bill(&id1);
@@ -1362,7 +1362,7 @@ mod testsuit {
             &0,
                     &String::from_str(&env, "XLM"),
         );
-        client.pay_bill(&owner, &1);
+        client.mark_bill_paid(&owner, &1);
 
         // Advance ledger so TTL drops below threshold
         // After pay_bill at seq 100: live_until = 518,500
@@ -1558,7 +1558,7 @@ mod testsuit {
 
         // Pay the bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (1 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1593,7 +1593,7 @@ mod testsuit {
 
         // Pay the bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (30 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1628,7 +1628,7 @@ mod testsuit {
 
         // Pay the bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify next bill's due_date = base_due_date + (365 * 86400)
         let next_bill = client.get_bill(&2).unwrap();
@@ -1666,7 +1666,7 @@ mod testsuit {
 
         // Pay the bill (at time 1_000_500, which is 500 seconds after due_date)
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify original bill has paid_at set
         let paid_bill = client.get_bill(&bill_id).unwrap();
@@ -1711,7 +1711,7 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify second bill
         let bill2 = client.get_bill(&2).unwrap();
@@ -1721,7 +1721,7 @@ mod testsuit {
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify second bill is now paid
         let bill2_paid = client.get_bill(&2).unwrap();
@@ -1762,15 +1762,15 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Pay third bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &3);
+        client.mark_bill_paid(&owner, &3);
 
         // Verify third bill is now paid
         let bill3_paid = client.get_bill(&3).unwrap();
@@ -1811,7 +1811,7 @@ mod testsuit {
 
         // Pay the bill early (at time 500_000)
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Verify original bill has paid_at set to early time
         let paid_bill = client.get_bill(&bill_id).unwrap();
@@ -1850,11 +1850,11 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same frequency_days
         let bill1 = client.get_bill(&1).unwrap();
@@ -1888,11 +1888,11 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same amount
         let bill1 = client.get_bill(&1).unwrap();
@@ -1926,11 +1926,11 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same name
         let bill1 = client.get_bill(&1).unwrap();
@@ -1963,11 +1963,11 @@ mod testsuit {
 
         // Pay first bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         // Pay second bill
         env.mock_all_auths();
-        client.pay_bill(&owner, &2);
+        client.mark_bill_paid(&owner, &2);
 
         // Verify all bills have the same owner
         let bill1 = client.get_bill(&1).unwrap();
@@ -2004,7 +2004,7 @@ mod testsuit {
         );
 
         env.mock_all_auths();
-        client.pay_bill(&owner, &bill_id);
+        client.mark_bill_paid(&owner, &bill_id);
 
         let next_bill = client.get_bill(&2).unwrap();
         let expected = 1_000_000u64 + (14u64 * 86400);
@@ -2068,8 +2068,8 @@ fn test_get_total_unpaid_all_bills_paid_returns_zero() {
         &0,
     );
 
-    client.pay_bill(&owner, &id1);
-    client.pay_bill(&owner, &id2);
+    client.mark_bill_paid(&owner, &id1);
+    client.mark_bill_paid(&owner, &id2);
 
     let total = client.get_total_unpaid(&owner);
     assert_eq!(
@@ -2192,7 +2192,7 @@ fn test_get_total_unpaid_decreases_after_pay() {
     assert_eq!(client.get_total_unpaid(&owner), 600);
 
     // Pay the 200-unit bill
-    client.pay_bill(&owner, &id_b);
+    client.mark_bill_paid(&owner, &id_b);
 
     let total = client.get_total_unpaid(&owner);
     assert_eq!(
@@ -2240,13 +2240,13 @@ fn test_get_total_unpaid_reaches_zero_as_bills_paid_incrementally() {
 
     assert_eq!(client.get_total_unpaid(&owner), 600);
 
-    client.pay_bill(&owner, &id1);
+    client.mark_bill_paid(&owner, &id1);
     assert_eq!(client.get_total_unpaid(&owner), 500, "after paying 100-bill: 500 remaining");
 
-    client.pay_bill(&owner, &id2);
+    client.mark_bill_paid(&owner, &id2);
     assert_eq!(client.get_total_unpaid(&owner), 300, "after paying 200-bill: 300 remaining");
 
-    client.pay_bill(&owner, &id3);
+    client.mark_bill_paid(&owner, &id3);
     assert_eq!(
         client.get_total_unpaid(&owner),
         0,
@@ -2340,7 +2340,7 @@ fn test_get_total_unpaid_paying_other_owner_bill_has_no_effect() {
     );
 
     // Pay owner_b's bill
-    client.pay_bill(&owner_b, &id_b);
+    client.mark_bill_paid(&owner_b, &id_b);
 
     // owner_a's total must be unchanged
     let total_a = client.get_total_unpaid(&owner_a);
@@ -2489,7 +2489,7 @@ fn test_get_total_unpaid_includes_new_recurring_bill_after_pay() {
     assert_eq!(client.get_total_unpaid(&owner), 500);
 
     // Pay it: original becomes paid, a new unpaid bill of 500 is created
-    client.pay_bill(&owner, &bill_id);
+    client.mark_bill_paid(&owner, &bill_id);
 
     // Total must still be 500 (the new recurring bill, not the paid one)
     let total = client.get_total_unpaid(&owner);
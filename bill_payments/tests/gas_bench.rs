@@ -1,6 +1,6 @@
 use bill_payments::{BillPayments, BillPaymentsClient};
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{symbol_short, Address, Env, String};
 
 fn bench_env() -> Env {
     let env = Env::new_with_config(EnvTestConfig {
@@ -42,23 +42,26 @@ fn bench_get_total_unpaid_worst_case() {
     let contract_id = env.register_contract(None, BillPayments);
     let client = BillPaymentsClient::new(&env, &contract_id);
     let owner = <Address as AddressTrait>::generate(&env);
+    let payee = <Address as AddressTrait>::generate(&env);
 
     let name = String::from_str(&env, "BenchBill");
     for _ in 0..100 {
         client.create_bill(
             &owner,
             &name,
+            &payee,
             &100i128,
             &1_000_000u64,
             &false,
             &0u32,
             &String::from_str(&env, "XLM"),
+            &symbol_short!("utilities"),
         );
     }
 
     // Create gaps to simulate worst-case scan behavior in previous implementation.
     for id in (2u32..=100u32).step_by(2) {
-        client.cancel_bill(&owner, &id);
+        client.cancel_bill(&owner, &id, &String::from_str(&env, "duplicate"));
     }
 
     let expected_total = 50i128 * 100i128;
@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use bill_payments::{BillPayments, BillPaymentsClient};
-use soroban_sdk::testutils::Address as _;
+use soroban_sdk::testutils::{Address as _, Ledger};
 use soroban_sdk::{symbol_short, testutils::Events, Address, Env, Symbol, TryFromVal};
 
 #[test]
@@ -22,11 +22,13 @@ fn test_notification_flow() {
     let bill_id = client.create_bill(
         &user,
         &soroban_sdk::String::from_str(&e, "Electricity"),
+        &Address::generate(&e),
         &1000,
         &1234567890,
         &false,
         &0,
         &soroban_sdk::String::from_str(&e, "XLM"),
+        &symbol_short!("utilities"),
     );
 
     // VERIFY: Get Events
@@ -48,7 +50,7 @@ fn test_notification_flow() {
     std::println!("✅ Creation Event Verified");
 
     // CALL: Pay Bill
-    client.pay_bill(&user, &bill_id);
+    client.mark_bill_paid(&user, &bill_id);
 
     // VERIFY: Check for Payment Event
     let new_events = e.events().all();
@@ -65,3 +67,61 @@ fn test_notification_flow() {
 
     std::println!("✅ Payment Event Verified");
 }
+
+#[test]
+fn test_upcoming_due_reminders() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, BillPayments);
+    let client = BillPaymentsClient::new(&e, &contract_id);
+    let user = Address::generate(&e);
+    e.mock_all_auths();
+    e.ledger().set_timestamp(1_000_000);
+
+    let name = soroban_sdk::String::from_str(&e, "Electricity");
+    let xlm = soroban_sdk::String::from_str(&e, "XLM");
+    let payee = Address::generate(&e);
+    // Due in 2 days, in 10 days, and already overdue
+    let soon = client.create_bill(
+        &user,
+        &name,
+        &payee,
+        &250,
+        &(1_000_000 + 2 * 86400 + 60),
+        &false,
+        &0,
+        &xlm,
+        &symbol_short!("utilities"),
+    );
+    client.create_bill(
+        &user,
+        &name,
+        &payee,
+        &400,
+        &(1_000_000 + 10 * 86400),
+        &false,
+        &0,
+        &xlm,
+        &symbol_short!("utilities"),
+    );
+    client.create_bill(
+        &user,
+        &name,
+        &payee,
+        &100,
+        &999_000,
+        &false,
+        &0,
+        &xlm,
+        &symbol_short!("utilities"),
+    );
+
+    assert_eq!(client.emit_upcoming_due(&user, &7), 1);
+
+    let reminder = e.events().all().last().unwrap();
+    let action: Symbol = Symbol::try_from_val(&e, &reminder.1.get(3).unwrap()).unwrap();
+    assert_eq!(action, symbol_short!("due_soon"));
+    let data: (u32, u32, i128) = <(u32, u32, i128)>::try_from_val(&e, &reminder.2).unwrap();
+    assert_eq!(data, (soon, 2, 250));
+
+    assert_eq!(client.emit_upcoming_due(&user, &30), 2);
+}
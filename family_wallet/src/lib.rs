@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
-    Env, Map, Symbol, Vec,
+    Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 // Storage TTL constants for active data
@@ -15,6 +15,27 @@ const ARCHIVE_BUMP_AMOUNT: u32 = 2592000;
 // Signature expiration time (24 hours in seconds)
 const SIGNATURE_EXPIRATION: u64 = 86400;
 
+// Spend limit windows: UTC days and 30-day months
+const DAY_SECS: u64 = 86400;
+const MONTH_SECS: u64 = 30 * DAY_SECS;
+
+// An allowance credited more than this after its due date is reported late
+const ALLOWANCE_LATE_SECS: u64 = DAY_SECS;
+
+// How long a membership invite can be accepted
+const INVITE_EXPIRATION: u64 = 7 * DAY_SECS;
+
+// TTL for per-member transaction history
+const HISTORY_LIFETIME_THRESHOLD: u32 = 17280;
+const HISTORY_BUMP_AMOUNT: u32 = 518400;
+
+/// Time between guardians reaching quorum on a recovery and it becoming
+/// executable, during which the member or an Owner/Admin can cancel it
+pub const RECOVERY_CHALLENGE_SECS: u64 = 3 * DAY_SECS;
+
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+pub const MAX_PAGE_LIMIT: u32 = 50;
+
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -37,6 +58,181 @@ pub enum FamilyRole {
     Viewer = 4,
 }
 
+/// Household role of a member, deciding what they may spend. Members
+/// without an assigned role take the default for their `FamilyRole`:
+/// Owner is Head, Admin and Member are Adult, and Viewer is ViewOnly.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MemberRole {
+    /// Full spending; the only role that assigns member roles
+    Head = 1,
+    /// Full spending, including large withdrawals and emergency transfers
+    Adult = 2,
+    /// Only withdrawals within the regular limit
+    Teen = 3,
+    /// Cannot spend or withdraw
+    ViewOnly = 4,
+}
+
+/// What a spend from a sub-balance is for
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SpendCategory {
+    School = 1,
+    Food = 2,
+    Health = 3,
+    Transport = 4,
+    Housing = 5,
+    Utilities = 6,
+    Other = 7,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategorySpend {
+    pub category: SpendCategory,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MemberTxKind {
+    /// Withdrawal from the member's own account
+    Withdrawal,
+    EmergencyTransfer,
+    /// Spend from the member's sub-balance, with its category
+    Spend(SpendCategory),
+    PoolWithdrawal,
+    /// Spend from the member's sub-balance made by their delegate
+    DelegatedSpend(Address, SpendCategory),
+    /// Spend the member made as delegate from the delegator's sub-balance;
+    /// counted in the delegator's spending, not this member's
+    SpendForDelegator(Address, SpendCategory),
+    /// Spend from the pool the member requested and a head approved
+    RequestedSpend(SpendCategory),
+    /// Spend from the category's budget envelope
+    EnvelopeSpend(SpendCategory),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SpendRequestStatus {
+    Pending = 1,
+    Approved = 2,
+    Rejected = 3,
+}
+
+/// A member's request for a head to pay for something from the pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendRequest {
+    pub id: u64,
+    pub member: Address,
+    pub token: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub category: SpendCategory,
+    pub memo: String,
+    pub status: SpendRequestStatus,
+    pub created_at: u64,
+    pub decided_by: Option<Address>,
+    pub decided_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum SpendRequestEvent {
+    Submitted,
+    Approved,
+    Rejected,
+}
+
+/// Temporary authority for `delegate` to spend from `delegator`'s
+/// sub-balance, up to `cap` in total until `expires_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingDelegation {
+    pub delegator: Address,
+    pub delegate: Address,
+    pub cap: i128,
+    pub spent: i128,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DelegationEvent {
+    Granted,
+    Revoked,
+    Spent,
+}
+
+/// Part of the pool set aside for one spending category. `budget` is the
+/// amount planned per reporting period; `balance` is what is left to spend.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Envelope {
+    pub category: SpendCategory,
+    pub token: Address,
+    pub budget: i128,
+    pub balance: i128,
+    /// Total ever spent from the envelope
+    pub spent: i128,
+    pub created_at: u64,
+}
+
+/// An envelope's budget compared with what was spent from it in a period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvelopeVsActual {
+    pub category: SpendCategory,
+    pub token: Address,
+    pub budget: i128,
+    pub actual: i128,
+    pub balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum EnvelopeEvent {
+    Set,
+    Funded,
+    Released,
+    Spent,
+}
+
+/// One outflow attributed to a member.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberTransaction {
+    pub kind: MemberTxKind,
+    pub token: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Published on `(spent, member)` for every outflow so an indexer can push
+/// notifications without polling. `kind` carries the category, if any.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendNotification {
+    pub member: Address,
+    pub kind: MemberTxKind,
+    pub token: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    /// What the member may still spend after this outflow
+    pub remaining: RemainingLimit,
+    /// Balance left in the envelope, for envelope spends
+    pub envelope_balance: Option<i128>,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct MultiSigConfig {
@@ -77,6 +273,32 @@ pub struct FamilyMember {
     pub added_at: u64,
 }
 
+/// Caps on a member's outflows per day and per 30-day month. 0 = no cap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendLimits {
+    pub daily: i128,
+    pub monthly: i128,
+}
+
+/// A member's outflows in the current day and month windows.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendUsage {
+    pub day_start: u64,
+    pub day_spent: i128,
+    pub month_start: u64,
+    pub month_spent: i128,
+}
+
+/// What a member may still spend today and this month. `None` = no cap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemainingLimit {
+    pub daily: Option<i128>,
+    pub monthly: Option<i128>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct EmergencyConfig {
@@ -103,6 +325,120 @@ pub struct MemberAddedEvent {
     pub timestamp: u64,
 }
 
+/// A pending offer of membership; the invitee joins by accepting it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberInvite {
+    pub invitee: Address,
+    pub role: FamilyRole,
+    pub spending_limit: i128,
+    pub invited_by: Address,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// What happens to a departing member's sub-balances.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Handover {
+    ReturnToPool,
+    TransferTo(Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MemberRemovedEvent {
+    pub member: Address,
+    pub removed_by: Address,
+    pub handover: Handover,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum MembershipEvent {
+    Invited,
+    InviteAccepted,
+    InviteRevoked,
+    Removed,
+    Frozen,
+    Unfrozen,
+    Graduated,
+}
+
+/// Spend limits to apply when a member graduates.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GraduationLimits {
+    /// Keep the member's current limits
+    Keep,
+    Set(SpendLimits),
+}
+
+/// A role change that takes effect on the member's first interaction at or
+/// after `at`, e.g. a teen becoming an adult at 18.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Graduation {
+    pub member: Address,
+    pub at: u64,
+    pub role: MemberRole,
+    pub limits: GraduationLimits,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MemberGraduatedEvent {
+    pub member: Address,
+    pub old_role: MemberRole,
+    pub new_role: MemberRole,
+    pub limits: GraduationLimits,
+    pub timestamp: u64,
+}
+
+/// Guardians who can together move a member's record to a new address
+/// if the member loses their key. Guardians need not be family members.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryGuardians {
+    pub guardians: Vec<Address>,
+    /// Guardian approvals needed to start the challenge window
+    pub threshold: u32,
+}
+
+/// A guardian proposal to rebind `member` to `new_address`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub member: Address,
+    pub new_address: Address,
+    pub approvals: Vec<Address>,
+    pub proposed_at: u64,
+    /// Set once `threshold` guardians approve; executable from then on
+    pub executable_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum RecoveryEvent {
+    GuardiansSet,
+    Proposed,
+    Approved,
+    /// Guardians reached quorum and the challenge window started
+    Challenge,
+    Cancelled,
+    Executed,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MemberFreezeEvent {
+    pub member: Address,
+    /// The head who acted, or the approver whose vote completed M-of-N
+    pub by: Address,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SpendingLimitUpdatedEvent {
@@ -112,6 +448,33 @@ pub struct SpendingLimitUpdatedEvent {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct SpendLimitsUpdatedEvent {
+    pub member: Address,
+    pub daily: i128,
+    pub monthly: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MemberRoleUpdatedEvent {
+    pub member: Address,
+    pub old_role: MemberRole,
+    pub new_role: MemberRole,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CategoryRestrictionsUpdatedEvent {
+    pub member: Address,
+    /// Empty when the member is unrestricted
+    pub categories: Vec<SpendCategory>,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct ArchivedTransaction {
@@ -143,6 +506,8 @@ pub struct AccessAuditEntry {
 
 const CONTRACT_VERSION: u32 = 1;
 const MAX_ACCESS_AUDIT_ENTRIES: u32 = 100;
+const MAX_SPLIT_INFLOWS: u32 = 100;
+const MAX_MEMBER_TRANSACTIONS: u32 = 100;
 const MAX_BATCH_MEMBERS: u32 = 30;
 
 #[contracttype]
@@ -159,6 +524,129 @@ pub enum ArchiveEvent {
     ExpiredCleaned,
 }
 
+/// A recurring credit from the family pool to a member's sub-balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allowance {
+    pub member: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Seconds between credits
+    pub interval: u64,
+    pub next_due: u64,
+    pub last_credited: Option<u64>,
+    /// Intervals that passed without a credit because no trigger ran
+    pub missed_count: u32,
+}
+
+/// A recurring transfer from one member's sub-balance to another's.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingTransfer {
+    pub id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Seconds between transfers
+    pub interval: u64,
+    pub next_due: u64,
+    pub last_executed: Option<u64>,
+    pub paused: bool,
+    /// Intervals that passed without a transfer because no trigger ran
+    pub missed_count: u32,
+}
+
+/// Who must approve large withdrawals from the family pool. Approvers
+/// need not be family members, e.g. a remitter abroad.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithdrawalApprovalConfig {
+    pub approvers: Vec<Address>,
+    /// Approvals needed (M of the N approvers)
+    pub required: u32,
+    /// Withdrawals above this amount need approval
+    pub threshold: i128,
+    /// Seconds a proposal stays open
+    pub ttl: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolWithdrawal {
+    pub id: u64,
+    pub proposer: Address,
+    pub token: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// Share of each split inflow credited straight to a member's sub-balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InflowShare {
+    pub member: Address,
+    /// Basis points of the inflow
+    pub bps: u32,
+}
+
+/// Spending-leg funds received from the remittance_split contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitInflow {
+    pub token: Address,
+    pub amount: i128,
+    /// Part credited to the pool
+    pub to_pool: i128,
+    /// Part credited to member sub-balances by `InflowShare`
+    pub to_members: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum PoolEvent {
+    Funded,
+    Spent,
+    WithdrawProposed,
+    WithdrawApproved,
+    Withdrawn,
+    SplitReceived,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum AllowanceEvent {
+    Set,
+    Removed,
+    Credited,
+    /// Credited more than `ALLOWANCE_LATE_SECS` after it was due
+    Late,
+    /// Whole intervals passed without a credit
+    Missed,
+    /// Due, but the pool cannot cover it; retried on the next trigger
+    Unfunded,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum StandingTransferEvent {
+    Created,
+    Executed,
+    Skipped,
+    Paused,
+    Resumed,
+    Cancelled,
+    /// Whole intervals passed without a transfer
+    Missed,
+    /// Due, but the sender's sub-balance cannot cover it; retried on the
+    /// next trigger
+    Unfunded,
+}
+
 #[contract]
 pub struct FamilyWallet;
 
@@ -179,6 +667,20 @@ pub enum Error {
     MemberNotFound = 11,
     TransactionAlreadyExecuted = 12,
     InvalidSpendingLimit = 13,
+    SpendLimitExceeded = 14,
+    InsufficientBalance = 15,
+    AllowanceNotFound = 16,
+    ApprovalsNotConfigured = 17,
+    CategoryNotAllowed = 18,
+    InviteNotFound = 19,
+    InviteExpired = 20,
+    MemberFrozen = 21,
+    DelegationNotFound = 22,
+    DelegationExpired = 23,
+    EnvelopeNotFound = 24,
+    RecoveryNotFound = 25,
+    RecoveryNotReady = 26,
+    GraduationNotFound = 27,
 }
 
 #[contractimpl]
@@ -393,14 +895,99 @@ impl FamilyWallet {
         Ok(true)
     }
 
+    /// Set a member's daily and monthly caps on outflows. A cap of 0 removes
+    /// it. Withdrawals and emergency transfers the member makes count
+    /// against both caps when they execute.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidSpendingLimit` - If either cap is negative
+    /// * `MemberNotFound` - If member is not in the wallet
+    pub fn set_spend_limits(
+        env: Env,
+        caller: Address,
+        member: Address,
+        daily: i128,
+        monthly: i128,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if daily < 0 || monthly < 0 {
+            return Err(Error::InvalidSpendingLimit);
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut limits: Map<Address, SpendLimits> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPND_LIM"))
+            .unwrap_or_else(|| Map::new(&env));
+        if daily == 0 && monthly == 0 {
+            limits.remove(member.clone());
+        } else {
+            limits.set(member.clone(), SpendLimits { daily, monthly });
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPND_LIM"), &limits);
+
+        env.events().publish(
+            (symbol_short!("updated"), symbol_short!("spendcap")),
+            SpendLimitsUpdatedEvent {
+                member,
+                daily,
+                monthly,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(true)
+    }
+
+    pub fn get_spend_limits(env: Env, member: Address) -> Option<SpendLimits> {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, SpendLimits>>(&symbol_short!("SPND_LIM"))
+            .unwrap_or_else(|| Map::new(&env))
+            .get(member)
+    }
+
+    /// Get what `member` may still spend in the current day and month.
+    pub fn get_remaining_limit(env: Env, member: Address) -> RemainingLimit {
+        let limits = match Self::get_spend_limits(env.clone(), member.clone()) {
+            Some(limits) => limits,
+            None => {
+                return RemainingLimit {
+                    daily: None,
+                    monthly: None,
+                }
+            }
+        };
+        let usage = Self::current_usage(&env, &member);
+        let remaining = |cap: i128, spent: i128| (cap > 0).then(|| (cap - spent).max(0));
+        RemainingLimit {
+            daily: remaining(limits.daily, usage.day_spent),
+            monthly: remaining(limits.monthly, usage.month_spent),
+        }
+    }
+
     /// Check if `caller` is allowed to spend `amount`.
     ///
     /// Rules (checked in order):
     /// 1. Unknown address → false
     /// 2. Negative amount → false
-    /// 3. Owner / Admin → always true (unlimited)
-    /// 4. Member with `spending_limit == 0` → unlimited → true
-    /// 5. Member with `spending_limit > 0` → true iff `amount <= spending_limit`
+    /// 3. ViewOnly member role → false
+    /// 4. Amount above the remaining daily or monthly limit → false
+    /// 5. Owner / Admin → always true (unlimited)
+    /// 6. Member with `spending_limit == 0` → unlimited → true
+    /// 7. Member with `spending_limit > 0` → true iff `amount <= spending_limit`
     pub fn check_spending_limit(env: Env, caller: Address, amount: i128) -> bool {
         if amount < 0 {
             return false;
@@ -412,11 +999,21 @@ impl FamilyWallet {
                 None => return false,
             };
 
-        let member = match members.get(caller) {
+        let member = match members.get(caller.clone()) {
             Some(m) => m,
             None => return false,
         };
 
+        if Self::member_role(&env, &caller) == Some(MemberRole::ViewOnly) {
+            return false;
+        }
+        let remaining = Self::get_remaining_limit(env.clone(), caller.clone());
+        if remaining.daily.is_some_and(|left| amount > left)
+            || remaining.monthly.is_some_and(|left| amount > left)
+        {
+            return false;
+        }
+
         // Owner and Admin are never restricted
         if member.role == FamilyRole::Owner || member.role == FamilyRole::Admin {
             return true;
@@ -518,6 +1115,16 @@ impl FamilyWallet {
             _ => true,
         };
 
+        match &data {
+            TransactionData::Withdrawal(..) => {
+                Self::require_spend_role(&env, &proposer, requires_multisig)
+            }
+            TransactionData::EmergencyTransfer(..) => {
+                Self::require_spend_role(&env, &proposer, true)
+            }
+            _ => {}
+        }
+
         if !requires_multisig {
             return Self::execute_transaction_internal(&env, &proposer, &tx_type, &data, false);
         }
@@ -573,6 +1180,7 @@ impl FamilyWallet {
         if !Self::is_family_member(&env, &signer) {
             panic!("Only family members can sign transactions");
         }
+        Self::require_not_frozen(&env, &signer);
 
         Self::extend_instance_ttl(&env);
 
@@ -886,32 +1494,595 @@ impl FamilyWallet {
         env.storage()
             .instance()
             .set(&symbol_short!("MEMBERS"), &members);
+        Self::clear_member_records(&env, &member);
 
-        Self::append_access_audit(&env, symbol_short!("rem_mem"), &caller, Some(member), true);
+        Self::append_access_audit(
+            &env,
+            symbol_short!("rem_mem"),
+            &caller,
+            Some(member.clone()),
+            true,
+        );
+        Self::publish_member_removed(&env, &member, &caller, Handover::ReturnToPool);
         true
     }
 
-    pub fn get_pending_transaction(env: Env, tx_id: u64) -> Option<PendingTransaction> {
-        let pending_txs: Map<u64, PendingTransaction> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("PEND_TXS"))
-            .expect("Pending transactions map not initialized");
-
-        pending_txs.get(tx_id)
-    }
-
-    pub fn get_multisig_config(env: Env, tx_type: TransactionType) -> Option<MultiSigConfig> {
-        env.storage().instance().get(&Self::get_config_key(tx_type))
-    }
+    /// Remove a member, first handing over their unspent sub-balances:
+    /// back to the pool, or transferred out to another address.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the owner
+    /// * `InvalidRole` - If member is the owner
+    /// * `MemberNotFound` - If member is not in the wallet
+    pub fn remove_member_with_handover(
+        env: Env,
+        caller: Address,
+        member: Address,
+        handover: Handover,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
 
-    pub fn get_family_member(env: Env, member: Address) -> Option<FamilyMember> {
-        let members: Map<Address, FamilyMember> = env
+        let owner: Address = env
             .storage()
             .instance()
-            .get(&symbol_short!("MEMBERS"))
+            .get(&symbol_short!("OWNER"))
             .expect("Wallet not initialized");
-
+        if caller != owner {
+            return Err(Error::Unauthorized);
+        }
+        if member == owner {
+            return Err(Error::InvalidRole);
+        }
+        let mut members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .expect("Wallet not initialized");
+        if members.get(member.clone()).is_none() {
+            return Err(Error::MemberNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        if let Handover::TransferTo(destination) = &handover {
+            let balances: Map<(Address, Address), i128> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("SUB_BAL"))
+                .unwrap_or_else(|| Map::new(&env));
+            for ((holder, token), balance) in balances.iter() {
+                if holder == member {
+                    Self::set_sub_balance(&env, &holder, &token, 0);
+                    TokenClient::new(&env, &token).transfer(
+                        &env.current_contract_address(),
+                        destination,
+                        &balance,
+                    );
+                }
+            }
+        }
+
+        members.remove(member.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MEMBERS"), &members);
+        // Whatever sub-balance is left goes back to the pool
+        Self::clear_member_records(&env, &member);
+        Self::update_storage_stats(&env);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("rem_mem"),
+            &caller,
+            Some(member.clone()),
+            true,
+        );
+        Self::publish_member_removed(&env, &member, &caller, handover);
+        Ok(true)
+    }
+
+    /// Invite `invitee` to join with `role`. They become a member only once
+    /// they call `accept_invite`, within `INVITE_EXPIRATION`. A new invite
+    /// replaces any pending one.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidRole` - If role is `Owner` or invitee is already a member
+    /// * `InvalidSpendingLimit` - If spending_limit is negative
+    pub fn invite_member(
+        env: Env,
+        caller: Address,
+        invitee: Address,
+        role: FamilyRole,
+        spending_limit: i128,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if role == FamilyRole::Owner || Self::is_family_member(&env, &invitee) {
+            return Err(Error::InvalidRole);
+        }
+        if spending_limit < 0 {
+            return Err(Error::InvalidSpendingLimit);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+        let invite = MemberInvite {
+            invitee: invitee.clone(),
+            role,
+            spending_limit,
+            invited_by: caller.clone(),
+            created_at: now,
+            expires_at: now + INVITE_EXPIRATION,
+        };
+        let mut invites = Self::load_invites(&env);
+        invites.set(invitee.clone(), invite.clone());
+        Self::save_invites(&env, &invites);
+
+        Self::append_access_audit(&env, symbol_short!("invite"), &caller, Some(invitee), true);
+        env.events()
+            .publish((symbol_short!("member"), MembershipEvent::Invited), invite);
+        Ok(true)
+    }
+
+    /// Accept a pending invite, joining the wallet with the invited role.
+    ///
+    /// # Errors
+    /// * `InviteNotFound` - If invitee has no pending invite
+    /// * `InviteExpired` - If the invite has expired
+    pub fn accept_invite(env: Env, invitee: Address) -> Result<bool, Error> {
+        invitee.require_auth();
+        Self::require_not_paused(&env);
+
+        let mut invites = Self::load_invites(&env);
+        let invite = invites.get(invitee.clone()).ok_or(Error::InviteNotFound)?;
+        let now = env.ledger().timestamp();
+        if now > invite.expires_at {
+            return Err(Error::InviteExpired);
+        }
+
+        Self::extend_instance_ttl(&env);
+        invites.remove(invitee.clone());
+        Self::save_invites(&env, &invites);
+
+        let mut members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .expect("Wallet not initialized");
+        members.set(
+            invitee.clone(),
+            FamilyMember {
+                address: invitee.clone(),
+                role: invite.role,
+                spending_limit: invite.spending_limit,
+                added_at: now,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MEMBERS"), &members);
+        Self::update_storage_stats(&env);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("accept"),
+            &invitee,
+            Some(invite.invited_by.clone()),
+            true,
+        );
+        env.events().publish(
+            (symbol_short!("member"), MembershipEvent::InviteAccepted),
+            invitee.clone(),
+        );
+        env.events().publish(
+            (symbol_short!("added"), symbol_short!("member")),
+            MemberAddedEvent {
+                member: invitee,
+                role: invite.role,
+                spending_limit: invite.spending_limit,
+                timestamp: now,
+            },
+        );
+        Ok(true)
+    }
+
+    /// Withdraw a pending invite.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InviteNotFound` - If invitee has no pending invite
+    pub fn revoke_invite(env: Env, caller: Address, invitee: Address) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        let mut invites = Self::load_invites(&env);
+        if invites.get(invitee.clone()).is_none() {
+            return Err(Error::InviteNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        invites.remove(invitee.clone());
+        Self::save_invites(&env, &invites);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("rev_inv"),
+            &caller,
+            Some(invitee.clone()),
+            true,
+        );
+        env.events().publish(
+            (symbol_short!("member"), MembershipEvent::InviteRevoked),
+            invitee,
+        );
+        Ok(true)
+    }
+
+    pub fn get_invite(env: Env, invitee: Address) -> Option<MemberInvite> {
+        Self::load_invites(&env).get(invitee)
+    }
+
+    /// Immediately block a member's spends, approvals and allowance credits,
+    /// e.g. when their phone or key is lost or stolen.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller's role is not `Head`
+    /// * `MemberFrozen` - If caller is frozen
+    /// * `MemberNotFound` - If member is not in the wallet
+    pub fn freeze_member(env: Env, caller: Address, member: Address) -> Result<bool, Error> {
+        Self::require_unfrozen_head(&env, &caller)?;
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        Self::set_frozen(&env, &caller, &member, true);
+        Ok(true)
+    }
+
+    /// Lift a freeze. A frozen head cannot unfreeze themselves; another
+    /// head or the M-of-N approvers must.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller's role is not `Head`
+    /// * `MemberFrozen` - If caller is frozen
+    /// * `MemberNotFound` - If member is not in the wallet
+    pub fn unfreeze_member(env: Env, caller: Address, member: Address) -> Result<bool, Error> {
+        Self::require_unfrozen_head(&env, &caller)?;
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        Self::set_frozen(&env, &caller, &member, false);
+        Ok(true)
+    }
+
+    /// Vote to freeze (`frozen = true`) or unfreeze a member as one of the
+    /// pool withdrawal approvers. The change applies once the configured
+    /// `required` approvers have voted the same way, so a head's lost key
+    /// can be frozen without the head.
+    ///
+    /// # Returns
+    /// Votes so far; 0 once the change has been applied
+    ///
+    /// # Errors
+    /// * `ApprovalsNotConfigured` - If no approvers are configured
+    /// * `InvalidSigner` - If approver is not a configured approver
+    /// * `MemberFrozen` - If approver is frozen
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `DuplicateSignature` - If approver already voted the same way
+    pub fn approve_member_freeze(
+        env: Env,
+        approver: Address,
+        member: Address,
+        frozen: bool,
+    ) -> Result<u32, Error> {
+        approver.require_auth();
+        Self::require_not_paused(&env);
+        let config =
+            Self::get_withdrawal_approvals(env.clone()).ok_or(Error::ApprovalsNotConfigured)?;
+        if !config.approvers.contains(&approver) {
+            return Err(Error::InvalidSigner);
+        }
+        if Self::is_member_frozen(env.clone(), approver.clone()) {
+            return Err(Error::MemberFrozen);
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut votes: Map<(Address, bool), Vec<Address>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FRZ_VOTE"))
+            .unwrap_or_else(|| Map::new(&env));
+        let key = (member.clone(), frozen);
+        let mut voters = votes.get(key.clone()).unwrap_or_else(|| Vec::new(&env));
+        if voters.contains(&approver) {
+            return Err(Error::DuplicateSignature);
+        }
+        voters.push_back(approver.clone());
+
+        let count = voters
+            .iter()
+            .filter(|voter| config.approvers.contains(voter))
+            .count() as u32;
+        let result = if count >= config.required {
+            votes.remove(key);
+            Self::set_frozen(&env, &approver, &member, frozen);
+            0
+        } else {
+            votes.set(key, voters);
+            count
+        };
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FRZ_VOTE"), &votes);
+        Ok(result)
+    }
+
+    pub fn is_member_frozen(env: Env, member: Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, u64>>(&symbol_short!("FROZEN"))
+            .map(|frozen| frozen.contains_key(member))
+            .unwrap_or(false)
+    }
+
+    /// Set the guardians who can recover `member`'s record, replacing any
+    /// earlier set and cancelling a pending recovery. An empty list removes
+    /// them.
+    ///
+    /// # Errors
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `InvalidRole` - If member is the owner
+    /// * `InvalidThreshold` - If threshold is 0 or exceeds the guardians
+    /// * `DuplicateSignature` - If a guardian is listed twice
+    /// * `InvalidSigner` - If member lists themselves
+    pub fn set_recovery_guardians(
+        env: Env,
+        member: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+    ) -> Result<bool, Error> {
+        member.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        if Self::is_owner(&env, &member) {
+            return Err(Error::InvalidRole);
+        }
+        if !guardians.is_empty() && (threshold == 0 || threshold > guardians.len()) {
+            return Err(Error::InvalidThreshold);
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == member {
+                return Err(Error::InvalidSigner);
+            }
+            if guardians.first_index_of(&guardian) != Some(i as u32) {
+                return Err(Error::DuplicateSignature);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut configs = Self::load_recovery_guardians(&env);
+        if guardians.is_empty() {
+            configs.remove(member.clone());
+        } else {
+            configs.set(
+                member.clone(),
+                RecoveryGuardians {
+                    guardians: guardians.clone(),
+                    threshold,
+                },
+            );
+        }
+        Self::save_recovery_guardians(&env, &configs);
+        let mut requests = Self::load_recovery_requests(&env);
+        if requests.contains_key(member.clone()) {
+            requests.remove(member.clone());
+            Self::save_recovery_requests(&env, &requests);
+        }
+
+        env.events().publish(
+            (symbol_short!("recovery"), RecoveryEvent::GuardiansSet),
+            (member, guardians.len(), threshold),
+        );
+        Ok(true)
+    }
+
+    pub fn get_recovery_guardians(env: Env, member: Address) -> Option<RecoveryGuardians> {
+        Self::load_recovery_guardians(&env).get(member)
+    }
+
+    /// Propose moving `member`'s record to `new_address` as one of their
+    /// guardians, replacing any pending proposal. The proposal counts as the
+    /// guardian's approval.
+    ///
+    /// # Returns
+    /// Approvals so far
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If guardian is not one of the member's guardians,
+    ///   or new_address is already a member
+    /// * `InvalidRole` - If member is the owner
+    pub fn propose_recovery(
+        env: Env,
+        guardian: Address,
+        member: Address,
+        new_address: Address,
+    ) -> Result<u32, Error> {
+        guardian.require_auth();
+        Self::require_not_paused(&env);
+        let config = Self::guardian_config(&env, &guardian, &member)?;
+        if Self::is_owner(&env, &member) {
+            return Err(Error::InvalidRole);
+        }
+        if Self::is_family_member(&env, &new_address) {
+            return Err(Error::InvalidSigner);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+        let mut request = RecoveryRequest {
+            member: member.clone(),
+            new_address: new_address.clone(),
+            approvals: Vec::new(&env),
+            proposed_at: now,
+            executable_at: None,
+        };
+        env.events().publish(
+            (symbol_short!("recovery"), RecoveryEvent::Proposed),
+            (member.clone(), guardian.clone(), new_address),
+        );
+        Ok(Self::add_recovery_approval(
+            &env,
+            &config,
+            &mut request,
+            &guardian,
+        ))
+    }
+
+    /// Approve the pending recovery of `member` as one of their guardians.
+    /// Once `threshold` guardians approve, the recovery can be executed
+    /// after `RECOVERY_CHALLENGE_SECS`.
+    ///
+    /// # Returns
+    /// Approvals so far
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If guardian is not one of the member's guardians
+    /// * `RecoveryNotFound` - If no recovery is pending for the member
+    /// * `DuplicateSignature` - If guardian already approved
+    pub fn approve_recovery(env: Env, guardian: Address, member: Address) -> Result<u32, Error> {
+        guardian.require_auth();
+        Self::require_not_paused(&env);
+        let config = Self::guardian_config(&env, &guardian, &member)?;
+        let mut request = Self::load_recovery_requests(&env)
+            .get(member.clone())
+            .ok_or(Error::RecoveryNotFound)?;
+        if request.approvals.contains(&guardian) {
+            return Err(Error::DuplicateSignature);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Ok(Self::add_recovery_approval(
+            &env,
+            &config,
+            &mut request,
+            &guardian,
+        ))
+    }
+
+    /// Cancel the pending recovery of `member`. The member can do this with
+    /// their current key during the challenge window, as can an Owner or
+    /// Admin.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is neither the member nor Owner or Admin
+    /// * `RecoveryNotFound` - If no recovery is pending for the member
+    pub fn cancel_recovery(env: Env, caller: Address, member: Address) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if caller != member && !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        let mut requests = Self::load_recovery_requests(&env);
+        if !requests.contains_key(member.clone()) {
+            return Err(Error::RecoveryNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        requests.remove(member.clone());
+        Self::save_recovery_requests(&env, &requests);
+
+        env.events().publish(
+            (symbol_short!("recovery"), RecoveryEvent::Cancelled),
+            (member, caller),
+        );
+        Ok(true)
+    }
+
+    /// Move `member`'s record to the approved new address once the
+    /// challenge window has passed. Anyone may call this. Role, limits,
+    /// balances, allowances, history and other per-member state move with
+    /// the record, and the new address replaces the old one in signer,
+    /// approver and guardian lists.
+    ///
+    /// # Errors
+    /// * `RecoveryNotFound` - If no recovery is pending for the member
+    /// * `RecoveryNotReady` - If guardians have not reached quorum or the
+    ///   challenge window has not passed
+    /// * `MemberNotFound` - If member has left the wallet
+    /// * `InvalidSigner` - If the new address has since become a member
+    pub fn execute_recovery(env: Env, member: Address) -> Result<Address, Error> {
+        Self::require_not_paused(&env);
+        let mut requests = Self::load_recovery_requests(&env);
+        let request = requests
+            .get(member.clone())
+            .ok_or(Error::RecoveryNotFound)?;
+        match request.executable_at {
+            Some(at) if env.ledger().timestamp() >= at => {}
+            _ => return Err(Error::RecoveryNotReady),
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        if Self::is_family_member(&env, &request.new_address) {
+            return Err(Error::InvalidSigner);
+        }
+
+        Self::extend_instance_ttl(&env);
+        requests.remove(member.clone());
+        Self::save_recovery_requests(&env, &requests);
+        Self::rebind_member_records(&env, &member, &request.new_address);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("recover"),
+            &request.new_address,
+            Some(member.clone()),
+            true,
+        );
+        env.events().publish(
+            (symbol_short!("recovery"), RecoveryEvent::Executed),
+            (member, request.new_address.clone()),
+        );
+        Ok(request.new_address)
+    }
+
+    pub fn get_recovery_request(env: Env, member: Address) -> Option<RecoveryRequest> {
+        Self::load_recovery_requests(&env).get(member)
+    }
+
+    pub fn get_pending_transaction(env: Env, tx_id: u64) -> Option<PendingTransaction> {
+        let pending_txs: Map<u64, PendingTransaction> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PEND_TXS"))
+            .expect("Pending transactions map not initialized");
+
+        pending_txs.get(tx_id)
+    }
+
+    pub fn get_multisig_config(env: Env, tx_type: TransactionType) -> Option<MultiSigConfig> {
+        env.storage().instance().get(&Self::get_config_key(tx_type))
+    }
+
+    pub fn get_family_member(env: Env, member: Address) -> Option<FamilyMember> {
+        let members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .expect("Wallet not initialized");
+
         members.get(member)
     }
 
@@ -1058,8 +2229,20 @@ impl FamilyWallet {
             .instance()
             .set(&symbol_short!("PEND_TXS"), &pending_txs);
 
-        Self::update_storage_stats(&env);
-
+        let mut pool_withdrawals = Self::load_pool_withdrawals(&env);
+        let before = pool_withdrawals.len();
+        for (id, proposal) in pool_withdrawals.clone().iter() {
+            if proposal.expires_at < current_time {
+                pool_withdrawals.remove(id);
+            }
+        }
+        if pool_withdrawals.len() != before {
+            removed_count += before - pool_withdrawals.len();
+            Self::save_pool_withdrawals(&env, &pool_withdrawals);
+        }
+
+        Self::update_storage_stats(&env);
+
         env.events().publish(
             (symbol_short!("wallet"), ArchiveEvent::ExpiredCleaned),
             (removed_count, caller),
@@ -1110,6 +2293,318 @@ impl FamilyWallet {
         Self::get_role_expiry(&env, &address)
     }
 
+    /// Assign a member's household role. Only a Head may assign roles, and
+    /// the owner always stays Head.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not a Head
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `InvalidRole` - If the owner would lose the Head role
+    pub fn set_member_role(
+        env: Env,
+        caller: Address,
+        member: Address,
+        role: MemberRole,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::member_role(&env, &caller) != Some(MemberRole::Head) {
+            return Err(Error::Unauthorized);
+        }
+        let old_role = Self::member_role(&env, &member).ok_or(Error::MemberNotFound)?;
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("OWNER"))
+            .expect("Wallet not initialized");
+        if member == owner && role != MemberRole::Head {
+            return Err(Error::InvalidRole);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut roles: Map<Address, MemberRole> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEM_ROLE"))
+            .unwrap_or_else(|| Map::new(&env));
+        roles.set(member.clone(), role);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MEM_ROLE"), &roles);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("mem_role"),
+            &caller,
+            Some(member.clone()),
+            true,
+        );
+        env.events().publish(
+            (symbol_short!("updated"), symbol_short!("role")),
+            MemberRoleUpdatedEvent {
+                member,
+                old_role,
+                new_role: role,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(true)
+    }
+
+    /// Schedule `member` to move to `role`, and optionally to new spend
+    /// limits, on their first interaction at or after `at`. Replaces any
+    /// earlier schedule.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not a Head
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `InvalidRole` - If member is the owner
+    /// * `InvalidSpendingLimit` - If a limit is negative
+    pub fn schedule_graduation(
+        env: Env,
+        caller: Address,
+        member: Address,
+        at: u64,
+        role: MemberRole,
+        limits: GraduationLimits,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if Self::member_role(&env, &caller) != Some(MemberRole::Head) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        if Self::is_owner(&env, &member) {
+            return Err(Error::InvalidRole);
+        }
+        if let GraduationLimits::Set(limits) = &limits {
+            if limits.daily < 0 || limits.monthly < 0 {
+                return Err(Error::InvalidSpendingLimit);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut graduations = Self::load_graduations(&env);
+        graduations.set(
+            member.clone(),
+            Graduation {
+                member: member.clone(),
+                at,
+                role,
+                limits,
+            },
+        );
+        Self::save_graduations(&env, &graduations);
+
+        Self::append_access_audit(&env, symbol_short!("grad_set"), &caller, Some(member), true);
+        Ok(true)
+    }
+
+    /// Cancel a member's scheduled graduation.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not a Head
+    /// * `GraduationNotFound` - If no graduation is scheduled
+    pub fn cancel_graduation(env: Env, caller: Address, member: Address) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if Self::member_role(&env, &caller) != Some(MemberRole::Head) {
+            return Err(Error::Unauthorized);
+        }
+        let mut graduations = Self::load_graduations(&env);
+        if !graduations.contains_key(member.clone()) {
+            return Err(Error::GraduationNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        graduations.remove(member.clone());
+        Self::save_graduations(&env, &graduations);
+
+        Self::append_access_audit(&env, symbol_short!("grad_del"), &caller, Some(member), true);
+        Ok(true)
+    }
+
+    pub fn get_graduation(env: Env, member: Address) -> Option<Graduation> {
+        Self::load_graduations(&env).get(member)
+    }
+
+    /// Get a member's household role, or `None` if the address is not a
+    /// member. Other contracts consult this before acting for a member.
+    pub fn get_member_role(env: Env, member: Address) -> Option<MemberRole> {
+        Self::member_role(&env, &member)
+    }
+
+    /// Limit the categories a member may `spend` on. An empty list lifts
+    /// the restriction. Restricted members cannot make uncategorized pool
+    /// withdrawals.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller's role is not `Head`
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `InvalidRole` - If member is the owner
+    pub fn set_category_restrictions(
+        env: Env,
+        caller: Address,
+        member: Address,
+        categories: Vec<SpendCategory>,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+
+        if Self::member_role(&env, &caller) != Some(MemberRole::Head) {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("OWNER"))
+            .expect("Wallet not initialized");
+        if member == owner {
+            return Err(Error::InvalidRole);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut restrictions: Map<Address, Vec<SpendCategory>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_RSTR"))
+            .unwrap_or_else(|| Map::new(&env));
+        if categories.is_empty() {
+            restrictions.remove(member.clone());
+        } else {
+            restrictions.set(member.clone(), categories.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_RSTR"), &restrictions);
+
+        Self::append_access_audit(
+            &env,
+            symbol_short!("cat_rstr"),
+            &caller,
+            Some(member.clone()),
+            true,
+        );
+        env.events().publish(
+            (symbol_short!("updated"), symbol_short!("category")),
+            CategoryRestrictionsUpdatedEvent {
+                member,
+                categories,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(true)
+    }
+
+    /// Get the categories a member is restricted to; empty if unrestricted.
+    pub fn get_category_restrictions(env: Env, member: Address) -> Vec<SpendCategory> {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, Vec<SpendCategory>>>(&symbol_short!("CAT_RSTR"))
+            .and_then(|restrictions| restrictions.get(member))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Total recorded spending per category over the days starting within
+    /// `[period_start, period_end]`, in category order. Categories with no
+    /// spending are omitted.
+    pub fn get_category_spending(
+        env: Env,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<CategorySpend> {
+        let spending: Map<(u64, SpendCategory), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_SPND"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut totals: Map<SpendCategory, i128> = Map::new(&env);
+        for ((day, category), amount) in spending.iter() {
+            if day >= period_start && day <= period_end {
+                totals.set(category, totals.get(category).unwrap_or(0) + amount);
+            }
+        }
+
+        let mut result = Vec::new(&env);
+        for (category, amount) in totals.iter() {
+            result.push_back(CategorySpend { category, amount });
+        }
+        result
+    }
+
+    /// Get a page of the outflows attributed to `member`, oldest first. Only
+    /// the latest `MAX_MEMBER_TRANSACTIONS` are kept.
+    /// `limit` is clamped to `MAX_PAGE_LIMIT`; 0 means `DEFAULT_PAGE_LIMIT`.
+    /// History outlives membership, so former members can still be queried.
+    pub fn get_member_transactions(
+        env: Env,
+        member: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<MemberTransaction> {
+        let limit = if limit == 0 {
+            DEFAULT_PAGE_LIMIT
+        } else {
+            limit.min(MAX_PAGE_LIMIT)
+        };
+        let history = Self::load_member_transactions(&env, &member);
+        let mut page = Vec::new(&env);
+        for tx in history.iter().skip(offset as usize).take(limit as usize) {
+            page.push_back(tx);
+        }
+        page
+    }
+
+    pub fn get_member_transaction_count(env: Env, member: Address) -> u32 {
+        Self::load_member_transactions(&env, &member).len()
+    }
+
+    /// Total outflows attributed to `member` within `[period_start, period_end]`.
+    pub fn get_member_spending(
+        env: Env,
+        member: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> i128 {
+        Self::load_member_transactions(&env, &member)
+            .iter()
+            .filter(|tx| tx.timestamp >= period_start && tx.timestamp <= period_end)
+            .filter(|tx| !matches!(tx.kind, MemberTxKind::SpendForDelegator(..)))
+            .map(|tx| tx.amount)
+            .sum()
+    }
+
+    /// Total outflows of all current members within
+    /// `[period_start, period_end]`.
+    pub fn get_family_spending(env: Env, period_start: u64, period_end: u64) -> i128 {
+        let members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .unwrap_or_else(|| Map::new(&env));
+        members
+            .keys()
+            .iter()
+            .map(|member| Self::get_member_spending(env.clone(), member, period_start, period_end))
+            .sum()
+    }
+
+    pub fn get_member_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, FamilyMember>>(&symbol_short!("MEMBERS"))
+            .map(|members| members.len())
+            .unwrap_or(0)
+    }
+
     pub fn pause(env: Env, caller: Address) -> bool {
         caller.require_auth();
         Self::require_role_at_least(&env, &caller, FamilyRole::Admin);
@@ -1280,6 +2775,7 @@ impl FamilyWallet {
             }
             if members_map.get(addr.clone()).is_some() {
                 members_map.remove(addr.clone());
+                Self::clear_member_records(&env, &addr);
                 Self::append_access_audit(
                     &env,
                     symbol_short!("rem_mem"),
@@ -1287,35 +2783,1792 @@ impl FamilyWallet {
                     Some(addr.clone()),
                     true,
                 );
+                Self::publish_member_removed(&env, &addr, &caller, Handover::ReturnToPool);
                 count += 1;
             }
         }
         env.storage()
             .instance()
-            .set(&symbol_short!("MEMBERS"), &members_map);
-        Self::update_storage_stats(&env);
-        count
+            .set(&symbol_short!("MEMBERS"), &members_map);
+        Self::update_storage_stats(&env);
+        count
+    }
+
+    pub fn get_access_audit(env: Env, limit: u32) -> Vec<AccessAuditEntry> {
+        let entries: Vec<AccessAuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ACC_AUDIT"))
+            .unwrap_or_else(|| Vec::new(&env));
+        let n = entries.len().min(limit);
+        let mut out = Vec::new(&env);
+        for i in (entries.len().saturating_sub(n))..entries.len() {
+            if let Some(e) = entries.get(i) {
+                out.push_back(e);
+            }
+        }
+        out
+    }
+
+    // -----------------------------------------------------------------------
+    // Family pool and allowances
+    // -----------------------------------------------------------------------
+
+    /// Deposit `amount` of `token` from `from` into the family pool that
+    /// allowances are paid from. Anyone may fund the pool.
+    ///
+    /// # Returns
+    /// The pool's balance of `token` after the deposit
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn fund_pool(env: Env, from: Address, token: Address, amount: i128) -> Result<i128, Error> {
+        from.require_auth();
+        Self::require_not_paused(&env);
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&from, &env.current_contract_address(), &amount);
+        let balance = Self::adjust_pool(&env, &token, amount);
+
+        env.events().publish(
+            (symbol_short!("pool"), PoolEvent::Funded),
+            (from, token, amount),
+        );
+        Ok(balance)
+    }
+
+    /// Set the remittance_split contract allowed to deliver Spending-leg
+    /// funds through `receive_split_funds`. `None` stops accepting them.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the owner
+    pub fn set_split_contract(
+        env: Env,
+        caller: Address,
+        split: Option<Address>,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        let owner: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("OWNER"))
+            .expect("Wallet not initialized");
+        if caller != owner {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &split {
+            Some(split) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("SPLIT_CT"), split),
+            None => env.storage().instance().remove(&symbol_short!("SPLIT_CT")),
+        }
+        Self::append_access_audit(&env, symbol_short!("split_ct"), &caller, split, true);
+        Ok(true)
+    }
+
+    pub fn get_split_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SPLIT_CT"))
+    }
+
+    /// Route a share of every split inflow straight to members'
+    /// sub-balances; the rest goes to the pool. An empty list sends
+    /// everything to the pool.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `MemberNotFound` - If a listed member is not in the wallet
+    /// * `DuplicateSignature` - If a member is listed twice
+    /// * `InvalidAmount` - If the shares exceed 10,000 basis points
+    pub fn set_inflow_shares(
+        env: Env,
+        caller: Address,
+        shares: Vec<InflowShare>,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        let mut total_bps = 0u32;
+        for (i, share) in shares.iter().enumerate() {
+            if !Self::is_family_member(&env, &share.member) {
+                return Err(Error::MemberNotFound);
+            }
+            if shares
+                .iter()
+                .take(i)
+                .any(|earlier| earlier.member == share.member)
+            {
+                return Err(Error::DuplicateSignature);
+            }
+            total_bps = total_bps.saturating_add(share.bps);
+        }
+        if total_bps > 10_000 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("IN_SHARE"), &shares);
+        Ok(true)
+    }
+
+    pub fn get_inflow_shares(env: Env) -> Vec<InflowShare> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("IN_SHARE"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Receive the Spending leg of a remittance split. Only the contract set
+    /// with `set_split_contract` may call this. Member shares are credited
+    /// to sub-balances and the rest, including rounding, to the pool. The
+    /// inflow is recorded for reconciliation; only the latest
+    /// `MAX_SPLIT_INFLOWS` are kept.
+    ///
+    /// # Returns
+    /// The amount credited to the pool
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If payer is not the configured split contract
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn receive_split_funds(
+        env: Env,
+        payer: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        payer.require_auth();
+        if Self::get_split_contract(env.clone()) != Some(payer.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+
+        let mut to_members = 0i128;
+        for share in Self::get_inflow_shares(env.clone()).iter() {
+            let credit = amount * share.bps as i128 / 10_000;
+            if credit > 0 {
+                let balance =
+                    Self::get_sub_balance(env.clone(), share.member.clone(), token.clone());
+                Self::set_sub_balance(&env, &share.member, &token, balance + credit);
+                to_members += credit;
+            }
+        }
+        let to_pool = amount - to_members;
+        Self::adjust_pool(&env, &token, to_pool);
+
+        let inflow = SplitInflow {
+            token,
+            amount,
+            to_pool,
+            to_members,
+            timestamp: env.ledger().timestamp(),
+        };
+        let key = symbol_short!("INFLOWS");
+        let mut inflows: Vec<SplitInflow> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        inflows.push_back(inflow.clone());
+        if inflows.len() > MAX_SPLIT_INFLOWS {
+            inflows.pop_front();
+        }
+        env.storage().persistent().set(&key, &inflows);
+        env.storage().persistent().extend_ttl(
+            &key,
+            HISTORY_LIFETIME_THRESHOLD,
+            HISTORY_BUMP_AMOUNT,
+        );
+
+        env.events()
+            .publish((symbol_short!("pool"), PoolEvent::SplitReceived), inflow);
+        Ok(to_pool)
+    }
+
+    /// Split inflows received within `[period_start, period_end]`, oldest
+    /// first.
+    pub fn get_split_inflows(env: Env, period_start: u64, period_end: u64) -> Vec<SplitInflow> {
+        let inflows: Vec<SplitInflow> = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("INFLOWS"))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut result = Vec::new(&env);
+        for inflow in inflows.iter() {
+            if inflow.timestamp >= period_start && inflow.timestamp <= period_end {
+                result.push_back(inflow);
+            }
+        }
+        result
+    }
+
+    pub fn get_pool_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, i128>>(&symbol_short!("POOL"))
+            .unwrap_or_else(|| Map::new(&env))
+            .get(token)
+            .unwrap_or(0)
+    }
+
+    /// Get the part of the pool credited to `member` and not yet spent.
+    pub fn get_sub_balance(env: Env, member: Address, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<(Address, Address), i128>>(&symbol_short!("SUB_BAL"))
+            .unwrap_or_else(|| Map::new(&env))
+            .get((member, token))
+            .unwrap_or(0)
+    }
+
+    /// Pay `amount` of `token` from the member's sub-balance to `recipient`
+    /// for `category`. Counts against the member's spend limits and is
+    /// recorded under the category for reporting.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `CategoryNotAllowed` - If the member is restricted to other categories
+    /// * `InsufficientBalance` - If the sub-balance does not cover amount
+    ///
+    /// # Panics
+    /// * If the member's role cannot spend
+    /// * With `SpendLimitExceeded` if a daily or monthly cap would be exceeded
+    pub fn spend(
+        env: Env,
+        member: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        category: SpendCategory,
+    ) -> Result<(), Error> {
+        member.require_auth();
+        Self::require_not_paused(&env);
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::require_spend_role(&env, &member, false);
+        Self::spend_sub_balance(&env, &member, &token, &recipient, amount, category)?;
+
+        Self::append_member_transaction(
+            &env,
+            &member,
+            MemberTxKind::Spend(category),
+            &token,
+            &recipient,
+            amount,
+        );
+        env.events().publish(
+            (symbol_short!("pool"), PoolEvent::Spent),
+            (member, recipient, token, amount, category),
+        );
+        Ok(())
+    }
+
+    /// Create or update the budget envelope for `category`. If the token
+    /// changes, the old token's remaining balance goes back to the pool.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidAmount` - If budget is negative
+    pub fn set_envelope(
+        env: Env,
+        caller: Address,
+        category: SpendCategory,
+        token: Address,
+        budget: i128,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if budget < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut envelopes = Self::load_envelopes(&env);
+        let envelope = match envelopes.get(category) {
+            Some(mut envelope) => {
+                if envelope.token != token {
+                    Self::adjust_pool(&env, &envelope.token, envelope.balance);
+                    envelope.token = token.clone();
+                    envelope.balance = 0;
+                }
+                envelope.budget = budget;
+                envelope
+            }
+            None => Envelope {
+                category,
+                token: token.clone(),
+                budget,
+                balance: 0,
+                spent: 0,
+                created_at: env.ledger().timestamp(),
+            },
+        };
+        envelopes.set(category, envelope);
+        Self::save_envelopes(&env, &envelopes);
+
+        env.events().publish(
+            (symbol_short!("envelope"), EnvelopeEvent::Set),
+            (category, token, budget),
+        );
+        Ok(true)
+    }
+
+    /// Move `amount` from the pool into `category`'s envelope, returning
+    /// the envelope's new balance.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `EnvelopeNotFound` - If there is no envelope for the category
+    /// * `InsufficientBalance` - If the pool does not cover amount
+    pub fn fund_envelope(
+        env: Env,
+        caller: Address,
+        category: SpendCategory,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut envelopes = Self::load_envelopes(&env);
+        let mut envelope = envelopes.get(category).ok_or(Error::EnvelopeNotFound)?;
+        if Self::get_pool_balance(env.clone(), envelope.token.clone()) < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::adjust_pool(&env, &envelope.token, -amount);
+        envelope.balance += amount;
+        let balance = envelope.balance;
+        envelopes.set(category, envelope);
+        Self::save_envelopes(&env, &envelopes);
+
+        env.events().publish(
+            (symbol_short!("envelope"), EnvelopeEvent::Funded),
+            (category, amount, balance),
+        );
+        Ok(balance)
+    }
+
+    /// Move `amount` from `category`'s envelope back into the pool,
+    /// returning the envelope's new balance.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `EnvelopeNotFound` - If there is no envelope for the category
+    /// * `InsufficientBalance` - If the envelope does not hold amount
+    pub fn release_envelope(
+        env: Env,
+        caller: Address,
+        category: SpendCategory,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut envelopes = Self::load_envelopes(&env);
+        let mut envelope = envelopes.get(category).ok_or(Error::EnvelopeNotFound)?;
+        if envelope.balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::adjust_pool(&env, &envelope.token, amount);
+        envelope.balance -= amount;
+        let balance = envelope.balance;
+        envelopes.set(category, envelope);
+        Self::save_envelopes(&env, &envelopes);
+
+        env.events().publish(
+            (symbol_short!("envelope"), EnvelopeEvent::Released),
+            (category, amount, balance),
+        );
+        Ok(balance)
+    }
+
+    /// Pay `amount` to `recipient` from `category`'s envelope. The spend
+    /// counts against the member's limits and category restrictions and is
+    /// attributed to them in their history.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `EnvelopeNotFound` - If there is no envelope for the category
+    /// * `CategoryNotAllowed` - If the member is restricted to other categories
+    /// * `InsufficientBalance` - If the envelope does not cover amount
+    ///
+    /// # Panics
+    /// * If the member's role cannot spend
+    /// * With `SpendLimitExceeded` if a daily or monthly cap would be exceeded
+    pub fn spend_from_envelope(
+        env: Env,
+        member: Address,
+        category: SpendCategory,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        member.require_auth();
+        Self::require_not_paused(&env);
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::require_spend_role(&env, &member, false);
+        let mut envelopes = Self::load_envelopes(&env);
+        let mut envelope = envelopes.get(category).ok_or(Error::EnvelopeNotFound)?;
+        let allowed = Self::get_category_restrictions(env.clone(), member.clone());
+        if !allowed.is_empty() && !allowed.contains(category) {
+            return Err(Error::CategoryNotAllowed);
+        }
+        if envelope.balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::record_outflow(&env, &member, amount);
+        envelope.balance -= amount;
+        envelope.spent += amount;
+        let token = envelope.token.clone();
+        envelopes.set(category, envelope);
+        Self::save_envelopes(&env, &envelopes);
+        Self::record_category_spend(&env, category, amount);
+        Self::record_envelope_spend(&env, category, amount);
+        TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        Self::append_member_transaction(
+            &env,
+            &member,
+            MemberTxKind::EnvelopeSpend(category),
+            &token,
+            &recipient,
+            amount,
+        );
+        env.events().publish(
+            (symbol_short!("envelope"), EnvelopeEvent::Spent),
+            (member, recipient, category, amount),
+        );
+        Ok(())
+    }
+
+    pub fn get_envelope(env: Env, category: SpendCategory) -> Option<Envelope> {
+        Self::load_envelopes(&env).get(category)
+    }
+
+    /// All budget envelopes, in category order.
+    pub fn get_envelopes(env: Env) -> Vec<Envelope> {
+        Self::load_envelopes(&env).values()
+    }
+
+    /// Compare each envelope's budget with what was spent from it over the
+    /// days starting within `[period_start, period_end]`, in category order.
+    pub fn get_envelope_vs_actual(
+        env: Env,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<EnvelopeVsActual> {
+        let spending: Map<(u64, SpendCategory), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ENV_SPND"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut report = Vec::new(&env);
+        for (category, envelope) in Self::load_envelopes(&env).iter() {
+            let mut actual = 0i128;
+            for ((day, spent_category), amount) in spending.iter() {
+                if spent_category == category && day >= period_start && day <= period_end {
+                    actual += amount;
+                }
+            }
+            report.push_back(EnvelopeVsActual {
+                category,
+                token: envelope.token,
+                budget: envelope.budget,
+                actual,
+                balance: envelope.balance,
+            });
+        }
+        report
+    }
+
+    /// Let `delegate` spend up to `cap` in total from the delegator's
+    /// sub-balances until `expires_at`, e.g. while the delegator travels.
+    /// Replaces any delegation the delegator already gave.
+    ///
+    /// # Errors
+    /// * `MemberNotFound` - If either party is not in the wallet
+    /// * `InvalidSigner` - If delegate is the delegator
+    /// * `InvalidAmount` - If cap is not positive
+    /// * `DelegationExpired` - If expires_at is not in the future
+    pub fn delegate_spending(
+        env: Env,
+        delegator: Address,
+        delegate: Address,
+        cap: i128,
+        expires_at: u64,
+    ) -> Result<bool, Error> {
+        delegator.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_family_member(&env, &delegator) || !Self::is_family_member(&env, &delegate) {
+            return Err(Error::MemberNotFound);
+        }
+        if delegate == delegator {
+            return Err(Error::InvalidSigner);
+        }
+        if cap <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(Error::DelegationExpired);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let delegation = SpendingDelegation {
+            delegator: delegator.clone(),
+            delegate,
+            cap,
+            spent: 0,
+            created_at: now,
+            expires_at,
+        };
+        let mut delegations = Self::load_delegations(&env);
+        delegations.set(delegator, delegation.clone());
+        Self::save_delegations(&env, &delegations);
+
+        env.events().publish(
+            (symbol_short!("delegate"), DelegationEvent::Granted),
+            delegation,
+        );
+        Ok(true)
+    }
+
+    /// End the delegator's delegation before it expires.
+    ///
+    /// # Errors
+    /// * `DelegationNotFound` - If the delegator has no delegation
+    pub fn revoke_delegation(env: Env, delegator: Address) -> Result<bool, Error> {
+        delegator.require_auth();
+        Self::require_not_paused(&env);
+        let mut delegations = Self::load_delegations(&env);
+        let delegation = delegations
+            .get(delegator.clone())
+            .ok_or(Error::DelegationNotFound)?;
+
+        Self::extend_instance_ttl(&env);
+        delegations.remove(delegator);
+        Self::save_delegations(&env, &delegations);
+
+        env.events().publish(
+            (symbol_short!("delegate"), DelegationEvent::Revoked),
+            (delegation.delegator, delegation.delegate),
+        );
+        Ok(true)
+    }
+
+    pub fn get_delegation(env: Env, delegator: Address) -> Option<SpendingDelegation> {
+        Self::load_delegations(&env).get(delegator)
+    }
+
+    /// Ask a head to pay `amount` of `token` to `recipient` from the pool.
+    /// Meant for members whose role or category restrictions keep them from
+    /// spending directly; a head's approval overrides those restrictions.
+    ///
+    /// # Returns
+    /// The request id
+    ///
+    /// # Errors
+    /// * `MemberNotFound` - If member is not in the wallet
+    /// * `MemberFrozen` - If member is frozen
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn request_spend(
+        env: Env,
+        member: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        category: SpendCategory,
+        memo: String,
+    ) -> Result<u64, Error> {
+        member.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+        if Self::is_member_frozen(env.clone(), member.clone()) {
+            return Err(Error::MemberFrozen);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Self::apply_due_graduation(&env, &member);
+
+        Self::extend_instance_ttl(&env);
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_SRQ"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_SRQ"), &(id + 1));
+
+        let mut requests = Self::load_spend_requests(&env);
+        requests.set(
+            id,
+            SpendRequest {
+                id,
+                member: member.clone(),
+                token,
+                recipient,
+                amount,
+                category,
+                memo,
+                status: SpendRequestStatus::Pending,
+                created_at: env.ledger().timestamp(),
+                decided_by: None,
+                decided_at: None,
+            },
+        );
+        Self::save_spend_requests(&env, &requests);
+
+        env.events().publish(
+            (symbol_short!("spendreq"), SpendRequestEvent::Submitted),
+            (id, member, amount, category),
+        );
+        Ok(id)
+    }
+
+    /// Approve a pending spend request and pay it from the pool. The payment
+    /// counts against the requester's spend limits and is recorded in their
+    /// history.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller's role is not `Head`
+    /// * `MemberFrozen` - If caller or the requester is frozen
+    /// * `TransactionNotFound` - If there is no such request
+    /// * `TransactionAlreadyExecuted` - If the request was already decided
+    /// * `MemberNotFound` - If the requester has left the wallet
+    /// * `InsufficientBalance` - If the pool cannot cover the amount
+    ///
+    /// # Panics
+    /// * With `SpendLimitExceeded` if the requester's caps would be exceeded
+    pub fn approve_spend_request(env: Env, caller: Address, id: u64) -> Result<bool, Error> {
+        Self::require_unfrozen_head(&env, &caller)?;
+        let mut requests = Self::load_spend_requests(&env);
+        let mut request = Self::pending_spend_request(&requests, id)?;
+        if !Self::is_family_member(&env, &request.member) {
+            return Err(Error::MemberNotFound);
+        }
+        if Self::is_member_frozen(env.clone(), request.member.clone()) {
+            return Err(Error::MemberFrozen);
+        }
+        if Self::get_pool_balance(env.clone(), request.token.clone()) < request.amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::record_outflow(&env, &request.member, request.amount);
+        Self::adjust_pool(&env, &request.token, -request.amount);
+        Self::record_category_spend(&env, request.category, request.amount);
+        TokenClient::new(&env, &request.token).transfer(
+            &env.current_contract_address(),
+            &request.recipient,
+            &request.amount,
+        );
+        Self::append_member_transaction(
+            &env,
+            &request.member,
+            MemberTxKind::RequestedSpend(request.category),
+            &request.token,
+            &request.recipient,
+            request.amount,
+        );
+
+        request.status = SpendRequestStatus::Approved;
+        request.decided_by = Some(caller.clone());
+        request.decided_at = Some(env.ledger().timestamp());
+        requests.set(id, request.clone());
+        Self::save_spend_requests(&env, &requests);
+
+        env.events().publish(
+            (symbol_short!("spendreq"), SpendRequestEvent::Approved),
+            (id, request.member, caller, request.amount),
+        );
+        Ok(true)
+    }
+
+    /// Reject a pending spend request.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller's role is not `Head`
+    /// * `MemberFrozen` - If caller is frozen
+    /// * `TransactionNotFound` - If there is no such request
+    /// * `TransactionAlreadyExecuted` - If the request was already decided
+    pub fn reject_spend_request(env: Env, caller: Address, id: u64) -> Result<bool, Error> {
+        Self::require_unfrozen_head(&env, &caller)?;
+        let mut requests = Self::load_spend_requests(&env);
+        let mut request = Self::pending_spend_request(&requests, id)?;
+
+        Self::extend_instance_ttl(&env);
+        request.status = SpendRequestStatus::Rejected;
+        request.decided_by = Some(caller.clone());
+        request.decided_at = Some(env.ledger().timestamp());
+        requests.set(id, request.clone());
+        Self::save_spend_requests(&env, &requests);
+
+        env.events().publish(
+            (symbol_short!("spendreq"), SpendRequestEvent::Rejected),
+            (id, request.member, caller),
+        );
+        Ok(true)
+    }
+
+    pub fn get_spend_request(env: Env, id: u64) -> Option<SpendRequest> {
+        Self::load_spend_requests(&env).get(id)
+    }
+
+    /// Requests still awaiting a decision, oldest first.
+    pub fn get_pending_spend_requests(env: Env) -> Vec<SpendRequest> {
+        let mut pending = Vec::new(&env);
+        for (_, request) in Self::load_spend_requests(&env).iter() {
+            if request.status == SpendRequestStatus::Pending {
+                pending.push_back(request);
+            }
+        }
+        pending
+    }
+
+    /// Spend from `delegator`'s sub-balance under their delegation. The
+    /// delegator's category restrictions and spend limits apply, and the
+    /// spend is recorded in both members' histories.
+    ///
+    /// # Errors
+    /// * `DelegationNotFound` - If delegator has not delegated to `delegate`
+    /// * `DelegationExpired` - If the delegation has expired
+    /// * `SpendLimitExceeded` - If amount would exceed the delegation cap
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `CategoryNotAllowed` - If the delegator is restricted to other categories
+    /// * `InsufficientBalance` - If the sub-balance does not cover amount
+    ///
+    /// # Panics
+    /// * If either member's role cannot spend or either is frozen
+    pub fn spend_as_delegate(
+        env: Env,
+        delegate: Address,
+        delegator: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+        category: SpendCategory,
+    ) -> Result<(), Error> {
+        delegate.require_auth();
+        Self::require_not_paused(&env);
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut delegations = Self::load_delegations(&env);
+        let mut delegation = delegations
+            .get(delegator.clone())
+            .filter(|d| d.delegate == delegate)
+            .ok_or(Error::DelegationNotFound)?;
+        if env.ledger().timestamp() > delegation.expires_at {
+            return Err(Error::DelegationExpired);
+        }
+        if delegation.spent + amount > delegation.cap {
+            return Err(Error::SpendLimitExceeded);
+        }
+        Self::require_spend_role(&env, &delegate, false);
+        Self::require_spend_role(&env, &delegator, false);
+        Self::spend_sub_balance(&env, &delegator, &token, &recipient, amount, category)?;
+
+        delegation.spent += amount;
+        delegations.set(delegator.clone(), delegation);
+        Self::save_delegations(&env, &delegations);
+        Self::append_member_transaction(
+            &env,
+            &delegator,
+            MemberTxKind::DelegatedSpend(delegate.clone(), category),
+            &token,
+            &recipient,
+            amount,
+        );
+        Self::append_member_transaction(
+            &env,
+            &delegate,
+            MemberTxKind::SpendForDelegator(delegator.clone(), category),
+            &token,
+            &recipient,
+            amount,
+        );
+        env.events().publish(
+            (symbol_short!("delegate"), DelegationEvent::Spent),
+            (delegator, delegate, recipient, token, amount, category),
+        );
+        Ok(())
+    }
+
+    /// Credit `member` with `amount` of `token` from the pool every
+    /// `interval` seconds, replacing any existing allowance. The first
+    /// credit is due immediately; credits are made by `process_allowances`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidAmount` - If amount or interval is not positive
+    /// * `MemberNotFound` - If member is not in the wallet
+    pub fn set_allowance(
+        env: Env,
+        caller: Address,
+        member: Address,
+        token: Address,
+        amount: i128,
+        interval: u64,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if amount <= 0 || interval == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !Self::is_family_member(&env, &member) {
+            return Err(Error::MemberNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut allowances = Self::load_allowances(&env);
+        allowances.set(
+            member.clone(),
+            Allowance {
+                member: member.clone(),
+                token,
+                amount,
+                interval,
+                next_due: env.ledger().timestamp(),
+                last_credited: None,
+                missed_count: 0,
+            },
+        );
+        Self::save_allowances(&env, &allowances);
+
+        env.events().publish(
+            (symbol_short!("allow"), AllowanceEvent::Set),
+            (member, amount, interval),
+        );
+        Ok(true)
+    }
+
+    /// Stop a member's allowance. Credits already made stay in their
+    /// sub-balance.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `AllowanceNotFound` - If the member has no allowance
+    pub fn remove_allowance(env: Env, caller: Address, member: Address) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        let mut allowances = Self::load_allowances(&env);
+        if allowances.get(member.clone()).is_none() {
+            return Err(Error::AllowanceNotFound);
+        }
+
+        Self::extend_instance_ttl(&env);
+        allowances.remove(member.clone());
+        Self::save_allowances(&env, &allowances);
+
+        env.events()
+            .publish((symbol_short!("allow"), AllowanceEvent::Removed), member);
+        Ok(true)
+    }
+
+    pub fn get_allowance(env: Env, member: Address) -> Option<Allowance> {
+        Self::load_allowances(&env).get(member)
+    }
+
+    /// Credit every allowance that is due from the pool. Anyone may call
+    /// this. An allowance the pool cannot cover stays due and is retried on
+    /// the next call; one whose trigger came whole intervals late is
+    /// credited once and the skipped intervals are reported as missed.
+    ///
+    /// # Returns
+    /// Number of allowances credited
+    pub fn process_allowances(env: Env) -> u32 {
+        Self::require_not_paused(&env);
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let mut allowances = Self::load_allowances(&env);
+        let mut credited = 0u32;
+        let mut changed = false;
+
+        for (member, mut allowance) in allowances.iter() {
+            // Frozen members stay due and are credited once unfrozen
+            if allowance.next_due > now || Self::is_member_frozen(env.clone(), member.clone()) {
+                continue;
+            }
+            if Self::get_pool_balance(env.clone(), allowance.token.clone()) < allowance.amount {
+                env.events().publish(
+                    (symbol_short!("allow"), AllowanceEvent::Unfunded),
+                    (member, allowance.amount, allowance.next_due),
+                );
+                continue;
+            }
+
+            Self::adjust_pool(&env, &allowance.token, -allowance.amount);
+            let balance =
+                Self::get_sub_balance(env.clone(), member.clone(), allowance.token.clone());
+            Self::set_sub_balance(&env, &member, &allowance.token, balance + allowance.amount);
+
+            let late_by = now - allowance.next_due;
+            let missed = (late_by / allowance.interval) as u32;
+            if late_by > ALLOWANCE_LATE_SECS {
+                env.events().publish(
+                    (symbol_short!("allow"), AllowanceEvent::Late),
+                    (member.clone(), allowance.next_due, late_by),
+                );
+            }
+            if missed > 0 {
+                allowance.missed_count += missed;
+                env.events().publish(
+                    (symbol_short!("allow"), AllowanceEvent::Missed),
+                    (member.clone(), missed),
+                );
+            }
+            env.events().publish(
+                (symbol_short!("allow"), AllowanceEvent::Credited),
+                (member.clone(), allowance.amount, allowance.next_due),
+            );
+
+            allowance.last_credited = Some(now);
+            allowance.next_due += (missed as u64 + 1) * allowance.interval;
+            allowances.set(member, allowance);
+            credited += 1;
+            changed = true;
+        }
+
+        if changed {
+            Self::save_allowances(&env, &allowances);
+        }
+        credited
+    }
+
+    /// Move `amount` of `token` from `from`'s sub-balance to `to`'s every
+    /// `interval` seconds, e.g. a weekly transfer to a grandparent. The first
+    /// transfer is due immediately; transfers are made by
+    /// `process_standing_transfers`.
+    ///
+    /// # Errors
+    /// * `MemberNotFound` - If either party is not in the wallet
+    /// * `InvalidSigner` - If from and to are the same member
+    /// * `InvalidAmount` - If amount or interval is not positive
+    pub fn create_standing_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        token: Address,
+        amount: i128,
+        interval: u64,
+    ) -> Result<u64, Error> {
+        from.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_family_member(&env, &from) || !Self::is_family_member(&env, &to) {
+            return Err(Error::MemberNotFound);
+        }
+        if from == to {
+            return Err(Error::InvalidSigner);
+        }
+        if amount <= 0 || interval == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_STD"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_STD"), &(id + 1));
+        let mut transfers = Self::load_standing_transfers(&env);
+        transfers.set(
+            id,
+            StandingTransfer {
+                id,
+                from: from.clone(),
+                to: to.clone(),
+                token,
+                amount,
+                interval,
+                next_due: env.ledger().timestamp(),
+                last_executed: None,
+                paused: false,
+                missed_count: 0,
+            },
+        );
+        Self::save_standing_transfers(&env, &transfers);
+
+        env.events().publish(
+            (symbol_short!("standing"), StandingTransferEvent::Created),
+            (id, from, to, amount, interval),
+        );
+        Ok(id)
+    }
+
+    /// Pause or resume a standing transfer. A resumed transfer is next due
+    /// at its first scheduled time after now; the intervals spent paused are
+    /// not made up.
+    ///
+    /// # Errors
+    /// * `TransactionNotFound` - If there is no such standing transfer
+    /// * `Unauthorized` - If caller is neither the sender nor Owner or Admin
+    pub fn set_standing_transfer_paused(
+        env: Env,
+        caller: Address,
+        id: u64,
+        paused: bool,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        let mut transfers = Self::load_standing_transfers(&env);
+        let mut transfer = Self::managed_standing_transfer(&env, &transfers, &caller, id)?;
+        if transfer.paused == paused {
+            return Ok(true);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+        if !paused && transfer.next_due <= now {
+            transfer.next_due +=
+                ((now - transfer.next_due) / transfer.interval + 1) * transfer.interval;
+        }
+        transfer.paused = paused;
+        transfers.set(id, transfer);
+        Self::save_standing_transfers(&env, &transfers);
+
+        let event = if paused {
+            StandingTransferEvent::Paused
+        } else {
+            StandingTransferEvent::Resumed
+        };
+        env.events()
+            .publish((symbol_short!("standing"), event), (id, caller));
+        Ok(true)
+    }
+
+    /// Skip the next occurrence of a standing transfer, returning when it
+    /// is next due.
+    ///
+    /// # Errors
+    /// * `TransactionNotFound` - If there is no such standing transfer
+    /// * `Unauthorized` - If caller is neither the sender nor Owner or Admin
+    pub fn skip_standing_transfer(env: Env, caller: Address, id: u64) -> Result<u64, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        let mut transfers = Self::load_standing_transfers(&env);
+        let mut transfer = Self::managed_standing_transfer(&env, &transfers, &caller, id)?;
+
+        Self::extend_instance_ttl(&env);
+        let skipped = transfer.next_due;
+        transfer.next_due += transfer.interval;
+        let next_due = transfer.next_due;
+        transfers.set(id, transfer);
+        Self::save_standing_transfers(&env, &transfers);
+
+        env.events().publish(
+            (symbol_short!("standing"), StandingTransferEvent::Skipped),
+            (id, caller, skipped),
+        );
+        Ok(next_due)
+    }
+
+    /// Stop a standing transfer. Transfers already made are kept.
+    ///
+    /// # Errors
+    /// * `TransactionNotFound` - If there is no such standing transfer
+    /// * `Unauthorized` - If caller is neither the sender nor Owner or Admin
+    pub fn cancel_standing_transfer(env: Env, caller: Address, id: u64) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        let mut transfers = Self::load_standing_transfers(&env);
+        Self::managed_standing_transfer(&env, &transfers, &caller, id)?;
+
+        Self::extend_instance_ttl(&env);
+        transfers.remove(id);
+        Self::save_standing_transfers(&env, &transfers);
+
+        env.events().publish(
+            (symbol_short!("standing"), StandingTransferEvent::Cancelled),
+            (id, caller),
+        );
+        Ok(true)
+    }
+
+    pub fn get_standing_transfer(env: Env, id: u64) -> Option<StandingTransfer> {
+        Self::load_standing_transfers(&env).get(id)
+    }
+
+    /// Standing transfers that `member` sends or receives, by id.
+    pub fn get_standing_transfers(env: Env, member: Address) -> Vec<StandingTransfer> {
+        let mut result = Vec::new(&env);
+        for (_, transfer) in Self::load_standing_transfers(&env).iter() {
+            if transfer.from == member || transfer.to == member {
+                result.push_back(transfer);
+            }
+        }
+        result
+    }
+
+    /// Make every standing transfer that is due. Anyone may call this.
+    /// Paused transfers and those from frozen members are left alone; one
+    /// the sender's sub-balance cannot cover stays due and is retried on
+    /// the next call. A transfer whose trigger came whole intervals late is
+    /// made once and the skipped intervals are reported as missed.
+    ///
+    /// # Returns
+    /// Number of transfers made
+    pub fn process_standing_transfers(env: Env) -> u32 {
+        Self::require_not_paused(&env);
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let mut transfers = Self::load_standing_transfers(&env);
+        let mut executed = 0u32;
+        let mut changed = false;
+
+        for (id, mut transfer) in transfers.iter() {
+            if transfer.paused
+                || transfer.next_due > now
+                || Self::is_member_frozen(env.clone(), transfer.from.clone())
+            {
+                continue;
+            }
+            let from_balance =
+                Self::get_sub_balance(env.clone(), transfer.from.clone(), transfer.token.clone());
+            if from_balance < transfer.amount {
+                env.events().publish(
+                    (symbol_short!("standing"), StandingTransferEvent::Unfunded),
+                    (id, transfer.amount, transfer.next_due),
+                );
+                continue;
+            }
+
+            let to_balance =
+                Self::get_sub_balance(env.clone(), transfer.to.clone(), transfer.token.clone());
+            Self::set_sub_balance(
+                &env,
+                &transfer.from,
+                &transfer.token,
+                from_balance - transfer.amount,
+            );
+            Self::set_sub_balance(
+                &env,
+                &transfer.to,
+                &transfer.token,
+                to_balance + transfer.amount,
+            );
+
+            let missed = ((now - transfer.next_due) / transfer.interval) as u32;
+            if missed > 0 {
+                transfer.missed_count += missed;
+                env.events().publish(
+                    (symbol_short!("standing"), StandingTransferEvent::Missed),
+                    (id, missed),
+                );
+            }
+            env.events().publish(
+                (symbol_short!("standing"), StandingTransferEvent::Executed),
+                (
+                    id,
+                    transfer.from.clone(),
+                    transfer.to.clone(),
+                    transfer.amount,
+                    transfer.next_due,
+                ),
+            );
+
+            transfer.last_executed = Some(now);
+            transfer.next_due += (missed as u64 + 1) * transfer.interval;
+            transfers.set(id, transfer);
+            executed += 1;
+            changed = true;
+        }
+
+        if changed {
+            Self::save_standing_transfers(&env, &transfers);
+        }
+        executed
+    }
+
+    /// Set who approves large pool withdrawals and how many approvals they
+    /// need.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not Owner or Admin
+    /// * `InvalidThreshold` - If `required` is 0 or exceeds the approvers
+    /// * `DuplicateSignature` - If an approver is listed twice
+    /// * `InvalidAmount` - If threshold is negative or ttl is 0
+    pub fn configure_withdrawal_approvals(
+        env: Env,
+        caller: Address,
+        approvers: Vec<Address>,
+        required: u32,
+        threshold: i128,
+        ttl: u64,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        if !Self::is_owner_or_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if required == 0 || required > approvers.len() {
+            return Err(Error::InvalidThreshold);
+        }
+        for (i, approver) in approvers.iter().enumerate() {
+            if approvers.first_index_of(approver) != Some(i as u32) {
+                return Err(Error::DuplicateSignature);
+            }
+        }
+        if threshold < 0 || ttl == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(
+            &symbol_short!("WD_APPR"),
+            &WithdrawalApprovalConfig {
+                approvers,
+                required,
+                threshold,
+                ttl,
+            },
+        );
+        Ok(true)
+    }
+
+    pub fn get_withdrawal_approvals(env: Env) -> Option<WithdrawalApprovalConfig> {
+        env.storage().instance().get(&symbol_short!("WD_APPR"))
+    }
+
+    /// Withdraw `amount` of `token` from the family pool to `recipient`.
+    /// Amounts up to the approval threshold are paid at once; larger ones
+    /// open a proposal that expires after the configured ttl. A proposer
+    /// who is also an approver approves their own proposal.
+    ///
+    /// # Returns
+    /// 0 if paid at once, otherwise the proposal id
+    ///
+    /// # Errors
+    /// * `ApprovalsNotConfigured` - If `configure_withdrawal_approvals` was never called
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `CategoryNotAllowed` - If the proposer has category restrictions
+    /// * `InsufficientBalance` - If the pool cannot cover an immediate payment
+    ///
+    /// # Panics
+    /// * If the proposer's role cannot spend, or is `Teen` and approval is needed
+    pub fn propose_pool_withdrawal(
+        env: Env,
+        proposer: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<u64, Error> {
+        proposer.require_auth();
+        Self::require_not_paused(&env);
+        let config =
+            Self::get_withdrawal_approvals(env.clone()).ok_or(Error::ApprovalsNotConfigured)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !Self::get_category_restrictions(env.clone(), proposer.clone()).is_empty() {
+            return Err(Error::CategoryNotAllowed);
+        }
+        let needs_approval = amount > config.threshold;
+        Self::require_spend_role(&env, &proposer, needs_approval);
+        Self::extend_instance_ttl(&env);
+
+        if !needs_approval {
+            Self::pay_from_pool(&env, &proposer, &token, &recipient, amount)?;
+            return Ok(0);
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_PWD"))
+            .unwrap_or(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_PWD"), &(id + 1));
+
+        let mut approvals = Vec::new(&env);
+        if config.approvers.contains(&proposer) {
+            approvals.push_back(proposer.clone());
+        }
+        let now = env.ledger().timestamp();
+        let mut proposals = Self::load_pool_withdrawals(&env);
+        proposals.set(
+            id,
+            PoolWithdrawal {
+                id,
+                proposer: proposer.clone(),
+                token,
+                recipient,
+                amount,
+                approvals,
+                created_at: now,
+                expires_at: now + config.ttl,
+            },
+        );
+        Self::save_pool_withdrawals(&env, &proposals);
+
+        env.events().publish(
+            (symbol_short!("pool"), PoolEvent::WithdrawProposed),
+            (id, proposer, amount),
+        );
+        Ok(id)
+    }
+
+    /// Approve a pending pool withdrawal.
+    ///
+    /// # Returns
+    /// Number of approvals the proposal now has
+    ///
+    /// # Errors
+    /// * `InvalidSigner` - If approver is not a configured approver
+    /// * `TransactionNotFound` - If no such proposal is pending
+    /// * `TransactionExpired` - If the proposal has expired
+    /// * `DuplicateSignature` - If approver already approved
+    pub fn approve_pool_withdrawal(env: Env, approver: Address, id: u64) -> Result<u32, Error> {
+        approver.require_auth();
+        Self::require_not_paused(&env);
+        let config =
+            Self::get_withdrawal_approvals(env.clone()).ok_or(Error::ApprovalsNotConfigured)?;
+        if !config.approvers.contains(&approver) {
+            return Err(Error::InvalidSigner);
+        }
+        if Self::is_member_frozen(env.clone(), approver.clone()) {
+            return Err(Error::MemberFrozen);
+        }
+        let mut proposals = Self::load_pool_withdrawals(&env);
+        let mut proposal = proposals.get(id).ok_or(Error::TransactionNotFound)?;
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(Error::TransactionExpired);
+        }
+        if proposal.approvals.contains(&approver) {
+            return Err(Error::DuplicateSignature);
+        }
+
+        Self::extend_instance_ttl(&env);
+        proposal.approvals.push_back(approver.clone());
+        let count = proposal.approvals.len();
+        proposals.set(id, proposal);
+        Self::save_pool_withdrawals(&env, &proposals);
+
+        env.events().publish(
+            (symbol_short!("pool"), PoolEvent::WithdrawApproved),
+            (id, approver, count),
+        );
+        Ok(count)
+    }
+
+    /// Pay out a pool withdrawal once it has the required approvals. The
+    /// proposer or any approver may execute it. Approvals from addresses
+    /// since dropped from the approver list do not count.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is neither the proposer nor an approver
+    /// * `TransactionNotFound` - If no such proposal is pending
+    /// * `TransactionExpired` - If the proposal has expired
+    /// * `InsufficientSignatures` - If approvals are still short
+    /// * `InsufficientBalance` - If the pool cannot cover the amount
+    pub fn execute_pool_withdrawal(env: Env, caller: Address, id: u64) -> Result<bool, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env);
+        let config =
+            Self::get_withdrawal_approvals(env.clone()).ok_or(Error::ApprovalsNotConfigured)?;
+        let mut proposals = Self::load_pool_withdrawals(&env);
+        let proposal = proposals.get(id).ok_or(Error::TransactionNotFound)?;
+        if caller != proposal.proposer && !config.approvers.contains(&caller) {
+            return Err(Error::Unauthorized);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(Error::TransactionExpired);
+        }
+        let valid = proposal
+            .approvals
+            .iter()
+            .filter(|a| config.approvers.contains(a))
+            .count() as u32;
+        if valid < config.required {
+            return Err(Error::InsufficientSignatures);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::pay_from_pool(
+            &env,
+            &proposal.proposer,
+            &proposal.token,
+            &proposal.recipient,
+            proposal.amount,
+        )?;
+        proposals.remove(id);
+        Self::save_pool_withdrawals(&env, &proposals);
+        Ok(true)
+    }
+
+    pub fn get_pool_withdrawal(env: Env, id: u64) -> Option<PoolWithdrawal> {
+        Self::load_pool_withdrawals(&env).get(id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn load_member_transactions(env: &Env, member: &Address) -> Vec<MemberTransaction> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("MEM_TXS"), member.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn append_member_transaction(
+        env: &Env,
+        member: &Address,
+        kind: MemberTxKind,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        let key = (symbol_short!("MEM_TXS"), member.clone());
+        let mut history = Self::load_member_transactions(env, member);
+        let timestamp = env.ledger().timestamp();
+        history.push_back(MemberTransaction {
+            kind: kind.clone(),
+            token: token.clone(),
+            recipient: recipient.clone(),
+            amount,
+            timestamp,
+        });
+        if history.len() > MAX_MEMBER_TRANSACTIONS {
+            history.pop_front();
+        }
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(
+            &key,
+            HISTORY_LIFETIME_THRESHOLD,
+            HISTORY_BUMP_AMOUNT,
+        );
+
+        // The delegator's entry already notifies for a delegated spend
+        if matches!(kind, MemberTxKind::SpendForDelegator(..)) {
+            return;
+        }
+        let envelope_balance = match kind {
+            MemberTxKind::EnvelopeSpend(category) => Self::load_envelopes(env)
+                .get(category)
+                .map(|envelope| envelope.balance),
+            _ => None,
+        };
+        env.events().publish(
+            (symbol_short!("spent"), member.clone()),
+            SpendNotification {
+                member: member.clone(),
+                kind,
+                token: token.clone(),
+                recipient: recipient.clone(),
+                amount,
+                remaining: Self::get_remaining_limit(env.clone(), member.clone()),
+                envelope_balance,
+                timestamp,
+            },
+        );
+    }
+
+    /// Add a spend to its category's total for the current day.
+    fn record_category_spend(env: &Env, category: SpendCategory, amount: i128) {
+        let now = env.ledger().timestamp();
+        let key = (now - now % DAY_SECS, category);
+        let mut spending: Map<(u64, SpendCategory), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_SPND"))
+            .unwrap_or_else(|| Map::new(env));
+        spending.set(key, spending.get(key).unwrap_or(0) + amount);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_SPND"), &spending);
+    }
+
+    /// Add an envelope spend to its category's total for the current day.
+    fn record_envelope_spend(env: &Env, category: SpendCategory, amount: i128) {
+        let now = env.ledger().timestamp();
+        let key = (now - now % DAY_SECS, category);
+        let mut spending: Map<(u64, SpendCategory), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ENV_SPND"))
+            .unwrap_or_else(|| Map::new(env));
+        spending.set(key, spending.get(key).unwrap_or(0) + amount);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ENV_SPND"), &spending);
+    }
+
+    fn load_envelopes(env: &Env) -> Map<SpendCategory, Envelope> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ENVELOPE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_envelopes(env: &Env, envelopes: &Map<SpendCategory, Envelope>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ENVELOPE"), envelopes);
+    }
+
+    /// Pay from `owner`'s sub-balance: checks their category restrictions
+    /// and balance, counts the spend against their limits and category
+    /// totals, and transfers. History and events are left to the caller.
+    fn spend_sub_balance(
+        env: &Env,
+        owner: &Address,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+        category: SpendCategory,
+    ) -> Result<(), Error> {
+        let allowed = Self::get_category_restrictions(env.clone(), owner.clone());
+        if !allowed.is_empty() && !allowed.contains(category) {
+            return Err(Error::CategoryNotAllowed);
+        }
+        let balance = Self::get_sub_balance(env.clone(), owner.clone(), token.clone());
+        if balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        Self::extend_instance_ttl(env);
+        Self::record_outflow(env, owner, amount);
+        Self::set_sub_balance(env, owner, token, balance - amount);
+        Self::record_category_spend(env, category, amount);
+        TokenClient::new(env, token).transfer(&env.current_contract_address(), recipient, &amount);
+        Ok(())
+    }
+
+    fn load_spend_requests(env: &Env) -> Map<u64, SpendRequest> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SPND_REQ"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_spend_requests(env: &Env, requests: &Map<u64, SpendRequest>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPND_REQ"), requests);
+    }
+
+    fn pending_spend_request(
+        requests: &Map<u64, SpendRequest>,
+        id: u64,
+    ) -> Result<SpendRequest, Error> {
+        let request = requests.get(id).ok_or(Error::TransactionNotFound)?;
+        if request.status != SpendRequestStatus::Pending {
+            return Err(Error::TransactionAlreadyExecuted);
+        }
+        Ok(request)
+    }
+
+    fn load_standing_transfers(env: &Env) -> Map<u64, StandingTransfer> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STANDING"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_standing_transfers(env: &Env, transfers: &Map<u64, StandingTransfer>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STANDING"), transfers);
+    }
+
+    /// The standing transfer `id`, if `caller` is its sender or an Owner or
+    /// Admin.
+    fn managed_standing_transfer(
+        env: &Env,
+        transfers: &Map<u64, StandingTransfer>,
+        caller: &Address,
+        id: u64,
+    ) -> Result<StandingTransfer, Error> {
+        let transfer = transfers.get(id).ok_or(Error::TransactionNotFound)?;
+        if transfer.from != *caller && !Self::is_owner_or_admin(env, caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(transfer)
+    }
+
+    fn load_delegations(env: &Env) -> Map<Address, SpendingDelegation> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("DELEGATE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_delegations(env: &Env, delegations: &Map<Address, SpendingDelegation>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DELEGATE"), delegations);
+    }
+
+    fn load_pool_withdrawals(env: &Env) -> Map<u64, PoolWithdrawal> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("POOL_WD"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_pool_withdrawals(env: &Env, proposals: &Map<u64, PoolWithdrawal>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POOL_WD"), proposals);
+    }
+
+    /// Transfer from the pool on behalf of `member`, counting against their
+    /// spend limits.
+    fn pay_from_pool(
+        env: &Env,
+        member: &Address,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if Self::get_pool_balance(env.clone(), token.clone()) < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        Self::record_outflow(env, member, amount);
+        Self::append_member_transaction(
+            env,
+            member,
+            MemberTxKind::PoolWithdrawal,
+            token,
+            recipient,
+            amount,
+        );
+        Self::adjust_pool(env, token, -amount);
+        TokenClient::new(env, token).transfer(&env.current_contract_address(), recipient, &amount);
+        env.events().publish(
+            (symbol_short!("pool"), PoolEvent::Withdrawn),
+            (member.clone(), recipient.clone(), token.clone(), amount),
+        );
+        Ok(())
+    }
+
+    fn load_invites(env: &Env) -> Map<Address, MemberInvite> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("INVITES"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_invites(env: &Env, invites: &Map<Address, MemberInvite>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("INVITES"), invites);
+    }
+
+    fn publish_member_removed(
+        env: &Env,
+        member: &Address,
+        removed_by: &Address,
+        handover: Handover,
+    ) {
+        env.events().publish(
+            (symbol_short!("member"), MembershipEvent::Removed),
+            MemberRemovedEvent {
+                member: member.clone(),
+                removed_by: removed_by.clone(),
+                handover,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn load_allowances(env: &Env) -> Map<Address, Allowance> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ALLOWS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_allowances(env: &Env, allowances: &Map<Address, Allowance>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ALLOWS"), allowances);
     }
 
-    pub fn get_access_audit(env: Env, limit: u32) -> Vec<AccessAuditEntry> {
-        let entries: Vec<AccessAuditEntry> = env
+    /// Add `delta` to the pool's balance of `token`, returning the result.
+    fn adjust_pool(env: &Env, token: &Address, delta: i128) -> i128 {
+        let mut pool: Map<Address, i128> = env
             .storage()
             .instance()
-            .get(&symbol_short!("ACC_AUDIT"))
-            .unwrap_or_else(|| Vec::new(&env));
-        let n = entries.len().min(limit);
-        let mut out = Vec::new(&env);
-        for i in (entries.len().saturating_sub(n))..entries.len() {
-            if let Some(e) = entries.get(i) {
-                out.push_back(e);
-            }
-        }
-        out
+            .get(&symbol_short!("POOL"))
+            .unwrap_or_else(|| Map::new(env));
+        let balance = pool.get(token.clone()).unwrap_or(0) + delta;
+        pool.set(token.clone(), balance);
+        env.storage().instance().set(&symbol_short!("POOL"), &pool);
+        balance
     }
 
-    // -----------------------------------------------------------------------
-    // Internal helpers
-    // -----------------------------------------------------------------------
+    fn set_sub_balance(env: &Env, member: &Address, token: &Address, balance: i128) {
+        let mut balances: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SUB_BAL"))
+            .unwrap_or_else(|| Map::new(env));
+        if balance == 0 {
+            balances.remove((member.clone(), token.clone()));
+        } else {
+            balances.set((member.clone(), token.clone()), balance);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SUB_BAL"), &balances);
+    }
 
     fn execute_emergency_transfer_now(
         env: Env,
@@ -1324,6 +4577,8 @@ impl FamilyWallet {
         recipient: Address,
         amount: i128,
     ) -> u64 {
+        Self::require_spend_role(&env, &proposer, true);
+
         let config: EmergencyConfig = env
             .storage()
             .instance()
@@ -1396,6 +4651,15 @@ impl FamilyWallet {
                 if require_auth {
                     proposer.require_auth();
                 }
+                Self::record_outflow(env, proposer, *amount);
+                Self::append_member_transaction(
+                    env,
+                    proposer,
+                    MemberTxKind::Withdrawal,
+                    token,
+                    recipient,
+                    *amount,
+                );
                 let token_client = TokenClient::new(env, token);
                 token_client.transfer(proposer, recipient, amount);
                 0
@@ -1431,6 +4695,15 @@ impl FamilyWallet {
                 if require_auth {
                     proposer.require_auth();
                 }
+                Self::record_outflow(env, proposer, *amount);
+                Self::append_member_transaction(
+                    env,
+                    proposer,
+                    MemberTxKind::EmergencyTransfer,
+                    token,
+                    recipient,
+                    *amount,
+                );
                 let token_client = TokenClient::new(env, token);
                 token_client.transfer(proposer, recipient, amount);
                 0
@@ -1486,6 +4759,587 @@ impl FamilyWallet {
         role as u32
     }
 
+    fn member_role(env: &Env, address: &Address) -> Option<MemberRole> {
+        let members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .unwrap_or_else(|| Map::new(env));
+        let member = members.get(address.clone())?;
+        let roles: Map<Address, MemberRole> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEM_ROLE"))
+            .unwrap_or_else(|| Map::new(env));
+        Some(roles.get(address.clone()).unwrap_or(match member.role {
+            FamilyRole::Owner => MemberRole::Head,
+            FamilyRole::Admin | FamilyRole::Member => MemberRole::Adult,
+            FamilyRole::Viewer => MemberRole::ViewOnly,
+        }))
+    }
+
+    /// Drop the role, spend limits and usage kept for a removed member.
+    fn clear_member_records(env: &Env, address: &Address) {
+        let mut roles: Map<Address, MemberRole> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEM_ROLE"))
+            .unwrap_or_else(|| Map::new(env));
+        if roles.contains_key(address.clone()) {
+            roles.remove(address.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("MEM_ROLE"), &roles);
+        }
+        let mut limits: Map<Address, SpendLimits> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPND_LIM"))
+            .unwrap_or_else(|| Map::new(env));
+        if limits.contains_key(address.clone()) {
+            limits.remove(address.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SPND_LIM"), &limits);
+        }
+        let mut usage: Map<Address, SpendUsage> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPND_USE"))
+            .unwrap_or_else(|| Map::new(env));
+        if usage.contains_key(address.clone()) {
+            usage.remove(address.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SPND_USE"), &usage);
+        }
+        let mut restrictions: Map<Address, Vec<SpendCategory>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_RSTR"))
+            .unwrap_or_else(|| Map::new(env));
+        if restrictions.contains_key(address.clone()) {
+            restrictions.remove(address.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CAT_RSTR"), &restrictions);
+        }
+        let mut frozen: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FROZEN"))
+            .unwrap_or_else(|| Map::new(env));
+        if frozen.contains_key(address.clone()) {
+            frozen.remove(address.clone());
+            env.storage()
+                .instance()
+                .set(&symbol_short!("FROZEN"), &frozen);
+        }
+        if let Some(mut votes) = env
+            .storage()
+            .instance()
+            .get::<_, Map<(Address, bool), Vec<Address>>>(&symbol_short!("FRZ_VOTE"))
+        {
+            votes.remove((address.clone(), true));
+            votes.remove((address.clone(), false));
+            env.storage()
+                .instance()
+                .set(&symbol_short!("FRZ_VOTE"), &votes);
+        }
+        let mut delegations = Self::load_delegations(env);
+        let before = delegations.len();
+        for (delegator, delegation) in delegations.clone().iter() {
+            if delegator == *address || delegation.delegate == *address {
+                delegations.remove(delegator);
+            }
+        }
+        if delegations.len() != before {
+            Self::save_delegations(env, &delegations);
+        }
+        let mut graduations = Self::load_graduations(env);
+        if graduations.contains_key(address.clone()) {
+            graduations.remove(address.clone());
+            Self::save_graduations(env, &graduations);
+        }
+        let mut guardians = Self::load_recovery_guardians(env);
+        if guardians.contains_key(address.clone()) {
+            guardians.remove(address.clone());
+            Self::save_recovery_guardians(env, &guardians);
+        }
+        let mut recoveries = Self::load_recovery_requests(env);
+        if recoveries.contains_key(address.clone()) {
+            recoveries.remove(address.clone());
+            Self::save_recovery_requests(env, &recoveries);
+        }
+        let mut transfers = Self::load_standing_transfers(env);
+        let before = transfers.len();
+        for (id, transfer) in transfers.clone().iter() {
+            if transfer.from == *address || transfer.to == *address {
+                transfers.remove(id);
+            }
+        }
+        if transfers.len() != before {
+            Self::save_standing_transfers(env, &transfers);
+        }
+        let shares = Self::get_inflow_shares(env.clone());
+        if let Some(index) = shares.iter().position(|share| share.member == *address) {
+            let mut shares = shares;
+            shares.remove(index as u32);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("IN_SHARE"), &shares);
+        }
+        let mut allowances = Self::load_allowances(env);
+        if allowances.contains_key(address.clone()) {
+            allowances.remove(address.clone());
+            Self::save_allowances(env, &allowances);
+        }
+        // Unspent allowance credits go back to the pool
+        let balances: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SUB_BAL"))
+            .unwrap_or_else(|| Map::new(env));
+        for ((member, token), balance) in balances.iter() {
+            if member == *address {
+                Self::set_sub_balance(env, &member, &token, 0);
+                Self::adjust_pool(env, &token, balance);
+            }
+        }
+    }
+
+    fn load_graduations(env: &Env) -> Map<Address, Graduation> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("GRADUATE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_graduations(env: &Env, graduations: &Map<Address, Graduation>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GRADUATE"), graduations);
+    }
+
+    /// Apply the member's scheduled graduation if its date has passed.
+    fn apply_due_graduation(env: &Env, member: &Address) {
+        let mut graduations = Self::load_graduations(env);
+        let graduation = match graduations.get(member.clone()) {
+            Some(graduation) if env.ledger().timestamp() >= graduation.at => graduation,
+            _ => return,
+        };
+        let old_role = match Self::member_role(env, member) {
+            Some(role) => role,
+            None => return,
+        };
+
+        Self::extend_instance_ttl(env);
+        graduations.remove(member.clone());
+        Self::save_graduations(env, &graduations);
+        let mut roles: Map<Address, MemberRole> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEM_ROLE"))
+            .unwrap_or_else(|| Map::new(env));
+        roles.set(member.clone(), graduation.role);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MEM_ROLE"), &roles);
+        if let GraduationLimits::Set(limits) = &graduation.limits {
+            let mut all_limits: Map<Address, SpendLimits> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("SPND_LIM"))
+                .unwrap_or_else(|| Map::new(env));
+            if limits.daily == 0 && limits.monthly == 0 {
+                all_limits.remove(member.clone());
+            } else {
+                all_limits.set(member.clone(), limits.clone());
+            }
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SPND_LIM"), &all_limits);
+        }
+
+        env.events().publish(
+            (symbol_short!("member"), MembershipEvent::Graduated),
+            MemberGraduatedEvent {
+                member: member.clone(),
+                old_role,
+                new_role: graduation.role,
+                limits: graduation.limits,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn load_recovery_guardians(env: &Env) -> Map<Address, RecoveryGuardians> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("GUARDIAN"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_recovery_guardians(env: &Env, configs: &Map<Address, RecoveryGuardians>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GUARDIAN"), configs);
+    }
+
+    fn load_recovery_requests(env: &Env) -> Map<Address, RecoveryRequest> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("RECOVERY"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_recovery_requests(env: &Env, requests: &Map<Address, RecoveryRequest>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("RECOVERY"), requests);
+    }
+
+    /// The member's guardian set, if `guardian` belongs to it.
+    fn guardian_config(
+        env: &Env,
+        guardian: &Address,
+        member: &Address,
+    ) -> Result<RecoveryGuardians, Error> {
+        Self::load_recovery_guardians(env)
+            .get(member.clone())
+            .filter(|config| config.guardians.contains(guardian))
+            .ok_or(Error::InvalidSigner)
+    }
+
+    /// Record `guardian`'s approval, start the challenge window on quorum,
+    /// and save the request. Returns the approvals so far.
+    fn add_recovery_approval(
+        env: &Env,
+        config: &RecoveryGuardians,
+        request: &mut RecoveryRequest,
+        guardian: &Address,
+    ) -> u32 {
+        request.approvals.push_back(guardian.clone());
+        let approvals = request.approvals.len();
+        env.events().publish(
+            (symbol_short!("recovery"), RecoveryEvent::Approved),
+            (request.member.clone(), guardian.clone(), approvals),
+        );
+        if approvals >= config.threshold && request.executable_at.is_none() {
+            let executable_at = env.ledger().timestamp() + RECOVERY_CHALLENGE_SECS;
+            request.executable_at = Some(executable_at);
+            env.events().publish(
+                (symbol_short!("recovery"), RecoveryEvent::Challenge),
+                (request.member.clone(), executable_at),
+            );
+        }
+        let mut requests = Self::load_recovery_requests(env);
+        requests.set(request.member.clone(), request.clone());
+        Self::save_recovery_requests(env, &requests);
+        approvals
+    }
+
+    fn is_owner(env: &Env, address: &Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&symbol_short!("OWNER"))
+            .map(|owner| owner == *address)
+            .unwrap_or(false)
+    }
+
+    /// Move everything recorded under `old` to `new`.
+    fn rebind_member_records(env: &Env, old: &Address, new: &Address) {
+        let mut members: Map<Address, FamilyMember> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MEMBERS"))
+            .unwrap_or_else(|| Map::new(env));
+        if let Some(mut record) = members.get(old.clone()) {
+            members.remove(old.clone());
+            record.address = new.clone();
+            members.set(new.clone(), record);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("MEMBERS"), &members);
+        }
+        Self::rekey_member_map::<MemberRole>(env, symbol_short!("MEM_ROLE"), old, new);
+        Self::rekey_member_map::<SpendLimits>(env, symbol_short!("SPND_LIM"), old, new);
+        Self::rekey_member_map::<SpendUsage>(env, symbol_short!("SPND_USE"), old, new);
+        Self::rekey_member_map::<Vec<SpendCategory>>(env, symbol_short!("CAT_RSTR"), old, new);
+        Self::rekey_member_map::<u64>(env, symbol_short!("FROZEN"), old, new);
+        Self::rekey_member_map::<u64>(env, symbol_short!("ROLE_EXP"), old, new);
+
+        let mut allowances = Self::load_allowances(env);
+        if let Some(mut allowance) = allowances.get(old.clone()) {
+            allowances.remove(old.clone());
+            allowance.member = new.clone();
+            allowances.set(new.clone(), allowance);
+            Self::save_allowances(env, &allowances);
+        }
+        let mut graduations = Self::load_graduations(env);
+        if let Some(mut graduation) = graduations.get(old.clone()) {
+            graduations.remove(old.clone());
+            graduation.member = new.clone();
+            graduations.set(new.clone(), graduation);
+            Self::save_graduations(env, &graduations);
+        }
+        let balances: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SUB_BAL"))
+            .unwrap_or_else(|| Map::new(env));
+        for ((member, token), balance) in balances.iter() {
+            if member == *old {
+                Self::set_sub_balance(env, old, &token, 0);
+                Self::set_sub_balance(env, new, &token, balance);
+            }
+        }
+        let mut shares = Self::get_inflow_shares(env.clone());
+        if let Some(index) = shares.iter().position(|share| share.member == *old) {
+            let mut share = shares.get(index as u32).unwrap();
+            share.member = new.clone();
+            shares.set(index as u32, share);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("IN_SHARE"), &shares);
+        }
+        let mut delegations = Map::new(env);
+        for (delegator, mut delegation) in Self::load_delegations(env).iter() {
+            if delegation.delegator == *old {
+                delegation.delegator = new.clone();
+            }
+            if delegation.delegate == *old {
+                delegation.delegate = new.clone();
+            }
+            let key = if delegator == *old {
+                new.clone()
+            } else {
+                delegator
+            };
+            delegations.set(key, delegation);
+        }
+        Self::save_delegations(env, &delegations);
+        let mut transfers = Self::load_standing_transfers(env);
+        for (id, mut transfer) in transfers.clone().iter() {
+            if transfer.from == *old {
+                transfer.from = new.clone();
+                transfers.set(id, transfer);
+            } else if transfer.to == *old {
+                transfer.to = new.clone();
+                transfers.set(id, transfer);
+            }
+        }
+        Self::save_standing_transfers(env, &transfers);
+        let mut requests = Self::load_spend_requests(env);
+        for (id, mut request) in requests.clone().iter() {
+            if request.member == *old {
+                request.member = new.clone();
+                requests.set(id, request);
+            }
+        }
+        Self::save_spend_requests(env, &requests);
+
+        // Signer, approver, voter and guardian lists
+        for key in [
+            symbol_short!("MS_WDRAW"),
+            symbol_short!("MS_SPLIT"),
+            symbol_short!("MS_ROLE"),
+            symbol_short!("MS_EMERG"),
+            symbol_short!("MS_POL"),
+            symbol_short!("MS_REG"),
+        ] {
+            if let Some(mut config) = env.storage().instance().get::<_, MultiSigConfig>(&key) {
+                if Self::replace_address(&mut config.signers, old, new) {
+                    env.storage().instance().set(&key, &config);
+                }
+            }
+        }
+        if let Some(mut config) = Self::get_withdrawal_approvals(env.clone()) {
+            if Self::replace_address(&mut config.approvers, old, new) {
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("WD_APPR"), &config);
+            }
+        }
+        let votes: Map<(Address, bool), Vec<Address>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FRZ_VOTE"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut rebound_votes = Map::new(env);
+        for ((member, frozen), mut voters) in votes.iter() {
+            Self::replace_address(&mut voters, old, new);
+            let member = if member == *old { new.clone() } else { member };
+            rebound_votes.set((member, frozen), voters);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FRZ_VOTE"), &rebound_votes);
+        let mut guardians = Map::new(env);
+        for (member, mut config) in Self::load_recovery_guardians(env).iter() {
+            Self::replace_address(&mut config.guardians, old, new);
+            let member = if member == *old { new.clone() } else { member };
+            guardians.set(member, config);
+        }
+        Self::save_recovery_guardians(env, &guardians);
+
+        let old_key = (symbol_short!("MEM_TXS"), old.clone());
+        if let Some(history) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<MemberTransaction>>(&old_key)
+        {
+            let new_key = (symbol_short!("MEM_TXS"), new.clone());
+            env.storage().persistent().set(&new_key, &history);
+            env.storage().persistent().extend_ttl(
+                &new_key,
+                HISTORY_LIFETIME_THRESHOLD,
+                HISTORY_BUMP_AMOUNT,
+            );
+            env.storage().persistent().remove(&old_key);
+        }
+    }
+
+    /// Move `old`'s entry in the instance map under `key` to `new`.
+    fn rekey_member_map<V>(env: &Env, key: Symbol, old: &Address, new: &Address)
+    where
+        V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let Some(mut map) = env.storage().instance().get::<_, Map<Address, V>>(&key) else {
+            return;
+        };
+        if let Some(value) = map.get(old.clone()) {
+            map.remove(old.clone());
+            map.set(new.clone(), value);
+            env.storage().instance().set(&key, &map);
+        }
+    }
+
+    /// Replace `old` with `new` in `list`, returning whether it was there.
+    fn replace_address(list: &mut Vec<Address>, old: &Address, new: &Address) -> bool {
+        match list.first_index_of(old) {
+            Some(index) => {
+                list.set(index, new.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The member's usage with windows that have ended reset to zero.
+    fn current_usage(env: &Env, member: &Address) -> SpendUsage {
+        let now = env.ledger().timestamp();
+        let day_start = now - now % DAY_SECS;
+        let month_start = now - now % MONTH_SECS;
+        let mut usage = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, SpendUsage>>(&symbol_short!("SPND_USE"))
+            .unwrap_or_else(|| Map::new(env))
+            .get(member.clone())
+            .unwrap_or(SpendUsage {
+                day_start,
+                day_spent: 0,
+                month_start,
+                month_spent: 0,
+            });
+        if usage.day_start != day_start {
+            usage.day_start = day_start;
+            usage.day_spent = 0;
+        }
+        if usage.month_start != month_start {
+            usage.month_start = month_start;
+            usage.month_spent = 0;
+        }
+        usage
+    }
+
+    /// Count an outflow against the member's spend limits, failing with
+    /// `SpendLimitExceeded` if it would go over either one.
+    fn record_outflow(env: &Env, member: &Address, amount: i128) {
+        let limits = match Self::get_spend_limits(env.clone(), member.clone()) {
+            Some(limits) => limits,
+            None => return,
+        };
+        let mut usage = Self::current_usage(env, member);
+        usage.day_spent = usage.day_spent.saturating_add(amount);
+        usage.month_spent = usage.month_spent.saturating_add(amount);
+        if (limits.daily > 0 && usage.day_spent > limits.daily)
+            || (limits.monthly > 0 && usage.month_spent > limits.monthly)
+        {
+            env.panic_with_error(Error::SpendLimitExceeded);
+        }
+        let mut all_usage: Map<Address, SpendUsage> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPND_USE"))
+            .unwrap_or_else(|| Map::new(env));
+        all_usage.set(member.clone(), usage);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPND_USE"), &all_usage);
+    }
+
+    /// Panic unless the member's role may make this spend. `large` covers
+    /// withdrawals that need multisig and emergency transfers.
+    fn require_spend_role(env: &Env, address: &Address, large: bool) {
+        Self::require_not_frozen(env, address);
+        Self::apply_due_graduation(env, address);
+        match Self::member_role(env, address) {
+            None | Some(MemberRole::ViewOnly) => panic!("Member role cannot spend"),
+            Some(MemberRole::Teen) if large => {
+                panic!("Teen role is limited to regular withdrawals")
+            }
+            _ => {}
+        }
+    }
+
+    fn require_not_frozen(env: &Env, address: &Address) {
+        if Self::is_member_frozen(env.clone(), address.clone()) {
+            env.panic_with_error(Error::MemberFrozen);
+        }
+    }
+
+    fn require_unfrozen_head(env: &Env, caller: &Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(env);
+        if Self::member_role(env, caller) != Some(MemberRole::Head) {
+            return Err(Error::Unauthorized);
+        }
+        if Self::is_member_frozen(env.clone(), caller.clone()) {
+            return Err(Error::MemberFrozen);
+        }
+        Ok(())
+    }
+
+    fn set_frozen(env: &Env, by: &Address, member: &Address, frozen: bool) {
+        Self::extend_instance_ttl(env);
+        let mut all: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FROZEN"))
+            .unwrap_or_else(|| Map::new(env));
+        let now = env.ledger().timestamp();
+        let (operation, event) = if frozen {
+            all.set(member.clone(), now);
+            (symbol_short!("freeze"), MembershipEvent::Frozen)
+        } else {
+            all.remove(member.clone());
+            (symbol_short!("unfreeze"), MembershipEvent::Unfrozen)
+        };
+        env.storage().instance().set(&symbol_short!("FROZEN"), &all);
+
+        Self::append_access_audit(env, operation, by, Some(member.clone()), true);
+        env.events().publish(
+            (symbol_short!("member"), event),
+            MemberFreezeEvent {
+                member: member.clone(),
+                by: by.clone(),
+                timestamp: now,
+            },
+        );
+    }
+
     fn get_role_expiry(env: &Env, address: &Address) -> Option<u64> {
         env.storage()
             .instance()
@@ -1,9 +1,9 @@
 use super::*;
 use soroban_sdk::testutils::storage::Instance as _;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
+    testutils::{Address as _, EnvTestConfig, Events, Ledger, LedgerInfo},
     token::{StellarAssetClient, TokenClient},
-    vec, Env,
+    vec, Env, TryIntoVal,
 };
 
 #[test]
@@ -549,6 +549,1196 @@ fn test_add_and_remove_family_member() {
     assert!(member_data.is_none());
 }
 
+#[test]
+fn test_member_roles_gate_spending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let teen = Address::generate(&env);
+    let viewer = Address::generate(&env);
+    client.init(&owner, &vec![&env, teen.clone(), viewer.clone()]);
+
+    assert_eq!(client.get_member_role(&owner), Some(MemberRole::Head));
+    assert_eq!(client.get_member_role(&teen), Some(MemberRole::Adult));
+    assert_eq!(client.get_member_role(&Address::generate(&env)), None);
+
+    // Only a Head assigns roles, and the owner stays Head
+    assert_eq!(
+        client.try_set_member_role(&teen, &viewer, &MemberRole::ViewOnly),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_member_role(&owner, &owner, &MemberRole::Adult),
+        Err(Ok(Error::InvalidRole))
+    );
+    client.set_member_role(&owner, &teen, &MemberRole::Teen);
+    client.set_member_role(&owner, &viewer, &MemberRole::ViewOnly);
+    assert_eq!(client.get_member_role(&teen), Some(MemberRole::Teen));
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let token_client = TokenClient::new(&env, &token);
+    StellarAssetClient::new(&env, &token).mint(&teen, &5000_0000000);
+    StellarAssetClient::new(&env, &token).mint(&viewer, &5000_0000000);
+    let recipient = Address::generate(&env);
+
+    // A Teen may withdraw within the regular limit but not above it
+    assert_eq!(client.withdraw(&teen, &token, &recipient, &100_0000000), 0);
+    assert_eq!(token_client.balance(&recipient), 100_0000000);
+    assert!(client
+        .try_withdraw(&teen, &token, &recipient, &2000_0000000)
+        .is_err());
+    assert!(client
+        .try_propose_emergency_transfer(&teen, &token, &recipient, &100_0000000)
+        .is_err());
+
+    // ViewOnly cannot spend at all
+    assert!(client
+        .try_withdraw(&viewer, &token, &recipient, &100_0000000)
+        .is_err());
+    assert!(!client.check_spending_limit(&viewer, &1));
+    assert!(client.check_spending_limit(&teen, &1));
+
+    client.remove_family_member(&owner, &viewer);
+    assert_eq!(client.get_member_role(&viewer), None);
+}
+
+#[test]
+fn test_daily_and_monthly_spend_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&member, &5000_0000000);
+    let recipient = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_spend_limits(&member, &member, &100, &300),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_spend_limits(&owner, &member, &-1, &300),
+        Err(Ok(Error::InvalidSpendingLimit))
+    );
+    client.set_spend_limits(&owner, &member, &100_0000000, &250_0000000);
+    assert_eq!(
+        client.get_remaining_limit(&member),
+        RemainingLimit {
+            daily: Some(100_0000000),
+            monthly: Some(250_0000000),
+        }
+    );
+
+    client.withdraw(&member, &token, &recipient, &60_0000000);
+    let remaining = client.get_remaining_limit(&member);
+    assert_eq!(remaining.daily, Some(40_0000000));
+    assert_eq!(remaining.monthly, Some(190_0000000));
+    assert!(!client.check_spending_limit(&member, &50_0000000));
+    assert_eq!(
+        client.try_withdraw(&member, &token, &recipient, &50_0000000),
+        Err(Ok(Error::SpendLimitExceeded.into()))
+    );
+
+    // The daily window resets the next day; the monthly one keeps counting
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.withdraw(&member, &token, &recipient, &100_0000000);
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.withdraw(&member, &token, &recipient, &90_0000000);
+    assert_eq!(client.get_remaining_limit(&member).monthly, Some(0));
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    assert_eq!(
+        client.try_withdraw(&member, &token, &recipient, &1),
+        Err(Ok(Error::SpendLimitExceeded.into()))
+    );
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&recipient),
+        250_0000000
+    );
+
+    // Lifting both caps lifts the limits
+    client.set_spend_limits(&owner, &member, &0, &0);
+    assert_eq!(client.get_remaining_limit(&member).daily, None);
+}
+
+#[test]
+fn test_allowances_credit_sub_balances_from_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &1000_0000000);
+    let recipient = Address::generate(&env);
+    let week = 7 * 86400;
+
+    assert_eq!(
+        client.try_set_allowance(&member, &member, &token, &50_0000000, &week),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_allowance(&owner, &member, &token, &50_0000000, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_allowance(&owner, &member, &token, &50_0000000, &week);
+
+    // Due now, but the pool is empty: the credit waits for funding
+    assert_eq!(client.process_allowances(), 0);
+    client.fund_pool(&owner, &token, &120_0000000);
+    assert_eq!(client.process_allowances(), 1);
+    assert_eq!(client.get_sub_balance(&member, &token), 50_0000000);
+    assert_eq!(client.get_pool_balance(&token), 70_0000000);
+    assert_eq!(client.process_allowances(), 0);
+
+    // Two weeks and a day later: one credit, one interval missed, late
+    env.ledger()
+        .with_mut(|li| li.timestamp += 2 * week + 86400 + 1);
+    assert_eq!(client.process_allowances(), 1);
+    let allowance = client.get_allowance(&member).unwrap();
+    assert_eq!(allowance.missed_count, 1);
+    assert_eq!(allowance.next_due, 3 * week);
+    assert_eq!(client.get_sub_balance(&member, &token), 100_0000000);
+
+    let mut missed = 0;
+    let mut late = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        let prefix: Result<Symbol, _> = topics.get(0).unwrap().try_into_val(&env);
+        if topics.len() != 2 || prefix != Ok(symbol_short!("allow")) {
+            continue;
+        }
+        let kind: Result<AllowanceEvent, _> = topics.get(1).unwrap().try_into_val(&env);
+        match kind {
+            Ok(AllowanceEvent::Missed) => missed += 1,
+            Ok(AllowanceEvent::Late) => late += 1,
+            _ => {}
+        }
+    }
+    assert_eq!((missed, late), (1, 1));
+
+    assert_eq!(
+        client.try_spend(
+            &member,
+            &token,
+            &recipient,
+            &101_0000000,
+            &SpendCategory::Food
+        ),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    client.spend(
+        &member,
+        &token,
+        &recipient,
+        &30_0000000,
+        &SpendCategory::Food,
+    );
+    assert_eq!(client.get_sub_balance(&member, &token), 70_0000000);
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&recipient),
+        30_0000000
+    );
+
+    // Removing the member stops the allowance and returns what is unspent
+    client.remove_family_member(&owner, &member);
+    assert!(client.get_allowance(&member).is_none());
+    assert_eq!(client.get_sub_balance(&member, &token), 0);
+    assert_eq!(client.get_pool_balance(&token), 90_0000000);
+}
+
+#[test]
+fn test_large_pool_withdrawal_needs_m_of_n_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let remitter = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&remitter, &2000_0000000);
+    client.fund_pool(&remitter, &token, &2000_0000000);
+    let recipient = Address::generate(&env);
+
+    assert_eq!(
+        client.try_propose_pool_withdrawal(&member, &token, &recipient, &50_0000000),
+        Err(Ok(Error::ApprovalsNotConfigured))
+    );
+    // The remitter abroad and the head must both approve above 100
+    let approvers = vec![&env, remitter.clone(), owner.clone()];
+    assert_eq!(
+        client.try_configure_withdrawal_approvals(&owner, &approvers, &3, &100_0000000, &172800),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    client.configure_withdrawal_approvals(&owner, &approvers, &2, &100_0000000, &172800);
+
+    assert_eq!(
+        client.propose_pool_withdrawal(&member, &token, &recipient, &50_0000000),
+        0
+    );
+    let id = client.propose_pool_withdrawal(&member, &token, &recipient, &500_0000000);
+    assert_eq!(
+        client.try_execute_pool_withdrawal(&member, &id),
+        Err(Ok(Error::InsufficientSignatures))
+    );
+    assert_eq!(
+        client.try_approve_pool_withdrawal(&member, &id),
+        Err(Ok(Error::InvalidSigner))
+    );
+    assert_eq!(client.approve_pool_withdrawal(&owner, &id), 1);
+    assert_eq!(
+        client.try_approve_pool_withdrawal(&owner, &id),
+        Err(Ok(Error::DuplicateSignature))
+    );
+    assert_eq!(client.approve_pool_withdrawal(&remitter, &id), 2);
+    client.execute_pool_withdrawal(&member, &id);
+    assert!(client.get_pool_withdrawal(&id).is_none());
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&recipient),
+        550_0000000
+    );
+    assert_eq!(client.get_pool_balance(&token), 1450_0000000);
+
+    // Proposals expire after the configured ttl
+    let id = client.propose_pool_withdrawal(&owner, &token, &recipient, &300_0000000);
+    assert_eq!(client.get_pool_withdrawal(&id).unwrap().approvals.len(), 1);
+    env.ledger().with_mut(|li| li.timestamp += 172801);
+    assert_eq!(
+        client.try_approve_pool_withdrawal(&remitter, &id),
+        Err(Ok(Error::TransactionExpired))
+    );
+    assert_eq!(client.cleanup_expired_pending(&owner), 1);
+    assert!(client.get_pool_withdrawal(&id).is_none());
+}
+
+#[test]
+fn test_category_restrictions_on_member_spending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let teen = Address::generate(&env);
+    let adult = Address::generate(&env);
+    client.init(&owner, &vec![&env, teen.clone(), adult.clone()]);
+    client.set_member_role(&owner, &teen, &MemberRole::Teen);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &1000_0000000);
+    client.fund_pool(&owner, &token, &1000_0000000);
+    client.set_allowance(&owner, &teen, &token, &100_0000000, &604800);
+    client.set_allowance(&owner, &adult, &token, &100_0000000, &604800);
+    client.process_allowances();
+    let shop = Address::generate(&env);
+
+    let school_and_food = vec![&env, SpendCategory::School, SpendCategory::Food];
+    assert_eq!(
+        client.try_set_category_restrictions(&adult, &teen, &school_and_food),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_category_restrictions(&owner, &owner, &school_and_food),
+        Err(Ok(Error::InvalidRole))
+    );
+    client.set_category_restrictions(&owner, &teen, &school_and_food);
+    assert_eq!(client.get_category_restrictions(&teen), school_and_food);
+
+    client.spend(&teen, &token, &shop, &20_0000000, &SpendCategory::School);
+    client.spend(&teen, &token, &shop, &5_0000000, &SpendCategory::Food);
+    assert_eq!(
+        client.try_spend(&teen, &token, &shop, &5_0000000, &SpendCategory::Transport),
+        Err(Ok(Error::CategoryNotAllowed))
+    );
+    client.configure_withdrawal_approvals(
+        &owner,
+        &vec![&env, owner.clone()],
+        &1,
+        &500_0000000,
+        &86400,
+    );
+    assert_eq!(
+        client.try_propose_pool_withdrawal(&teen, &token, &shop, &1_0000000),
+        Err(Ok(Error::CategoryNotAllowed))
+    );
+    // Unrestricted members spend on anything
+    client.spend(
+        &adult,
+        &token,
+        &shop,
+        &15_0000000,
+        &SpendCategory::Transport,
+    );
+    client.spend(&adult, &token, &shop, &10_0000000, &SpendCategory::Food);
+
+    assert_eq!(
+        client.get_category_spending(&0, &86400),
+        vec![
+            &env,
+            CategorySpend {
+                category: SpendCategory::School,
+                amount: 20_0000000,
+            },
+            CategorySpend {
+                category: SpendCategory::Food,
+                amount: 15_0000000,
+            },
+            CategorySpend {
+                category: SpendCategory::Transport,
+                amount: 15_0000000,
+            },
+        ]
+    );
+    assert_eq!(client.get_category_spending(&86400, &172800).len(), 0);
+
+    client.set_category_restrictions(&owner, &teen, &vec![&env]);
+    client.spend(&teen, &token, &shop, &5_0000000, &SpendCategory::Transport);
+}
+
+#[test]
+fn test_invite_accept_and_remove_with_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let invitee = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    assert_eq!(
+        client.try_invite_member(&member, &invitee, &FamilyRole::Member, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_invite_member(&owner, &member, &FamilyRole::Member, &0),
+        Err(Ok(Error::InvalidRole))
+    );
+    client.invite_member(&owner, &invitee, &FamilyRole::Member, &50_0000000);
+    client.invite_member(&owner, &late, &FamilyRole::Admin, &0);
+    assert!(client.get_family_member(&invitee).is_none());
+
+    client.accept_invite(&invitee);
+    let joined = client.get_family_member(&invitee).unwrap();
+    assert_eq!(joined.role, FamilyRole::Member);
+    assert_eq!(joined.spending_limit, 50_0000000);
+    assert!(client.get_invite(&invitee).is_none());
+    assert_eq!(
+        client.try_accept_invite(&invitee),
+        Err(Ok(Error::InviteNotFound))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86400 + 1);
+    assert_eq!(
+        client.try_accept_invite(&late),
+        Err(Ok(Error::InviteExpired))
+    );
+    client.revoke_invite(&owner, &late);
+    assert!(client.get_invite(&late).is_none());
+
+    // The departing member's sub-balance is transferred out
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &invitee, &token, &40_0000000, &604800);
+    client.process_allowances();
+    let new_wallet = Address::generate(&env);
+    assert_eq!(
+        client.try_remove_member_with_handover(
+            &member,
+            &invitee,
+            &Handover::TransferTo(new_wallet.clone())
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.remove_member_with_handover(&owner, &invitee, &Handover::TransferTo(new_wallet.clone()));
+    assert!(client.get_family_member(&invitee).is_none());
+    assert_eq!(
+        TokenClient::new(&env, &token).balance(&new_wallet),
+        40_0000000
+    );
+    assert_eq!(client.get_sub_balance(&invitee, &token), 0);
+    assert_eq!(client.get_pool_balance(&token), 60_0000000);
+
+    let audit = client.get_access_audit(&10);
+    for op in [
+        symbol_short!("accept"),
+        symbol_short!("rev_inv"),
+        symbol_short!("rem_mem"),
+    ] {
+        assert!(audit.iter().any(|e| e.operation == op));
+    }
+}
+
+#[test]
+fn test_member_transaction_history_pages_and_totals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&member, &100_0000000);
+    StellarAssetClient::new(&env, &token).mint(&owner, &110_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &member, &token, &30_0000000, &604800);
+    client.process_allowances();
+    let shop = Address::generate(&env);
+
+    for _ in 0..3 {
+        client.withdraw(&member, &token, &shop, &5_0000000);
+    }
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.spend(&member, &token, &shop, &12_0000000, &SpendCategory::Food);
+    client.withdraw(&owner, &token, &shop, &1_0000000);
+
+    assert_eq!(client.get_member_transaction_count(&member), 4);
+    let page = client.get_member_transactions(&member, &2, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().kind, MemberTxKind::Withdrawal);
+    let last = page.get(1).unwrap();
+    assert_eq!(last.kind, MemberTxKind::Spend(SpendCategory::Food));
+    assert_eq!(last.amount, 12_0000000);
+    assert_eq!(last.timestamp, 86400);
+    assert_eq!(client.get_member_transactions(&member, &0, &2).len(), 2);
+    assert_eq!(client.get_member_transactions(&member, &4, &10).len(), 0);
+
+    assert_eq!(client.get_member_spending(&member, &0, &86399), 15_0000000);
+    assert_eq!(
+        client.get_member_spending(&member, &86400, &172800),
+        12_0000000
+    );
+    assert_eq!(client.get_family_spending(&0, &172800), 28_0000000);
+}
+
+#[test]
+fn test_receive_split_funds_credits_pool_and_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let split = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&split, &2000);
+
+    assert_eq!(
+        client.try_receive_split_funds(&split, &token, &1000),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_split_contract(&member, &Some(split.clone())),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_split_contract(&owner, &Some(split.clone()));
+
+    let shares = vec![
+        &env,
+        InflowShare {
+            member: member.clone(),
+            bps: 2500,
+        },
+    ];
+    assert_eq!(
+        client.try_set_inflow_shares(
+            &owner,
+            &vec![
+                &env,
+                InflowShare {
+                    member: member.clone(),
+                    bps: 10_001,
+                },
+            ]
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_inflow_shares(&owner, &shares);
+
+    assert_eq!(client.receive_split_funds(&split, &token, &1000), 750);
+    assert_eq!(client.get_pool_balance(&token), 750);
+    assert_eq!(client.get_sub_balance(&member, &token), 250);
+    assert_eq!(TokenClient::new(&env, &token).balance(&contract_id), 1000);
+
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.set_inflow_shares(&owner, &vec![&env]);
+    assert_eq!(client.receive_split_funds(&split, &token, &1000), 1000);
+
+    let inflows = client.get_split_inflows(&0, &86400);
+    assert_eq!(inflows.len(), 2);
+    let first = inflows.get(0).unwrap();
+    assert_eq!(
+        (first.amount, first.to_pool, first.to_members),
+        (1000, 750, 250)
+    );
+    assert_eq!(client.get_split_inflows(&1, &86399).len(), 0);
+}
+
+#[test]
+fn test_split_inflows_keep_only_latest() {
+    // The full history would make a very large snapshot
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    client.init(&owner, &vec![&env]);
+    let split = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&split, &10_000);
+    client.set_split_contract(&owner, &Some(split.clone()));
+
+    for amount in 1..=(MAX_SPLIT_INFLOWS as i128 + 1) {
+        client.receive_split_funds(&split, &token, &amount);
+    }
+
+    let inflows = client.get_split_inflows(&0, &0);
+    assert_eq!(inflows.len(), MAX_SPLIT_INFLOWS);
+    assert_eq!(inflows.get(0).unwrap().amount, 2);
+    assert_eq!(
+        inflows.last().unwrap().amount,
+        MAX_SPLIT_INFLOWS as i128 + 1
+    );
+}
+
+#[test]
+fn test_frozen_member_cannot_spend_or_receive_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let remitter = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    StellarAssetClient::new(&env, &token).mint(&member, &10_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &member, &token, &10_0000000, &604800);
+    client.process_allowances();
+    let shop = Address::generate(&env);
+
+    assert_eq!(
+        client.try_freeze_member(&member, &owner),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.freeze_member(&owner, &member);
+    assert!(client.is_member_frozen(&member));
+    assert_eq!(
+        client.try_spend(&member, &token, &shop, &1_0000000, &SpendCategory::Food),
+        Err(Ok(Error::MemberFrozen))
+    );
+    assert_eq!(
+        client.try_withdraw(&member, &token, &shop, &1_0000000),
+        Err(Ok(Error::MemberFrozen.into()))
+    );
+
+    // The weekly credit stays due while frozen
+    env.ledger().with_mut(|li| li.timestamp += 604800);
+    assert_eq!(client.process_allowances(), 0);
+    client.unfreeze_member(&owner, &member);
+    assert_eq!(client.process_allowances(), 1);
+    assert_eq!(client.get_sub_balance(&member, &token), 20_0000000);
+    client.spend(&member, &token, &shop, &1_0000000, &SpendCategory::Food);
+
+    // Two of the approvers can freeze the head, who then cannot act
+    client.configure_withdrawal_approvals(
+        &owner,
+        &vec![&env, owner.clone(), remitter.clone(), member.clone()],
+        &2,
+        &50_0000000,
+        &86400,
+    );
+    assert_eq!(client.approve_member_freeze(&remitter, &owner, &true), 1);
+    assert_eq!(
+        client.try_approve_member_freeze(&remitter, &owner, &true),
+        Err(Ok(Error::DuplicateSignature))
+    );
+    assert_eq!(client.approve_member_freeze(&member, &owner, &true), 0);
+    assert!(client.is_member_frozen(&owner));
+    assert_eq!(
+        client.try_unfreeze_member(&owner, &owner),
+        Err(Ok(Error::MemberFrozen))
+    );
+    assert_eq!(
+        client.try_approve_member_freeze(&owner, &owner, &false),
+        Err(Ok(Error::MemberFrozen))
+    );
+    client.approve_member_freeze(&remitter, &owner, &false);
+    client.approve_member_freeze(&member, &owner, &false);
+    assert!(!client.is_member_frozen(&owner));
+}
+
+#[test]
+fn test_spending_delegation_with_cap_and_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let child = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.init(&owner, &vec![&env, child.clone(), other.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &owner, &token, &80_0000000, &2_592_000);
+    client.process_allowances();
+    let shop = Address::generate(&env);
+
+    assert_eq!(
+        client.try_delegate_spending(&owner, &owner, &10_0000000, &86400),
+        Err(Ok(Error::InvalidSigner))
+    );
+    assert_eq!(
+        client.try_delegate_spending(&owner, &child, &10_0000000, &0),
+        Err(Ok(Error::DelegationExpired))
+    );
+    client.delegate_spending(&owner, &child, &30_0000000, &(7 * 86400));
+
+    assert_eq!(
+        client.try_spend_as_delegate(
+            &other,
+            &owner,
+            &token,
+            &shop,
+            &1_0000000,
+            &SpendCategory::Food
+        ),
+        Err(Ok(Error::DelegationNotFound))
+    );
+    client.spend_as_delegate(
+        &child,
+        &owner,
+        &token,
+        &shop,
+        &20_0000000,
+        &SpendCategory::Food,
+    );
+    assert_eq!(
+        client.try_spend_as_delegate(
+            &child,
+            &owner,
+            &token,
+            &shop,
+            &11_0000000,
+            &SpendCategory::Food
+        ),
+        Err(Ok(Error::SpendLimitExceeded))
+    );
+    assert_eq!(client.get_delegation(&owner).unwrap().spent, 20_0000000);
+    assert_eq!(client.get_sub_balance(&owner, &token), 60_0000000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&shop), 20_0000000);
+
+    // Attributed to both, counted once
+    let owner_tx = client
+        .get_member_transactions(&owner, &0, &10)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        owner_tx.kind,
+        MemberTxKind::DelegatedSpend(child.clone(), SpendCategory::Food)
+    );
+    let child_tx = client
+        .get_member_transactions(&child, &0, &10)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        child_tx.kind,
+        MemberTxKind::SpendForDelegator(owner.clone(), SpendCategory::Food)
+    );
+    assert_eq!(client.get_member_spending(&child, &0, &86400), 0);
+    assert_eq!(client.get_family_spending(&0, &86400), 20_0000000);
+
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86400 + 1);
+    assert_eq!(
+        client.try_spend_as_delegate(
+            &child,
+            &owner,
+            &token,
+            &shop,
+            &1_0000000,
+            &SpendCategory::Food
+        ),
+        Err(Ok(Error::DelegationExpired))
+    );
+    client.revoke_delegation(&owner);
+    assert!(client.get_delegation(&owner).is_none());
+}
+
+#[test]
+fn test_spend_requests_approved_or_rejected_by_head() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let teen = Address::generate(&env);
+    client.init(&owner, &vec![&env, teen.clone()]);
+    client.set_member_role(&owner, &teen, &MemberRole::Teen);
+    client.set_category_restrictions(&owner, &teen, &vec![&env, SpendCategory::Food]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    let school = Address::generate(&env);
+
+    let memo = String::from_str(&env, "Term fees");
+    let id = client.request_spend(
+        &teen,
+        &token,
+        &school,
+        &40_0000000,
+        &SpendCategory::School,
+        &memo,
+    );
+    let second = client.request_spend(
+        &teen,
+        &token,
+        &school,
+        &5_0000000,
+        &SpendCategory::Transport,
+        &memo,
+    );
+    assert_eq!(client.get_pending_spend_requests().len(), 2);
+
+    assert_eq!(
+        client.try_approve_spend_request(&teen, &id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.approve_spend_request(&owner, &id);
+    assert_eq!(
+        client.try_approve_spend_request(&owner, &id),
+        Err(Ok(Error::TransactionAlreadyExecuted))
+    );
+    let approved = client.get_spend_request(&id).unwrap();
+    assert_eq!(approved.status, SpendRequestStatus::Approved);
+    assert_eq!(approved.decided_by, Some(owner.clone()));
+    assert_eq!(TokenClient::new(&env, &token).balance(&school), 40_0000000);
+    assert_eq!(client.get_pool_balance(&token), 60_0000000);
+
+    let history = client.get_member_transactions(&teen, &0, &10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history.get(0).unwrap().kind,
+        MemberTxKind::RequestedSpend(SpendCategory::School)
+    );
+
+    client.reject_spend_request(&owner, &second);
+    assert_eq!(
+        client.get_spend_request(&second).unwrap().status,
+        SpendRequestStatus::Rejected
+    );
+    assert_eq!(client.get_pending_spend_requests().len(), 0);
+    assert_eq!(
+        client.try_approve_spend_request(&owner, &99),
+        Err(Ok(Error::TransactionNotFound))
+    );
+}
+
+#[test]
+fn test_budget_envelopes_funded_from_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let adult = Address::generate(&env);
+    client.init(&owner, &vec![&env, adult.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    let market = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_envelope(&adult, &SpendCategory::Food, &token, &30_0000000),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_envelope(&owner, &SpendCategory::Food, &token, &30_0000000);
+    assert_eq!(
+        client.fund_envelope(&owner, &SpendCategory::Food, &40_0000000),
+        40_0000000
+    );
+    assert_eq!(client.get_pool_balance(&token), 60_0000000);
+    assert_eq!(
+        client.try_fund_envelope(&owner, &SpendCategory::School, &1_0000000),
+        Err(Ok(Error::EnvelopeNotFound))
+    );
+
+    client.spend_from_envelope(&adult, &SpendCategory::Food, &market, &25_0000000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&market), 25_0000000);
+    assert_eq!(
+        client.try_spend_from_envelope(&adult, &SpendCategory::Food, &market, &20_0000000),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        client.release_envelope(&owner, &SpendCategory::Food, &5_0000000),
+        10_0000000
+    );
+    assert_eq!(client.get_pool_balance(&token), 65_0000000);
+
+    let envelope = client.get_envelope(&SpendCategory::Food).unwrap();
+    assert_eq!(envelope.spent, 25_0000000);
+    assert_eq!(
+        client
+            .get_member_transactions(&adult, &0, &10)
+            .get(0)
+            .unwrap()
+            .kind,
+        MemberTxKind::EnvelopeSpend(SpendCategory::Food)
+    );
+
+    let now = env.ledger().timestamp();
+    let report = client.get_envelope_vs_actual(&0, &(now + 86400));
+    assert_eq!(report.len(), 1);
+    let food = report.get(0).unwrap();
+    assert_eq!(food.budget, 30_0000000);
+    assert_eq!(food.actual, 25_0000000);
+    assert_eq!(food.balance, 10_0000000);
+    assert_eq!(
+        client
+            .get_category_spending(&0, &(now + 86400))
+            .get(0)
+            .unwrap()
+            .amount,
+        25_0000000
+    );
+}
+
+#[test]
+fn test_standing_transfers_between_sub_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let grandmother = Address::generate(&env);
+    client.init(&owner, &vec![&env, grandmother.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &owner, &token, &50_0000000, &(365 * 86400));
+    client.process_allowances();
+    let week = 7 * 86400;
+
+    assert_eq!(
+        client.try_create_standing_transfer(&owner, &owner, &token, &20_0000000, &week),
+        Err(Ok(Error::InvalidSigner))
+    );
+    let id = client.create_standing_transfer(&owner, &grandmother, &token, &20_0000000, &week);
+    assert_eq!(client.process_standing_transfers(), 1);
+    assert_eq!(client.process_standing_transfers(), 0);
+    assert_eq!(client.get_sub_balance(&owner, &token), 30_0000000);
+    assert_eq!(client.get_sub_balance(&grandmother, &token), 20_0000000);
+
+    // The receiver cannot manage the sender's transfer
+    assert_eq!(
+        client.try_skip_standing_transfer(&grandmother, &id),
+        Err(Ok(Error::Unauthorized))
+    );
+    let next_due = client.get_standing_transfer(&id).unwrap().next_due;
+    assert_eq!(client.skip_standing_transfer(&owner, &id), next_due + week);
+    env.ledger().with_mut(|li| li.timestamp += week);
+    assert_eq!(client.process_standing_transfers(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += week);
+    client.set_standing_transfer_paused(&owner, &id, &true);
+    assert_eq!(client.process_standing_transfers(), 0);
+    env.ledger().with_mut(|li| li.timestamp += 2 * week);
+    client.set_standing_transfer_paused(&owner, &id, &false);
+    // Intervals spent paused are not made up
+    assert_eq!(client.process_standing_transfers(), 0);
+    let transfer = client.get_standing_transfer(&id).unwrap();
+    assert!(transfer.next_due > env.ledger().timestamp());
+    assert_eq!(transfer.missed_count, 0);
+
+    env.ledger().with_mut(|li| li.timestamp += week);
+    assert_eq!(client.process_standing_transfers(), 1);
+    assert_eq!(client.get_sub_balance(&owner, &token), 10_0000000);
+    env.ledger().with_mut(|li| li.timestamp += week);
+    // The sender's balance no longer covers the transfer; it stays due
+    assert_eq!(client.process_standing_transfers(), 0);
+    assert_eq!(client.get_sub_balance(&grandmother, &token), 40_0000000);
+
+    assert_eq!(client.get_standing_transfers(&grandmother).len(), 1);
+    client.cancel_standing_transfer(&owner, &id);
+    assert_eq!(client.get_standing_transfer(&id), None);
+    assert_eq!(
+        client.try_cancel_standing_transfer(&owner, &id),
+        Err(Ok(Error::TransactionNotFound))
+    );
+}
+
+#[test]
+fn test_guardians_recover_member_after_challenge_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    client.init(&owner, &vec![&env, member.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &member, &token, &30_0000000, &604800);
+    client.process_allowances();
+    let shop = Address::generate(&env);
+    client.spend(&member, &token, &shop, &10_0000000, &SpendCategory::Food);
+
+    let g1 = Address::generate(&env);
+    let g2 = Address::generate(&env);
+    let g3 = Address::generate(&env);
+    let guardians = vec![&env, g1.clone(), g2.clone(), g3.clone()];
+    assert_eq!(
+        client.try_set_recovery_guardians(&owner, &guardians, &2),
+        Err(Ok(Error::InvalidRole))
+    );
+    assert_eq!(
+        client.try_set_recovery_guardians(&member, &guardians, &4),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    client.set_recovery_guardians(&member, &guardians, &2);
+
+    let new_key = Address::generate(&env);
+    assert_eq!(
+        client.try_propose_recovery(&shop, &member, &new_key),
+        Err(Ok(Error::InvalidSigner))
+    );
+    assert_eq!(client.propose_recovery(&g1, &member, &new_key), 1);
+    assert_eq!(
+        client.try_execute_recovery(&member),
+        Err(Ok(Error::RecoveryNotReady))
+    );
+    assert_eq!(client.approve_recovery(&g2, &member), 2);
+    assert_eq!(
+        client.try_approve_recovery(&g2, &member),
+        Err(Ok(Error::DuplicateSignature))
+    );
+
+    // The member still holds their key and challenges the recovery
+    client.cancel_recovery(&member, &member);
+    assert_eq!(client.get_recovery_request(&member), None);
+
+    client.propose_recovery(&g1, &member, &new_key);
+    client.approve_recovery(&g3, &member);
+    let request = client.get_recovery_request(&member).unwrap();
+    assert_eq!(
+        request.executable_at,
+        Some(env.ledger().timestamp() + RECOVERY_CHALLENGE_SECS)
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp += RECOVERY_CHALLENGE_SECS - 1);
+    assert_eq!(
+        client.try_execute_recovery(&member),
+        Err(Ok(Error::RecoveryNotReady))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    assert_eq!(client.execute_recovery(&member), new_key);
+
+    assert!(client.get_family_member(&member).is_none());
+    let record = client.get_family_member(&new_key).unwrap();
+    assert_eq!(record.address, new_key);
+    assert_eq!(client.get_sub_balance(&member, &token), 0);
+    assert_eq!(client.get_sub_balance(&new_key, &token), 20_0000000);
+    assert_eq!(client.get_member_transaction_count(&new_key), 1);
+    assert_eq!(client.get_member_transaction_count(&member), 0);
+    assert_eq!(client.get_allowance(&new_key).unwrap().member, new_key);
+    assert_eq!(
+        client.get_recovery_guardians(&new_key).unwrap().threshold,
+        2
+    );
+
+    client.spend(&new_key, &token, &shop, &5_0000000, &SpendCategory::Food);
+    assert_eq!(TokenClient::new(&env, &token).balance(&shop), 15_0000000);
+}
+
+#[test]
+fn test_spends_publish_notifications_with_remaining_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let adult = Address::generate(&env);
+    client.init(&owner, &vec![&env, adult.clone()]);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_spend_limits(&owner, &adult, &50_0000000, &0);
+    client.set_envelope(&owner, &SpendCategory::Food, &token, &30_0000000);
+    client.fund_envelope(&owner, &SpendCategory::Food, &30_0000000);
+    let market = Address::generate(&env);
+
+    client.spend_from_envelope(&adult, &SpendCategory::Food, &market, &12_0000000);
+
+    let mut notifications = Vec::<SpendNotification>::new(&env);
+    for (_, topics, data) in env.events().all().iter() {
+        let prefix: Result<Symbol, _> = topics.get(0).unwrap().try_into_val(&env);
+        if prefix != Ok(symbol_short!("spent")) {
+            continue;
+        }
+        let member: Address = topics.get(1).unwrap().try_into_val(&env).unwrap();
+        assert_eq!(member, adult);
+        notifications.push_back(data.try_into_val(&env).unwrap());
+    }
+    assert_eq!(notifications.len(), 1);
+    let notification = notifications.get(0).unwrap();
+    assert_eq!(
+        notification.kind,
+        MemberTxKind::EnvelopeSpend(SpendCategory::Food)
+    );
+    assert_eq!(notification.recipient, market);
+    assert_eq!(notification.amount, 12_0000000);
+    assert_eq!(notification.remaining.daily, Some(38_0000000));
+    assert_eq!(notification.remaining.monthly, None);
+    assert_eq!(notification.envelope_balance, Some(18_0000000));
+}
+
+#[test]
+fn test_scheduled_graduation_applies_on_next_interaction() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, FamilyWallet);
+    let client = FamilyWalletClient::new(&env, &contract_id);
+
+    let owner = Address::generate(&env);
+    let teen = Address::generate(&env);
+    client.init(&owner, &vec![&env, teen.clone()]);
+    client.set_member_role(&owner, &teen, &MemberRole::Teen);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&owner, &100_0000000);
+    client.fund_pool(&owner, &token, &100_0000000);
+    client.set_allowance(&owner, &teen, &token, &50_0000000, &(365 * 86400));
+    client.process_allowances();
+    let shop = Address::generate(&env);
+
+    let birthday = env.ledger().timestamp() + 86400;
+    let adult_limits = SpendLimits {
+        daily: 20_0000000,
+        monthly: 0,
+    };
+    let graduation_limits = GraduationLimits::Set(adult_limits.clone());
+    assert_eq!(
+        client.try_schedule_graduation(
+            &teen,
+            &teen,
+            &birthday,
+            &MemberRole::Adult,
+            &graduation_limits
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_schedule_graduation(
+            &owner,
+            &owner,
+            &birthday,
+            &MemberRole::Adult,
+            &GraduationLimits::Keep
+        ),
+        Err(Ok(Error::InvalidRole))
+    );
+    client.schedule_graduation(
+        &owner,
+        &teen,
+        &birthday,
+        &MemberRole::Adult,
+        &graduation_limits,
+    );
+
+    client.spend(&teen, &token, &shop, &5_0000000, &SpendCategory::Food);
+    assert_eq!(client.get_member_role(&teen), Some(MemberRole::Teen));
+    assert!(client.get_graduation(&teen).is_some());
+
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    // Nothing changes until the member next acts
+    assert_eq!(client.get_member_role(&teen), Some(MemberRole::Teen));
+    client.spend(&teen, &token, &shop, &5_0000000, &SpendCategory::Food);
+
+    let mut graduated = 0;
+    for (_, topics, _) in env.events().all().iter() {
+        let prefix: Result<Symbol, _> = topics.get(0).unwrap().try_into_val(&env);
+        if topics.len() != 2 || prefix != Ok(symbol_short!("member")) {
+            continue;
+        }
+        let kind: Result<MembershipEvent, _> = topics.get(1).unwrap().try_into_val(&env);
+        if matches!(kind, Ok(MembershipEvent::Graduated)) {
+            graduated += 1;
+        }
+    }
+    assert_eq!(graduated, 1);
+    assert_eq!(client.get_member_role(&teen), Some(MemberRole::Adult));
+    assert_eq!(client.get_spend_limits(&teen), Some(adult_limits));
+    assert_eq!(client.get_remaining_limit(&teen).daily, Some(15_0000000));
+    assert_eq!(client.get_graduation(&teen), None);
+    assert_eq!(
+        client.try_cancel_graduation(&owner, &teen),
+        Err(Ok(Error::GraduationNotFound))
+    );
+}
+
 #[test]
 #[should_panic(expected = "Only Owner or Admin can add family members")]
 fn test_add_member_unauthorized() {
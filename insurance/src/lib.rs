@@ -1,6 +1,8 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, token, Address, BytesN,
+    Env, Map, String, Symbol, Vec,
 };
 
 // Event topics
@@ -37,6 +39,35 @@ pub struct PolicyDeactivatedEvent {
     pub timestamp: u64,
 }
 
+/// Policy lifecycle stages, published as
+/// `("policy", stage, owner, policy_id)` so indexers can follow an owner or a
+/// single policy without polling state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyLifecycle {
+    Issued,
+    PremiumPaid,
+    /// The due date passed unpaid; the policy is in its grace period.
+    PremiumMissed,
+    Lapsed,
+    ClaimFiled,
+    ClaimSettled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyLifecycleEvent {
+    pub policy_id: u32,
+    pub owner: Address,
+    /// Premium for issue and payment, claim or payout amount for claims; 0
+    /// for a missed premium or lapse
+    pub amount: i128,
+    /// Claim the event concerns, for claim stages paid through a claim
+    pub claim_id: Option<u32>,
+    pub next_payment_date: u64,
+    pub timestamp: u64,
+}
+
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280;
 const INSTANCE_BUMP_AMOUNT: u32 = 518400;
 
@@ -44,6 +75,33 @@ const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
 const STORAGE_PREMIUM_TOTALS: Symbol = symbol_short!("PRM_TOT");
 
+/// Days a premium may stay unpaid past its due date before it counts as
+/// missed, unless a product or policy grace period overrides it. A policy
+/// lapses once its premium is unpaid past its grace period.
+const DEFAULT_GRACE_DAYS: u32 = 30;
+const MAX_GRACE_DAYS: u32 = 90;
+
+/// Length of a policy term, and how long before `term_end` renewal opens.
+const POLICY_TERM_SECS: u64 = 365 * 86400;
+const RENEWAL_WINDOW_SECS: u64 = 30 * 86400;
+
+/// Oldest oracle reading `check_trigger` will act on.
+const MAX_ORACLE_AGE_SECS: u64 = 86400;
+
+/// How long a claimant has to escalate a rejected claim, and how long the
+/// arbiter then has to rule.
+const DISPUTE_WINDOW_SECS: u64 = 30 * 86400;
+const RULING_WINDOW_SECS: u64 = 30 * 86400;
+
+const MAX_BENEFICIARIES: u32 = 10;
+const BPS_TOTAL: u32 = 10_000;
+
+/// Most premium installments that can be prepaid into escrow at once.
+const MAX_PREPAID_PERIODS: u32 = 12;
+
+const HISTORY_LIFETIME_THRESHOLD: u32 = 17280;
+const HISTORY_BUMP_AMOUNT: u32 = 518400;
+
 /// Pagination constants
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
 pub const MAX_PAGE_LIMIT: u32 = 50;
@@ -56,6 +114,9 @@ pub mod pause_functions {
     pub const CREATE_SCHED: Symbol = symbol_short!("crt_sch");
     pub const MODIFY_SCHED: Symbol = symbol_short!("mod_sch");
     pub const CANCEL_SCHED: Symbol = symbol_short!("can_sch");
+    pub const FILE_CLAIM: Symbol = symbol_short!("file_clm");
+    pub const REINSTATE: Symbol = symbol_short!("reinstat");
+    pub const RENEW: Symbol = symbol_short!("renew");
 }
 
 #[derive(Clone)]
@@ -65,11 +126,429 @@ pub struct InsurancePolicy {
     pub owner: Address,
     pub name: String,
     pub coverage_type: String,
+    /// Premium billed each period, including any riders
     pub monthly_premium: i128,
+    /// Coverage of the base policy; riders carry their own coverage
     pub coverage_amount: i128,
     pub active: bool,
     pub next_payment_date: u64,
     pub schedule_id: Option<u32>,
+    /// End of the current coverage term. The policy is deactivated at expiry
+    /// unless it is renewed.
+    pub term_end: u64,
+}
+
+/// A premium paid with tokens through `pay_premium`.
+///
+/// `due_date` is the payment date this premium covered, so period totals can
+/// be attributed to the month the premium belonged to.
+#[contracttype]
+#[derive(Clone)]
+pub struct PremiumPayment {
+    pub policy_id: u32,
+    pub amount: i128,
+    pub token: Address,
+    pub due_date: u64,
+    pub paid_at: u64,
+}
+
+/// Premiums prepaid into escrow for a policy with `prepay_premium`.
+///
+/// The balance is earned evenly over `[earned_from, paid_through)` and
+/// released to the premium collector as it is earned. Whatever is still
+/// unearned when the policy is cancelled is refunded to the owner.
+#[contracttype]
+#[derive(Clone)]
+pub struct PrepaidPremium {
+    pub policy_id: u32,
+    pub token: Address,
+    pub balance: i128,
+    pub earned_from: u64,
+    pub paid_through: u64,
+}
+
+/// The unearned part of a prepaid premium returned on cancellation.
+#[contracttype]
+#[derive(Clone)]
+pub struct PremiumRefund {
+    pub policy_id: u32,
+    pub amount: i128,
+    pub token: Address,
+    pub refunded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClaimStatus {
+    Filed,
+    Approved,
+    Rejected,
+    Paid,
+    /// Rejected and escalated to the claims arbiter
+    Disputed,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Pending,
+    Upheld,
+    Overturned,
+    /// The arbiter did not rule by the deadline
+    Expired,
+}
+
+/// A rejected claim escalated to the claims arbiter.
+///
+/// The arbiter must rule by `ruling_due`; otherwise the dispute can be
+/// expired and the claimant may escalate again.
+#[contracttype]
+#[derive(Clone)]
+pub struct ClaimDispute {
+    pub claim_id: u32,
+    pub arbiter: Address,
+    pub grounds: String,
+    pub status: DisputeStatus,
+    pub raised_at: u64,
+    pub ruling_due: u64,
+    /// When the arbiter ruled or the dispute expired
+    pub closed_at: Option<u64>,
+    /// Amount paid when the rejection was overturned
+    pub payout: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiderKind {
+    AccidentalDeath,
+    HospitalCash,
+}
+
+/// An add-on attached to a base policy. Its premium is billed together with
+/// the policy's; its coverage is separate from the base coverage.
+#[contracttype]
+#[derive(Clone)]
+pub struct Rider {
+    pub kind: RiderKind,
+    pub monthly_premium: i128,
+    pub coverage_amount: i128,
+    pub added_at: u64,
+}
+
+/// Premium and coverage of a policy split into its base and riders.
+#[contracttype]
+#[derive(Clone)]
+pub struct CoverageBreakdown {
+    pub policy_id: u32,
+    pub base_premium: i128,
+    pub base_coverage: i128,
+    pub riders: Vec<Rider>,
+    pub total_premium: i128,
+    pub total_coverage: i128,
+}
+
+/// A claim filed by a policyholder against one of their policies.
+///
+/// `reason` is empty until an adjuster approves or rejects the claim.
+#[contracttype]
+#[derive(Clone)]
+pub struct Claim {
+    pub id: u32,
+    pub policy_id: u32,
+    pub claimant: Address,
+    pub amount: i128,
+    pub evidence_hash: BytesN<32>,
+    pub status: ClaimStatus,
+    pub reason: String,
+    pub adjuster: Option<Address>,
+    pub filed_at: u64,
+    pub decided_at: Option<u64>,
+    pub paid_at: Option<u64>,
+}
+
+/// A community mutual pool. Members contribute into the pool and claims are
+/// paid from its balance, up to `member_cap` per member and only after the
+/// member has been in the pool for `waiting_period_days`.
+#[contracttype]
+#[derive(Clone)]
+pub struct MutualPool {
+    pub id: u32,
+    pub name: String,
+    pub token: Address,
+    pub member_cap: i128,
+    pub waiting_period_days: u32,
+    pub balance: i128,
+    pub total_contributed: i128,
+    pub total_claimed: i128,
+    pub member_count: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PoolMember {
+    pub joined_at: u64,
+    pub contributed: i128,
+    pub claimed: i128,
+}
+
+/// A member's position in a pool, as read by the insurance report.
+#[contracttype]
+#[derive(Clone)]
+pub struct PoolSummary {
+    pub pool_id: u32,
+    pub contributed: i128,
+    pub claimed: i128,
+    pub solvency_bps: u32,
+}
+
+/// Claim activity for one owner over a period, as read by the insurance
+/// report. Decisions and payouts are counted in the period they happened in.
+#[contracttype]
+#[derive(Clone)]
+pub struct ClaimStats {
+    pub filed: u32,
+    pub approved: u32,
+    pub rejected: u32,
+    pub total_paid: i128,
+}
+
+/// Parametric payout terms attached to a policy. When the oracle-reported
+/// `index` crosses `threshold` (above it when `trigger_above`, otherwise
+/// below it) the policy pays `payout` of `token`, the policy's settlement
+/// token, to its owner, once.
+#[contracttype]
+#[derive(Clone)]
+pub struct ParametricTerms {
+    pub oracle: Address,
+    pub index: Symbol,
+    pub threshold: i128,
+    pub trigger_above: bool,
+    pub payout: i128,
+    pub token: Address,
+    pub triggered_at: Option<u64>,
+}
+
+/// A reading published by an index oracle.
+#[contracttype]
+#[derive(Clone)]
+pub struct IndexReading {
+    pub value: i128,
+    pub updated_at: u64,
+}
+
+// Client trait for oracles reporting parametric indices (rainfall, flight
+// delay, FX moves)
+
+#[contractclient(name = "IndexOracleClient")]
+pub trait IndexOracleTrait {
+    fn get_index(env: Env, index: Symbol) -> Option<IndexReading>;
+}
+
+// Client trait for checking covered lives against the family_wallet contract
+
+#[contractclient(name = "FamilyWalletClient")]
+pub trait FamilyWalletTrait {
+    fn get_family_member(env: Env, member: Address) -> Option<FamilyMember>;
+}
+
+// Data structures from the family_wallet contract (needed for the client trait)
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum FamilyRole {
+    Owner = 1,
+    Admin = 2,
+    Member = 3,
+    Viewer = 4,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FamilyMember {
+    pub address: Address,
+    pub role: FamilyRole,
+    pub spending_limit: i128,
+    pub added_at: u64,
+}
+
+/// A life covered by a group policy, with its own coverage amount.
+#[contracttype]
+#[derive(Clone)]
+pub struct CoveredMember {
+    pub member: Address,
+    pub coverage_amount: i128,
+    pub added_at: u64,
+}
+
+/// A policy covering several members of a family wallet under a single
+/// premium. The policy's `coverage_amount` is the sum of member coverage.
+#[contracttype]
+#[derive(Clone)]
+pub struct GroupPolicy {
+    pub policy_id: u32,
+    pub family_wallet: Address,
+    pub members: Vec<CoveredMember>,
+}
+
+/// One member's coverage under a group policy, as read by the insurance
+/// report.
+#[contracttype]
+#[derive(Clone)]
+pub struct MemberCoverage {
+    pub policy_id: u32,
+    pub owner: Address,
+    pub coverage_amount: i128,
+    /// Claim payouts the member has received under this policy
+    pub claimed: i128,
+}
+
+/// Hash of the policy document issued to the holder, anchored on-chain so
+/// the document can later be proven to match the policy record.
+/// `signature_ref` points to the issuer's signature over the document
+/// (for example a URI or a signature identifier).
+#[contracttype]
+#[derive(Clone)]
+pub struct PolicyDocument {
+    pub hash: BytesN<32>,
+    pub issuer: Address,
+    pub signature_ref: String,
+    pub anchored_at: u64,
+}
+
+/// One line of an insurer's rate table: the product it covers, the coverage
+/// range it will write, and the monthly premium as basis points of coverage.
+#[contracttype]
+#[derive(Clone)]
+pub struct InsurerProduct {
+    pub coverage_type: String,
+    pub min_coverage: i128,
+    pub max_coverage: i128,
+    pub rate_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Insurer {
+    pub address: Address,
+    pub name: String,
+    pub products: Vec<InsurerProduct>,
+    pub active: bool,
+}
+
+/// A premium quote from one approved insurer.
+#[contracttype]
+#[derive(Clone)]
+pub struct Quote {
+    pub insurer: Address,
+    pub insurer_name: String,
+    pub coverage_type: String,
+    pub coverage_amount: i128,
+    pub monthly_premium: i128,
+}
+
+/// A pending hand-over of a policy to a new owner. It completes once the
+/// new owner has accepted and the insurer has acknowledged it.
+#[contracttype]
+#[derive(Clone)]
+pub struct PolicyAssignment {
+    pub policy_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub accepted: bool,
+    pub acknowledged: bool,
+    pub created_at: u64,
+}
+
+/// Commission rates, in basis points of each premium collected, for
+/// policies an agent sells. `first_year_bps` applies during the policy's
+/// first term and `renewal_bps` after it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommissionSchedule {
+    pub first_year_bps: u32,
+    pub renewal_bps: u32,
+}
+
+/// A field agent registered to sell policies.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Agent {
+    pub address: Address,
+    pub schedule: CommissionSchedule,
+    /// Inactive agents cannot sell new policies but keep earning on old ones
+    pub active: bool,
+    pub policies_sold: u32,
+    /// Commission accrued over the agent's lifetime, including claimed
+    pub accrued: i128,
+    pub claimed: i128,
+}
+
+/// The agent who sold a policy.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyAgent {
+    pub agent: Address,
+    pub sold_at: u64,
+}
+
+/// Commission totals across all agents
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommissionTotals {
+    pub agents: u32,
+    pub policies_sold: u32,
+    pub accrued: i128,
+    pub claimed: i128,
+}
+
+/// The claims reserve held in one token against the coverage of the
+/// policies that settle in it. Premiums forwarded to the collector and
+/// mutual pool balances are not part of it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReserveStatus {
+    pub token: Address,
+    /// Claims and parametric payouts made from the reserve
+    pub claims_paid: i128,
+    /// Claims reserve balance the contract holds in `token`
+    pub reserves: i128,
+    /// Base and rider coverage of active policies settling in `token`
+    pub exposed_coverage: i128,
+    pub solvency_bps: u32,
+    /// Solvency below which new policies are refused; 0 when unchecked
+    pub min_solvency_bps: u32,
+}
+
+/// Where a policy stands. Policies deactivated by their owner are
+/// `Cancelled`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyStatus {
+    Active,
+    Lapsed,
+    Expired,
+    Cancelled,
+}
+
+/// Totals across an owner's policies in force
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageTotals {
+    pub active_policies: u32,
+    pub total_coverage: i128,
+    pub monthly_premium: i128,
+}
+
+/// An owner's premium and coverage in force from `recorded_at` until the
+/// next recorded level.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PremiumLevel {
+    pub active_policies: u32,
+    pub total_coverage: i128,
+    pub monthly_premium: i128,
+    pub recorded_at: u64,
 }
 
 /// Paginated result for insurance policy queries
@@ -84,6 +563,27 @@ pub struct PolicyPage {
     pub count: u32,
 }
 
+/// Maximum coverage for insured lives aged `min_age..=max_age` years.
+#[contracttype]
+#[derive(Clone)]
+pub struct AgeBand {
+    pub min_age: u32,
+    pub max_age: u32,
+    pub max_coverage: i128,
+}
+
+/// Operator-set constraints on a product, checked by `create_policy`.
+///
+/// With `age_bands` set, the owner must have recorded a date of birth and
+/// fall in a band whose `max_coverage` covers the policy. Claims on a new
+/// policy open once `waiting_period_days` have passed.
+#[contracttype]
+#[derive(Clone)]
+pub struct UnderwritingRule {
+    pub age_bands: Vec<AgeBand>,
+    pub waiting_period_days: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PremiumSchedule {
@@ -108,6 +608,46 @@ pub enum InsuranceError {
     PolicyInactive = 4,
     Unauthorized = 5,
     BatchTooLarge = 6,
+    CollectorNotSet = 7,
+    ClaimNotFound = 8,
+    InvalidClaimStatus = 9,
+    InvalidClaimAmount = 10,
+    PolicyNotLapsed = 11,
+    InvalidGracePeriod = 12,
+    InvalidBeneficiaries = 13,
+    RenewalNotOpen = 14,
+    PoolNotFound = 15,
+    AlreadyPoolMember = 16,
+    NotPoolMember = 17,
+    WaitingPeriodActive = 18,
+    MemberCapExceeded = 19,
+    PoolInsufficientFunds = 20,
+    NotParametric = 21,
+    AlreadyTriggered = 22,
+    StaleIndex = 23,
+    InsurerNotFound = 24,
+    NoQuote = 25,
+    AssignmentNotFound = 26,
+    InvalidAssignment = 27,
+    InvalidAmount = 28,
+    RiderExists = 29,
+    RiderNotFound = 30,
+    NotFamilyMember = 31,
+    InvalidGroupMembers = 32,
+    NotCovered = 33,
+    EscrowTokenMismatch = 34,
+    PolicyLimitReached = 35,
+    CoverageLimitExceeded = 36,
+    AgeUnknown = 37,
+    InvalidUnderwritingRule = 38,
+    InsufficientReserves = 39,
+    AgentNotFound = 40,
+    InvalidCommission = 41,
+    ArbiterNotSet = 42,
+    DisputeWindowClosed = 43,
+    DisputePending = 44,
+    PolicyTokenNotSet = 45,
+    PremiumTokenMismatch = 46,
 }
 
 impl From<InsuranceError> for soroban_sdk::Error {
@@ -137,75 +677,290 @@ impl From<InsuranceError> for soroban_sdk::Error {
                 soroban_sdk::xdr::ScErrorType::Contract,
                 soroban_sdk::xdr::ScErrorCode::InvalidInput,
             )),
-        }
-    }
-}
-
-impl From<&InsuranceError> for soroban_sdk::Error {
-    fn from(err: &InsuranceError) -> Self {
-        (*err).into()
-    }
-}
-
-impl From<soroban_sdk::Error> for InsuranceError {
-    fn from(_err: soroban_sdk::Error) -> Self {
-        InsuranceError::Unauthorized
-    }
-}
-
-#[contracttype]
-#[derive(Clone)]
-pub enum InsuranceEvent {
-    PolicyCreated,
-    PremiumPaid,
-    PolicyDeactivated,
-    ScheduleCreated,
-    ScheduleExecuted,
-    ScheduleMissed,
-    ScheduleModified,
-    ScheduleCancelled,
-}
-
-#[contract]
-pub struct Insurance;
-
-#[contractimpl]
-impl Insurance {
-    // -----------------------------------------------------------------------
-    // Internal helpers
-    // -----------------------------------------------------------------------
-
-    fn clamp_limit(limit: u32) -> u32 {
-        if limit == 0 {
-            DEFAULT_PAGE_LIMIT
-        } else if limit > MAX_PAGE_LIMIT {
-            MAX_PAGE_LIMIT
-        } else {
-            limit
-        }
-    }
-
-    fn get_pause_admin(env: &Env) -> Option<Address> {
-        env.storage().instance().get(&symbol_short!("PAUSE_ADM"))
-    }
-    fn get_global_paused(env: &Env) -> bool {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("PAUSED"))
-            .unwrap_or(false)
-    }
-    fn is_function_paused(env: &Env, func: Symbol) -> bool {
-        env.storage()
-            .instance()
-            .get::<_, Map<Symbol, bool>>(&symbol_short!("PAUSED_FN"))
-            .unwrap_or_else(|| Map::new(env))
-            .get(func)
-            .unwrap_or(false)
-    }
-    fn require_not_paused(env: &Env, func: Symbol) {
-        if Self::get_global_paused(env) {
-            panic!("Contract is paused");
-        }
+            InsuranceError::CollectorNotSet => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::ClaimNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::InvalidClaimStatus => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::InvalidClaimAmount => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::PolicyNotLapsed => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::InvalidGracePeriod => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::InvalidBeneficiaries => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::RenewalNotOpen => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::PoolNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::AlreadyPoolMember => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExistingValue,
+            )),
+            InsuranceError::NotPoolMember => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::WaitingPeriodActive => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::MemberCapExceeded => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+            )),
+            InsuranceError::PoolInsufficientFunds => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+            )),
+            InsuranceError::NotParametric => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::AlreadyTriggered => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::StaleIndex => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::InsurerNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::NoQuote => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::AssignmentNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::InvalidAssignment => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::InvalidAmount => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::RiderExists => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExistingValue,
+            )),
+            InsuranceError::RiderNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::NotFamilyMember => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::InvalidGroupMembers => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::NotCovered => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::EscrowTokenMismatch => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::PolicyLimitReached => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+            )),
+            InsuranceError::CoverageLimitExceeded => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+            )),
+            InsuranceError::AgeUnknown => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::InvalidUnderwritingRule => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::InsufficientReserves => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::ExceededLimit,
+            )),
+            InsuranceError::AgentNotFound => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::InvalidCommission => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            InsuranceError::ArbiterNotSet => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::DisputeWindowClosed => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::DisputePending => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            InsuranceError::PolicyTokenNotSet => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            InsuranceError::PremiumTokenMismatch => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+        }
+    }
+}
+
+impl From<&InsuranceError> for soroban_sdk::Error {
+    fn from(err: &InsuranceError) -> Self {
+        (*err).into()
+    }
+}
+
+impl From<soroban_sdk::Error> for InsuranceError {
+    fn from(_err: soroban_sdk::Error) -> Self {
+        InsuranceError::Unauthorized
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum InsuranceEvent {
+    PolicyCreated,
+    PremiumPaid,
+    PolicyDeactivated,
+    ScheduleCreated,
+    ScheduleExecuted,
+    ScheduleMissed,
+    ScheduleModified,
+    ScheduleCancelled,
+    ClaimFiled,
+    ClaimApproved,
+    ClaimRejected,
+    ClaimPaid,
+    PolicyLapsed,
+    PolicyReinstated,
+    PolicyExpired,
+    PolicyRenewed,
+    PoolCreated,
+    PoolJoined,
+    PoolContribution,
+    PoolClaimPaid,
+    ParametricPayout,
+    PolicyAssigned,
+    PremiumFunded,
+    RiderAdded,
+    RiderRemoved,
+    GroupMemberAdded,
+    GroupMemberRemoved,
+    PremiumPrepaid,
+    PremiumRefunded,
+    AgentRegistered,
+    CommissionAccrued,
+    CommissionClaimed,
+    ClaimEscalated,
+    DisputeRuled,
+    DisputeExpired,
+    ReserveFunded,
+    ReserveWithdrawn,
+    EscrowWithdrawn,
+}
+
+#[contract]
+pub struct Insurance;
+
+#[contractimpl]
+impl Insurance {
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn clamp_limit(limit: u32) -> u32 {
+        if limit == 0 {
+            DEFAULT_PAGE_LIMIT
+        } else if limit > MAX_PAGE_LIMIT {
+            MAX_PAGE_LIMIT
+        } else {
+            limit
+        }
+    }
+
+    fn emit_lifecycle(
+        env: &Env,
+        stage: PolicyLifecycle,
+        policy: &InsurancePolicy,
+        amount: i128,
+        claim_id: Option<u32>,
+    ) {
+        env.events().publish(
+            (
+                symbol_short!("policy"),
+                stage,
+                policy.owner.clone(),
+                policy.id,
+            ),
+            PolicyLifecycleEvent {
+                policy_id: policy.id,
+                owner: policy.owner.clone(),
+                amount,
+                claim_id,
+                next_payment_date: policy.next_payment_date,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn get_pause_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("PAUSE_ADM"))
+    }
+    fn get_global_paused(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PAUSED"))
+            .unwrap_or(false)
+    }
+    fn is_function_paused(env: &Env, func: Symbol) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Map<Symbol, bool>>(&symbol_short!("PAUSED_FN"))
+            .unwrap_or_else(|| Map::new(env))
+            .get(func)
+            .unwrap_or(false)
+    }
+    fn require_not_paused(env: &Env, func: Symbol) {
+        if Self::get_global_paused(env) {
+            panic!("Contract is paused");
+        }
         if Self::is_function_paused(env, func) {
             panic!("Function is paused");
         }
@@ -351,6 +1106,77 @@ impl Insurance {
         );
     }
 
+    /// Set the address that receives premiums paid through `pay_premium`.
+    /// Only the upgrade admin may change it.
+    pub fn set_premium_collector(env: Env, caller: Address, collector: Address) {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PRM_COLL"), &collector);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("collector")),
+            collector,
+        );
+    }
+
+    pub fn get_premium_collector(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("PRM_COLL"))
+    }
+
+    /// Set the token new policies settle claims in. Each policy keeps the
+    /// token that was set when it was issued. Only the upgrade admin may
+    /// change it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn set_settlement_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SETL_TKN"), &token);
+        env.events()
+            .publish((symbol_short!("insure"), symbol_short!("settle")), token);
+        Ok(())
+    }
+
+    pub fn get_settlement_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SETL_TKN"))
+    }
+
+    /// Get the token a policy settles premiums and claims in, fixed when it
+    /// was issued. `None` for policies issued before a settlement token was
+    /// set, whose premiums may be paid in any token.
+    pub fn get_policy_token(env: Env, policy_id: u32) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Address>>(&symbol_short!("POL_TKN"))
+            .and_then(|tokens| tokens.get(policy_id))
+    }
+
+    fn require_premium_token(
+        env: &Env,
+        policy_id: u32,
+        token: &Address,
+    ) -> Result<(), InsuranceError> {
+        match Self::get_policy_token(env.clone(), policy_id) {
+            Some(policy_token) if policy_token != *token => {
+                Err(InsuranceError::PremiumTokenMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Core policy operations (unchanged)
     // -----------------------------------------------------------------------
@@ -370,6 +1196,12 @@ impl Insurance {
     /// # Errors
     /// * `InvalidPremium` - If monthly_premium ≤ 0
     /// * `InvalidCoverage` - If coverage_amount ≤ 0
+    /// * `PolicyLimitReached` - If the owner holds the maximum number of active policies
+    /// * `AgeUnknown` - If the product has age bands and the owner has no date of birth
+    /// * `CoverageLimitExceeded` - If coverage_amount exceeds the owner's age band
+    /// * `InsufficientReserves` - If the solvency ratio in the settlement token is
+    ///   below the admin minimum
+    /// * `PolicyTokenNotSet` - If a minimum solvency is set but no settlement token
     ///
     /// # Panics
     /// * If `owner` does not authorize the transaction (implicit via `require_auth()`)
@@ -391,6 +1223,15 @@ impl Insurance {
         if coverage_amount <= 0 {
             return Err(InsuranceError::InvalidCoverage);
         }
+        let min_solvency = Self::get_min_solvency(env.clone());
+        if min_solvency > 0 {
+            let token =
+                Self::get_settlement_token(env.clone()).ok_or(InsuranceError::PolicyTokenNotSet)?;
+            if Self::get_solvency_ratio(env.clone(), token) < min_solvency {
+                return Err(InsuranceError::InsufficientReserves);
+            }
+        }
+        let waiting_period_days = Self::underwrite(&env, &owner, &coverage_type, coverage_amount)?;
 
         Self::extend_instance_ttl(&env);
 
@@ -408,6 +1249,7 @@ impl Insurance {
             + 1;
 
         let next_payment_date = env.ledger().timestamp() + (30 * 86400);
+        let term_end = env.ledger().timestamp() + POLICY_TERM_SECS;
 
         let policy = InsurancePolicy {
             id: next_id,
@@ -419,6 +1261,7 @@ impl Insurance {
             active: true,
             next_payment_date,
             schedule_id: None,
+            term_end,
         };
 
         let policy_owner = policy.owner.clone();
@@ -429,7 +1272,33 @@ impl Insurance {
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
+        if let Some(token) = Self::get_settlement_token(env.clone()) {
+            let mut tokens: Map<u32, Address> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("POL_TKN"))
+                .unwrap_or_else(|| Map::new(&env));
+            tokens.set(next_id, token);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("POL_TKN"), &tokens);
+        }
         Self::adjust_active_premium_total(&env, &owner, monthly_premium);
+        Self::record_premium_level(&env, &owner);
+        if waiting_period_days > 0 {
+            let mut waiting: Map<u32, u64> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("UW_WAIT"))
+                .unwrap_or_else(|| Map::new(&env));
+            waiting.set(
+                next_id,
+                env.ledger().timestamp() + waiting_period_days as u64 * 86400,
+            );
+            env.storage()
+                .instance()
+                .set(&symbol_short!("UW_WAIT"), &waiting);
+        }
 
         let event = PolicyCreatedEvent {
             policy_id: next_id,
@@ -444,15 +1313,25 @@ impl Insurance {
             (symbol_short!("insure"), InsuranceEvent::PolicyCreated),
             (next_id, policy_owner),
         );
+        if let Some(policy) = Self::get_policy(env.clone(), next_id) {
+            let premium = policy.monthly_premium;
+            Self::emit_lifecycle(&env, PolicyLifecycle::Issued, &policy, premium, None);
+        }
 
         Ok(next_id)
     }
 
-    /// Pays a premium for a specific policy.
+    /// Pays the monthly premium for a specific policy.
+    ///
+    /// Transfers `monthly_premium` of `token` from the caller to the premium
+    /// collector, advances `next_payment_date` by one 30-day period from the
+    /// date that was due, and appends a `PremiumPayment` to the owner's
+    /// premium history.
     ///
     /// # Arguments
     /// * `caller` - Address of the policy owner (must authorize)
     /// * `policy_id` - ID of the policy to pay premium for
+    /// * `token` - Token contract used to pay the premium
     ///
     /// # Returns
     /// `Ok(())` on successful premium payment
@@ -461,10 +1340,18 @@ impl Insurance {
     /// * `PolicyNotFound` - If policy_id does not exist
     /// * `Unauthorized` - If caller is not the policy owner
     /// * `PolicyInactive` - If the policy is not active
+    /// * `PremiumTokenMismatch` - If token is not the policy's settlement token
+    /// * `CollectorNotSet` - If no premium collector has been configured
     ///
     /// # Panics
     /// * If `caller` does not authorize the transaction
-    pub fn pay_premium(env: Env, caller: Address, policy_id: u32) -> Result<(), InsuranceError> {
+    /// * If the caller's token balance does not cover the premium
+    pub fn pay_premium(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        token: Address,
+    ) -> Result<(), InsuranceError> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::PAY_PREMIUM);
         Self::extend_instance_ttl(&env);
@@ -486,8 +1373,27 @@ impl Insurance {
         if !policy.active {
             return Err(InsuranceError::PolicyInactive);
         }
-
-        policy.next_payment_date = env.ledger().timestamp() + (30 * 86400);
+        Self::require_premium_token(&env, policy_id, &token)?;
+        let collector =
+            Self::get_premium_collector(env.clone()).ok_or(InsuranceError::CollectorNotSet)?;
+
+        token::Client::new(&env, &token).transfer(&caller, &collector, &policy.monthly_premium);
+        Self::premium_collected(&env, policy_id, policy.monthly_premium);
+
+        let now = env.ledger().timestamp();
+        let due_date = policy.next_payment_date;
+        policy.next_payment_date = due_date + (30 * 86400);
+        Self::append_premium_payment(
+            &env,
+            &caller,
+            PremiumPayment {
+                policy_id,
+                amount: policy.monthly_premium,
+                token,
+                due_date,
+                paid_at: now,
+            },
+        );
 
         let event = PremiumPaidEvent {
             policy_id,
@@ -497,6 +1403,8 @@ impl Insurance {
             timestamp: env.ledger().timestamp(),
         };
         env.events().publish((PREMIUM_PAID,), event);
+        let amount = policy.monthly_premium;
+        Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, amount, None);
 
         policies.set(policy_id, policy);
         env.storage()
@@ -561,6 +1469,8 @@ impl Insurance {
                 (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
                 (id, caller.clone()),
             );
+            let amount = policy.monthly_premium;
+            Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, amount, None);
             policies.set(id, policy);
             paid_count += 1;
         }
@@ -583,23 +1493,535 @@ impl Insurance {
         policies.get(policy_id)
     }
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES  (new in this version)
-    // -----------------------------------------------------------------------
-
-    /// Get a page of ACTIVE policies for `owner`.
-    ///
-    /// # Arguments
-    /// * `owner`  – whose policies to return
-    /// * `cursor` – start after this policy ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
-    ///
-    /// # Returns
-    /// `PolicyPage { items, next_cursor, count }`.
-    /// `next_cursor == 0` means no more pages.
-    pub fn get_active_policies(env: Env, owner: Address, cursor: u32, limit: u32) -> PolicyPage {
-        let limit = Self::clamp_limit(limit);
-        let policies: Map<u32, InsurancePolicy> = env
+    fn append_premium_payment(env: &Env, owner: &Address, payment: PremiumPayment) {
+        let key = (symbol_short!("PRM_HIST"), owner.clone());
+        let mut history: Vec<PremiumPayment> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        history.push_back(payment);
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().extend_ttl(
+            &key,
+            HISTORY_LIFETIME_THRESHOLD,
+            HISTORY_BUMP_AMOUNT,
+        );
+    }
+
+    /// Get the premiums `owner` paid through `pay_premium` whose due date
+    /// falls within `[period_start, period_end]`, oldest first.
+    pub fn get_premium_payments(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<PremiumPayment> {
+        let history: Vec<PremiumPayment> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("PRM_HIST"), owner))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut result = Vec::new(&env);
+        for payment in history.iter() {
+            if payment.due_date >= period_start && payment.due_date <= period_end {
+                result.push_back(payment);
+            }
+        }
+        result
+    }
+
+    /// Get the total premium `owner` paid for due dates within
+    /// `[period_start, period_end]`.
+    pub fn get_premiums_paid(env: Env, owner: Address, period_start: u64, period_end: u64) -> i128 {
+        let mut total = 0i128;
+        for payment in Self::get_premium_payments(env, owner, period_start, period_end).iter() {
+            total = total.saturating_add(payment.amount);
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // Premium auto-pay
+    // -----------------------------------------------------------------------
+
+    /// Deposit `amount` of `token` from `payer` for `owner`'s premiums, e.g.
+    /// the insurance leg of a remittance split. Together with any escrowed
+    /// balance the funds settle one installment of each active policy whose
+    /// settlement token is `token`, soonest-due first, stopping at the first
+    /// premium they cannot cover. Whatever is left stays in the owner's
+    /// premium escrow for the next deposit or a withdrawal. Nothing is paid
+    /// out while no collector is configured or premium payments are paused.
+    ///
+    /// # Returns
+    /// The owner's escrow balance after settlement
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn deposit_premium_funds(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, InsuranceError> {
+        payer.require_auth();
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&payer, &contract, &amount);
+
+        let mut available =
+            Self::get_premium_escrow(env.clone(), owner.clone(), token.clone()) + amount;
+        let paused = Self::get_global_paused(&env)
+            || Self::is_function_paused(&env, pause_functions::PAY_PREMIUM);
+        if let (false, Some(collector)) = (paused, Self::get_premium_collector(env.clone())) {
+            let mut policies: Map<u32, InsurancePolicy> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("POLICIES"))
+                .unwrap_or_else(|| Map::new(&env));
+            let policy_tokens: Map<u32, Address> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("POL_TKN"))
+                .unwrap_or_else(|| Map::new(&env));
+            let mut pending = Vec::new(&env);
+            for (id, policy) in policies.iter() {
+                if policy.active
+                    && policy.owner == owner
+                    && policy_tokens.get(id).as_ref() == Some(&token)
+                {
+                    pending.push_back(id);
+                }
+            }
+
+            let now = env.ledger().timestamp();
+            while !pending.is_empty() {
+                let mut soonest = 0u32;
+                for i in 1..pending.len() {
+                    let due = policies
+                        .get(pending.get(i).unwrap())
+                        .unwrap()
+                        .next_payment_date;
+                    let best = policies
+                        .get(pending.get(soonest).unwrap())
+                        .unwrap()
+                        .next_payment_date;
+                    if due < best {
+                        soonest = i;
+                    }
+                }
+                let policy_id = pending.get(soonest).unwrap();
+                pending.remove(soonest);
+                let mut policy = policies.get(policy_id).unwrap();
+                if policy.monthly_premium > available {
+                    break;
+                }
+
+                token_client.transfer(&contract, &collector, &policy.monthly_premium);
+                Self::premium_collected(&env, policy_id, policy.monthly_premium);
+                available -= policy.monthly_premium;
+                let due_date = policy.next_payment_date;
+                policy.next_payment_date = due_date + (30 * 86400);
+                Self::append_premium_payment(
+                    &env,
+                    &owner,
+                    PremiumPayment {
+                        policy_id,
+                        amount: policy.monthly_premium,
+                        token: token.clone(),
+                        due_date,
+                        paid_at: now,
+                    },
+                );
+                env.events().publish(
+                    (PREMIUM_PAID,),
+                    PremiumPaidEvent {
+                        policy_id,
+                        name: policy.name.clone(),
+                        amount: policy.monthly_premium,
+                        next_payment_date: policy.next_payment_date,
+                        timestamp: now,
+                    },
+                );
+                env.events().publish(
+                    (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
+                    (policy_id, owner.clone()),
+                );
+                let amount = policy.monthly_premium;
+                Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, amount, None);
+                policies.set(policy_id, policy);
+            }
+            env.storage()
+                .instance()
+                .set(&symbol_short!("POLICIES"), &policies);
+        }
+
+        Self::set_premium_escrow(&env, &owner, &token, available);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PremiumFunded),
+            (owner, payer, token, amount, available),
+        );
+        Ok(available)
+    }
+
+    fn set_premium_escrow(env: &Env, owner: &Address, token: &Address, balance: i128) {
+        let mut escrow: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PRM_ESC"))
+            .unwrap_or_else(|| Map::new(env));
+        escrow.set((owner.clone(), token.clone()), balance);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PRM_ESC"), &escrow);
+    }
+
+    /// Get `owner`'s unspent premium escrow balance in `token`.
+    pub fn get_premium_escrow(env: Env, owner: Address, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<(Address, Address), i128>>(&symbol_short!("PRM_ESC"))
+            .and_then(|escrow| escrow.get((owner, token)))
+            .unwrap_or(0)
+    }
+
+    /// Withdraw `amount` of `owner`'s unspent premium escrow in `token` back
+    /// to the owner.
+    ///
+    /// # Returns
+    /// The owner's escrow balance after the withdrawal
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive or exceeds the escrow balance
+    pub fn withdraw_premium_escrow(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, InsuranceError> {
+        owner.require_auth();
+        let balance = Self::get_premium_escrow(env.clone(), owner.clone(), token.clone());
+        if amount <= 0 || amount > balance {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let remaining = balance - amount;
+        Self::set_premium_escrow(&env, &owner, &token, remaining);
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &owner, &amount);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::EscrowWithdrawn),
+            (owner, token, amount, remaining),
+        );
+        Ok(remaining)
+    }
+
+    // -----------------------------------------------------------------------
+    // Premium prepayment
+    // -----------------------------------------------------------------------
+
+    fn load_prepaid(env: &Env) -> Map<u32, PrepaidPremium> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PREPAID"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_prepaid(env: &Env, prepaid: &Map<u32, PrepaidPremium>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PREPAID"), prepaid);
+    }
+
+    /// Move the part of `escrow` earned by `now` out of its balance and
+    /// return it.
+    fn take_earned(escrow: &mut PrepaidPremium, now: u64) -> i128 {
+        if now <= escrow.earned_from || escrow.balance == 0 {
+            return 0;
+        }
+        let earned = if now >= escrow.paid_through {
+            escrow.balance
+        } else {
+            escrow.balance * (now - escrow.earned_from) as i128
+                / (escrow.paid_through - escrow.earned_from) as i128
+        };
+        escrow.balance -= earned;
+        escrow.earned_from = now.min(escrow.paid_through);
+        earned
+    }
+
+    /// Prepay the next `periods` premium installments of a policy into
+    /// escrow. Each installment is recorded as paid for its due date, and
+    /// the escrowed funds reach the premium collector as the covered time
+    /// passes. Prepaying again extends the escrow, which must use the same
+    /// token while it holds a balance.
+    ///
+    /// # Returns
+    /// The policy's escrow balance after the prepayment
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If periods is 0 or above `MAX_PREPAID_PERIODS`
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `PremiumTokenMismatch` - If token is not the policy's settlement token
+    /// * `CollectorNotSet` - If no premium collector has been configured
+    /// * `EscrowTokenMismatch` - If the escrow holds a balance in another token
+    pub fn prepay_premium(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        token: Address,
+        periods: u32,
+    ) -> Result<i128, InsuranceError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_PREMIUM);
+        if periods == 0 || periods > MAX_PREPAID_PERIODS {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        Self::require_premium_token(&env, policy_id, &token)?;
+        let collector =
+            Self::get_premium_collector(env.clone()).ok_or(InsuranceError::CollectorNotSet)?;
+
+        let mut prepaid = Self::load_prepaid(&env);
+        let now = env.ledger().timestamp();
+        let mut escrow = match prepaid.get(policy_id) {
+            Some(existing) if existing.balance > 0 && existing.token != token => {
+                return Err(InsuranceError::EscrowTokenMismatch);
+            }
+            Some(existing) if existing.balance > 0 => existing,
+            _ => PrepaidPremium {
+                policy_id,
+                token: token.clone(),
+                balance: 0,
+                earned_from: policy.next_payment_date,
+                paid_through: policy.next_payment_date,
+            },
+        };
+        Self::extend_instance_ttl(&env);
+
+        let token_client = token::Client::new(&env, &token);
+        let earned = Self::take_earned(&mut escrow, now);
+        if earned > 0 {
+            token_client.transfer(&env.current_contract_address(), &collector, &earned);
+            Self::premium_collected(&env, policy_id, earned);
+        }
+        let amount = policy.monthly_premium * periods as i128;
+        token_client.transfer(&caller, &env.current_contract_address(), &amount);
+
+        for _ in 0..periods {
+            let due_date = policy.next_payment_date;
+            policy.next_payment_date = due_date + (30 * 86400);
+            Self::append_premium_payment(
+                &env,
+                &caller,
+                PremiumPayment {
+                    policy_id,
+                    amount: policy.monthly_premium,
+                    token: token.clone(),
+                    due_date,
+                    paid_at: now,
+                },
+            );
+        }
+        escrow.balance += amount;
+        escrow.paid_through = policy.next_payment_date;
+        let balance = escrow.balance;
+        prepaid.set(policy_id, escrow);
+        Self::save_prepaid(&env, &prepaid);
+
+        env.events().publish(
+            (PREMIUM_PAID,),
+            PremiumPaidEvent {
+                policy_id,
+                name: policy.name.clone(),
+                amount,
+                next_payment_date: policy.next_payment_date,
+                timestamp: now,
+            },
+        );
+        Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, amount, None);
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PremiumPrepaid),
+            (policy_id, caller, amount, periods),
+        );
+        Ok(balance)
+    }
+
+    /// Release the part of a policy's prepaid premium earned so far to the
+    /// premium collector. Anyone may call this.
+    ///
+    /// # Returns
+    /// The amount released
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If the policy has no prepaid escrow
+    /// * `CollectorNotSet` - If no premium collector has been configured
+    pub fn release_earned_premium(env: Env, policy_id: u32) -> Result<i128, InsuranceError> {
+        let mut prepaid = Self::load_prepaid(&env);
+        let mut escrow = prepaid
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        let collector =
+            Self::get_premium_collector(env.clone()).ok_or(InsuranceError::CollectorNotSet)?;
+        Self::extend_instance_ttl(&env);
+
+        let earned = Self::take_earned(&mut escrow, env.ledger().timestamp());
+        if earned > 0 {
+            token::Client::new(&env, &escrow.token).transfer(
+                &env.current_contract_address(),
+                &collector,
+                &earned,
+            );
+            Self::premium_collected(&env, policy_id, earned);
+        }
+        if escrow.balance == 0 {
+            prepaid.remove(policy_id);
+        } else {
+            prepaid.set(policy_id, escrow);
+        }
+        Self::save_prepaid(&env, &prepaid);
+        Ok(earned)
+    }
+
+    /// Close a cancelled policy's escrow: the earned part goes to the
+    /// premium collector and the unearned part back to `owner`.
+    fn refund_prepaid(env: &Env, policy_id: u32, owner: &Address) {
+        let mut prepaid = Self::load_prepaid(env);
+        let mut escrow = match prepaid.get(policy_id) {
+            Some(escrow) => escrow,
+            None => return,
+        };
+        prepaid.remove(policy_id);
+        Self::save_prepaid(env, &prepaid);
+
+        let now = env.ledger().timestamp();
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(env, &escrow.token);
+        let earned = Self::take_earned(&mut escrow, now);
+        if earned > 0 {
+            // The collector was required to prepay and cannot be unset
+            if let Some(collector) = Self::get_premium_collector(env.clone()) {
+                token_client.transfer(&contract, &collector, &earned);
+                Self::premium_collected(env, policy_id, earned);
+            }
+        }
+        if escrow.balance == 0 {
+            return;
+        }
+        token_client.transfer(&contract, owner, &escrow.balance);
+
+        let key = (symbol_short!("PRM_RFND"), owner.clone());
+        let mut refunds: Vec<PremiumRefund> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        refunds.push_back(PremiumRefund {
+            policy_id,
+            amount: escrow.balance,
+            token: escrow.token.clone(),
+            refunded_at: now,
+        });
+        env.storage().persistent().set(&key, &refunds);
+        env.storage().persistent().extend_ttl(
+            &key,
+            HISTORY_LIFETIME_THRESHOLD,
+            HISTORY_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PremiumRefunded),
+            (policy_id, owner.clone(), escrow.balance),
+        );
+    }
+
+    pub fn get_prepaid_premium(env: Env, policy_id: u32) -> Option<PrepaidPremium> {
+        Self::load_prepaid(&env).get(policy_id)
+    }
+
+    /// Get the premium refunds `owner` received within
+    /// `[period_start, period_end]`, oldest first.
+    pub fn get_premium_refunds(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<PremiumRefund> {
+        let refunds: Vec<PremiumRefund> = env
+            .storage()
+            .persistent()
+            .get(&(symbol_short!("PRM_RFND"), owner))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut result = Vec::new(&env);
+        for refund in refunds.iter() {
+            if refund.refunded_at >= period_start && refund.refunded_at <= period_end {
+                result.push_back(refund);
+            }
+        }
+        result
+    }
+
+    /// Get the total premium refunded to `owner` within
+    /// `[period_start, period_end]`.
+    pub fn get_premiums_refunded(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> i128 {
+        let mut total = 0i128;
+        for refund in Self::get_premium_refunds(env, owner, period_start, period_end).iter() {
+            total = total.saturating_add(refund.amount);
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // PAGINATED LIST QUERIES  (new in this version)
+    // -----------------------------------------------------------------------
+
+    /// Get a page of ACTIVE policies for `owner`.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose policies to return
+    /// * `cursor` – start after this policy ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `PolicyPage { items, next_cursor, count }`.
+    /// `next_cursor == 0` means no more pages.
+    pub fn get_active_policies(env: Env, owner: Address, cursor: u32, limit: u32) -> PolicyPage {
+        let limit = Self::clamp_limit(limit);
+        let policies: Map<u32, InsurancePolicy> = env
             .storage()
             .instance()
             .get(&symbol_short!("POLICIES"))
@@ -681,42 +2103,220 @@ impl Insurance {
         }
     }
 
-    pub fn get_total_monthly_premium(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_active_premium_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
+    fn policy_status(
+        policy: &InsurancePolicy,
+        lapsed: &Map<u32, u64>,
+        expired: &Map<u32, u64>,
+    ) -> PolicyStatus {
+        if policy.active {
+            PolicyStatus::Active
+        } else if lapsed.contains_key(policy.id) {
+            PolicyStatus::Lapsed
+        } else if expired.contains_key(policy.id) {
+            PolicyStatus::Expired
+        } else {
+            PolicyStatus::Cancelled
         }
+    }
 
-        let mut total = 0i128;
+    pub fn get_policy_status(env: Env, policy_id: u32) -> Option<PolicyStatus> {
+        let policy = Self::get_policy(env.clone(), policy_id)?;
+        Some(Self::policy_status(
+            &policy,
+            &Self::load_lapsed(&env),
+            &Self::load_expired(&env),
+        ))
+    }
+
+    /// Get a page of `owner`'s policies, optionally only those of one
+    /// `coverage_type` and/or `status`.
+    ///
+    /// Same cursor/limit semantics as `get_active_policies`.
+    pub fn query_policies(
+        env: Env,
+        owner: Address,
+        coverage_type: Option<String>,
+        status: Option<PolicyStatus>,
+        cursor: u32,
+        limit: u32,
+    ) -> PolicyPage {
+        let limit = Self::clamp_limit(limit);
         let policies: Map<u32, InsurancePolicy> = env
             .storage()
             .instance()
             .get(&symbol_short!("POLICIES"))
             .unwrap_or_else(|| Map::new(&env));
-        for (_, policy) in policies.iter() {
-            if policy.active && policy.owner == owner {
-                total += policy.monthly_premium;
+        let lapsed = Self::load_lapsed(&env);
+        let expired = Self::load_expired(&env);
+
+        let mut result = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let mut collected: u32 = 0;
+
+        for (id, policy) in policies.iter() {
+            if id <= cursor || policy.owner != owner {
+                continue;
+            }
+            if let Some(ref wanted) = coverage_type {
+                if policy.coverage_type != *wanted {
+                    continue;
+                }
+            }
+            if let Some(wanted) = status {
+                if Self::policy_status(&policy, &lapsed, &expired) != wanted {
+                    continue;
+                }
+            }
+            if collected == limit {
+                break;
             }
+            result.push_back(policy);
+            collected += 1;
+            next_cursor = id;
         }
-        total
-    }
 
-    pub fn deactivate_policy(env: Env, caller: Address, policy_id: u32) -> bool {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::DEACTIVATE);
-        Self::extend_instance_ttl(&env);
+        if collected < limit {
+            next_cursor = 0;
+        }
 
-        let mut policies: Map<u32, InsurancePolicy> = env
+        PolicyPage {
+            items: result,
+            next_cursor,
+            count: collected,
+        }
+    }
+
+    /// Get the totals of `owner`'s policies in force: active and within
+    /// their term. Coverage is the base coverage; see
+    /// `get_coverage_breakdown` for riders.
+    pub fn get_coverage_totals(env: Env, owner: Address) -> CoverageTotals {
+        let policies: Map<u32, InsurancePolicy> = env
             .storage()
             .instance()
             .get(&symbol_short!("POLICIES"))
             .unwrap_or_else(|| Map::new(&env));
-
-        let mut policy = policies.get(policy_id).expect("Policy not found");
-
-        if policy.owner != caller {
-            panic!("Only the policy owner can deactivate this policy");
+        let now = env.ledger().timestamp();
+        let mut totals = CoverageTotals {
+            active_policies: 0,
+            total_coverage: 0,
+            monthly_premium: 0,
+        };
+        for (_, policy) in policies.iter() {
+            if !policy.active || policy.owner != owner || policy.term_end <= now {
+                continue;
+            }
+            totals.active_policies += 1;
+            totals.total_coverage = totals.total_coverage.saturating_add(policy.coverage_amount);
+            totals.monthly_premium = totals
+                .monthly_premium
+                .saturating_add(policy.monthly_premium);
+        }
+        totals
+    }
+
+    /// Append `owner`'s current coverage totals to their premium level
+    /// history when they differ from the last recorded level. Call after
+    /// the policy change has been saved.
+    fn record_premium_level(env: &Env, owner: &Address) {
+        let totals = Self::get_coverage_totals(env.clone(), owner.clone());
+        let now = env.ledger().timestamp();
+        let mut all_levels: Map<Address, Vec<PremiumLevel>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PRM_LVL"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut levels = all_levels
+            .get(owner.clone())
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(last) = levels.last() {
+            if last.active_policies == totals.active_policies
+                && last.total_coverage == totals.total_coverage
+                && last.monthly_premium == totals.monthly_premium
+            {
+                return;
+            }
+            // Several changes in one ledger keep only the final level
+            if last.recorded_at == now {
+                levels.pop_back();
+            }
+        }
+        levels.push_back(PremiumLevel {
+            active_policies: totals.active_policies,
+            total_coverage: totals.total_coverage,
+            monthly_premium: totals.monthly_premium,
+            recorded_at: now,
+        });
+        all_levels.set(owner.clone(), levels);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PRM_LVL"), &all_levels);
+    }
+
+    /// Get every change in `owner`'s monthly premium and coverage in force,
+    /// oldest first.
+    pub fn get_premium_levels(env: Env, owner: Address) -> Vec<PremiumLevel> {
+        let levels: Map<Address, Vec<PremiumLevel>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PRM_LVL"))
+            .unwrap_or_else(|| Map::new(&env));
+        levels.get(owner).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get `owner`'s monthly premium and coverage in force at `timestamp`,
+    /// or a zero level recorded at 0 if nothing was in force yet.
+    pub fn get_premium_level_at(env: Env, owner: Address, timestamp: u64) -> PremiumLevel {
+        let mut level = PremiumLevel {
+            active_policies: 0,
+            total_coverage: 0,
+            monthly_premium: 0,
+            recorded_at: 0,
+        };
+        for entry in Self::get_premium_levels(env, owner).iter() {
+            if entry.recorded_at > timestamp {
+                break;
+            }
+            level = entry;
+        }
+        level
+    }
+
+    pub fn get_total_monthly_premium(env: Env, owner: Address) -> i128 {
+        if let Some(totals) = Self::get_active_premium_totals_map(&env) {
+            if let Some(total) = totals.get(owner.clone()) {
+                return total;
+            }
+        }
+
+        let mut total = 0i128;
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        for (_, policy) in policies.iter() {
+            if policy.active && policy.owner == owner {
+                total += policy.monthly_premium;
+            }
+        }
+        total
+    }
+
+    pub fn deactivate_policy(env: Env, caller: Address, policy_id: u32) -> bool {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::DEACTIVATE);
+        Self::extend_instance_ttl(&env);
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies.get(policy_id).expect("Policy not found");
+
+        if policy.owner != caller {
+            panic!("Only the policy owner can deactivate this policy");
         }
 
         let was_active = policy.active;
@@ -729,6 +2329,19 @@ impl Insurance {
 
         if was_active {
             Self::adjust_active_premium_total(&env, &caller, -premium_amount);
+            Self::record_premium_level(&env, &caller);
+        }
+        Self::refund_prepaid(&env, policy_id, &caller);
+        // A deactivated policy can no longer be reinstated or renewed
+        let mut lapsed = Self::load_lapsed(&env);
+        if lapsed.contains_key(policy_id) {
+            lapsed.remove(policy_id);
+            Self::save_lapsed(&env, &lapsed);
+        }
+        let mut expired = Self::load_expired(&env);
+        if expired.contains_key(policy_id) {
+            expired.remove(policy_id);
+            Self::save_expired(&env, &expired);
         }
         let event = PolicyDeactivatedEvent {
             policy_id,
@@ -961,6 +2574,8 @@ impl Insurance {
                 if policy.active {
                     policy.next_payment_date = current_time + (30 * 86400);
                     policies.set(schedule.policy_id, policy.clone());
+                    let amount = policy.monthly_premium;
+                    Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, amount, None);
                     env.events().publish(
                         (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
                         (schedule.policy_id, policy.owner),
@@ -1032,260 +2647,5272 @@ impl Insurance {
             .unwrap_or_else(|| Map::new(&env));
         schedules.get(schedule_id)
     }
-}
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::storage::Instance as _;
-    use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
-    use soroban_sdk::{Env, String};
+    // -----------------------------------------------------------------------
+    // Grace periods
+    // -----------------------------------------------------------------------
 
-    fn make_env() -> Env {
-        Env::default()
+    /// Set the grace period, in days, for every policy of a product
+    /// (`coverage_type`). Only the upgrade admin may set it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidGracePeriod` - If days exceeds `MAX_GRACE_DAYS`
+    pub fn set_product_grace_period(
+        env: Env,
+        caller: Address,
+        coverage_type: String,
+        days: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if days > MAX_GRACE_DAYS {
+            return Err(InsuranceError::InvalidGracePeriod);
+        }
+        let mut grace: Map<String, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GRACE_PRD"))
+            .unwrap_or_else(|| Map::new(&env));
+        grace.set(coverage_type.clone(), days);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GRACE_PRD"), &grace);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("grace")),
+            (coverage_type, days),
+        );
+        Ok(())
+    }
+
+    /// Override the grace period, in days, for one policy. Pass `None` to
+    /// fall back to the product grace period. Only the upgrade admin may set
+    /// it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `InvalidGracePeriod` - If days exceeds `MAX_GRACE_DAYS`
+    pub fn set_policy_grace_period(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        days: Option<u32>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if Self::get_policy(env.clone(), policy_id).is_none() {
+            return Err(InsuranceError::PolicyNotFound);
+        }
+        let mut grace: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GRACE_POL"))
+            .unwrap_or_else(|| Map::new(&env));
+        match days {
+            Some(d) if d > MAX_GRACE_DAYS => return Err(InsuranceError::InvalidGracePeriod),
+            Some(d) => grace.set(policy_id, d),
+            None => {
+                grace.remove(policy_id);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GRACE_POL"), &grace);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("grace")),
+            (policy_id, days),
+        );
+        Ok(())
+    }
+
+    fn grace_days(env: &Env, policy: &InsurancePolicy) -> u32 {
+        let per_policy: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GRACE_POL"))
+            .unwrap_or_else(|| Map::new(env));
+        if let Some(days) = per_policy.get(policy.id) {
+            return days;
+        }
+        let per_product: Map<String, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GRACE_PRD"))
+            .unwrap_or_else(|| Map::new(env));
+        per_product
+            .get(policy.coverage_type.clone())
+            .unwrap_or(DEFAULT_GRACE_DAYS)
+    }
+
+    fn premium_overdue(env: &Env, policy: &InsurancePolicy, now: u64) -> bool {
+        let grace = Self::grace_days(env, policy) as u64 * 86400;
+        now > policy.next_payment_date.saturating_add(grace)
     }
 
-    fn setup_policies(
-        env: &Env,
-        client: &InsuranceClient,
-        owner: &Address,
-        count: u32,
-    ) -> Vec<u32> {
-        let mut ids = Vec::new(env);
-        for i in 0..count {
-            let id = client.create_policy(
-                owner,
-                &String::from_str(env, "Policy"),
-                &String::from_str(env, "health"),
-                &(50i128 * (i as i128 + 1)),
-                &(10000i128 * (i as i128 + 1)),
-            );
-            ids.push_back(id);
-        }
-        ids
+    /// Get the grace period, in days, that applies to a policy: its own
+    /// override, else its product's, else the default of 30 days.
+    pub fn get_grace_period(env: Env, policy_id: u32) -> Result<u32, InsuranceError> {
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        Ok(Self::grace_days(&env, &policy))
+    }
+
+    /// Get the IDs of `owner`'s active policies whose premium is unpaid past
+    /// their grace period.
+    pub fn get_overdue_policies(env: Env, owner: Address) -> Vec<u32> {
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let now = env.ledger().timestamp();
+        let mut result = Vec::new(&env);
+        for (id, policy) in policies.iter() {
+            if policy.active && policy.owner == owner && Self::premium_overdue(&env, &policy, now) {
+                result.push_back(id);
+            }
+        }
+        result
+    }
+
+    // -----------------------------------------------------------------------
+    // Underwriting rules
+    // -----------------------------------------------------------------------
+
+    fn load_underwriting(env: &Env) -> Map<String, UnderwritingRule> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("UW_RULES"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Set the underwriting rule for a product (`coverage_type`), replacing
+    /// any previous one. Only the upgrade admin may set it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidUnderwritingRule` - If an age band is inverted or its
+    ///   maximum coverage is not positive
+    pub fn set_underwriting_rule(
+        env: Env,
+        caller: Address,
+        coverage_type: String,
+        age_bands: Vec<AgeBand>,
+        waiting_period_days: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        for band in age_bands.iter() {
+            if band.min_age > band.max_age || band.max_coverage <= 0 {
+                return Err(InsuranceError::InvalidUnderwritingRule);
+            }
+        }
+        let mut rules = Self::load_underwriting(&env);
+        rules.set(
+            coverage_type.clone(),
+            UnderwritingRule {
+                age_bands,
+                waiting_period_days,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("UW_RULES"), &rules);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("uw_rule")),
+            (coverage_type, waiting_period_days),
+        );
+        Ok(())
+    }
+
+    /// Remove the underwriting rule for a product. Only the upgrade admin
+    /// may remove it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn remove_underwriting_rule(
+        env: Env,
+        caller: Address,
+        coverage_type: String,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut rules = Self::load_underwriting(&env);
+        rules.remove(coverage_type.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("UW_RULES"), &rules);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("uw_rule")),
+            (coverage_type, 0u32),
+        );
+        Ok(())
+    }
+
+    pub fn get_underwriting_rule(env: Env, coverage_type: String) -> Option<UnderwritingRule> {
+        Self::load_underwriting(&env).get(coverage_type)
+    }
+
+    /// Cap the number of active policies any one owner may hold. Pass 0 to
+    /// remove the cap. Only the upgrade admin may set it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn set_max_policies_per_user(
+        env: Env,
+        caller: Address,
+        max_policies: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("UW_MAXPOL"), &max_policies);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("uw_maxpol")),
+            max_policies,
+        );
+        Ok(())
+    }
+
+    /// Get the cap on active policies per owner; 0 means no cap.
+    pub fn get_max_policies_per_user(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("UW_MAXPOL"))
+            .unwrap_or(0)
+    }
+
+    /// Record the owner's date of birth (a Unix timestamp), used to place
+    /// them in a product's age bands.
+    ///
+    /// # Errors
+    /// * `InvalidUnderwritingRule` - If the date is in the future
+    pub fn set_date_of_birth(
+        env: Env,
+        owner: Address,
+        date_of_birth: u64,
+    ) -> Result<(), InsuranceError> {
+        owner.require_auth();
+        if date_of_birth > env.ledger().timestamp() {
+            return Err(InsuranceError::InvalidUnderwritingRule);
+        }
+        Self::extend_instance_ttl(&env);
+        let mut births: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("UW_DOB"))
+            .unwrap_or_else(|| Map::new(&env));
+        births.set(owner, date_of_birth);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("UW_DOB"), &births);
+        Ok(())
+    }
+
+    pub fn get_date_of_birth(env: Env, owner: Address) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, u64>>(&symbol_short!("UW_DOB"))
+            .and_then(|births| births.get(owner))
+    }
+
+    /// Check a new policy against the policy cap and its product's
+    /// underwriting rule. Returns the product's waiting period in days.
+    fn underwrite(
+        env: &Env,
+        owner: &Address,
+        coverage_type: &String,
+        coverage_amount: i128,
+    ) -> Result<u32, InsuranceError> {
+        let max_policies = Self::get_max_policies_per_user(env.clone());
+        if max_policies > 0 {
+            let policies: Map<u32, InsurancePolicy> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("POLICIES"))
+                .unwrap_or_else(|| Map::new(env));
+            let mut held = 0u32;
+            for (_, policy) in policies.iter() {
+                if policy.active && policy.owner == *owner {
+                    held += 1;
+                }
+            }
+            if held >= max_policies {
+                return Err(InsuranceError::PolicyLimitReached);
+            }
+        }
+
+        let rule = match Self::load_underwriting(env).get(coverage_type.clone()) {
+            Some(rule) => rule,
+            None => return Ok(0),
+        };
+        if !rule.age_bands.is_empty() {
+            let born = Self::get_date_of_birth(env.clone(), owner.clone())
+                .ok_or(InsuranceError::AgeUnknown)?;
+            let age = ((env.ledger().timestamp() - born) / (365 * 86400)) as u32;
+            let band = rule
+                .age_bands
+                .iter()
+                .find(|b| age >= b.min_age && age <= b.max_age)
+                .ok_or(InsuranceError::CoverageLimitExceeded)?;
+            if coverage_amount > band.max_coverage {
+                return Err(InsuranceError::CoverageLimitExceeded);
+            }
+        }
+        Ok(rule.waiting_period_days)
+    }
+
+    /// Get when claims open on a policy issued with a waiting period.
+    pub fn get_claims_open_at(env: Env, policy_id: u32) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, u64>>(&symbol_short!("UW_WAIT"))
+            .and_then(|waiting| waiting.get(policy_id))
+    }
+
+    // -----------------------------------------------------------------------
+    // Lapse and reinstatement
+    // -----------------------------------------------------------------------
+
+    fn load_lapsed(env: &Env) -> Map<u32, u64> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("LAPSED"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_lapsed(env: &Env, lapsed: &Map<u32, u64>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("LAPSED"), lapsed);
+    }
+
+    /// Mark every active policy whose premium is unpaid past its grace period
+    /// as lapsed, and expire every active policy past its `term_end`.
+    ///
+    /// Anyone may call this. Lapsed and expired policies are inactive, so they
+    /// drop out of `get_active_policies` and the owner's monthly premium total
+    /// until they are reinstated or renewed. An expiring policy whose owner
+    /// consented to auto-renewal is renewed for another term instead. A
+    /// policy past its due date but still within grace gets one
+    /// `PremiumMissed` lifecycle event per missed due date.
+    ///
+    /// # Returns
+    /// IDs of the policies lapsed by this call
+    pub fn sync_policy_status(env: Env) -> Vec<u32> {
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut lapsed = Self::load_lapsed(&env);
+        let mut expired = Self::load_expired(&env);
+        let auto_renew = Self::load_auto_renew(&env);
+        let mut missed: Map<u32, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("MISSED"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut missed_changed = false;
+        let mut newly_lapsed = Vec::new(&env);
+        let mut owners_changed: Vec<Address> = Vec::new(&env);
+        let mut changed = false;
+
+        for (id, mut policy) in policies.iter() {
+            if !policy.active {
+                continue;
+            }
+            if now >= policy.term_end {
+                changed = true;
+                if auto_renew.get(id).unwrap_or(false) {
+                    policy.term_end += POLICY_TERM_SECS;
+                    env.events().publish(
+                        (symbol_short!("insure"), InsuranceEvent::PolicyRenewed),
+                        (id, policy.term_end),
+                    );
+                } else {
+                    policy.active = false;
+                    Self::adjust_active_premium_total(&env, &policy.owner, -policy.monthly_premium);
+                    if !owners_changed.contains(&policy.owner) {
+                        owners_changed.push_back(policy.owner.clone());
+                    }
+                    expired.set(id, now);
+                    env.events().publish(
+                        (symbol_short!("insure"), InsuranceEvent::PolicyExpired),
+                        (id, policy.owner.clone()),
+                    );
+                }
+                policies.set(id, policy);
+                continue;
+            }
+            if !Self::premium_overdue(&env, &policy, now) {
+                // Announce each missed due date once, while grace runs
+                if now > policy.next_payment_date
+                    && missed.get(id) != Some(policy.next_payment_date)
+                {
+                    missed.set(id, policy.next_payment_date);
+                    missed_changed = true;
+                    Self::emit_lifecycle(&env, PolicyLifecycle::PremiumMissed, &policy, 0, None);
+                }
+                continue;
+            }
+            policy.active = false;
+            Self::adjust_active_premium_total(&env, &policy.owner, -policy.monthly_premium);
+            if !owners_changed.contains(&policy.owner) {
+                owners_changed.push_back(policy.owner.clone());
+            }
+            lapsed.set(id, now);
+            env.events().publish(
+                (symbol_short!("insure"), InsuranceEvent::PolicyLapsed),
+                (id, policy.owner.clone()),
+            );
+            Self::emit_lifecycle(&env, PolicyLifecycle::Lapsed, &policy, 0, None);
+            policies.set(id, policy);
+            newly_lapsed.push_back(id);
+            changed = true;
+        }
+
+        if changed {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("POLICIES"), &policies);
+            Self::save_lapsed(&env, &lapsed);
+            Self::save_expired(&env, &expired);
+        }
+        for owner in owners_changed.iter() {
+            Self::record_premium_level(&env, &owner);
+        }
+        if missed_changed {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("MISSED"), &missed);
+        }
+        newly_lapsed
+    }
+
+    /// Get the time a policy lapsed, or `None` if it is not lapsed.
+    pub fn get_lapsed_at(env: Env, policy_id: u32) -> Option<u64> {
+        Self::load_lapsed(&env).get(policy_id)
+    }
+
+    /// Get the back premiums owed to reinstate a lapsed policy now: one
+    /// monthly premium for every period due since the last paid one.
+    pub fn get_reinstatement_cost(env: Env, policy_id: u32) -> Result<i128, InsuranceError> {
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if !Self::load_lapsed(&env).contains_key(policy_id) {
+            return Err(InsuranceError::PolicyNotLapsed);
+        }
+        let periods = Self::periods_due(&policy, env.ledger().timestamp());
+        Ok(policy.monthly_premium * periods as i128)
+    }
+
+    fn periods_due(policy: &InsurancePolicy, now: u64) -> u64 {
+        if now < policy.next_payment_date {
+            return 0;
+        }
+        (now - policy.next_payment_date) / (30 * 86400) + 1
+    }
+
+    /// Reinstate a lapsed policy by settling its back premiums.
+    ///
+    /// Transfers one monthly premium of `token` to the premium collector for
+    /// every period due since the last paid one, records each in the premium
+    /// history, advances `next_payment_date` past now and reactivates the
+    /// policy.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyNotLapsed` - If the policy is not lapsed
+    /// * `PremiumTokenMismatch` - If token is not the policy's settlement token
+    /// * `CollectorNotSet` - If no premium collector has been configured
+    pub fn reinstate_policy(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        token: Address,
+    ) -> Result<i128, InsuranceError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::REINSTATE);
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut lapsed = Self::load_lapsed(&env);
+        if !lapsed.contains_key(policy_id) {
+            return Err(InsuranceError::PolicyNotLapsed);
+        }
+        Self::require_premium_token(&env, policy_id, &token)?;
+        let collector =
+            Self::get_premium_collector(env.clone()).ok_or(InsuranceError::CollectorNotSet)?;
+
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let periods = Self::periods_due(&policy, now);
+        let settled = policy.monthly_premium * periods as i128;
+        if settled > 0 {
+            token::Client::new(&env, &token).transfer(&caller, &collector, &settled);
+            Self::premium_collected(&env, policy_id, settled);
+        }
+        for _ in 0..periods {
+            Self::append_premium_payment(
+                &env,
+                &caller,
+                PremiumPayment {
+                    policy_id,
+                    amount: policy.monthly_premium,
+                    token: token.clone(),
+                    due_date: policy.next_payment_date,
+                    paid_at: now,
+                },
+            );
+            policy.next_payment_date += 30 * 86400;
+        }
+
+        policy.active = true;
+        Self::adjust_active_premium_total(&env, &caller, policy.monthly_premium);
+        Self::emit_lifecycle(&env, PolicyLifecycle::PremiumPaid, &policy, settled, None);
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::record_premium_level(&env, &caller);
+        lapsed.remove(policy_id);
+        Self::save_lapsed(&env, &lapsed);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PolicyReinstated),
+            (policy_id, caller, settled),
+        );
+
+        Ok(settled)
+    }
+
+    // -----------------------------------------------------------------------
+    // Terms and renewal
+    // -----------------------------------------------------------------------
+
+    fn load_expired(env: &Env) -> Map<u32, u64> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("EXPIRED"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_expired(env: &Env, expired: &Map<u32, u64>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("EXPIRED"), expired);
+    }
+
+    fn load_auto_renew(env: &Env) -> Map<u32, bool> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("AUTO_RNW"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Consent to (or withdraw consent from) automatic renewal. With consent,
+    /// `sync_policy_status` renews the policy for another term at expiry
+    /// instead of deactivating it.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    pub fn set_auto_renew(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        enabled: bool,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+        let mut auto_renew = Self::load_auto_renew(&env);
+        if enabled {
+            auto_renew.set(policy_id, true);
+        } else {
+            auto_renew.remove(policy_id);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("AUTO_RNW"), &auto_renew);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("auto_rnw")),
+            (policy_id, enabled),
+        );
+        Ok(())
+    }
+
+    pub fn get_auto_renew(env: Env, policy_id: u32) -> bool {
+        Self::load_auto_renew(&env).get(policy_id).unwrap_or(false)
+    }
+
+    /// Renew a policy for another term.
+    ///
+    /// An active policy can be renewed within 30 days of its `term_end`; the
+    /// new term starts where the old one ends. An expired policy can be
+    /// renewed at any time; it is reactivated with a term starting now.
+    ///
+    /// # Returns
+    /// `Ok(term_end)` - The new end of the coverage term
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `RenewalNotOpen` - If the policy is not expired and not within the
+    ///   renewal window, or is inactive for another reason
+    pub fn renew_policy(env: Env, caller: Address, policy_id: u32) -> Result<u64, InsuranceError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::RENEW);
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut expired = Self::load_expired(&env);
+        if expired.contains_key(policy_id) {
+            policy.active = true;
+            policy.term_end = now + POLICY_TERM_SECS;
+            if policy.next_payment_date < now {
+                policy.next_payment_date = now + (30 * 86400);
+            }
+            Self::adjust_active_premium_total(&env, &caller, policy.monthly_premium);
+            expired.remove(policy_id);
+            Self::save_expired(&env, &expired);
+        } else if policy.active && policy.term_end.saturating_sub(now) <= RENEWAL_WINDOW_SECS {
+            policy.term_end += POLICY_TERM_SECS;
+        } else {
+            return Err(InsuranceError::RenewalNotOpen);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let term_end = policy.term_end;
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::record_premium_level(&env, &caller);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PolicyRenewed),
+            (policy_id, term_end),
+        );
+
+        Ok(term_end)
+    }
+
+    /// Get the time a policy expired, or `None` if it is not expired.
+    pub fn get_expired_at(env: Env, policy_id: u32) -> Option<u64> {
+        Self::load_expired(&env).get(policy_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Claims
+    // -----------------------------------------------------------------------
+
+    fn load_claims(env: &Env) -> Map<u32, Claim> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CLAIMS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_claims(env: &Env, claims: &Map<u32, Claim>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CLAIMS"), claims);
+    }
+
+    fn require_adjuster(env: &Env, caller: &Address) -> Result<(), InsuranceError> {
+        let adjusters: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADJUSTERS"))
+            .unwrap_or_else(|| Map::new(env));
+        if adjusters.get(caller.clone()).unwrap_or(false) {
+            Ok(())
+        } else {
+            Err(InsuranceError::Unauthorized)
+        }
+    }
+
+    /// Grant or revoke the claims adjuster role. Only the upgrade admin may
+    /// manage adjusters.
+    pub fn set_claims_adjuster(env: Env, caller: Address, adjuster: Address, enabled: bool) {
+        caller.require_auth();
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        let mut adjusters: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADJUSTERS"))
+            .unwrap_or_else(|| Map::new(&env));
+        if enabled {
+            adjusters.set(adjuster.clone(), true);
+        } else {
+            adjusters.remove(adjuster.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ADJUSTERS"), &adjusters);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("adjuster")),
+            (adjuster, enabled),
+        );
+    }
+
+    pub fn is_claims_adjuster(env: Env, address: Address) -> bool {
+        Self::require_adjuster(&env, &address).is_ok()
+    }
+
+    /// File a claim against an active policy.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the policy owner (must authorize)
+    /// * `policy_id` - ID of the policy to claim against
+    /// * `amount` - Amount claimed (must be > 0 and ≤ the coverage amount)
+    /// * `evidence_hash` - Hash of the supporting evidence stored off-chain
+    ///
+    /// # Returns
+    /// `Ok(claim_id)` - The newly filed claim ID
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidClaimAmount` - If amount ≤ 0 or exceeds the coverage amount
+    /// * `WaitingPeriodActive` - If the product's waiting period has not passed
+    pub fn file_claim(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        amount: i128,
+        evidence_hash: BytesN<32>,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::FILE_CLAIM);
+
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        // Group policies are claimed by the covered member, up to their own
+        // coverage and only while they remain in the family wallet.
+        let coverage_amount = match Self::load_groups(&env).get(policy_id) {
+            Some(group) => {
+                let covered = group
+                    .members
+                    .iter()
+                    .find(|m| m.member == caller)
+                    .ok_or(InsuranceError::NotCovered)?;
+                FamilyWalletClient::new(&env, &group.family_wallet)
+                    .get_family_member(&caller)
+                    .ok_or(InsuranceError::NotFamilyMember)?;
+                covered.coverage_amount
+            }
+            None if policy.owner != caller => return Err(InsuranceError::Unauthorized),
+            None => policy.coverage_amount,
+        };
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if amount <= 0 || amount > coverage_amount {
+            return Err(InsuranceError::InvalidClaimAmount);
+        }
+        if let Some(open_at) = Self::get_claims_open_at(env.clone(), policy_id) {
+            if env.ledger().timestamp() < open_at {
+                return Err(InsuranceError::WaitingPeriodActive);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let claim_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_CLM"))
+            .unwrap_or(0u32)
+            + 1;
+        let claim = Claim {
+            id: claim_id,
+            policy_id,
+            claimant: caller.clone(),
+            amount,
+            evidence_hash,
+            status: ClaimStatus::Filed,
+            reason: String::from_str(&env, ""),
+            adjuster: None,
+            filed_at: env.ledger().timestamp(),
+            decided_at: None,
+            paid_at: None,
+        };
+
+        let mut claims = Self::load_claims(&env);
+        claims.set(claim_id, claim);
+        Self::save_claims(&env, &claims);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_CLM"), &claim_id);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ClaimFiled),
+            (claim_id, policy_id, caller, amount),
+        );
+        Self::emit_lifecycle(
+            &env,
+            PolicyLifecycle::ClaimFiled,
+            &policy,
+            amount,
+            Some(claim_id),
+        );
+
+        Ok(claim_id)
+    }
+
+    fn decide_claim(
+        env: &Env,
+        adjuster: Address,
+        claim_id: u32,
+        approved: bool,
+        reason: String,
+    ) -> Result<(), InsuranceError> {
+        adjuster.require_auth();
+        Self::require_adjuster(env, &adjuster)?;
+
+        let mut claims = Self::load_claims(env);
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Filed {
+            return Err(InsuranceError::InvalidClaimStatus);
+        }
+
+        Self::extend_instance_ttl(env);
+
+        claim.status = if approved {
+            ClaimStatus::Approved
+        } else {
+            ClaimStatus::Rejected
+        };
+        claim.reason = reason.clone();
+        claim.adjuster = Some(adjuster.clone());
+        claim.decided_at = Some(env.ledger().timestamp());
+        claims.set(claim_id, claim);
+        Self::save_claims(env, &claims);
+
+        let event = if approved {
+            InsuranceEvent::ClaimApproved
+        } else {
+            InsuranceEvent::ClaimRejected
+        };
+        env.events().publish(
+            (symbol_short!("insure"), event),
+            (claim_id, adjuster, reason),
+        );
+
+        Ok(())
+    }
+
+    /// Approve a filed claim. Only a claims adjuster may approve.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If adjuster does not hold the adjuster role
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `InvalidClaimStatus` - If the claim has already been decided
+    pub fn approve_claim(
+        env: Env,
+        adjuster: Address,
+        claim_id: u32,
+        reason: String,
+    ) -> Result<(), InsuranceError> {
+        Self::decide_claim(&env, adjuster, claim_id, true, reason)
+    }
+
+    /// Reject a filed claim with a reason. Only a claims adjuster may reject.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If adjuster does not hold the adjuster role
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `InvalidClaimStatus` - If the claim has already been decided
+    pub fn reject_claim(
+        env: Env,
+        adjuster: Address,
+        claim_id: u32,
+        reason: String,
+    ) -> Result<(), InsuranceError> {
+        Self::decide_claim(&env, adjuster, claim_id, false, reason)
+    }
+
+    /// Pay an approved claim to the policyholder.
+    ///
+    /// The payout is made in the policy's token and drawn from the claims
+    /// reserve funded for that token. Claims on life coverage go to the
+    /// policy's beneficiaries by share when any are designated.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If adjuster does not hold the adjuster role
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `InvalidClaimStatus` - If the claim is not approved
+    /// * `PolicyTokenNotSet` - If the policy was issued without a settlement token
+    /// * `InsufficientReserves` - If the claims reserve does not cover the payout
+    pub fn pay_claim(env: Env, adjuster: Address, claim_id: u32) -> Result<(), InsuranceError> {
+        adjuster.require_auth();
+        Self::require_adjuster(&env, &adjuster)?;
+
+        let mut claims = Self::load_claims(&env);
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Approved {
+            return Err(InsuranceError::InvalidClaimStatus);
+        }
+        let token = Self::get_policy_token(env.clone(), claim.policy_id)
+            .ok_or(InsuranceError::PolicyTokenNotSet)?;
+        Self::debit_claims_reserve(&env, &token, claim.amount)?;
+
+        Self::extend_instance_ttl(&env);
+
+        let token_client = token::Client::new(&env, &token);
+        let reserve = env.current_contract_address();
+        let beneficiaries = Self::get_beneficiaries(env.clone(), claim.policy_id);
+        let is_life = Self::get_policy(env.clone(), claim.policy_id)
+            .map(|p| p.coverage_type == String::from_str(&env, "life"))
+            .unwrap_or(false);
+        if is_life && !beneficiaries.is_empty() {
+            // Pay each share; the last beneficiary receives the rounding
+            // remainder so the full amount is paid out
+            let mut remaining = claim.amount;
+            let last = beneficiaries.len() - 1;
+            for (i, (beneficiary, share_bps)) in beneficiaries.iter().enumerate() {
+                let share = if i as u32 == last {
+                    remaining
+                } else {
+                    claim.amount * share_bps as i128 / BPS_TOTAL as i128
+                };
+                remaining -= share;
+                if share > 0 {
+                    token_client.transfer(&reserve, &beneficiary, &share);
+                }
+            }
+        } else {
+            token_client.transfer(&reserve, &claim.claimant, &claim.amount);
+        }
+
+        Self::record_claim_paid(&env, &token, claim.amount);
+        claim.status = ClaimStatus::Paid;
+        claim.paid_at = Some(env.ledger().timestamp());
+        let claimant = claim.claimant.clone();
+        let amount = claim.amount;
+        let policy_id = claim.policy_id;
+        claims.set(claim_id, claim);
+        Self::save_claims(&env, &claims);
+        if let Some(policy) = Self::get_policy(env.clone(), policy_id) {
+            Self::emit_lifecycle(
+                &env,
+                PolicyLifecycle::ClaimSettled,
+                &policy,
+                amount,
+                Some(claim_id),
+            );
+        }
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ClaimPaid),
+            (claim_id, claimant, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Designate the arbiter who rules on disputed claims. Only the upgrade
+    /// admin may set it; pending disputes stay with the arbiter they were
+    /// escalated to.
+    pub fn set_claims_arbiter(
+        env: Env,
+        caller: Address,
+        arbiter: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARBITER"), &arbiter);
+        env.events()
+            .publish((symbol_short!("insure"), symbol_short!("arbiter")), arbiter);
+        Ok(())
+    }
+
+    pub fn get_claims_arbiter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("ARBITER"))
+    }
+
+    fn load_disputes(env: &Env) -> Map<u32, ClaimDispute> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("DISPUTES"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_disputes(env: &Env, disputes: &Map<u32, ClaimDispute>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("DISPUTES"), disputes);
+    }
+
+    /// Escalate a rejected claim to the claims arbiter. The claimant has
+    /// `DISPUTE_WINDOW_SECS` after the rejection, or after an earlier
+    /// dispute expired unruled, to escalate; the arbiter then has
+    /// `RULING_WINDOW_SECS` to rule.
+    ///
+    /// # Errors
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `Unauthorized` - If caller is not the claimant
+    /// * `InvalidClaimStatus` - If the claim is not rejected or was already ruled on
+    /// * `DisputeWindowClosed` - If the escalation window has passed
+    /// * `ArbiterNotSet` - If no arbiter has been designated
+    pub fn escalate_claim(
+        env: Env,
+        caller: Address,
+        claim_id: u32,
+        grounds: String,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let mut claims = Self::load_claims(&env);
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.claimant != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if claim.status != ClaimStatus::Rejected {
+            return Err(InsuranceError::InvalidClaimStatus);
+        }
+        let mut disputes = Self::load_disputes(&env);
+        let window_opens = match disputes.get(claim_id) {
+            None => claim.decided_at.unwrap_or(claim.filed_at),
+            Some(dispute) if dispute.status == DisputeStatus::Expired => {
+                dispute.closed_at.unwrap_or(dispute.ruling_due)
+            }
+            Some(_) => return Err(InsuranceError::InvalidClaimStatus),
+        };
+        let now = env.ledger().timestamp();
+        if now > window_opens.saturating_add(DISPUTE_WINDOW_SECS) {
+            return Err(InsuranceError::DisputeWindowClosed);
+        }
+        let arbiter = Self::get_claims_arbiter(env.clone()).ok_or(InsuranceError::ArbiterNotSet)?;
+        Self::extend_instance_ttl(&env);
+
+        let ruling_due = now + RULING_WINDOW_SECS;
+        disputes.set(
+            claim_id,
+            ClaimDispute {
+                claim_id,
+                arbiter: arbiter.clone(),
+                grounds,
+                status: DisputeStatus::Pending,
+                raised_at: now,
+                ruling_due,
+                closed_at: None,
+                payout: 0,
+            },
+        );
+        Self::save_disputes(&env, &disputes);
+        claim.status = ClaimStatus::Disputed;
+        claims.set(claim_id, claim);
+        Self::save_claims(&env, &claims);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ClaimEscalated),
+            (claim_id, caller, arbiter, ruling_due),
+        );
+        Ok(())
+    }
+
+    /// Rule on a disputed claim. The ruling is binding: upholding leaves the
+    /// claim rejected for good, while overturning (`overturn`) pays `payout`
+    /// to the claimant in the policy's token from its claims reserve and
+    /// marks the claim paid with that amount.
+    ///
+    /// # Errors
+    /// * `ClaimNotFound` - If the claim is not under dispute
+    /// * `Unauthorized` - If caller is not the dispute's arbiter
+    /// * `DisputeWindowClosed` - If the ruling deadline has passed
+    /// * `InvalidClaimAmount` - If an overturning payout is not positive or
+    ///   exceeds the amount claimed
+    /// * `PolicyTokenNotSet` - If an overturned claim's policy has no settlement token
+    /// * `InsufficientReserves` - If the claims reserve does not cover the payout
+    pub fn rule_on_dispute(
+        env: Env,
+        arbiter: Address,
+        claim_id: u32,
+        overturn: bool,
+        payout: i128,
+        reason: String,
+    ) -> Result<(), InsuranceError> {
+        arbiter.require_auth();
+        let mut claims = Self::load_claims(&env);
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        let mut disputes = Self::load_disputes(&env);
+        let mut dispute = match disputes.get(claim_id) {
+            Some(dispute) if claim.status == ClaimStatus::Disputed => dispute,
+            _ => return Err(InsuranceError::ClaimNotFound),
+        };
+        if dispute.arbiter != arbiter {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if now > dispute.ruling_due {
+            return Err(InsuranceError::DisputeWindowClosed);
+        }
+        if overturn && (payout <= 0 || payout > claim.amount) {
+            return Err(InsuranceError::InvalidClaimAmount);
+        }
+        let payout = if overturn { payout } else { 0 };
+        if overturn {
+            let token = Self::get_policy_token(env.clone(), claim.policy_id)
+                .ok_or(InsuranceError::PolicyTokenNotSet)?;
+            Self::debit_claims_reserve(&env, &token, payout)?;
+            token::Client::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &claim.claimant,
+                &payout,
+            );
+            Self::record_claim_paid(&env, &token, payout);
+        }
+        Self::extend_instance_ttl(&env);
+
+        claim.reason = reason.clone();
+        if overturn {
+            claim.status = ClaimStatus::Paid;
+            claim.amount = payout;
+            claim.paid_at = Some(now);
+            dispute.status = DisputeStatus::Overturned;
+            dispute.payout = payout;
+        } else {
+            claim.status = ClaimStatus::Rejected;
+            dispute.status = DisputeStatus::Upheld;
+        }
+        dispute.closed_at = Some(now);
+        let policy_id = claim.policy_id;
+        claims.set(claim_id, claim);
+        Self::save_claims(&env, &claims);
+        disputes.set(claim_id, dispute);
+        Self::save_disputes(&env, &disputes);
+        if overturn {
+            if let Some(policy) = Self::get_policy(env.clone(), policy_id) {
+                Self::emit_lifecycle(
+                    &env,
+                    PolicyLifecycle::ClaimSettled,
+                    &policy,
+                    payout,
+                    Some(claim_id),
+                );
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::DisputeRuled),
+            (claim_id, arbiter, overturn, payout, reason),
+        );
+        Ok(())
+    }
+
+    /// Return a dispute the arbiter did not rule on in time to the rejected
+    /// state, reopening the escalation window. Anyone may call this.
+    ///
+    /// # Errors
+    /// * `ClaimNotFound` - If the claim is not under dispute
+    /// * `DisputePending` - If the ruling deadline has not passed
+    pub fn expire_dispute(env: Env, claim_id: u32) -> Result<(), InsuranceError> {
+        let mut claims = Self::load_claims(&env);
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        let mut disputes = Self::load_disputes(&env);
+        let mut dispute = match disputes.get(claim_id) {
+            Some(dispute) if claim.status == ClaimStatus::Disputed => dispute,
+            _ => return Err(InsuranceError::ClaimNotFound),
+        };
+        let now = env.ledger().timestamp();
+        if now <= dispute.ruling_due {
+            return Err(InsuranceError::DisputePending);
+        }
+        Self::extend_instance_ttl(&env);
+
+        dispute.status = DisputeStatus::Expired;
+        dispute.closed_at = Some(now);
+        disputes.set(claim_id, dispute);
+        Self::save_disputes(&env, &disputes);
+        claim.status = ClaimStatus::Rejected;
+        claims.set(claim_id, claim);
+        Self::save_claims(&env, &claims);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::DisputeExpired),
+            claim_id,
+        );
+        Ok(())
+    }
+
+    pub fn get_claim_dispute(env: Env, claim_id: u32) -> Option<ClaimDispute> {
+        Self::load_disputes(&env).get(claim_id)
+    }
+
+    /// Designate who receives claim payouts on a life policy.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the policy owner (must authorize)
+    /// * `policy_id` - ID of the policy
+    /// * `beneficiaries` - `(address, share_bps)` pairs; shares must be
+    ///   positive and sum to 10,000 (100%). An empty list clears the
+    ///   designation so payouts go to the owner again.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `InvalidBeneficiaries` - If shares are zero, do not sum to 100%,
+    ///   repeat an address, or exceed `MAX_BENEFICIARIES` entries
+    pub fn set_beneficiaries(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        beneficiaries: Vec<(Address, u32)>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if beneficiaries.len() > MAX_BENEFICIARIES {
+            return Err(InsuranceError::InvalidBeneficiaries);
+        }
+        let mut total: u32 = 0;
+        let mut seen: Map<Address, bool> = Map::new(&env);
+        for (beneficiary, share_bps) in beneficiaries.iter() {
+            if share_bps == 0 || seen.contains_key(beneficiary.clone()) {
+                return Err(InsuranceError::InvalidBeneficiaries);
+            }
+            seen.set(beneficiary, true);
+            total = total.saturating_add(share_bps);
+        }
+        if !beneficiaries.is_empty() && total != BPS_TOTAL {
+            return Err(InsuranceError::InvalidBeneficiaries);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut all: Map<u32, Vec<(Address, u32)>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BENEFIC"))
+            .unwrap_or_else(|| Map::new(&env));
+        if beneficiaries.is_empty() {
+            all.remove(policy_id);
+        } else {
+            all.set(policy_id, beneficiaries.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BENEFIC"), &all);
+
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("benefic")),
+            (policy_id, beneficiaries.len()),
+        );
+        Ok(())
+    }
+
+    pub fn get_beneficiaries(env: Env, policy_id: u32) -> Vec<(Address, u32)> {
+        let all: Map<u32, Vec<(Address, u32)>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BENEFIC"))
+            .unwrap_or_else(|| Map::new(&env));
+        all.get(policy_id).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn get_claim(env: Env, claim_id: u32) -> Option<Claim> {
+        Self::load_claims(&env).get(claim_id)
+    }
+
+    /// Get every claim filed against `policy_id`, oldest first.
+    pub fn get_policy_claims(env: Env, policy_id: u32) -> Vec<Claim> {
+        let mut result = Vec::new(&env);
+        for (_, claim) in Self::load_claims(&env).iter() {
+            if claim.policy_id == policy_id {
+                result.push_back(claim);
+            }
+        }
+        result
+    }
+
+    /// Get the claims filed by `owner` that are in `status`.
+    pub fn get_claims_by_status(env: Env, owner: Address, status: ClaimStatus) -> Vec<Claim> {
+        let mut result = Vec::new(&env);
+        for (_, claim) in Self::load_claims(&env).iter() {
+            if claim.claimant == owner && claim.status == status {
+                result.push_back(claim);
+            }
+        }
+        result
+    }
+
+    /// Summarize `owner`'s claims filed, approved, rejected and paid within
+    /// `[period_start, period_end]`.
+    pub fn get_claim_stats(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> ClaimStats {
+        let in_period = |ts: u64| ts >= period_start && ts <= period_end;
+        let mut stats = ClaimStats {
+            filed: 0,
+            approved: 0,
+            rejected: 0,
+            total_paid: 0,
+        };
+        for (_, claim) in Self::load_claims(&env).iter() {
+            if claim.claimant != owner {
+                continue;
+            }
+            if in_period(claim.filed_at) {
+                stats.filed += 1;
+            }
+            if claim.decided_at.is_some_and(in_period) {
+                match claim.status {
+                    ClaimStatus::Rejected | ClaimStatus::Disputed => stats.rejected += 1,
+                    ClaimStatus::Approved | ClaimStatus::Paid => stats.approved += 1,
+                    ClaimStatus::Filed => {}
+                }
+            }
+            if claim.paid_at.is_some_and(in_period) {
+                stats.total_paid = stats.total_paid.saturating_add(claim.amount);
+            }
+        }
+        stats
+    }
+
+    // -----------------------------------------------------------------------
+    // Reserves and solvency
+    // -----------------------------------------------------------------------
+
+    fn load_claims_reserves(env: &Env) -> Map<Address, i128> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CLM_RSV"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_claims_reserves(env: &Env, reserves: &Map<Address, i128>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CLM_RSV"), reserves);
+    }
+
+    /// Take `amount` out of the claims reserve held in `token`, failing
+    /// when the reserve does not cover it.
+    fn debit_claims_reserve(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+    ) -> Result<(), InsuranceError> {
+        let mut reserves = Self::load_claims_reserves(env);
+        let balance = reserves.get(token.clone()).unwrap_or(0);
+        if amount > balance {
+            return Err(InsuranceError::InsufficientReserves);
+        }
+        reserves.set(token.clone(), balance - amount);
+        Self::save_claims_reserves(env, &reserves);
+        Ok(())
+    }
+
+    /// Fund the claims reserve for `token` with `amount` from `funder`.
+    /// Claims are only ever paid from this reserve, never from premium
+    /// escrows, prepaid premiums or mutual pool balances.
+    ///
+    /// # Returns
+    /// The reserve balance in `token` after funding
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    pub fn fund_claims_reserve(
+        env: Env,
+        funder: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, InsuranceError> {
+        funder.require_auth();
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let mut reserves = Self::load_claims_reserves(&env);
+        let balance = reserves.get(token.clone()).unwrap_or(0) + amount;
+        reserves.set(token.clone(), balance);
+        Self::save_claims_reserves(&env, &reserves);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ReserveFunded),
+            (funder, token, amount, balance),
+        );
+        Ok(balance)
+    }
+
+    /// Withdraw `amount` of the claims reserve held in `token` to `to`.
+    /// Only the upgrade admin may withdraw.
+    ///
+    /// # Returns
+    /// The reserve balance in `token` after the withdrawal
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InsufficientReserves` - If the reserve holds less than amount
+    pub fn withdraw_claims_reserve(
+        env: Env,
+        caller: Address,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<i128, InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::debit_claims_reserve(&env, &token, amount)?;
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+        let balance = Self::get_claims_reserve(env.clone(), token.clone());
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ReserveWithdrawn),
+            (token, to, amount, balance),
+        );
+        Ok(balance)
+    }
+
+    /// Get the claims reserve balance held in `token`.
+    pub fn get_claims_reserve(env: Env, token: Address) -> i128 {
+        Self::load_claims_reserves(&env).get(token).unwrap_or(0)
+    }
+
+    fn record_claim_paid(env: &Env, token: &Address, amount: i128) {
+        let mut paid: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CLM_PAID"))
+            .unwrap_or_else(|| Map::new(env));
+        let total = paid.get(token.clone()).unwrap_or(0);
+        paid.set(token.clone(), total.saturating_add(amount));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CLM_PAID"), &paid);
+    }
+
+    /// Book a premium that reached the collector against the selling
+    /// agent's commission.
+    fn premium_collected(env: &Env, policy_id: u32, amount: i128) {
+        Self::accrue_commission(env, policy_id, amount);
+    }
+
+    /// Coverage the book is exposed to in `token`: base and rider coverage
+    /// of every active policy that settles in it.
+    fn exposed_coverage(env: &Env, token: &Address) -> i128 {
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+        let policy_tokens: Map<u32, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POL_TKN"))
+            .unwrap_or_else(|| Map::new(env));
+        let riders = Self::load_riders(env);
+        let mut exposure = 0i128;
+        for (id, policy) in policies.iter() {
+            if !policy.active || policy_tokens.get(id).as_ref() != Some(token) {
+                continue;
+            }
+            exposure = exposure.saturating_add(policy.coverage_amount);
+            for rider in riders.get(id).unwrap_or_else(|| Vec::new(env)).iter() {
+                exposure = exposure.saturating_add(rider.coverage_amount);
+            }
+        }
+        exposure
+    }
+
+    /// Get the claims reserve held in `token` against the coverage of the
+    /// policies settling in it, with the resulting solvency ratio.
+    pub fn get_reserve_status(env: Env, token: Address) -> ReserveStatus {
+        let claims_paid = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, i128>>(&symbol_short!("CLM_PAID"))
+            .and_then(|paid| paid.get(token.clone()))
+            .unwrap_or(0);
+        let reserves = Self::get_claims_reserve(env.clone(), token.clone());
+        let exposed_coverage = Self::exposed_coverage(&env, &token);
+        let solvency_bps = if exposed_coverage == 0 {
+            BPS_TOTAL
+        } else {
+            (reserves * BPS_TOTAL as i128 / exposed_coverage).min(u32::MAX as i128) as u32
+        };
+        ReserveStatus {
+            token,
+            claims_paid,
+            reserves,
+            exposed_coverage,
+            solvency_bps,
+            min_solvency_bps: Self::get_min_solvency(env),
+        }
+    }
+
+    /// Get the solvency ratio in `token` in basis points: the claims reserve
+    /// over the coverage settling in it. 10,000 means every such policy
+    /// could pay out in full.
+    pub fn get_solvency_ratio(env: Env, token: Address) -> u32 {
+        Self::get_reserve_status(env, token).solvency_bps
+    }
+
+    /// Set the solvency ratio, in basis points, below which no new policies
+    /// are issued. New policies are checked against the settlement token's
+    /// reserve. Pass 0 to stop checking. Only the upgrade admin may set it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn set_min_solvency(env: Env, caller: Address, min_bps: u32) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("RSV_MIN"), &min_bps);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("min_solv")),
+            min_bps,
+        );
+        Ok(())
+    }
+
+    pub fn get_min_solvency(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("RSV_MIN"))
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Agent commissions
+    // -----------------------------------------------------------------------
+
+    fn load_agents(env: &Env) -> Map<Address, Agent> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("AGENTS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_agents(env: &Env, agents: &Map<Address, Agent>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("AGENTS"), agents);
+    }
+
+    fn load_policy_agents(env: &Env) -> Map<u32, PolicyAgent> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("POL_AGENT"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Accrue the commission on a premium collected for an agent-sold
+    /// policy, at the first-year rate during the policy's first term.
+    fn accrue_commission(env: &Env, policy_id: u32, premium: i128) {
+        let attribution = match Self::load_policy_agents(env).get(policy_id) {
+            Some(attribution) => attribution,
+            None => return,
+        };
+        let mut agents = Self::load_agents(env);
+        let mut agent = match agents.get(attribution.agent.clone()) {
+            Some(agent) => agent,
+            None => return,
+        };
+        let first_year =
+            env.ledger().timestamp() < attribution.sold_at.saturating_add(POLICY_TERM_SECS);
+        let bps = if first_year {
+            agent.schedule.first_year_bps
+        } else {
+            agent.schedule.renewal_bps
+        };
+        let commission = premium * bps as i128 / BPS_TOTAL as i128;
+        if commission == 0 {
+            return;
+        }
+        agent.accrued = agent.accrued.saturating_add(commission);
+        agents.set(attribution.agent.clone(), agent);
+        Self::save_agents(env, &agents);
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::CommissionAccrued),
+            (attribution.agent, policy_id, commission),
+        );
+    }
+
+    /// Register a field agent with their commission schedule, or update the
+    /// schedule of one already registered. Only the upgrade admin may call
+    /// this.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidCommission` - If either rate exceeds 10,000 bps
+    pub fn register_agent(
+        env: Env,
+        caller: Address,
+        agent: Address,
+        schedule: CommissionSchedule,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if schedule.first_year_bps > BPS_TOTAL || schedule.renewal_bps > BPS_TOTAL {
+            return Err(InsuranceError::InvalidCommission);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut agents = Self::load_agents(&env);
+        let record = match agents.get(agent.clone()) {
+            Some(mut existing) => {
+                existing.schedule = schedule;
+                existing
+            }
+            None => Agent {
+                address: agent.clone(),
+                schedule,
+                active: true,
+                policies_sold: 0,
+                accrued: 0,
+                claimed: 0,
+            },
+        };
+        agents.set(agent.clone(), record);
+        Self::save_agents(&env, &agents);
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::AgentRegistered),
+            agent,
+        );
+        Ok(())
+    }
+
+    /// Allow or stop an agent selling new policies. Commission keeps
+    /// accruing on policies they already sold. Only the upgrade admin may
+    /// call this.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `AgentNotFound` - If the agent is not registered
+    pub fn set_agent_active(
+        env: Env,
+        caller: Address,
+        agent: Address,
+        active: bool,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut agents = Self::load_agents(&env);
+        let mut record = agents
+            .get(agent.clone())
+            .ok_or(InsuranceError::AgentNotFound)?;
+        record.active = active;
+        agents.set(agent, record);
+        Self::save_agents(&env, &agents);
+        Ok(())
+    }
+
+    /// Create a policy sold by `agent`, who then earns commission on its
+    /// premiums. Otherwise identical to `create_policy`.
+    ///
+    /// # Errors
+    /// * `AgentNotFound` - If the agent is not registered or not active
+    /// * Any error from `create_policy`
+    pub fn create_agent_policy(
+        env: Env,
+        owner: Address,
+        agent: Address,
+        name: String,
+        coverage_type: String,
+        monthly_premium: i128,
+        coverage_amount: i128,
+    ) -> Result<u32, InsuranceError> {
+        let mut agents = Self::load_agents(&env);
+        let mut record = match agents.get(agent.clone()) {
+            Some(record) if record.active => record,
+            _ => return Err(InsuranceError::AgentNotFound),
+        };
+        let policy_id = Self::create_policy(
+            env.clone(),
+            owner,
+            name,
+            coverage_type,
+            monthly_premium,
+            coverage_amount,
+        )?;
+
+        record.policies_sold += 1;
+        agents.set(agent.clone(), record);
+        Self::save_agents(&env, &agents);
+        let mut policy_agents = Self::load_policy_agents(&env);
+        policy_agents.set(
+            policy_id,
+            PolicyAgent {
+                agent,
+                sold_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POL_AGENT"), &policy_agents);
+        Ok(policy_id)
+    }
+
+    pub fn get_policy_agent(env: Env, policy_id: u32) -> Option<PolicyAgent> {
+        Self::load_policy_agents(&env).get(policy_id)
+    }
+
+    pub fn get_agent(env: Env, agent: Address) -> Option<Agent> {
+        Self::load_agents(&env).get(agent)
+    }
+
+    /// Pay an agent their unclaimed commission in `token` from the
+    /// contract's balance.
+    ///
+    /// # Returns
+    /// The amount paid
+    ///
+    /// # Errors
+    /// * `AgentNotFound` - If the agent is not registered
+    /// * `InvalidAmount` - If there is no unclaimed commission
+    ///
+    /// # Panics
+    /// * If the contract's token balance does not cover the commission
+    pub fn claim_commission(
+        env: Env,
+        agent: Address,
+        token: Address,
+    ) -> Result<i128, InsuranceError> {
+        agent.require_auth();
+        let mut agents = Self::load_agents(&env);
+        let mut record = agents
+            .get(agent.clone())
+            .ok_or(InsuranceError::AgentNotFound)?;
+        let amount = record.accrued - record.claimed;
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &agent, &amount);
+        record.claimed = record.accrued;
+        agents.set(agent.clone(), record);
+        Self::save_agents(&env, &agents);
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::CommissionClaimed),
+            (agent, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Get commission totals across all agents. Only the upgrade admin may
+    /// read them.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    pub fn get_commission_totals(
+        env: Env,
+        caller: Address,
+    ) -> Result<CommissionTotals, InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut totals = CommissionTotals {
+            agents: 0,
+            policies_sold: 0,
+            accrued: 0,
+            claimed: 0,
+        };
+        for (_, agent) in Self::load_agents(&env).iter() {
+            totals.agents += 1;
+            totals.policies_sold += agent.policies_sold;
+            totals.accrued = totals.accrued.saturating_add(agent.accrued);
+            totals.claimed = totals.claimed.saturating_add(agent.claimed);
+        }
+        Ok(totals)
+    }
+
+    // -----------------------------------------------------------------------
+    // Insurer registry and quotes
+    // -----------------------------------------------------------------------
+
+    fn load_insurers(env: &Env) -> Map<Address, Insurer> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("INSURERS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_insurers(env: &Env, insurers: &Map<Address, Insurer>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("INSURERS"), insurers);
+    }
+
+    fn validate_products(products: &Vec<InsurerProduct>) -> Result<(), InsuranceError> {
+        for product in products.iter() {
+            if product.rate_bps == 0 {
+                return Err(InsuranceError::InvalidPremium);
+            }
+            if product.min_coverage <= 0 || product.max_coverage < product.min_coverage {
+                return Err(InsuranceError::InvalidCoverage);
+            }
+        }
+        Ok(())
+    }
+
+    /// Approve an insurer with its rate table, or replace an existing
+    /// registration. Only the upgrade admin may register insurers.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidPremium` - If a product has a zero rate
+    /// * `InvalidCoverage` - If a product's coverage range is empty or not positive
+    pub fn register_insurer(
+        env: Env,
+        caller: Address,
+        insurer: Address,
+        name: String,
+        products: Vec<InsurerProduct>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        Self::validate_products(&products)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut insurers = Self::load_insurers(&env);
+        insurers.set(
+            insurer.clone(),
+            Insurer {
+                address: insurer.clone(),
+                name,
+                products,
+                active: true,
+            },
+        );
+        Self::save_insurers(&env, &insurers);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("insurer")),
+            (insurer, true),
+        );
+        Ok(())
+    }
+
+    /// Approve or suspend a registered insurer. Suspended insurers are left
+    /// out of quotes. Only the upgrade admin may call this.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InsurerNotFound` - If insurer is not registered
+    pub fn set_insurer_active(
+        env: Env,
+        caller: Address,
+        insurer: Address,
+        active: bool,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut insurers = Self::load_insurers(&env);
+        let mut record = insurers
+            .get(insurer.clone())
+            .ok_or(InsuranceError::InsurerNotFound)?;
+        Self::extend_instance_ttl(&env);
+        record.active = active;
+        insurers.set(insurer.clone(), record);
+        Self::save_insurers(&env, &insurers);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("insurer")),
+            (insurer, active),
+        );
+        Ok(())
+    }
+
+    /// Replace an insurer's own rate table. Only the insurer may call this.
+    ///
+    /// # Errors
+    /// * `InsurerNotFound` - If insurer is not registered
+    /// * `InvalidPremium` / `InvalidCoverage` - As for `register_insurer`
+    pub fn update_rate_table(
+        env: Env,
+        insurer: Address,
+        products: Vec<InsurerProduct>,
+    ) -> Result<(), InsuranceError> {
+        insurer.require_auth();
+        let mut insurers = Self::load_insurers(&env);
+        let mut record = insurers
+            .get(insurer.clone())
+            .ok_or(InsuranceError::InsurerNotFound)?;
+        Self::validate_products(&products)?;
+        Self::extend_instance_ttl(&env);
+        record.products = products;
+        insurers.set(insurer.clone(), record);
+        Self::save_insurers(&env, &insurers);
+        env.events()
+            .publish((symbol_short!("insure"), symbol_short!("rates")), insurer);
+        Ok(())
+    }
+
+    pub fn get_insurer(env: Env, insurer: Address) -> Option<Insurer> {
+        Self::load_insurers(&env).get(insurer)
+    }
+
+    fn quote_from(
+        insurer: &Insurer,
+        coverage_type: &String,
+        coverage_amount: i128,
+    ) -> Option<Quote> {
+        for product in insurer.products.iter() {
+            if product.coverage_type != *coverage_type
+                || coverage_amount < product.min_coverage
+                || coverage_amount > product.max_coverage
+            {
+                continue;
+            }
+            // Round up so a quote never undercharges
+            let scaled = coverage_amount * product.rate_bps as i128;
+            let monthly_premium = (scaled + BPS_TOTAL as i128 - 1) / BPS_TOTAL as i128;
+            return Some(Quote {
+                insurer: insurer.address.clone(),
+                insurer_name: insurer.name.clone(),
+                coverage_type: coverage_type.clone(),
+                coverage_amount,
+                monthly_premium,
+            });
+        }
+        None
+    }
+
+    /// Get a quote from every active insurer that writes `coverage_type` at
+    /// `coverage_amount`, cheapest first.
+    pub fn get_quotes(env: Env, coverage_type: String, coverage_amount: i128) -> Vec<Quote> {
+        let mut quotes: Vec<Quote> = Vec::new(&env);
+        for (_, insurer) in Self::load_insurers(&env).iter() {
+            if !insurer.active {
+                continue;
+            }
+            if let Some(quote) = Self::quote_from(&insurer, &coverage_type, coverage_amount) {
+                // Insert in premium order
+                let mut at = quotes.len();
+                for (i, existing) in quotes.iter().enumerate() {
+                    if quote.monthly_premium < existing.monthly_premium {
+                        at = i as u32;
+                        break;
+                    }
+                }
+                quotes.insert(at, quote);
+            }
+        }
+        quotes
+    }
+
+    /// Create a policy on the terms an insurer currently quotes. The policy
+    /// is recorded against the insurer (see `get_policy_insurer`).
+    ///
+    /// # Errors
+    /// * `InsurerNotFound` - If insurer is not registered or is suspended
+    /// * `NoQuote` - If the insurer does not write this coverage
+    pub fn accept_quote(
+        env: Env,
+        owner: Address,
+        insurer: Address,
+        name: String,
+        coverage_type: String,
+        coverage_amount: i128,
+    ) -> Result<u32, InsuranceError> {
+        // `create_policy` requires the owner's authorization
+        let record = Self::load_insurers(&env)
+            .get(insurer.clone())
+            .filter(|i| i.active)
+            .ok_or(InsuranceError::InsurerNotFound)?;
+        let quote = Self::quote_from(&record, &coverage_type, coverage_amount)
+            .ok_or(InsuranceError::NoQuote)?;
+
+        let policy_id = Self::create_policy(
+            env.clone(),
+            owner,
+            name,
+            coverage_type,
+            quote.monthly_premium,
+            coverage_amount,
+        )?;
+
+        let mut policy_insurers: Map<u32, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POL_INS"))
+            .unwrap_or_else(|| Map::new(&env));
+        policy_insurers.set(policy_id, insurer);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POL_INS"), &policy_insurers);
+        Ok(policy_id)
+    }
+
+    /// Get the insurer a policy was written by through `accept_quote`.
+    pub fn get_policy_insurer(env: Env, policy_id: u32) -> Option<Address> {
+        let policy_insurers: Map<u32, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POL_INS"))
+            .unwrap_or_else(|| Map::new(&env));
+        policy_insurers.get(policy_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Riders
+    // -----------------------------------------------------------------------
+
+    fn load_riders(env: &Env) -> Map<u32, Vec<Rider>> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("RIDERS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Apply a rider's premium change to the policy's billed premium and,
+    /// while the policy is active, to the owner's premium total.
+    fn apply_rider_premium(
+        env: &Env,
+        policy_id: u32,
+        delta: i128,
+    ) -> Result<InsurancePolicy, InsuranceError> {
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        policy.monthly_premium += delta;
+        if policy.active {
+            Self::adjust_active_premium_total(env, &policy.owner, delta);
+        }
+        policies.set(policy_id, policy.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::record_premium_level(env, &policy.owner);
+        Ok(policy)
+    }
+
+    /// Attach a rider to an active policy owned by `caller`. The rider's
+    /// premium is added to the policy's billed premium. A policy carries at
+    /// most one rider of each kind.
+    ///
+    /// # Errors
+    /// * `InvalidPremium` / `InvalidCoverage` - If either amount is not positive
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `RiderExists` - If the policy already has a rider of this kind
+    pub fn add_rider(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        kind: RiderKind,
+        monthly_premium: i128,
+        coverage_amount: i128,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if monthly_premium <= 0 {
+            return Err(InsuranceError::InvalidPremium);
+        }
+        if coverage_amount <= 0 {
+            return Err(InsuranceError::InvalidCoverage);
+        }
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut all = Self::load_riders(&env);
+        let mut riders = all.get(policy_id).unwrap_or_else(|| Vec::new(&env));
+        if riders.iter().any(|r| r.kind == kind) {
+            return Err(InsuranceError::RiderExists);
+        }
+        riders.push_back(Rider {
+            kind,
+            monthly_premium,
+            coverage_amount,
+            added_at: env.ledger().timestamp(),
+        });
+        all.set(policy_id, riders);
+        env.storage().instance().set(&symbol_short!("RIDERS"), &all);
+        Self::apply_rider_premium(&env, policy_id, monthly_premium)?;
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::RiderAdded),
+            (policy_id, kind, monthly_premium, coverage_amount),
+        );
+        Ok(())
+    }
+
+    /// Detach the rider of `kind` from a policy owned by `caller`, removing
+    /// its premium from the policy's billed premium.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `RiderNotFound` - If the policy has no rider of this kind
+    pub fn remove_rider(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        kind: RiderKind,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut all = Self::load_riders(&env);
+        let mut riders = all.get(policy_id).unwrap_or_else(|| Vec::new(&env));
+        let index = riders
+            .iter()
+            .position(|r| r.kind == kind)
+            .ok_or(InsuranceError::RiderNotFound)? as u32;
+        let rider = riders.get(index).unwrap();
+        riders.remove(index);
+        if riders.is_empty() {
+            all.remove(policy_id);
+        } else {
+            all.set(policy_id, riders);
+        }
+        env.storage().instance().set(&symbol_short!("RIDERS"), &all);
+        Self::apply_rider_premium(&env, policy_id, -rider.monthly_premium)?;
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::RiderRemoved),
+            (policy_id, kind),
+        );
+        Ok(())
+    }
+
+    pub fn get_riders(env: Env, policy_id: u32) -> Vec<Rider> {
+        Self::load_riders(&env)
+            .get(policy_id)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Split a policy's billed premium and coverage into the base policy and
+    /// each rider, with the aggregated totals.
+    pub fn get_coverage_breakdown(env: Env, policy_id: u32) -> Option<CoverageBreakdown> {
+        let policy = Self::get_policy(env.clone(), policy_id)?;
+        let riders = Self::get_riders(env, policy_id);
+        let mut rider_premium = 0i128;
+        let mut rider_coverage = 0i128;
+        for rider in riders.iter() {
+            rider_premium += rider.monthly_premium;
+            rider_coverage += rider.coverage_amount;
+        }
+        Some(CoverageBreakdown {
+            policy_id,
+            base_premium: policy.monthly_premium - rider_premium,
+            base_coverage: policy.coverage_amount,
+            riders,
+            total_premium: policy.monthly_premium,
+            total_coverage: policy.coverage_amount + rider_coverage,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Group policies
+    // -----------------------------------------------------------------------
+
+    fn load_groups(env: &Env) -> Map<u32, GroupPolicy> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("GROUPS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_groups(env: &Env, groups: &Map<u32, GroupPolicy>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GROUPS"), groups);
+    }
+
+    fn require_family_member(
+        env: &Env,
+        family_wallet: &Address,
+        member: &Address,
+    ) -> Result<FamilyMember, InsuranceError> {
+        FamilyWalletClient::new(env, family_wallet)
+            .get_family_member(member)
+            .ok_or(InsuranceError::NotFamilyMember)
+    }
+
+    /// Load a group policy for a change by its owner.
+    fn group_for_owner(
+        env: &Env,
+        caller: &Address,
+        policy_id: u32,
+    ) -> Result<(InsurancePolicy, GroupPolicy), InsuranceError> {
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != *caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        let group = Self::load_groups(env)
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        Ok((policy, group))
+    }
+
+    fn set_policy_coverage(env: &Env, mut policy: InsurancePolicy, coverage_amount: i128) {
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+        policy.coverage_amount = coverage_amount;
+        let owner = policy.owner.clone();
+        policies.set(policy.id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::record_premium_level(env, &owner);
+    }
+
+    /// Create a policy covering members of `family_wallet` under a single
+    /// premium. `members` pairs each covered member with their coverage
+    /// amount. The owner must be an owner or admin of the family wallet and
+    /// every covered life must be a member of it.
+    ///
+    /// # Errors
+    /// * `NotFamilyMember` - If the owner or a covered member is not in the wallet
+    /// * `Unauthorized` - If the owner is neither wallet owner nor admin
+    /// * `InvalidGroupMembers` - If members is empty or lists an address twice
+    /// * `InvalidCoverage` - If a member's coverage is not positive
+    pub fn create_group_policy(
+        env: Env,
+        owner: Address,
+        name: String,
+        coverage_type: String,
+        monthly_premium: i128,
+        family_wallet: Address,
+        members: Vec<(Address, i128)>,
+    ) -> Result<u32, InsuranceError> {
+        let role = Self::require_family_member(&env, &family_wallet, &owner)?.role;
+        if role != FamilyRole::Owner && role != FamilyRole::Admin {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if members.is_empty() {
+            return Err(InsuranceError::InvalidGroupMembers);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut covered: Vec<CoveredMember> = Vec::new(&env);
+        let mut total_coverage = 0i128;
+        for (member, coverage_amount) in members.iter() {
+            if coverage_amount <= 0 {
+                return Err(InsuranceError::InvalidCoverage);
+            }
+            if covered.iter().any(|m| m.member == member) {
+                return Err(InsuranceError::InvalidGroupMembers);
+            }
+            Self::require_family_member(&env, &family_wallet, &member)?;
+            total_coverage = total_coverage.saturating_add(coverage_amount);
+            covered.push_back(CoveredMember {
+                member,
+                coverage_amount,
+                added_at: now,
+            });
+        }
+
+        let policy_id = Self::create_policy(
+            env.clone(),
+            owner,
+            name,
+            coverage_type,
+            monthly_premium,
+            total_coverage,
+        )?;
+        let mut groups = Self::load_groups(&env);
+        groups.set(
+            policy_id,
+            GroupPolicy {
+                policy_id,
+                family_wallet,
+                members: covered,
+            },
+        );
+        Self::save_groups(&env, &groups);
+        Ok(policy_id)
+    }
+
+    /// Add a family member to a group policy owned by `caller`, raising the
+    /// policy's coverage by the member's amount.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id is not a group policy
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidCoverage` - If coverage_amount is not positive
+    /// * `InvalidGroupMembers` - If the member is already covered
+    /// * `NotFamilyMember` - If the member is not in the family wallet
+    pub fn add_covered_member(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        member: Address,
+        coverage_amount: i128,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let (policy, mut group) = Self::group_for_owner(&env, &caller, policy_id)?;
+        if coverage_amount <= 0 {
+            return Err(InsuranceError::InvalidCoverage);
+        }
+        if group.members.iter().any(|m| m.member == member) {
+            return Err(InsuranceError::InvalidGroupMembers);
+        }
+        Self::require_family_member(&env, &group.family_wallet, &member)?;
+        Self::extend_instance_ttl(&env);
+
+        group.members.push_back(CoveredMember {
+            member: member.clone(),
+            coverage_amount,
+            added_at: env.ledger().timestamp(),
+        });
+        let coverage = policy.coverage_amount.saturating_add(coverage_amount);
+        Self::set_policy_coverage(&env, policy, coverage);
+        let mut groups = Self::load_groups(&env);
+        groups.set(policy_id, group);
+        Self::save_groups(&env, &groups);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::GroupMemberAdded),
+            (policy_id, member, coverage_amount),
+        );
+        Ok(())
+    }
+
+    /// Remove a member from a group policy owned by `caller`, lowering the
+    /// policy's coverage by the member's amount. The last member cannot be
+    /// removed; deactivate the policy instead.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id is not a group policy
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `NotCovered` - If the member is not covered by the policy
+    /// * `InvalidGroupMembers` - If the member is the last one covered
+    pub fn remove_covered_member(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        member: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let (policy, mut group) = Self::group_for_owner(&env, &caller, policy_id)?;
+        let index = group
+            .members
+            .iter()
+            .position(|m| m.member == member)
+            .ok_or(InsuranceError::NotCovered)? as u32;
+        if group.members.len() == 1 {
+            return Err(InsuranceError::InvalidGroupMembers);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let removed = group.members.get(index).unwrap();
+        group.members.remove(index);
+        let coverage = policy.coverage_amount - removed.coverage_amount;
+        Self::set_policy_coverage(&env, policy, coverage);
+        let mut groups = Self::load_groups(&env);
+        groups.set(policy_id, group);
+        Self::save_groups(&env, &groups);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::GroupMemberRemoved),
+            (policy_id, member),
+        );
+        Ok(())
+    }
+
+    pub fn get_group_policy(env: Env, policy_id: u32) -> Option<GroupPolicy> {
+        Self::load_groups(&env).get(policy_id)
+    }
+
+    /// Get the active group policies owned by `owner`.
+    pub fn get_group_policies(env: Env, owner: Address) -> Vec<GroupPolicy> {
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for (policy_id, group) in Self::load_groups(&env).iter() {
+            if let Some(policy) = policies.get(policy_id) {
+                if policy.active && policy.owner == owner {
+                    result.push_back(group);
+                }
+            }
+        }
+        result
+    }
+
+    /// Get `member`'s coverage under every active group policy covering
+    /// them, with the claim payouts they have received under each.
+    pub fn get_member_coverage(env: Env, member: Address) -> Vec<MemberCoverage> {
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let claims = Self::load_claims(&env);
+        let mut result = Vec::new(&env);
+        for (policy_id, group) in Self::load_groups(&env).iter() {
+            let policy = match policies.get(policy_id) {
+                Some(p) if p.active => p,
+                _ => continue,
+            };
+            if let Some(covered) = group.members.iter().find(|m| m.member == member) {
+                let mut claimed = 0i128;
+                for (_, claim) in claims.iter() {
+                    if claim.policy_id == policy_id
+                        && claim.claimant == member
+                        && claim.status == ClaimStatus::Paid
+                    {
+                        claimed = claimed.saturating_add(claim.amount);
+                    }
+                }
+                result.push_back(MemberCoverage {
+                    policy_id,
+                    owner: policy.owner,
+                    coverage_amount: covered.coverage_amount,
+                    claimed,
+                });
+            }
+        }
+        result
+    }
+
+    // -----------------------------------------------------------------------
+    // Policy assignment
+    // -----------------------------------------------------------------------
+
+    fn load_assignments(env: &Env) -> Map<u32, PolicyAssignment> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ASSIGN"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_assignments(env: &Env, assignments: &Map<u32, PolicyAssignment>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ASSIGN"), assignments);
+    }
+
+    /// The insurer that must acknowledge assignments of a policy: the one
+    /// that wrote it through `accept_quote`, else the upgrade admin.
+    fn assignment_insurer(env: &Env, policy_id: u32) -> Option<Address> {
+        Self::get_policy_insurer(env.clone(), policy_id).or_else(|| Self::get_upgrade_admin(env))
+    }
+
+    /// Offer a policy to a new owner, for example so a family member
+    /// in-country can administer a policy bought by the remitter abroad.
+    /// A new offer replaces any pending one.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `InvalidAssignment` - If new_owner is the caller or the policy is inactive
+    pub fn assign_policy(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        new_owner: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if new_owner == caller || !policy.active {
+            return Err(InsuranceError::InvalidAssignment);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut assignments = Self::load_assignments(&env);
+        assignments.set(
+            policy_id,
+            PolicyAssignment {
+                policy_id,
+                from: caller.clone(),
+                to: new_owner.clone(),
+                accepted: false,
+                acknowledged: false,
+                created_at: env.ledger().timestamp(),
+            },
+        );
+        Self::save_assignments(&env, &assignments);
+
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("assign")),
+            (policy_id, caller, new_owner),
+        );
+        Ok(())
+    }
+
+    /// Withdraw a pending assignment. Only the current owner may cancel.
+    pub fn cancel_policy_assignment(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let mut assignments = Self::load_assignments(&env);
+        let assignment = assignments
+            .get(policy_id)
+            .ok_or(InsuranceError::AssignmentNotFound)?;
+        if assignment.from != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        assignments.remove(policy_id);
+        Self::save_assignments(&env, &assignments);
+        Ok(())
+    }
+
+    /// Accept a pending assignment as the new owner.
+    ///
+    /// # Returns
+    /// `Ok(true)` if this completed the assignment
+    pub fn accept_policy_assignment(
+        env: Env,
+        new_owner: Address,
+        policy_id: u32,
+    ) -> Result<bool, InsuranceError> {
+        new_owner.require_auth();
+        let mut assignments = Self::load_assignments(&env);
+        let mut assignment = assignments
+            .get(policy_id)
+            .ok_or(InsuranceError::AssignmentNotFound)?;
+        if assignment.to != new_owner {
+            return Err(InsuranceError::Unauthorized);
+        }
+        assignment.accepted = true;
+        assignments.set(policy_id, assignment);
+        Self::save_assignments(&env, &assignments);
+        Self::try_complete_assignment(&env, policy_id)
+    }
+
+    /// Acknowledge a pending assignment as the policy's insurer.
+    ///
+    /// # Returns
+    /// `Ok(true)` if this completed the assignment
+    pub fn acknowledge_policy_assignment(
+        env: Env,
+        insurer: Address,
+        policy_id: u32,
+    ) -> Result<bool, InsuranceError> {
+        insurer.require_auth();
+        if Self::assignment_insurer(&env, policy_id) != Some(insurer) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut assignments = Self::load_assignments(&env);
+        let mut assignment = assignments
+            .get(policy_id)
+            .ok_or(InsuranceError::AssignmentNotFound)?;
+        assignment.acknowledged = true;
+        assignments.set(policy_id, assignment);
+        Self::save_assignments(&env, &assignments);
+        Self::try_complete_assignment(&env, policy_id)
+    }
+
+    fn try_complete_assignment(env: &Env, policy_id: u32) -> Result<bool, InsuranceError> {
+        let mut assignments = Self::load_assignments(env);
+        let assignment = assignments
+            .get(policy_id)
+            .ok_or(InsuranceError::AssignmentNotFound)?;
+        if !assignment.accepted || !assignment.acknowledged {
+            return Ok(false);
+        }
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        // The owner may have changed state since offering the policy
+        if policy.owner != assignment.from || !policy.active {
+            return Err(InsuranceError::InvalidAssignment);
+        }
+        Self::extend_instance_ttl(env);
+
+        // Premium schedules belong to the old owner; the new owner sets up
+        // their own
+        if let Some(schedule_id) = policy.schedule_id {
+            let mut schedules: Map<u32, PremiumSchedule> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("PREM_SCH"))
+                .unwrap_or_else(|| Map::new(env));
+            if let Some(mut schedule) = schedules.get(schedule_id) {
+                schedule.active = false;
+                schedules.set(schedule_id, schedule);
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("PREM_SCH"), &schedules);
+            }
+            policy.schedule_id = None;
+        }
+
+        Self::adjust_active_premium_total(env, &assignment.from, -policy.monthly_premium);
+        Self::adjust_active_premium_total(env, &assignment.to, policy.monthly_premium);
+        policy.owner = assignment.to.clone();
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::record_premium_level(env, &assignment.from);
+        Self::record_premium_level(env, &assignment.to);
+        assignments.remove(policy_id);
+        Self::save_assignments(env, &assignments);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PolicyAssigned),
+            (policy_id, assignment.from, assignment.to),
+        );
+        Ok(true)
+    }
+
+    pub fn get_policy_assignment(env: Env, policy_id: u32) -> Option<PolicyAssignment> {
+        Self::load_assignments(&env).get(policy_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Policy documents
+    // -----------------------------------------------------------------------
+
+    /// Anchor the hash of a policy's document. Only the upgrade admin, acting
+    /// as issuer, may anchor; anchoring again replaces the previous document.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `PolicyNotFound` - If policy_id does not exist
+    pub fn anchor_policy_document(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        hash: BytesN<32>,
+        signature_ref: String,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller.clone()) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if Self::get_policy(env.clone(), policy_id).is_none() {
+            return Err(InsuranceError::PolicyNotFound);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut documents: Map<u32, PolicyDocument> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POL_DOCS"))
+            .unwrap_or_else(|| Map::new(&env));
+        documents.set(
+            policy_id,
+            PolicyDocument {
+                hash: hash.clone(),
+                issuer: caller,
+                signature_ref,
+                anchored_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POL_DOCS"), &documents);
+
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("doc_anchr")),
+            (policy_id, hash),
+        );
+        Ok(())
+    }
+
+    pub fn get_policy_document(env: Env, policy_id: u32) -> Option<PolicyDocument> {
+        let documents: Map<u32, PolicyDocument> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POL_DOCS"))
+            .unwrap_or_else(|| Map::new(&env));
+        documents.get(policy_id)
+    }
+
+    /// Check whether `hash` matches the document anchored for a policy.
+    /// Returns false when no document has been anchored.
+    pub fn verify_policy_document(env: Env, policy_id: u32, hash: BytesN<32>) -> bool {
+        Self::get_policy_document(env, policy_id)
+            .map(|doc| doc.hash == hash)
+            .unwrap_or(false)
+    }
+
+    // -----------------------------------------------------------------------
+    // Parametric policies
+    // -----------------------------------------------------------------------
+
+    fn load_parametric(env: &Env) -> Map<u32, ParametricTerms> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("PARAM"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Attach parametric payout terms to a policy. Only the upgrade admin may
+    /// set them. The payout is made in the policy's settlement token.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `InvalidClaimAmount` - If payout ≤ 0 or exceeds the coverage amount
+    /// * `PolicyTokenNotSet` - If the policy was issued without a settlement token
+    pub fn set_parametric_terms(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        oracle: Address,
+        index: Symbol,
+        threshold: i128,
+        trigger_above: bool,
+        payout: i128,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if payout <= 0 || payout > policy.coverage_amount {
+            return Err(InsuranceError::InvalidClaimAmount);
+        }
+        let token = Self::get_policy_token(env.clone(), policy_id)
+            .ok_or(InsuranceError::PolicyTokenNotSet)?;
+        Self::extend_instance_ttl(&env);
+
+        let mut terms = Self::load_parametric(&env);
+        terms.set(
+            policy_id,
+            ParametricTerms {
+                oracle,
+                index: index.clone(),
+                threshold,
+                trigger_above,
+                payout,
+                token,
+                triggered_at: None,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PARAM"), &terms);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("param")),
+            (policy_id, index, threshold),
+        );
+        Ok(())
+    }
+
+    pub fn get_parametric_terms(env: Env, policy_id: u32) -> Option<ParametricTerms> {
+        Self::load_parametric(&env).get(policy_id)
+    }
+
+    /// Check a parametric policy's index and pay out if it crossed the
+    /// threshold.
+    ///
+    /// Anyone may call this. The payout is transferred from the claims
+    /// reserve held in the terms' token to the policy owner and emitted as a
+    /// `ParametricPayout` event. Each policy pays out at most once.
+    ///
+    /// # Returns
+    /// `Ok(true)` if the trigger fired and the payout was made, `Ok(false)`
+    /// if the index has not crossed the threshold
+    ///
+    /// # Errors
+    /// * `NotParametric` - If the policy has no parametric terms
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `AlreadyTriggered` - If the policy has already paid out
+    /// * `StaleIndex` - If the oracle has no reading newer than one day
+    /// * `InsufficientReserves` - If the claims reserve does not cover the payout
+    pub fn check_trigger(env: Env, policy_id: u32) -> Result<bool, InsuranceError> {
+        let mut all_terms = Self::load_parametric(&env);
+        let mut terms = all_terms
+            .get(policy_id)
+            .ok_or(InsuranceError::NotParametric)?;
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if terms.triggered_at.is_some() {
+            return Err(InsuranceError::AlreadyTriggered);
+        }
+
+        let now = env.ledger().timestamp();
+        let reading = IndexOracleClient::new(&env, &terms.oracle)
+            .get_index(&terms.index)
+            .ok_or(InsuranceError::StaleIndex)?;
+        if now.saturating_sub(reading.updated_at) > MAX_ORACLE_AGE_SECS {
+            return Err(InsuranceError::StaleIndex);
+        }
+        let crossed = if terms.trigger_above {
+            reading.value >= terms.threshold
+        } else {
+            reading.value <= terms.threshold
+        };
+        if !crossed {
+            return Ok(false);
+        }
+
+        Self::debit_claims_reserve(&env, &terms.token, terms.payout)?;
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &terms.token).transfer(
+            &env.current_contract_address(),
+            &policy.owner,
+            &terms.payout,
+        );
+        Self::record_claim_paid(&env, &terms.token, terms.payout);
+        terms.triggered_at = Some(now);
+        let payout = terms.payout;
+        all_terms.set(policy_id, terms);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PARAM"), &all_terms);
+
+        Self::emit_lifecycle(&env, PolicyLifecycle::ClaimSettled, &policy, payout, None);
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::ParametricPayout),
+            (policy_id, policy.owner, payout, reading.value),
+        );
+        Ok(true)
+    }
+
+    // -----------------------------------------------------------------------
+    // Mutual pools
+    // -----------------------------------------------------------------------
+
+    fn load_pools(env: &Env) -> Map<u32, MutualPool> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("POOLS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_pools(env: &Env, pools: &Map<u32, MutualPool>) {
+        env.storage().instance().set(&symbol_short!("POOLS"), pools);
+    }
+
+    fn load_pool_members(env: &Env) -> Map<(u32, Address), PoolMember> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("POOL_MBR"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_pool_members(env: &Env, members: &Map<(u32, Address), PoolMember>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POOL_MBR"), members);
+    }
+
+    /// Create a mutual pool. Only the upgrade admin may create pools.
+    ///
+    /// # Arguments
+    /// * `token` - Token contributions and claims are paid in
+    /// * `member_cap` - Most a single member can claim over their membership
+    /// * `waiting_period_days` - Days after joining before a member can claim
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidCoverage` - If member_cap ≤ 0
+    pub fn create_pool(
+        env: Env,
+        caller: Address,
+        name: String,
+        token: Address,
+        member_cap: i128,
+        waiting_period_days: u32,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if member_cap <= 0 {
+            return Err(InsuranceError::InvalidCoverage);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let pool_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_POOL"))
+            .unwrap_or(0u32)
+            + 1;
+        let mut pools = Self::load_pools(&env);
+        pools.set(
+            pool_id,
+            MutualPool {
+                id: pool_id,
+                name,
+                token,
+                member_cap,
+                waiting_period_days,
+                balance: 0,
+                total_contributed: 0,
+                total_claimed: 0,
+                member_count: 0,
+            },
+        );
+        Self::save_pools(&env, &pools);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_POOL"), &pool_id);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PoolCreated),
+            (pool_id, member_cap, waiting_period_days),
+        );
+        Ok(pool_id)
+    }
+
+    /// Join a mutual pool. The waiting period starts now.
+    ///
+    /// # Errors
+    /// * `PoolNotFound` - If pool_id does not exist
+    /// * `AlreadyPoolMember` - If member already belongs to the pool
+    pub fn join_pool(env: Env, member: Address, pool_id: u32) -> Result<(), InsuranceError> {
+        member.require_auth();
+        let mut pools = Self::load_pools(&env);
+        let mut pool = pools.get(pool_id).ok_or(InsuranceError::PoolNotFound)?;
+        let mut members = Self::load_pool_members(&env);
+        let key = (pool_id, member.clone());
+        if members.contains_key(key.clone()) {
+            return Err(InsuranceError::AlreadyPoolMember);
+        }
+        Self::extend_instance_ttl(&env);
+
+        members.set(
+            key,
+            PoolMember {
+                joined_at: env.ledger().timestamp(),
+                contributed: 0,
+                claimed: 0,
+            },
+        );
+        pool.member_count += 1;
+        pools.set(pool_id, pool);
+        Self::save_pool_members(&env, &members);
+        Self::save_pools(&env, &pools);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PoolJoined),
+            (pool_id, member),
+        );
+        Ok(())
+    }
+
+    /// Contribute `amount` of the pool's token into the pool.
+    ///
+    /// # Errors
+    /// * `InvalidPremium` - If amount ≤ 0
+    /// * `PoolNotFound` - If pool_id does not exist
+    /// * `NotPoolMember` - If member has not joined the pool
+    pub fn contribute_to_pool(
+        env: Env,
+        member: Address,
+        pool_id: u32,
+        amount: i128,
+    ) -> Result<(), InsuranceError> {
+        member.require_auth();
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidPremium);
+        }
+        let mut pools = Self::load_pools(&env);
+        let mut pool = pools.get(pool_id).ok_or(InsuranceError::PoolNotFound)?;
+        let mut members = Self::load_pool_members(&env);
+        let key = (pool_id, member.clone());
+        let mut position = members
+            .get(key.clone())
+            .ok_or(InsuranceError::NotPoolMember)?;
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &pool.token).transfer(
+            &member,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        position.contributed += amount;
+        pool.balance += amount;
+        pool.total_contributed += amount;
+        members.set(key, position);
+        pools.set(pool_id, pool);
+        Self::save_pool_members(&env, &members);
+        Self::save_pools(&env, &pools);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PoolContribution),
+            (pool_id, member, amount),
+        );
+        Ok(())
+    }
+
+    /// Pay a member's claim from the pool. Only a claims adjuster may pay.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If adjuster does not hold the adjuster role
+    /// * `InvalidClaimAmount` - If amount ≤ 0
+    /// * `PoolNotFound` - If pool_id does not exist
+    /// * `NotPoolMember` - If member has not joined the pool
+    /// * `WaitingPeriodActive` - If the member's waiting period has not passed
+    /// * `MemberCapExceeded` - If the claim would take the member past the cap
+    /// * `PoolInsufficientFunds` - If the pool balance cannot cover the claim
+    pub fn pay_pool_claim(
+        env: Env,
+        adjuster: Address,
+        pool_id: u32,
+        member: Address,
+        amount: i128,
+    ) -> Result<(), InsuranceError> {
+        adjuster.require_auth();
+        Self::require_adjuster(&env, &adjuster)?;
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidClaimAmount);
+        }
+        let mut pools = Self::load_pools(&env);
+        let mut pool = pools.get(pool_id).ok_or(InsuranceError::PoolNotFound)?;
+        let mut members = Self::load_pool_members(&env);
+        let key = (pool_id, member.clone());
+        let mut position = members
+            .get(key.clone())
+            .ok_or(InsuranceError::NotPoolMember)?;
+
+        let eligible_at = position.joined_at + pool.waiting_period_days as u64 * 86400;
+        if env.ledger().timestamp() < eligible_at {
+            return Err(InsuranceError::WaitingPeriodActive);
+        }
+        if position.claimed + amount > pool.member_cap {
+            return Err(InsuranceError::MemberCapExceeded);
+        }
+        if amount > pool.balance {
+            return Err(InsuranceError::PoolInsufficientFunds);
+        }
+        Self::extend_instance_ttl(&env);
+
+        token::Client::new(&env, &pool.token).transfer(
+            &env.current_contract_address(),
+            &member,
+            &amount,
+        );
+
+        position.claimed += amount;
+        pool.balance -= amount;
+        pool.total_claimed += amount;
+        members.set(key, position);
+        pools.set(pool_id, pool);
+        Self::save_pool_members(&env, &members);
+        Self::save_pools(&env, &pools);
+
+        env.events().publish(
+            (symbol_short!("insure"), InsuranceEvent::PoolClaimPaid),
+            (pool_id, member, amount),
+        );
+        Ok(())
+    }
+
+    pub fn get_pool(env: Env, pool_id: u32) -> Option<MutualPool> {
+        Self::load_pools(&env).get(pool_id)
+    }
+
+    pub fn get_pool_member(env: Env, pool_id: u32, member: Address) -> Option<PoolMember> {
+        Self::load_pool_members(&env).get((pool_id, member))
+    }
+
+    fn solvency_bps(pool: &MutualPool) -> u32 {
+        // Outstanding exposure is what members could still claim under the cap
+        let exposure = (pool.member_cap * pool.member_count as i128 - pool.total_claimed).max(0);
+        if exposure == 0 {
+            return BPS_TOTAL;
+        }
+        (pool.balance * BPS_TOTAL as i128 / exposure).min(u32::MAX as i128) as u32
+    }
+
+    /// Get a pool's solvency ratio in basis points: its balance over the
+    /// amount its members could still claim. 10,000 means fully funded.
+    pub fn get_pool_solvency(env: Env, pool_id: u32) -> Result<u32, InsuranceError> {
+        let pool = Self::load_pools(&env)
+            .get(pool_id)
+            .ok_or(InsuranceError::PoolNotFound)?;
+        Ok(Self::solvency_bps(&pool))
+    }
+
+    /// Get `member`'s position and the solvency of every pool they belong to.
+    pub fn get_pool_summaries(env: Env, member: Address) -> Vec<PoolSummary> {
+        let pools = Self::load_pools(&env);
+        let mut result = Vec::new(&env);
+        for ((pool_id, address), position) in Self::load_pool_members(&env).iter() {
+            if address != member {
+                continue;
+            }
+            if let Some(pool) = pools.get(pool_id) {
+                result.push_back(PoolSummary {
+                    pool_id,
+                    contributed: position.contributed,
+                    claimed: position.claimed,
+                    solvency_bps: Self::solvency_bps(&pool),
+                });
+            }
+        }
+        result
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::storage::Instance as _;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
+    use soroban_sdk::{Env, String};
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    fn setup_policies(
+        env: &Env,
+        client: &InsuranceClient,
+        owner: &Address,
+        count: u32,
+    ) -> Vec<u32> {
+        let mut ids = Vec::new(env);
+        for i in 0..count {
+            let id = client.create_policy(
+                owner,
+                &String::from_str(env, "Policy"),
+                &String::from_str(env, "health"),
+                &(50i128 * (i as i128 + 1)),
+                &(10000i128 * (i as i128 + 1)),
+            );
+            ids.push_back(id);
+        }
+        ids
+    }
+
+    fn setup_premium_token(env: &Env, client: &InsuranceClient, payer: &Address) -> Address {
+        let admin = Address::generate(env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_premium_collector(&admin, &Address::generate(env));
+        let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+        client.set_settlement_token(&admin, &token.address());
+        soroban_sdk::token::StellarAssetClient::new(env, &token.address()).mint(payer, &10_000);
+        token.address()
+    }
+
+    // --- get_active_policies ---
+
+    #[test]
+    fn test_get_active_policies_empty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let page = client.get_active_policies(&owner, &0, &0);
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_active_policies_single_page() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner, 5);
+
+        let page = client.get_active_policies(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_pay_premium_policy_not_found() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        // No policies created — policy ID 999 does not exist
+        let token = setup_premium_token(&env, &client, &owner);
+        let result = client.try_pay_premium(&owner, &999u32, &token);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_active_policies_paginated() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner, 7);
+
+        let page1 = client.get_active_policies(&owner, &0, &3);
+        assert_eq!(page1.count, 3);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_active_policies(&owner, &page1.next_cursor, &3);
+        assert_eq!(page2.count, 3);
+        assert!(page2.next_cursor > 0);
+
+        let page3 = client.get_active_policies(&owner, &page2.next_cursor, &3);
+        assert_eq!(page3.count, 1);
+        assert_eq!(page3.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_active_policies_excludes_inactive() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let ids = setup_policies(&env, &client, &owner, 4);
+        // Deactivate policy #2
+        client.deactivate_policy(&owner, &ids.get(1).unwrap());
+
+        let page = client.get_active_policies(&owner, &0, &10);
+        assert_eq!(page.count, 3); // only 3 active
+        for p in page.items.iter() {
+            assert!(p.active, "only active policies should be returned");
+        }
+    }
+
+    #[test]
+    fn test_get_active_policies_multi_owner_isolation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner_a, 3);
+        setup_policies(&env, &client, &owner_b, 5);
+
+        let page = client.get_active_policies(&owner_a, &0, &20);
+        assert_eq!(page.count, 3);
+        for p in page.items.iter() {
+            assert_eq!(p.owner, owner_a);
+        }
+    }
+
+    #[test]
+    fn test_get_all_policies_for_owner_includes_inactive() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let ids = setup_policies(&env, &client, &owner, 4);
+        client.deactivate_policy(&owner, &ids.get(0).unwrap());
+        client.deactivate_policy(&owner, &ids.get(2).unwrap());
+
+        let page = client.get_all_policies_for_owner(&owner, &0, &10);
+        assert_eq!(page.count, 4); // all 4 regardless of active status
+    }
+
+    #[test]
+    fn test_query_policies_filters_by_type_and_status() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let ids = setup_policies(&env, &client, &owner, 3);
+        let life = String::from_str(&env, "life");
+        let life_id =
+            client.create_policy(&owner, &String::from_str(&env, "Life"), &life, &70, &90000);
+        client.deactivate_policy(&owner, &ids.get(0).unwrap());
+        let health = Some(String::from_str(&env, "health"));
+
+        let page = client.query_policies(&owner, &health, &Some(PolicyStatus::Active), &0, &1);
+        assert_eq!(page.items.get(0).unwrap().id, ids.get(1).unwrap());
+        let page = client.query_policies(
+            &owner,
+            &health,
+            &Some(PolicyStatus::Active),
+            &page.next_cursor,
+            &1,
+        );
+        assert_eq!(page.items.get(0).unwrap().id, ids.get(2).unwrap());
+        let page = client.query_policies(
+            &owner,
+            &health,
+            &Some(PolicyStatus::Active),
+            &page.next_cursor,
+            &1,
+        );
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+
+        let cancelled =
+            client.query_policies(&owner, &None, &Some(PolicyStatus::Cancelled), &0, &0);
+        assert_eq!(cancelled.count, 1);
+        let life_page = client.query_policies(&owner, &Some(life), &None, &0, &0);
+        assert_eq!(life_page.items.get(0).unwrap().id, life_id);
+
+        let totals = client.get_coverage_totals(&owner);
+        assert_eq!(totals.active_policies, 3);
+        assert_eq!(totals.total_coverage, 20000 + 30000 + 90000);
+        assert_eq!(totals.monthly_premium, 100 + 150 + 70);
+
+        // Unpaid premiums past the grace period lapse the active policies
+        set_ledger_time(&env, 61 * 86400);
+        client.sync_policy_status();
+        let lapsed = client.query_policies(&owner, &None, &Some(PolicyStatus::Lapsed), &0, &0);
+        assert_eq!(lapsed.count, 3);
+        assert_eq!(
+            client.get_policy_status(&ids.get(0).unwrap()),
+            Some(PolicyStatus::Cancelled)
+        );
+        assert_eq!(client.get_coverage_totals(&owner).active_policies, 0);
+    }
+
+    #[test]
+    fn test_premium_levels_track_premium_and_coverage() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        set_ledger_time(&env, 1000);
+        let ids = setup_policies(&env, &client, &owner, 2);
+        set_ledger_time(&env, 2000);
+        client.deactivate_policy(&owner, &ids.get(0).unwrap());
+
+        // Both policies created in one ledger collapse into one level
+        let levels = client.get_premium_levels(&owner);
+        assert_eq!(levels.len(), 2);
+        let first = levels.get(0).unwrap();
+        assert_eq!(first.recorded_at, 1000);
+        assert_eq!(first.active_policies, 2);
+        assert_eq!(first.total_coverage, 30000);
+        assert_eq!(first.monthly_premium, 150);
+
+        assert_eq!(client.get_premium_level_at(&owner, &999).monthly_premium, 0);
+        assert_eq!(client.get_premium_level_at(&owner, &1500), first);
+        let second = client.get_premium_level_at(&owner, &5000);
+        assert_eq!(second.recorded_at, 2000);
+        assert_eq!(second.total_coverage, 20000);
+        assert_eq!(second.monthly_premium, 100);
+
+        // An unchanged total adds no level
+        client.deactivate_policy(&owner, &ids.get(0).unwrap());
+        assert_eq!(client.get_premium_levels(&owner).len(), 2);
+    }
+
+    // --- limit clamping ---
+
+    #[test]
+    fn test_limit_zero_uses_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner, 3);
+        let page = client.get_active_policies(&owner, &0, &0);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_limit_clamped_to_max() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner, 55);
+        let page = client.get_active_policies(&owner, &0, &9999);
+        assert_eq!(page.count, MAX_PAGE_LIMIT);
+        assert!(page.next_cursor > 0);
+    }
+
+    // --- existing event tests (unchanged) ---
+
+    #[test]
+    fn test_create_policy_emits_event() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Health Insurance"),
+            &String::from_str(&env, "health"),
+            &100,
+            &50000,
+        );
+        assert_eq!(policy_id, 1);
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_pay_premium_emits_event() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Emergency Coverage"),
+            &String::from_str(&env, "emergency"),
+            &75,
+            &25000,
+        );
+        let token = setup_premium_token(&env, &client, &owner);
+        let events_before = env.events().all().len();
+
+        client.pay_premium(&owner, &policy_id, &token);
+
+        // Three policy events plus the token transfer
+        let events_after = env.events().all().len();
+        assert_eq!(events_after - events_before, 4);
+    }
+
+    #[test]
+    fn test_policy_lifecycle_emits_all_events() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Complete Lifecycle"),
+            &String::from_str(&env, "health"),
+            &150,
+            &75000,
+        );
+
+        let token = setup_premium_token(&env, &client, &owner);
+        client.pay_premium(&owner, &policy_id, &token);
+        client.deactivate_policy(&owner, &policy_id);
+
+        // Eight policy events plus collector, settlement token, token setup
+        // and transfer events
+        let events = env.events().all();
+        assert_eq!(events.len(), 13);
+    }
+
+    fn lifecycle_events(env: &Env) -> Vec<(PolicyLifecycle, PolicyLifecycleEvent)> {
+        use soroban_sdk::TryFromVal;
+        let mut out = Vec::new(env);
+        for (_, topics, data) in env.events().all().iter() {
+            let Ok(topic) = Symbol::try_from_val(env, &topics.get(0).unwrap()) else {
+                continue;
+            };
+            if topic != symbol_short!("policy") {
+                continue;
+            }
+            let stage = PolicyLifecycle::try_from_val(env, &topics.get(1).unwrap()).unwrap();
+            let event = PolicyLifecycleEvent::try_from_val(env, &data).unwrap();
+            assert_eq!(
+                Address::try_from_val(env, &topics.get(2).unwrap()).unwrap(),
+                event.owner
+            );
+            assert_eq!(
+                u32::try_from_val(env, &topics.get(3).unwrap()).unwrap(),
+                event.policy_id
+            );
+            out.push_back((stage, event));
+        }
+        out
+    }
+
+    #[test]
+    fn test_policy_lifecycle_structured_events() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let adjuster = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        client.set_premium_collector(&admin, &Address::generate(&env));
+        let token = setup_settlement(&env, &client, &admin, 10000);
+        let policy_id = setup_policies(&env, &client, &owner, 1).get(0).unwrap();
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &10000);
+
+        client.pay_premium(&owner, &policy_id, &token);
+        let evidence = BytesN::from_array(&env, &[1u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &4000, &evidence);
+        client.approve_claim(&adjuster, &claim_id, &String::from_str(&env, "ok"));
+        client.pay_claim(&adjuster, &claim_id);
+
+        // Missed once per due date while in grace, then lapsed after it
+        let due = client.get_policy(&policy_id).unwrap().next_payment_date;
+        set_ledger_time(&env, due + 86400);
+        client.sync_policy_status();
+        set_ledger_time(&env, due + 2 * 86400);
+        client.sync_policy_status();
+        set_ledger_time(&env, due + 31 * 86400);
+        client.sync_policy_status();
+
+        let events = lifecycle_events(&env);
+        let mut stages = Vec::new(&env);
+        for (stage, _) in events.iter() {
+            stages.push_back(stage);
+        }
+        assert_eq!(
+            stages,
+            soroban_sdk::vec![
+                &env,
+                PolicyLifecycle::Issued,
+                PolicyLifecycle::PremiumPaid,
+                PolicyLifecycle::ClaimFiled,
+                PolicyLifecycle::ClaimSettled,
+                PolicyLifecycle::PremiumMissed,
+                PolicyLifecycle::Lapsed,
+            ]
+        );
+        assert_eq!(events.get(0).unwrap().1.amount, 50);
+        let filed = events.get(2).unwrap().1;
+        assert_eq!(filed.claim_id, Some(claim_id));
+        assert_eq!(filed.amount, 4000);
+        assert_eq!(events.get(3).unwrap().1.claim_id, Some(claim_id));
+        assert_eq!(events.get(4).unwrap().1.next_payment_date, due);
+    }
+
+    // --- pay_premium with token ---
+
+    #[test]
+    fn test_pay_premium_transfers_and_records_history() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Health"),
+            &String::from_str(&env, "health"),
+            &150,
+            &50000,
+        );
+        let token = setup_premium_token(&env, &client, &owner);
+        let collector = client.get_premium_collector().unwrap();
+        let first_due = client.get_policy(&policy_id).unwrap().next_payment_date;
+
+        client.pay_premium(&owner, &policy_id, &token);
+        client.pay_premium(&owner, &policy_id, &token);
+
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 10_000 - 300);
+        assert_eq!(token_client.balance(&collector), 300);
+
+        // Each payment advances the due date by one period
+        let policy = client.get_policy(&policy_id).unwrap();
+        assert_eq!(policy.next_payment_date, first_due + 2 * 30 * 86400);
+
+        let payments = client.get_premium_payments(&owner, &0, &u64::MAX);
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments.get(0).unwrap().due_date, first_due);
+        assert_eq!(payments.get(1).unwrap().due_date, first_due + 30 * 86400);
+
+        // Totals are attributed to the period each premium was due in
+        assert_eq!(client.get_premiums_paid(&owner, &0, &first_due), 150);
+        assert_eq!(client.get_premiums_paid(&owner, &0, &u64::MAX), 300);
+    }
+
+    #[test]
+    fn test_pay_premium_requires_collector() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Health"),
+            &String::from_str(&env, "health"),
+            &150,
+            &50000,
+        );
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+        let result = client.try_pay_premium(&owner, &policy_id, &token.address());
+        assert!(result.is_err());
+        assert!(client
+            .get_premium_payments(&owner, &0, &u64::MAX)
+            .is_empty());
+    }
+
+    // --- lapse and reinstatement ---
+
+    fn set_ledger_time(env: &Env, timestamp: u64) {
+        let mut ledger = env.ledger().get();
+        ledger.timestamp = timestamp;
+        env.ledger().set(ledger);
+    }
+
+    /// Register a token, make it the settlement token for new policies and
+    /// fund its claims reserve with `reserve`.
+    fn setup_settlement(
+        env: &Env,
+        client: &InsuranceClient,
+        admin: &Address,
+        reserve: i128,
+    ) -> Address {
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        client.set_settlement_token(admin, &token);
+        if reserve > 0 {
+            let funder = Address::generate(env);
+            soroban_sdk::token::StellarAssetClient::new(env, &token).mint(&funder, &reserve);
+            client.fund_claims_reserve(&funder, &token, &reserve);
+        }
+        token
+    }
+
+    #[test]
+    fn test_sync_policy_status_lapses_after_grace() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let ids = setup_policies(&env, &client, &owner, 2);
+        let due = client.get_policy(&1).unwrap().next_payment_date;
+
+        // Still inside the grace window
+        set_ledger_time(&env, due + DEFAULT_GRACE_DAYS as u64 * 86400);
+        assert!(client.sync_policy_status().is_empty());
+
+        // Keep policy 2 current, let policy 1 lapse
+        let token = setup_premium_token(&env, &client, &owner);
+        client.pay_premium(&owner, &ids.get(1).unwrap(), &token);
+        set_ledger_time(&env, due + DEFAULT_GRACE_DAYS as u64 * 86400 + 1);
+        let lapsed = client.sync_policy_status();
+        assert_eq!(lapsed, soroban_sdk::vec![&env, 1u32]);
+
+        assert!(!client.get_policy(&1).unwrap().active);
+        assert!(client.get_lapsed_at(&1).is_some());
+        let page = client.get_active_policies(&owner, &0, &0);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.items.get(0).unwrap().id, 2);
+        assert_eq!(client.get_total_monthly_premium(&owner), 100);
+
+        // Lapsed policies no longer accept regular premiums
+        let result = client.try_pay_premium(&owner, &1, &token);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reinstate_policy_settles_back_premiums() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        setup_policies(&env, &client, &owner, 1);
+        let token = setup_premium_token(&env, &client, &owner);
+        let due = client.get_policy(&1).unwrap().next_payment_date;
+
+        // Two full periods overdue, so two premiums are owed
+        let now = due + 2 * 30 * 86400 - 1;
+        set_ledger_time(&env, now);
+        client.sync_policy_status();
+        assert_eq!(client.get_reinstatement_cost(&1), 100);
+
+        let settled = client.reinstate_policy(&owner, &1, &token);
+        assert_eq!(settled, 100);
+
+        let policy = client.get_policy(&1).unwrap();
+        assert!(policy.active);
+        assert!(policy.next_payment_date > now);
+        assert_eq!(policy.next_payment_date, due + 2 * 30 * 86400);
+        assert!(client.get_lapsed_at(&1).is_none());
+        assert_eq!(client.get_total_monthly_premium(&owner), 50);
+        assert_eq!(client.get_premiums_paid(&owner, &0, &u64::MAX), 100);
+
+        // Reinstating again fails because the policy is no longer lapsed
+        let result = client.try_reinstate_policy(&owner, &1, &token);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deactivated_lapsed_policy_cannot_be_reinstated() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        setup_policies(&env, &client, &owner, 1);
+        let token = setup_premium_token(&env, &client, &owner);
+        let due = client.get_policy(&1).unwrap().next_payment_date;
+
+        set_ledger_time(&env, due + DEFAULT_GRACE_DAYS as u64 * 86400 + 1);
+        client.sync_policy_status();
+        client.deactivate_policy(&owner, &1);
+
+        let result = client.try_reinstate_policy(&owner, &1, &token);
+        assert!(result.is_err());
+    }
+
+    // --- grace periods ---
+
+    #[test]
+    fn test_grace_period_resolution_order() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &owner, 2);
+
+        assert_eq!(client.get_grace_period(&1), DEFAULT_GRACE_DAYS);
+
+        client.set_product_grace_period(&admin, &String::from_str(&env, "health"), &5);
+        assert_eq!(client.get_grace_period(&1), 5);
+        assert_eq!(client.get_grace_period(&2), 5);
+
+        client.set_policy_grace_period(&admin, &2, &Some(10));
+        assert_eq!(client.get_grace_period(&2), 10);
+        client.set_policy_grace_period(&admin, &2, &None);
+        assert_eq!(client.get_grace_period(&2), 5);
+
+        let result = client.try_set_product_grace_period(
+            &admin,
+            &String::from_str(&env, "health"),
+            &(MAX_GRACE_DAYS + 1),
+        );
+        assert!(result.is_err());
+        let result = client.try_set_policy_grace_period(&owner, &1, &Some(1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_overdue_policies_respect_grace_period() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &owner, 2);
+        client.set_policy_grace_period(&admin, &2, &Some(3));
+        let due = client.get_policy(&1).unwrap().next_payment_date;
+
+        // One day late is inside both grace periods
+        set_ledger_time(&env, due + 86400);
+        assert!(client.get_overdue_policies(&owner).is_empty());
+
+        // Four days late misses only the 3-day grace period
+        set_ledger_time(&env, due + 4 * 86400);
+        assert_eq!(
+            client.get_overdue_policies(&owner),
+            soroban_sdk::vec![&env, 2u32]
+        );
+        assert_eq!(client.sync_policy_status(), soroban_sdk::vec![&env, 2u32]);
+        assert!(client.get_policy(&1).unwrap().active);
+    }
+
+    // --- terms and renewal ---
+
+    #[test]
+    fn test_policy_expires_at_term_end() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &owner, 1);
+        // Keep premiums from lapsing so only the term matters
+        client.set_policy_grace_period(&admin, &1, &Some(MAX_GRACE_DAYS));
+        let policy = client.get_policy(&1).unwrap();
+        assert_eq!(policy.term_end, POLICY_TERM_SECS);
+
+        // Renewal is not open until 30 days before term_end
+        assert!(client.try_renew_policy(&owner, &1).is_err());
+
+        set_ledger_time(&env, policy.term_end);
+        client.sync_policy_status();
+        assert!(!client.get_policy(&1).unwrap().active);
+        assert_eq!(client.get_expired_at(&1), Some(policy.term_end));
+        assert_eq!(client.get_active_policies(&owner, &0, &0).count, 0);
+        assert_eq!(client.get_total_monthly_premium(&owner), 0);
+
+        // Renewing an expired policy reactivates it with a fresh term
+        let term_end = client.renew_policy(&owner, &1);
+        assert_eq!(term_end, policy.term_end + POLICY_TERM_SECS);
+        assert!(client.get_policy(&1).unwrap().active);
+        assert!(client.get_expired_at(&1).is_none());
+        assert_eq!(client.get_total_monthly_premium(&owner), 50);
+    }
+
+    #[test]
+    fn test_renew_within_window_and_auto_renew() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &owner, 2);
+        client.set_product_grace_period(&admin, &String::from_str(&env, "health"), &MAX_GRACE_DAYS);
+        let term_end = client.get_policy(&1).unwrap().term_end;
+
+        set_ledger_time(&env, term_end - RENEWAL_WINDOW_SECS);
+        assert_eq!(client.renew_policy(&owner, &1), term_end + POLICY_TERM_SECS);
+
+        client.set_auto_renew(&owner, &2, &true);
+        assert!(client.get_auto_renew(&2));
+        set_ledger_time(&env, term_end);
+        client.sync_policy_status();
+        let policy = client.get_policy(&2).unwrap();
+        assert!(policy.active);
+        assert_eq!(policy.term_end, term_end + POLICY_TERM_SECS);
+        assert!(client.get_expired_at(&2).is_none());
+    }
+
+    // --- premium auto-pay ---
+
+    #[test]
+    fn test_deposit_premium_funds_pays_soonest_due_first() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let split = Address::generate(&env);
+
+        let token = setup_premium_token(&env, &client, &owner);
+        let ids = setup_policies(&env, &client, &owner, 2);
+        let (cheap, dear) = (ids.get(0).unwrap(), ids.get(1).unwrap());
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&split, &1_000);
+        let collector = client.get_premium_collector().unwrap();
+
+        // Prepaying the cheap policy leaves the dear one due first
+        client.pay_premium(&owner, &cheap, &token);
+        let dear_due = client.get_policy(&dear).unwrap().next_payment_date;
+
+        // 120 covers the dear premium (100); the cheap one (50) waits
+        assert_eq!(
+            client.deposit_premium_funds(&split, &owner, &token, &120),
+            20
+        );
+        assert_eq!(
+            client.get_policy(&dear).unwrap().next_payment_date,
+            dear_due + 30 * 86400
+        );
+        assert_eq!(client.get_premium_escrow(&owner, &token), 20);
+
+        // The escrowed 20 plus 40 now covers the cheap premium
+        assert_eq!(
+            client.deposit_premium_funds(&split, &owner, &token, &40),
+            10
+        );
+
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&collector), 50 + 100 + 50);
+        assert_eq!(token_client.balance(&contract_id), 10);
+        assert_eq!(token_client.balance(&split), 1_000 - 160);
+
+        let payments = client.get_premium_payments(&owner, &0, &u64::MAX);
+        assert_eq!(payments.len(), 3);
+        assert_eq!(payments.get(1).unwrap().policy_id, dear);
+        assert_eq!(payments.get(2).unwrap().policy_id, cheap);
+
+        // The owner can take back what the escrow still holds
+        assert!(client
+            .try_withdraw_premium_escrow(&owner, &token, &11)
+            .is_err());
+        assert_eq!(client.withdraw_premium_escrow(&owner, &token, &10), 0);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert_eq!(token_client.balance(&owner), 10_000 - 50 + 10);
+    }
+
+    #[test]
+    fn test_deposit_premium_funds_settles_only_matching_token() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let token = setup_premium_token(&env, &client, &owner);
+        let policy_id = setup_policies(&env, &client, &owner, 1).get(0).unwrap();
+        let due = client.get_policy(&policy_id).unwrap().next_payment_date;
+        let other = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &other).mint(&owner, &500);
+
+        // A deposit in another token stays in escrow
+        assert_eq!(
+            client.deposit_premium_funds(&owner, &owner, &other, &200),
+            200
+        );
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().next_payment_date,
+            due
+        );
+        assert!(client.try_pay_premium(&owner, &policy_id, &other).is_err());
+        assert_eq!(client.withdraw_premium_escrow(&owner, &other, &200), 0);
+        assert_eq!(
+            soroban_sdk::token::Client::new(&env, &other).balance(&owner),
+            500
+        );
+
+        client.deposit_premium_funds(&owner, &owner, &token, &200);
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().next_payment_date,
+            due + 30 * 86400
+        );
+    }
+
+    #[test]
+    fn test_deposit_premium_funds_escrows_without_collector() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        setup_policies(&env, &client, &owner, 1);
+        let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+        soroban_sdk::token::StellarAssetClient::new(&env, &token.address()).mint(&owner, &500);
+
+        assert_eq!(
+            client.deposit_premium_funds(&owner, &owner, &token.address(), &200),
+            200
+        );
+        assert_eq!(client.get_premium_escrow(&owner, &token.address()), 200);
+        assert!(client
+            .get_premium_payments(&owner, &0, &u64::MAX)
+            .is_empty());
+        assert!(client
+            .try_deposit_premium_funds(&owner, &owner, &token.address(), &0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_prepaid_premium_refunded_pro_rata_on_cancel() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+
+        let policy_id = setup_policies(&env, &client, &owner, 1).get(0).unwrap();
+        let token = setup_premium_token(&env, &client, &owner);
+        let collector = client.get_premium_collector().unwrap();
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        let start = env.ledger().timestamp();
+
+        assert!(client
+            .try_prepay_premium(&owner, &policy_id, &token, &0)
+            .is_err());
+        // Four installments of 50, covering days 30..150
+        assert_eq!(client.prepay_premium(&owner, &policy_id, &token, &4), 200);
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().next_payment_date,
+            start + 150 * 86400
+        );
+        assert_eq!(client.get_premium_payments(&owner, &0, &u64::MAX).len(), 4);
+        assert_eq!(token_client.balance(&contract_id), 200);
+
+        // Nothing is earned before the first prepaid installment falls due
+        assert_eq!(client.release_earned_premium(&policy_id), 0);
+
+        // Halfway through the prepaid window half the escrow is earned
+        set_ledger_time(&env, start + 90 * 86400);
+        assert_eq!(client.release_earned_premium(&policy_id), 100);
+        assert_eq!(client.get_prepaid_premium(&policy_id).unwrap().balance, 100);
+
+        // Cancelling with 30 of the 120 days left refunds a quarter
+        set_ledger_time(&env, start + 120 * 86400);
+        client.deactivate_policy(&owner, &policy_id);
+        assert_eq!(token_client.balance(&collector), 150);
+        assert_eq!(token_client.balance(&owner), 10_000 - 200 + 50);
+        assert_eq!(token_client.balance(&contract_id), 0);
+        assert!(client.get_prepaid_premium(&policy_id).is_none());
+
+        let refunds = client.get_premium_refunds(&owner, &0, &u64::MAX);
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds.get(0).unwrap().policy_id, policy_id);
+        assert_eq!(
+            client.get_premiums_refunded(&owner, &(start + 100 * 86400), &u64::MAX),
+            50
+        );
+        assert_eq!(client.get_premiums_refunded(&owner, &0, &start), 0);
+    }
+
+    // --- underwriting ---
+
+    #[test]
+    fn test_underwriting_rules_enforced_at_create_policy() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let year = 365 * 86400u64;
+        set_ledger_time(&env, 40 * year);
+
+        let name = String::from_str(&env, "Life");
+        let life = String::from_str(&env, "life");
+        let bands = soroban_sdk::vec![
+            &env,
+            AgeBand {
+                min_age: 18,
+                max_age: 45,
+                max_coverage: 100_000,
+            },
+            AgeBand {
+                min_age: 46,
+                max_age: 65,
+                max_coverage: 40_000,
+            },
+        ];
+        assert!(client
+            .try_set_underwriting_rule(&owner, &life, &bands, &30)
+            .is_err());
+        client.set_underwriting_rule(&admin, &life, &bands, &30);
+        client.set_max_policies_per_user(&admin, &2);
+
+        // Age bands need a date of birth
+        assert!(client
+            .try_create_policy(&owner, &name, &life, &100, &50_000)
+            .is_err());
+        client.set_date_of_birth(&owner, &(5 * year));
+        assert!(client
+            .try_create_policy(&owner, &name, &life, &100, &150_000)
+            .is_err());
+        let policy_id = client.create_policy(&owner, &name, &life, &100, &50_000);
+
+        // Claims wait out the product's waiting period
+        assert_eq!(
+            client.get_claims_open_at(&policy_id),
+            Some(40 * year + 30 * 86400)
+        );
+        let evidence = BytesN::from_array(&env, &[1u8; 32]);
+        assert!(client
+            .try_file_claim(&owner, &policy_id, &1000, &evidence)
+            .is_err());
+        set_ledger_time(&env, 40 * year + 30 * 86400);
+        client.file_claim(&owner, &policy_id, &1000, &evidence);
+
+        // Products without a rule only count against the policy cap
+        let health = String::from_str(&env, "health");
+        client.create_policy(&owner, &name, &health, &100, &500_000);
+        assert!(client
+            .try_create_policy(&owner, &name, &health, &100, &1000)
+            .is_err());
+        client.set_max_policies_per_user(&admin, &0);
+        client.create_policy(&owner, &name, &health, &100, &1000);
+    }
+
+    // --- reserves and solvency ---
+
+    #[test]
+    fn test_issuance_blocked_below_min_solvency() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let adjuster = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let funder = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        client.set_premium_collector(&admin, &Address::generate(&env));
+        let token = setup_settlement(&env, &client, &admin, 0);
+        let minter = soroban_sdk::token::StellarAssetClient::new(&env, &token);
+        minter.mint(&owner, &10_000);
+        minter.mint(&funder, &10_000);
+
+        assert_eq!(client.get_solvency_ratio(&token), 10_000);
+        let name = String::from_str(&env, "Health");
+        let health = String::from_str(&env, "health");
+        let policy_id = client.create_policy(&owner, &name, &health, &1000, &20000);
+        assert_eq!(client.get_solvency_ratio(&token), 0);
+
+        // Premiums go to the collector and do not build reserves
+        for _ in 0..3 {
+            client.pay_premium(&owner, &policy_id, &token);
+        }
+        assert_eq!(client.get_solvency_ratio(&token), 0);
+        client.fund_claims_reserve(&funder, &token, &3000);
+        assert_eq!(client.get_solvency_ratio(&token), 1500);
+
+        assert!(client.try_set_min_solvency(&owner, &1000).is_err());
+        client.set_min_solvency(&admin, &1000);
+        client.create_policy(&owner, &name, &health, &100, &20000);
+        assert_eq!(client.get_solvency_ratio(&token), 750);
+        assert!(client
+            .try_create_policy(&owner, &name, &health, &100, &20000)
+            .is_err());
+
+        // A reserve in another token does not back this token's policies
+        let other = setup_settlement(&env, &client, &admin, 100_000);
+        client.set_settlement_token(&admin, &token);
+        assert_eq!(client.get_solvency_ratio(&other), 10_000);
+        assert!(client
+            .try_create_policy(&owner, &name, &health, &100, &20000)
+            .is_err());
+
+        // Paid claims draw down the reserves
+        let evidence = BytesN::from_array(&env, &[4u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &1000, &evidence);
+        client.approve_claim(&adjuster, &claim_id, &String::from_str(&env, "covered"));
+        client.pay_claim(&adjuster, &claim_id);
+        let status = client.get_reserve_status(&token);
+        assert_eq!(status.token, token);
+        assert_eq!(status.claims_paid, 1000);
+        assert_eq!(status.reserves, 2000);
+        assert_eq!(status.exposed_coverage, 40000);
+        assert_eq!(status.solvency_bps, 500);
+        assert_eq!(status.min_solvency_bps, 1000);
+
+        client.set_min_solvency(&admin, &0);
+        client.create_policy(&owner, &name, &health, &100, &20000);
+    }
+
+    // --- agent commissions ---
+
+    #[test]
+    fn test_agent_commission_accrues_and_is_claimed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let agent = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_premium_collector(&admin, &Address::generate(&env));
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token_admin = soroban_sdk::token::StellarAssetClient::new(&env, &token);
+        token_admin.mint(&owner, &10_000);
+        token_admin.mint(&contract_id, &1_000);
+
+        let schedule = CommissionSchedule {
+            first_year_bps: 1000,
+            renewal_bps: 250,
+        };
+        let name = String::from_str(&env, "Health");
+        let health = String::from_str(&env, "health");
+        assert!(client
+            .try_create_agent_policy(&owner, &agent, &name, &health, &1000, &20000)
+            .is_err());
+        client.register_agent(&admin, &agent, &schedule);
+        let policy_id = client.create_agent_policy(&owner, &agent, &name, &health, &1000, &20000);
+        assert_eq!(client.get_policy_agent(&policy_id).unwrap().agent, agent);
+
+        // 10% during the first term, 2.5% after it
+        client.pay_premium(&owner, &policy_id, &token);
+        set_ledger_time(&env, 365 * 86400);
+        client.pay_premium(&owner, &policy_id, &token);
+        // Premiums on policies without an agent earn nothing
+        let direct = client.create_policy(&owner, &name, &health, &1000, &20000);
+        client.pay_premium(&owner, &direct, &token);
+        assert_eq!(client.get_agent(&agent).unwrap().accrued, 125);
+
+        client.set_agent_active(&admin, &agent, &false);
+        assert!(client
+            .try_create_agent_policy(&owner, &agent, &name, &health, &1000, &20000)
+            .is_err());
+
+        assert_eq!(client.claim_commission(&agent, &token), 125);
+        assert!(client.try_claim_commission(&agent, &token).is_err());
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&agent), 125);
+
+        assert!(client.try_get_commission_totals(&owner).is_err());
+        let totals = client.get_commission_totals(&admin);
+        assert_eq!(totals.agents, 1);
+        assert_eq!(totals.policies_sold, 1);
+        assert_eq!(totals.accrued, 125);
+        assert_eq!(totals.claimed, 125);
+    }
+
+    // --- claims ---
+
+    fn setup_claim(env: &Env, client: &InsuranceClient, owner: &Address) -> (u32, Address) {
+        let admin = Address::generate(env);
+        let adjuster = Address::generate(env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        setup_settlement(env, client, &admin, 50000);
+        let policy_id = client.create_policy(
+            owner,
+            &String::from_str(env, "Health"),
+            &String::from_str(env, "health"),
+            &150,
+            &50000,
+        );
+        (policy_id, adjuster)
+    }
+
+    #[test]
+    fn test_claim_lifecycle_pays_policyholder() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let (policy_id, adjuster) = setup_claim(&env, &client, &owner);
+
+        let evidence = BytesN::from_array(&env, &[7u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &20000, &evidence);
+        let claim = client.get_claim(&claim_id).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Filed);
+        assert_eq!(claim.evidence_hash, evidence);
+
+        client.approve_claim(&adjuster, &claim_id, &String::from_str(&env, "covered"));
+        let claim = client.get_claim(&claim_id).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Approved);
+        assert_eq!(claim.adjuster, Some(adjuster.clone()));
+
+        client.pay_claim(&adjuster, &claim_id);
+
+        let token = client.get_policy_token(&policy_id).unwrap();
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 20000);
+        assert_eq!(token_client.balance(&contract_id), 30000);
+        assert_eq!(client.get_claims_reserve(&token), 30000);
+        assert_eq!(
+            client.get_claim(&claim_id).unwrap().status,
+            ClaimStatus::Paid
+        );
+        assert_eq!(
+            client
+                .get_claims_by_status(&owner, &ClaimStatus::Paid)
+                .len(),
+            1
+        );
+
+        // A paid claim cannot be paid again
+        let result = client.try_pay_claim(&adjuster, &claim_id);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_life_claim_pays_beneficiaries_by_share() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let (_, adjuster) = setup_claim(&env, &client, &owner);
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Life"),
+            &String::from_str(&env, "life"),
+            &100,
+            &90000,
+        );
+
+        let spouse = Address::generate(&env);
+        let child = Address::generate(&env);
+        let beneficiaries =
+            soroban_sdk::vec![&env, (spouse.clone(), 6000u32), (child.clone(), 4000u32)];
+        client.set_beneficiaries(&owner, &policy_id, &beneficiaries);
+        assert_eq!(client.get_beneficiaries(&policy_id), beneficiaries);
+
+        let evidence = BytesN::from_array(&env, &[9u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &10001, &evidence);
+        client.approve_claim(&adjuster, &claim_id, &String::from_str(&env, "covered"));
+        client.pay_claim(&adjuster, &claim_id);
+
+        let token = client.get_policy_token(&policy_id).unwrap();
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&spouse), 6000);
+        assert_eq!(token_client.balance(&child), 4001);
+        assert_eq!(token_client.balance(&owner), 0);
     }
 
-    // --- get_active_policies ---
+    #[test]
+    fn test_set_beneficiaries_validates_shares() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let (policy_id, _) = setup_claim(&env, &client, &owner);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+
+        let short = soroban_sdk::vec![&env, (a.clone(), 5000u32), (b.clone(), 4000u32)];
+        assert!(client
+            .try_set_beneficiaries(&owner, &policy_id, &short)
+            .is_err());
+        let zero = soroban_sdk::vec![&env, (a.clone(), 10000u32), (b.clone(), 0u32)];
+        assert!(client
+            .try_set_beneficiaries(&owner, &policy_id, &zero)
+            .is_err());
+        let dup = soroban_sdk::vec![&env, (a.clone(), 5000u32), (a.clone(), 5000u32)];
+        assert!(client
+            .try_set_beneficiaries(&owner, &policy_id, &dup)
+            .is_err());
+        let full = soroban_sdk::vec![&env, (a.clone(), 10000u32)];
+        assert!(client.try_set_beneficiaries(&b, &policy_id, &full).is_err());
+
+        client.set_beneficiaries(&owner, &policy_id, &full);
+        assert_eq!(client.get_beneficiaries(&policy_id).len(), 1);
+        client.set_beneficiaries(&owner, &policy_id, &Vec::new(&env));
+        assert!(client.get_beneficiaries(&policy_id).is_empty());
+    }
 
     #[test]
-    fn test_get_active_policies_empty() {
+    fn test_rejected_claim_records_reason_and_cannot_be_paid() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let (policy_id, adjuster) = setup_claim(&env, &client, &owner);
 
-        let page = client.get_active_policies(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
+        let evidence = BytesN::from_array(&env, &[1u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &1000, &evidence);
+        let reason = String::from_str(&env, "not covered");
+        client.reject_claim(&adjuster, &claim_id, &reason);
+
+        let claim = client.get_claim(&claim_id).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Rejected);
+        assert_eq!(claim.reason, reason);
+        assert_eq!(client.get_policy_claims(&policy_id).len(), 1);
+
+        let result = client.try_pay_claim(&adjuster, &claim_id);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_get_active_policies_single_page() {
+    fn test_claims_are_paid_only_from_funded_reserve() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let adjuster = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        let name = String::from_str(&env, "Health");
+        let health = String::from_str(&env, "health");
+        let evidence = BytesN::from_array(&env, &[5u8; 32]);
+        let approved = String::from_str(&env, "covered");
+
+        // Issued before a settlement token was set: nothing to pay in
+        let legacy = client.create_policy(&owner, &name, &health, &150, &50000);
+        let claim_id = client.file_claim(&owner, &legacy, &1000, &evidence);
+        client.approve_claim(&adjuster, &claim_id, &approved);
+        assert!(client.try_pay_claim(&adjuster, &claim_id).is_err());
+
+        let token = setup_settlement(&env, &client, &admin, 5000);
+        let policy_id = client.create_policy(&owner, &name, &health, &150, &50000);
+        assert_eq!(client.get_policy_token(&policy_id), Some(token.clone()));
+        assert_eq!(client.get_policy_token(&legacy), None);
+
+        // Funds the contract holds for other purposes do not back claims
+        let minter = soroban_sdk::token::StellarAssetClient::new(&env, &token);
+        minter.mint(&contract_id, &50000);
+        let claim_id = client.file_claim(&owner, &policy_id, &20000, &evidence);
+        client.approve_claim(&adjuster, &claim_id, &approved);
+        assert!(client.try_pay_claim(&adjuster, &claim_id).is_err());
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 0);
+
+        let funder = Address::generate(&env);
+        minter.mint(&funder, &20000);
+        assert!(client.try_fund_claims_reserve(&funder, &token, &0).is_err());
+        assert_eq!(client.fund_claims_reserve(&funder, &token, &20000), 25000);
+        client.pay_claim(&adjuster, &claim_id);
+        assert_eq!(token_client.balance(&owner), 20000);
+        assert_eq!(client.get_claims_reserve(&token), 5000);
+
+        // Only the admin withdraws, and only what the reserve holds
+        let treasury = Address::generate(&env);
+        assert!(client
+            .try_withdraw_claims_reserve(&owner, &token, &1000, &treasury)
+            .is_err());
+        assert!(client
+            .try_withdraw_claims_reserve(&admin, &token, &5001, &treasury)
+            .is_err());
+        assert_eq!(
+            client.withdraw_claims_reserve(&admin, &token, &5000, &treasury),
+            0
+        );
+        assert_eq!(token_client.balance(&treasury), 5000);
+        assert_eq!(token_client.balance(&contract_id), 50000);
+    }
 
-        setup_policies(&env, &client, &owner, 5);
+    #[test]
+    fn test_claim_adjudication_requires_adjuster() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let (policy_id, _adjuster) = setup_claim(&env, &client, &owner);
 
-        let page = client.get_active_policies(&owner, &0, &10);
-        assert_eq!(page.count, 5);
-        assert_eq!(page.next_cursor, 0);
+        let evidence = BytesN::from_array(&env, &[2u8; 32]);
+        let claim_id = client.file_claim(&owner, &policy_id, &1000, &evidence);
+        let result = client.try_approve_claim(&owner, &claim_id, &String::from_str(&env, "ok"));
+        assert!(result.is_err());
+        assert_eq!(
+            client.get_claim(&claim_id).unwrap().status,
+            ClaimStatus::Filed
+        );
     }
 
     #[test]
-    fn test_pay_premium_policy_not_found() {
-        let env = Env::default();
+    fn test_file_claim_rejects_amount_above_coverage() {
+        let env = make_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, Insurance);
         let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let (policy_id, _adjuster) = setup_claim(&env, &client, &owner);
 
-        // No policies created — policy ID 999 does not exist
-        let result = client.try_pay_premium(&owner, &999u32);
-
+        let evidence = BytesN::from_array(&env, &[3u8; 32]);
+        let result = client.try_file_claim(&owner, &policy_id, &50001, &evidence);
+        assert!(result.is_err());
+        let result = client.try_file_claim(&owner, &policy_id, &0, &evidence);
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_get_active_policies_paginated() {
-        let env = Env::default();
+    fn test_claim_stats_count_by_period() {
+        let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        set_ledger_time(&env, 1000);
+        let (policy_id, adjuster) = setup_claim(&env, &client, &owner);
+
+        let evidence = BytesN::from_array(&env, &[1u8; 32]);
+        let paid = client.file_claim(&owner, &policy_id, &10000, &evidence);
+        let rejected = client.file_claim(&owner, &policy_id, &5000, &evidence);
+        client.file_claim(&owner, &policy_id, &2000, &evidence);
+        client.approve_claim(&adjuster, &paid, &String::from_str(&env, "covered"));
+        client.reject_claim(&adjuster, &rejected, &String::from_str(&env, "excluded"));
+
+        set_ledger_time(&env, 5000);
+        client.pay_claim(&adjuster, &paid);
+
+        let early = client.get_claim_stats(&owner, &0, &2000);
+        assert_eq!(early.filed, 3);
+        assert_eq!(early.approved, 1);
+        assert_eq!(early.rejected, 1);
+        assert_eq!(early.total_paid, 0);
+
+        // The payout lands in the later period; the decisions do not
+        let late = client.get_claim_stats(&owner, &3000, &u64::MAX);
+        assert_eq!(late.filed, 0);
+        assert_eq!(late.approved, 0);
+        assert_eq!(late.total_paid, 10000);
+
+        let other = client.get_claim_stats(&Address::generate(&env), &0, &u64::MAX);
+        assert_eq!(other.filed, 0);
+    }
 
-        setup_policies(&env, &client, &owner, 7);
+    // --- insurer registry and quotes ---
 
-        let page1 = client.get_active_policies(&owner, &0, &3);
-        assert_eq!(page1.count, 3);
-        assert!(page1.next_cursor > 0);
+    fn product(
+        env: &Env,
+        coverage_type: &str,
+        max_coverage: i128,
+        rate_bps: u32,
+    ) -> InsurerProduct {
+        InsurerProduct {
+            coverage_type: String::from_str(env, coverage_type),
+            min_coverage: 1000,
+            max_coverage,
+            rate_bps,
+        }
+    }
 
-        let page2 = client.get_active_policies(&owner, &page1.next_cursor, &3);
-        assert_eq!(page2.count, 3);
-        assert!(page2.next_cursor > 0);
+    #[test]
+    fn test_get_quotes_compares_active_insurers() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let acme = Address::generate(&env);
+        let mutual = Address::generate(&env);
+        let small = Address::generate(&env);
+
+        client.register_insurer(
+            &admin,
+            &acme,
+            &String::from_str(&env, "Acme"),
+            &soroban_sdk::vec![&env, product(&env, "health", 100_000, 150)],
+        );
+        client.register_insurer(
+            &admin,
+            &mutual,
+            &String::from_str(&env, "Mutual"),
+            &soroban_sdk::vec![
+                &env,
+                product(&env, "life", 100_000, 50),
+                product(&env, "health", 100_000, 120)
+            ],
+        );
+        // Only writes small policies
+        client.register_insurer(
+            &admin,
+            &small,
+            &String::from_str(&env, "Small"),
+            &soroban_sdk::vec![&env, product(&env, "health", 5_000, 100)],
+        );
 
-        let page3 = client.get_active_policies(&owner, &page2.next_cursor, &3);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
+        let health = String::from_str(&env, "health");
+        let quotes = client.get_quotes(&health, &10_000);
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes.get(0).unwrap().insurer, mutual);
+        assert_eq!(quotes.get(0).unwrap().monthly_premium, 120);
+        assert_eq!(quotes.get(1).unwrap().insurer, acme);
+        assert_eq!(quotes.get(1).unwrap().monthly_premium, 150);
+
+        // Suspended insurers are not quoted
+        client.set_insurer_active(&admin, &mutual, &false);
+        let quotes = client.get_quotes(&health, &10_000);
+        assert_eq!(quotes.len(), 1);
+        assert_eq!(quotes.get(0).unwrap().insurer, acme);
+
+        // Insurers manage their own rate table
+        client.update_rate_table(
+            &acme,
+            &soroban_sdk::vec![&env, product(&env, "health", 100_000, 90)],
+        );
+        assert_eq!(
+            client
+                .get_quotes(&health, &10_000)
+                .get(0)
+                .unwrap()
+                .monthly_premium,
+            90
+        );
     }
 
     #[test]
-    fn test_get_active_policies_excludes_inactive() {
+    fn test_accept_quote_creates_policy() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
+        let insurer = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.register_insurer(
+            &admin,
+            &insurer,
+            &String::from_str(&env, "Acme"),
+            &soroban_sdk::vec![&env, product(&env, "health", 100_000, 125)],
+        );
 
-        let ids = setup_policies(&env, &client, &owner, 4);
-        // Deactivate policy #2
-        client.deactivate_policy(&owner, &ids.get(1).unwrap());
+        let health = String::from_str(&env, "health");
+        let policy_id = client.accept_quote(
+            &owner,
+            &insurer,
+            &String::from_str(&env, "Family health"),
+            &health,
+            &10_001,
+        );
+        let policy = client.get_policy(&policy_id).unwrap();
+        // 10,001 * 1.25% = 125.0125, rounded up
+        assert_eq!(policy.monthly_premium, 126);
+        assert_eq!(policy.coverage_amount, 10_001);
+        assert_eq!(client.get_policy_insurer(&policy_id), Some(insurer.clone()));
 
-        let page = client.get_active_policies(&owner, &0, &10);
-        assert_eq!(page.count, 3); // only 3 active
-        for p in page.items.iter() {
-            assert!(p.active, "only active policies should be returned");
-        }
+        let result = client.try_accept_quote(
+            &owner,
+            &insurer,
+            &String::from_str(&env, "Too big"),
+            &health,
+            &200_000,
+        );
+        assert!(result.is_err());
     }
 
+    // --- riders ---
+
     #[test]
-    fn test_get_active_policies_multi_owner_isolation() {
+    fn test_riders_aggregate_premium_and_coverage() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
 
-        setup_policies(&env, &client, &owner_a, 3);
-        setup_policies(&env, &client, &owner_b, 5);
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Life"),
+            &String::from_str(&env, "life"),
+            &100,
+            &50000,
+        );
+        client.add_rider(
+            &owner,
+            &policy_id,
+            &RiderKind::AccidentalDeath,
+            &20,
+            &100000,
+        );
+        client.add_rider(&owner, &policy_id, &RiderKind::HospitalCash, &10, &5000);
+        assert!(client
+            .try_add_rider(&owner, &policy_id, &RiderKind::HospitalCash, &10, &5000)
+            .is_err());
+        assert!(client
+            .try_add_rider(
+                &Address::generate(&env),
+                &policy_id,
+                &RiderKind::AccidentalDeath,
+                &20,
+                &100000
+            )
+            .is_err());
+
+        assert_eq!(client.get_total_monthly_premium(&owner), 130);
+        let breakdown = client.get_coverage_breakdown(&policy_id).unwrap();
+        assert_eq!(breakdown.base_premium, 100);
+        assert_eq!(breakdown.base_coverage, 50000);
+        assert_eq!(breakdown.riders.len(), 2);
+        assert_eq!(breakdown.total_premium, 130);
+        assert_eq!(breakdown.total_coverage, 155000);
+
+        // Rider premiums are billed with the base premium
+        let token = setup_premium_token(&env, &client, &owner);
+        client.pay_premium(&owner, &policy_id, &token);
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 10_000 - 130);
+
+        client.remove_rider(&owner, &policy_id, &RiderKind::AccidentalDeath);
+        assert!(client
+            .try_remove_rider(&owner, &policy_id, &RiderKind::AccidentalDeath)
+            .is_err());
+        assert_eq!(client.get_total_monthly_premium(&owner), 110);
+        assert_eq!(
+            client
+                .get_coverage_breakdown(&policy_id)
+                .unwrap()
+                .total_coverage,
+            55000
+        );
 
-        let page = client.get_active_policies(&owner_a, &0, &20);
-        assert_eq!(page.count, 3);
-        for p in page.items.iter() {
-            assert_eq!(p.owner, owner_a);
-        }
+        client.deactivate_policy(&owner, &policy_id);
+        assert_eq!(client.get_total_monthly_premium(&owner), 0);
     }
 
     #[test]
-    fn test_get_all_policies_for_owner_includes_inactive() {
+    fn test_rejected_claim_arbitration() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let adjuster = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        let token = setup_settlement(&env, &client, &admin, 50000);
+        let policy_id = client.create_policy(
+            &owner,
+            &String::from_str(&env, "Health"),
+            &String::from_str(&env, "health"),
+            &150,
+            &50000,
+        );
+        let evidence = BytesN::from_array(&env, &[9u8; 32]);
+        let reason = String::from_str(&env, "not covered");
+        let grounds = String::from_str(&env, "covered under section 4");
+
+        let overturned = client.file_claim(&owner, &policy_id, &20000, &evidence);
+        let upheld = client.file_claim(&owner, &policy_id, &5000, &evidence);
+        let late = client.file_claim(&owner, &policy_id, &1000, &evidence);
+        for claim_id in [overturned, upheld, late] {
+            client.reject_claim(&adjuster, &claim_id, &reason);
+        }
+        assert!(client
+            .try_escalate_claim(&owner, &overturned, &grounds)
+            .is_err());
+        client.set_claims_arbiter(&admin, &arbiter);
+
+        // Overturned with a partial payout
+        client.escalate_claim(&owner, &overturned, &grounds);
+        assert_eq!(
+            client.get_claim(&overturned).unwrap().status,
+            ClaimStatus::Disputed
+        );
+        let ruling_reason = String::from_str(&env, "partly covered");
+        assert!(client
+            .try_rule_on_dispute(&adjuster, &overturned, &true, &15000, &ruling_reason)
+            .is_err());
+        assert!(client
+            .try_rule_on_dispute(&arbiter, &overturned, &true, &25000, &ruling_reason)
+            .is_err());
+        client.rule_on_dispute(&arbiter, &overturned, &true, &15000, &ruling_reason);
+        let claim = client.get_claim(&overturned).unwrap();
+        assert_eq!(claim.status, ClaimStatus::Paid);
+        assert_eq!(claim.amount, 15000);
+        assert_eq!(
+            soroban_sdk::token::Client::new(&env, &token).balance(&owner),
+            15000
+        );
+        assert_eq!(client.get_claims_reserve(&token), 35000);
+        assert!(client
+            .try_escalate_claim(&owner, &overturned, &grounds)
+            .is_err());
+
+        // An unruled dispute expires and can be escalated again
+        client.escalate_claim(&owner, &upheld, &grounds);
+        set_ledger_time(&env, 31 * 86400);
+        assert!(client
+            .try_rule_on_dispute(&arbiter, &upheld, &false, &0, &reason)
+            .is_err());
+        client.expire_dispute(&upheld);
+        assert_eq!(
+            client.get_claim(&upheld).unwrap().status,
+            ClaimStatus::Rejected
+        );
+        client.escalate_claim(&owner, &upheld, &grounds);
+        client.rule_on_dispute(&arbiter, &upheld, &false, &0, &reason);
+        let dispute = client.get_claim_dispute(&upheld).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+        assert!(client
+            .try_escalate_claim(&owner, &upheld, &grounds)
+            .is_err());
+
+        // The escalation window closes 30 days after the rejection
+        assert!(client.try_escalate_claim(&owner, &late, &grounds).is_err());
+    }
 
-        let ids = setup_policies(&env, &client, &owner, 4);
-        client.deactivate_policy(&owner, &ids.get(0).unwrap());
-        client.deactivate_policy(&owner, &ids.get(2).unwrap());
+    // --- group policies ---
 
-        let page = client.get_all_policies_for_owner(&owner, &0, &10);
-        assert_eq!(page.count, 4); // all 4 regardless of active status
-    }
+    #[contract]
+    pub struct MockFamilyWallet;
 
-    // --- limit clamping ---
+    #[contractimpl]
+    impl MockFamilyWallet {
+        pub fn add_member(env: Env, member: Address, role: FamilyRole) {
+            let record = FamilyMember {
+                address: member.clone(),
+                role,
+                spending_limit: 0,
+                added_at: env.ledger().timestamp(),
+            };
+            env.storage().instance().set(&member, &record);
+        }
+
+        pub fn remove_member(env: Env, member: Address) {
+            env.storage().instance().remove(&member);
+        }
+
+        pub fn get_family_member(env: Env, member: Address) -> Option<FamilyMember> {
+            env.storage().instance().get(&member)
+        }
+    }
 
     #[test]
-    fn test_limit_zero_uses_default() {
+    fn test_group_policy_covers_family_members() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let wallet_id = env.register_contract(None, MockFamilyWallet);
+        let wallet = MockFamilyWalletClient::new(&env, &wallet_id);
         let owner = Address::generate(&env);
+        let spouse = Address::generate(&env);
+        let child = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        wallet.add_member(&owner, &FamilyRole::Owner);
+        wallet.add_member(&spouse, &FamilyRole::Member);
+        wallet.add_member(&child, &FamilyRole::Member);
+
+        let name = String::from_str(&env, "Family health");
+        let health = String::from_str(&env, "health");
+        assert!(client
+            .try_create_group_policy(
+                &owner,
+                &name,
+                &health,
+                &200,
+                &wallet_id,
+                &soroban_sdk::vec![&env, (outsider.clone(), 1000i128)],
+            )
+            .is_err());
+        assert!(client
+            .try_create_group_policy(
+                &spouse,
+                &name,
+                &health,
+                &200,
+                &wallet_id,
+                &soroban_sdk::vec![&env, (child.clone(), 1000i128)],
+            )
+            .is_err());
+
+        let policy_id = client.create_group_policy(
+            &owner,
+            &name,
+            &health,
+            &200,
+            &wallet_id,
+            &soroban_sdk::vec![
+                &env,
+                (spouse.clone(), 30000i128),
+                (child.clone(), 10000i128)
+            ],
+        );
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().coverage_amount,
+            40000
+        );
+        assert_eq!(client.get_total_monthly_premium(&owner), 200);
+        assert_eq!(client.get_group_policies(&owner).len(), 1);
+
+        // Each member claims against their own coverage
+        let evidence = BytesN::from_array(&env, &[3u8; 32]);
+        assert!(client
+            .try_file_claim(&child, &policy_id, &20000, &evidence)
+            .is_err());
+        assert!(client
+            .try_file_claim(&owner, &policy_id, &1000, &evidence)
+            .is_err());
+        let claim_id = client.file_claim(&child, &policy_id, &10000, &evidence);
+        assert_eq!(client.get_claim(&claim_id).unwrap().claimant, child);
+
+        client.add_covered_member(&owner, &policy_id, &owner, &5000);
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().coverage_amount,
+            45000
+        );
+        client.file_claim(&owner, &policy_id, &5000, &evidence);
+
+        // Leaving the family wallet ends claim eligibility
+        wallet.remove_member(&spouse);
+        assert!(client
+            .try_file_claim(&spouse, &policy_id, &1000, &evidence)
+            .is_err());
+
+        client.remove_covered_member(&owner, &policy_id, &spouse);
+        assert_eq!(
+            client.get_policy(&policy_id).unwrap().coverage_amount,
+            15000
+        );
+        assert!(client.get_member_coverage(&spouse).is_empty());
+        let coverage = client.get_member_coverage(&child);
+        assert_eq!(coverage.len(), 1);
+        assert_eq!(coverage.get(0).unwrap().coverage_amount, 10000);
+        assert_eq!(coverage.get(0).unwrap().owner, owner);
+    }
 
-        setup_policies(&env, &client, &owner, 3);
-        let page = client.get_active_policies(&owner, &0, &0);
-        assert_eq!(page.count, 3);
+    // --- policy assignment ---
+
+    #[test]
+    fn test_assign_policy_completes_after_accept_and_acknowledge() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let remitter = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &remitter, 1);
+
+        client.assign_policy(&remitter, &1, &sibling);
+        assert!(!client.accept_policy_assignment(&sibling, &1));
+        assert_eq!(client.get_policy(&1).unwrap().owner, remitter);
+
+        // Only the insurer (the admin here) can acknowledge
+        assert!(client
+            .try_acknowledge_policy_assignment(&sibling, &1)
+            .is_err());
+        assert!(client.acknowledge_policy_assignment(&admin, &1));
+
+        assert_eq!(client.get_policy(&1).unwrap().owner, sibling);
+        assert!(client.get_policy_assignment(&1).is_none());
+        assert_eq!(client.get_total_monthly_premium(&remitter), 0);
+        assert_eq!(client.get_total_monthly_premium(&sibling), 50);
+        assert_eq!(client.get_active_policies(&sibling, &0, &0).count, 1);
     }
 
     #[test]
-    fn test_limit_clamped_to_max() {
+    fn test_assign_policy_requires_the_named_recipient() {
         let env = make_env();
         env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        setup_policies(&env, &client, &owner, 1);
 
-        setup_policies(&env, &client, &owner, 55);
-        let page = client.get_active_policies(&owner, &0, &9999);
-        assert_eq!(page.count, MAX_PAGE_LIMIT);
-        assert!(page.next_cursor > 0);
+        assert!(client.try_assign_policy(&owner, &1, &owner).is_err());
+        assert!(client.try_assign_policy(&stranger, &1, &sibling).is_err());
+
+        client.assign_policy(&owner, &1, &sibling);
+        assert!(client.try_accept_policy_assignment(&stranger, &1).is_err());
+
+        client.cancel_policy_assignment(&owner, &1);
+        assert!(client.get_policy_assignment(&1).is_none());
+        assert!(client.try_accept_policy_assignment(&sibling, &1).is_err());
     }
 
-    // --- existing event tests (unchanged) ---
+    // --- policy documents ---
 
     #[test]
-    fn test_create_policy_emits_event() {
+    fn test_policy_document_anchor_and_verify() {
         let env = make_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, Insurance);
         let client = InsuranceClient::new(&env, &contract_id);
         let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_policies(&env, &client, &owner, 1);
+
+        let pdf = BytesN::from_array(&env, &[4u8; 32]);
+        let other = BytesN::from_array(&env, &[5u8; 32]);
+        assert!(!client.verify_policy_document(&1, &pdf));
+
+        let sig = String::from_str(&env, "ipfs://sig/1");
+        client.anchor_policy_document(&admin, &1, &pdf, &sig);
+        assert!(client.verify_policy_document(&1, &pdf));
+        assert!(!client.verify_policy_document(&1, &other));
+        let doc = client.get_policy_document(&1).unwrap();
+        assert_eq!(doc.issuer, admin);
+        assert_eq!(doc.signature_ref, sig);
+
+        // Only the issuer can anchor, and only for existing policies
+        assert!(client
+            .try_anchor_policy_document(&owner, &1, &other, &sig)
+            .is_err());
+        assert!(client
+            .try_anchor_policy_document(&admin, &99, &other, &sig)
+            .is_err());
+    }
 
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Health Insurance"),
-            &String::from_str(&env, "health"),
-            &100,
-            &50000,
-        );
-        assert_eq!(policy_id, 1);
+    // --- parametric policies ---
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 2);
+    #[contract]
+    pub struct MockIndexOracle;
+
+    #[contractimpl]
+    impl MockIndexOracle {
+        pub fn set_index(env: Env, index: Symbol, value: i128) {
+            let reading = IndexReading {
+                value,
+                updated_at: env.ledger().timestamp(),
+            };
+            env.storage().instance().set(&index, &reading);
+        }
+
+        pub fn get_index(env: Env, index: Symbol) -> Option<IndexReading> {
+            env.storage().instance().get(&index)
+        }
     }
 
     #[test]
-    fn test_pay_premium_emits_event() {
+    fn test_check_trigger_pays_once_when_threshold_crossed() {
         let env = make_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, Insurance);
         let client = InsuranceClient::new(&env, &contract_id);
+        let oracle_id = env.register_contract(None, MockIndexOracle);
+        let oracle = MockIndexOracleClient::new(&env, &oracle_id);
         let owner = Address::generate(&env);
-
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let token = setup_settlement(&env, &client, &admin, 5000);
         let policy_id = client.create_policy(
             &owner,
-            &String::from_str(&env, "Emergency Coverage"),
-            &String::from_str(&env, "emergency"),
-            &75,
-            &25000,
+            &String::from_str(&env, "Harvest"),
+            &String::from_str(&env, "crop"),
+            &20,
+            &5000,
         );
-        let events_before = env.events().all().len();
 
-        let result = client.pay_premium(&owner, &policy_id);
-        assert!(result);
-
-        let events_after = env.events().all().len();
-        assert_eq!(events_after - events_before, 2);
+        // Pays when monthly rainfall (mm) drops to 20 or below
+        let rain = symbol_short!("rain_mm");
+        client.set_parametric_terms(&admin, &policy_id, &oracle_id, &rain, &20, &false, &3000);
+
+        // No reading yet
+        assert!(client.try_check_trigger(&policy_id).is_err());
+
+        oracle.set_index(&rain, &45);
+        assert!(!client.check_trigger(&policy_id));
+
+        oracle.set_index(&rain, &12);
+        assert!(client.check_trigger(&policy_id));
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 3000);
+        assert_eq!(client.get_claims_reserve(&token), 2000);
+        assert!(client
+            .get_parametric_terms(&policy_id)
+            .unwrap()
+            .triggered_at
+            .is_some());
+
+        // A second check cannot pay again
+        assert!(client.try_check_trigger(&policy_id).is_err());
+        assert_eq!(token_client.balance(&owner), 3000);
     }
 
     #[test]
-    fn test_policy_lifecycle_emits_all_events() {
+    fn test_check_trigger_rejects_stale_index() {
         let env = make_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, Insurance);
         let client = InsuranceClient::new(&env, &contract_id);
+        let oracle_id = env.register_contract(None, MockIndexOracle);
+        let oracle = MockIndexOracleClient::new(&env, &oracle_id);
         let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        setup_settlement(&env, &client, &admin, 0);
+        setup_policies(&env, &client, &owner, 1);
+        let delay = symbol_short!("delay_min");
+        client.set_parametric_terms(&admin, &1, &oracle_id, &delay, &180, &true, &1000);
+
+        oracle.set_index(&delay, &240);
+        set_ledger_time(&env, MAX_ORACLE_AGE_SECS + 1);
+        assert!(client.try_check_trigger(&1).is_err());
+
+        // Payouts above the coverage amount are rejected
+        let result =
+            client.try_set_parametric_terms(&admin, &1, &oracle_id, &delay, &180, &true, &10001);
+        assert!(result.is_err());
+    }
 
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Complete Lifecycle"),
-            &String::from_str(&env, "health"),
-            &150,
-            &75000,
+    // --- mutual pools ---
+
+    fn setup_pool(
+        env: &Env,
+        client: &InsuranceClient,
+        members: &[&Address],
+    ) -> (u32, Address, Address) {
+        let admin = Address::generate(env);
+        let adjuster = Address::generate(env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_claims_adjuster(&admin, &adjuster, &true);
+        let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+        let pool_id = client.create_pool(
+            &admin,
+            &String::from_str(env, "Village pool"),
+            &token.address(),
+            &1000,
+            &7,
         );
+        for member in members {
+            soroban_sdk::token::StellarAssetClient::new(env, &token.address()).mint(member, &1000);
+            client.join_pool(member, &pool_id);
+            client.contribute_to_pool(member, &pool_id, &500);
+        }
+        (pool_id, adjuster, token.address())
+    }
 
-        client.pay_premium(&owner, &policy_id);
-        client.deactivate_policy(&owner, &policy_id);
+    #[test]
+    fn test_pool_claim_after_waiting_period() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let (pool_id, adjuster, token) = setup_pool(&env, &client, &[&a, &b]);
+
+        let pool = client.get_pool(&pool_id).unwrap();
+        assert_eq!(pool.balance, 1000);
+        assert_eq!(pool.member_count, 2);
+        // 1000 held against 2000 of member caps
+        assert_eq!(client.get_pool_solvency(&pool_id), 5000);
+
+        // Still inside the 7-day waiting period
+        assert!(client
+            .try_pay_pool_claim(&adjuster, &pool_id, &a, &300)
+            .is_err());
+
+        set_ledger_time(&env, 7 * 86400);
+        client.pay_pool_claim(&adjuster, &pool_id, &a, &300);
+        let token_client = soroban_sdk::token::Client::new(&env, &token);
+        assert_eq!(token_client.balance(&a), 800);
+        assert_eq!(client.get_pool(&pool_id).unwrap().balance, 700);
+        assert_eq!(client.get_pool_member(&pool_id, &a).unwrap().claimed, 300);
+        // 700 held against 1700 still claimable
+        assert_eq!(client.get_pool_solvency(&pool_id), 4117);
+
+        let summaries = client.get_pool_summaries(&a);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries.get(0).unwrap().contributed, 500);
+        assert_eq!(summaries.get(0).unwrap().claimed, 300);
+    }
 
-        let events = env.events().all();
-        assert_eq!(events.len(), 6);
+    #[test]
+    fn test_pool_claim_limits() {
+        let env = make_env();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, Insurance);
+        let client = InsuranceClient::new(&env, &contract_id);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let (pool_id, adjuster, _) = setup_pool(&env, &client, &[&a, &b]);
+        set_ledger_time(&env, 7 * 86400);
+
+        // Above the per-member cap
+        assert!(client
+            .try_pay_pool_claim(&adjuster, &pool_id, &a, &1001)
+            .is_err());
+        // Not a member
+        assert!(client
+            .try_pay_pool_claim(&adjuster, &pool_id, &outsider, &10)
+            .is_err());
+        // Within the cap but above the pool balance
+        client.pay_pool_claim(&adjuster, &pool_id, &a, &900);
+        assert!(client
+            .try_pay_pool_claim(&adjuster, &pool_id, &b, &200)
+            .is_err());
+        // Only adjusters pay pool claims
+        assert!(client.try_pay_pool_claim(&a, &pool_id, &b, &50).is_err());
+        // Joining twice is rejected
+        assert!(client.try_join_pool(&a, &pool_id).is_err());
     }
 
     // ====================================================================
@@ -1391,7 +8018,8 @@ mod test {
         });
 
         // pay_premium calls extend_instance_ttl → re-extends TTL to 518,400
-        client.pay_premium(&owner, &1);
+        let token = setup_premium_token(&env, &client, &owner);
+        client.pay_premium(&owner, &1, &token);
 
         let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
         assert!(
@@ -1444,7 +8072,8 @@ mod test {
             max_entry_ttl: 700_000,
         });
 
-        client.pay_premium(&owner, &policy_id);
+        let token = setup_premium_token(&env, &client, &owner);
+        client.pay_premium(&owner, &policy_id, &token);
 
         // Phase 3: Advance to seq 1,020,000 (TTL = 8,400 < 17,280)
         env.ledger().set(LedgerInfo {
@@ -1542,10 +8171,9 @@ mod test {
     // Test: pay_premium after deactivate_policy (#104)
     // ──────────────────────────────────────────────────────────────────
 
-    /// After deactivating a policy, `pay_premium` must panic with
-    /// "Policy is not active". The policy must remain inactive.
+    /// After deactivating a policy, `pay_premium` must be rejected and the
+    /// policy must remain inactive.
     #[test]
-    #[should_panic(expected = "Policy is not active")]
     fn test_pay_premium_after_deactivate() {
         let env = Env::default();
         env.mock_all_auths();
@@ -1574,7 +8202,10 @@ mod test {
         let policy_after_deactivate = client.get_policy(&policy_id).unwrap();
         assert!(!policy_after_deactivate.active);
 
-        // 3. Attempt to pay premium — must panic
-        client.pay_premium(&owner, &policy_id);
+        // 3. Attempt to pay premium — must be rejected
+        let token = setup_premium_token(&env, &client, &owner);
+        let result = client.try_pay_premium(&owner, &policy_id, &token);
+        assert!(result.is_err());
+        assert!(!client.get_policy(&policy_id).unwrap().active);
     }
 }
@@ -127,6 +127,15 @@ pub trait SavingsGoalsTrait {
     /// # Gas Estimation
     /// ~4000 gas
     fn add_to_goal(env: Env, caller: Address, goal_id: u32, amount: i128) -> i128;
+
+    /// Get a savings goal by ID
+    ///
+    /// # Arguments
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Returns
+    /// The goal if it exists, None otherwise
+    fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal>;
 }
 
 /// Bill Payments contract client interface
@@ -135,18 +144,31 @@ pub trait SavingsGoalsTrait {
 /// Gas estimation: ~4000 gas per payment
 #[contractclient(name = "BillPaymentsClient")]
 pub trait BillPaymentsTrait {
-    /// Mark a bill as paid
+    /// Pay a bill to its payee in `token` and mark it paid
     ///
     /// # Arguments
     /// * `caller` - Address of the caller (must be the bill owner)
     /// * `bill_id` - ID of the bill
+    /// * `token` - Token the payee is paid in
     ///
     /// # Returns
-    /// Result indicating success or error
+    /// The amount paid
     ///
     /// # Gas Estimation
     /// ~4000 gas
-    fn pay_bill(env: Env, caller: Address, bill_id: u32);
+    fn pay_bill(env: Env, caller: Address, bill_id: u32, token: Address) -> i128;
+
+    /// Get a bill by ID
+    ///
+    /// # Arguments
+    /// * `bill_id` - ID of the bill
+    ///
+    /// # Returns
+    /// The bill, or None if it does not exist
+    ///
+    /// # Gas Estimation
+    /// ~1000 gas
+    fn get_bill(env: Env, bill_id: u32) -> Option<Bill>;
 }
 
 /// Insurance contract client interface
@@ -167,6 +189,84 @@ pub trait InsuranceTrait {
     /// # Gas Estimation
     /// ~4000 gas
     fn pay_premium(env: Env, caller: Address, policy_id: u32) -> bool;
+
+    /// Get a policy by ID
+    ///
+    /// # Arguments
+    /// * `policy_id` - ID of the policy
+    ///
+    /// # Returns
+    /// The policy, or None if it does not exist
+    ///
+    /// # Gas Estimation
+    /// ~1000 gas
+    fn get_policy(env: Env, policy_id: u32) -> Option<InsurancePolicy>;
+}
+
+// ============================================================================
+// Data structures from other contracts (needed for client traits)
+// ============================================================================
+
+/// Savings goal as stored by the Savings Goals contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SavingsGoal {
+    pub id: u32,
+    pub owner: Address,
+    pub name: soroban_sdk::String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub target_date: u64,
+    pub locked: bool,
+    pub unlock_date: Option<u64>,
+    pub tag: Option<Symbol>,
+}
+
+/// Bill as stored by the Bill Payments contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Bill {
+    pub id: u32,
+    pub owner: Address,
+    pub payee: Address,
+    pub name: soroban_sdk::String,
+    pub category: Symbol,
+    pub amount: i128,
+    pub amount_paid: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub paid: bool,
+    pub created_at: u64,
+    pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub status: BillStatus,
+    pub currency: soroban_sdk::String,
+}
+
+/// Bill status as stored by the Bill Payments contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillStatus {
+    Open,
+    Disputed(soroban_sdk::String),
+    Cancelled(soroban_sdk::String),
+}
+
+/// Insurance policy as stored by the Insurance contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InsurancePolicy {
+    pub id: u32,
+    pub owner: Address,
+    pub name: soroban_sdk::String,
+    pub coverage_type: soroban_sdk::String,
+    pub monthly_premium: i128,
+    pub coverage_amount: i128,
+    pub active: bool,
+    pub next_payment_date: u64,
+    pub schedule_id: Option<u32>,
+    pub term_end: u64,
 }
 
 /// Orchestrator-specific errors
@@ -192,6 +292,10 @@ pub enum OrchestratorError {
     InvalidContractAddress = 8,
     /// Generic cross-contract call failure
     CrossContractCallFailed = 9,
+    /// Savings goal does not exist
+    GoalNotFound = 10,
+    /// Savings goal belongs to another owner
+    GoalNotOwned = 11,
 }
 
 /// Result of a complete remittance flow execution
@@ -200,7 +304,8 @@ pub enum OrchestratorError {
 pub struct RemittanceFlowResult {
     /// Total remittance amount processed
     pub total_amount: i128,
-    /// Amount allocated to spending
+    /// Amount allocated to spending, including any leftover that could not
+    /// be swept
     pub spending_amount: i128,
     /// Amount allocated to savings
     pub savings_amount: i128,
@@ -214,10 +319,22 @@ pub struct RemittanceFlowResult {
     pub bills_success: bool,
     /// Whether insurance payment succeeded
     pub insurance_success: bool,
+    /// Unused bills/insurance allocation swept into the savings goal
+    pub swept_amount: i128,
     /// Timestamp of execution
     pub timestamp: u64,
 }
 
+/// Per-user preference for sweeping unused bill and premium allocations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeftoverSweepConfig {
+    /// Whether leftovers are swept after a remittance flow
+    pub enabled: bool,
+    /// Top-priority savings goal that receives the leftover
+    pub goal_id: u32,
+}
+
 /// Event emitted on successful remittance flow completion
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -471,7 +588,8 @@ impl Orchestrator {
 
     /// Execute bill payment via cross-contract call
     ///
-    /// This function calls the Bill Payments contract to mark a bill as paid.
+    /// This function calls the Bill Payments contract to pay a bill, which
+    /// transfers the bill amount in `token` to the payee and marks it paid.
     /// If the call fails (e.g., bill not found, already paid), the error is
     /// converted to OrchestratorError::BillPaymentFailed.
     ///
@@ -480,6 +598,7 @@ impl Orchestrator {
     /// * `bills_addr` - Address of the Bill Payments contract
     /// * `caller` - Address of the caller (must be bill owner)
     /// * `bill_id` - ID of the bill to pay
+    /// * `token` - Token the payee is paid in
     ///
     /// # Returns
     /// Ok(()) if payment succeeds, Err(OrchestratorError::BillPaymentFailed) otherwise
@@ -497,6 +616,7 @@ impl Orchestrator {
         bills_addr: &Address,
         caller: &Address,
         bill_id: u32,
+        token: &Address,
     ) -> Result<(), OrchestratorError> {
         // Create client for cross-contract call
         let bills_client = BillPaymentsClient::new(env, bills_addr);
@@ -505,7 +625,7 @@ impl Orchestrator {
         // Call pay_bill on the bills contract
         // This will panic if the bill doesn't exist or is already paid
         // The panic will cause the entire transaction to revert (atomicity)
-        bills_client.pay_bill(caller, &bill_id);
+        bills_client.pay_bill(caller, &bill_id, token);
 
         Ok(())
     }
@@ -551,6 +671,48 @@ impl Orchestrator {
         Ok(())
     }
 
+    /// Compute the unused part of the bills and insurance allocations
+    ///
+    /// The leftover is the bills allocation minus the amount of the bill being
+    /// paid, plus the insurance allocation minus the policy's monthly premium.
+    /// Each leg is floored at zero so an underfunded leg never reduces the other.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `bills_addr` - Address of the Bill Payments contract
+    /// * `insurance_addr` - Address of the Insurance contract
+    /// * `bill_id` - ID of the bill paid in this flow
+    /// * `policy_id` - ID of the policy paid in this flow
+    /// * `bills_amount` - Amount allocated to bills
+    /// * `insurance_amount` - Amount allocated to insurance
+    ///
+    /// # Gas Estimation
+    /// ~2000 gas for the two cross-contract reads
+    fn compute_leftover(
+        env: &Env,
+        bills_addr: &Address,
+        insurance_addr: &Address,
+        bill_id: u32,
+        policy_id: u32,
+        bills_amount: i128,
+        insurance_amount: i128,
+    ) -> i128 {
+        let bill_due = BillPaymentsClient::new(env, bills_addr)
+            .get_bill(&bill_id)
+            .filter(|bill| !bill.paid && !matches!(bill.status, BillStatus::Cancelled(_)))
+            .map(|bill| bill.amount - bill.amount_paid)
+            .unwrap_or(0);
+        let premium_due = InsuranceClient::new(env, insurance_addr)
+            .get_policy(&policy_id)
+            .filter(|policy| policy.active)
+            .map(|policy| policy.monthly_premium)
+            .unwrap_or(0);
+
+        let bills_left = bills_amount.saturating_sub(bill_due).max(0);
+        let insurance_left = insurance_amount.saturating_sub(premium_due).max(0);
+        bills_left.saturating_add(insurance_left)
+    }
+
     // ============================================================================
     // Helper Functions - Event Emission
     // ============================================================================
@@ -707,6 +869,7 @@ impl Orchestrator {
     /// * `amount` - Amount of the bill payment
     /// * `family_wallet_addr` - Address of the Family Wallet contract
     /// * `bills_addr` - Address of the Bill Payments contract
+    /// * `bill_token` - Token the bill's payee is paid in
     /// * `bill_id` - Target bill ID
     ///
     /// # Returns
@@ -731,6 +894,7 @@ impl Orchestrator {
         amount: i128,
         family_wallet_addr: Address,
         bills_addr: Address,
+        bill_token: Address,
         bill_id: u32,
     ) -> Result<(), OrchestratorError> {
         // Require caller authorization
@@ -765,10 +929,11 @@ impl Orchestrator {
         })?;
 
         // Step 3: Execute bill payment
-        Self::execute_bill_payment_internal(&env, &bills_addr, &caller, bill_id).map_err(|e| {
-            Self::emit_error_event(&env, &caller, symbol_short!("bills"), e as u32, timestamp);
-            e
-        })?;
+        Self::execute_bill_payment_internal(&env, &bills_addr, &caller, bill_id, &bill_token)
+            .map_err(|e| {
+                Self::emit_error_event(&env, &caller, symbol_short!("bills"), e as u32, timestamp);
+                e
+            })?;
 
         // Emit success event
         let allocations = Vec::from_array(&env, [0, 0, amount, 0]);
@@ -911,10 +1076,11 @@ impl Orchestrator {
     /// 4. Check spending limit
     /// 5. Extract allocations from remittance split
     /// 6. Deposit to savings goal
-    /// 7. Pay bill
+    /// 7. Pay bill in the token set with `set_bill_token`
     /// 8. Pay insurance premium
-    /// 9. Build and return result
-    /// 10. On error, emit error event and return error
+    /// 9. Sweep unused bills/insurance allocation into savings (if enabled)
+    /// 10. Build and return result
+    /// 11. On error, emit error event and return error
     pub fn execute_remittance_flow(
         env: Env,
         caller: Address,
@@ -980,7 +1146,7 @@ impl Orchestrator {
             })?;
 
         // Extract individual amounts
-        let spending_amount = allocations.get(0).unwrap_or(0);
+        let mut spending_amount = allocations.get(0).unwrap_or(0);
         let savings_amount = allocations.get(1).unwrap_or(0);
         let bills_amount = allocations.get(2).unwrap_or(0);
         let insurance_amount = allocations.get(3).unwrap_or(0);
@@ -1000,14 +1166,41 @@ impl Orchestrator {
                 })
                 .is_ok();
 
-        // Step 6: Pay bill
-        let bills_success =
-            Self::execute_bill_payment_internal(&env, &bills_addr, &caller, bill_id)
+        // Leftover must be measured before the bill is marked paid
+        let sweep = Self::get_leftover_sweep(env.clone(), caller.clone()).filter(|c| c.enabled);
+        let leftover = match sweep {
+            Some(_) => Self::compute_leftover(
+                &env,
+                &bills_addr,
+                &insurance_addr,
+                bill_id,
+                policy_id,
+                bills_amount,
+                insurance_amount,
+            ),
+            None => 0,
+        };
+
+        // Step 6: Pay bill in the caller's configured token
+        let bills_success = Self::get_bill_token(env.clone(), caller.clone())
+            .ok_or(OrchestratorError::BillPaymentFailed)
+            .and_then(|token| {
+                Self::execute_bill_payment_internal(&env, &bills_addr, &caller, bill_id, &token)
+            })
+            .map_err(|e| {
+                Self::emit_error_event(&env, &caller, symbol_short!("bills"), e as u32, timestamp);
+                e
+            })
+            .is_ok();
+
+        // Step 7: Pay insurance premium
+        let insurance_success =
+            Self::pay_insurance_premium(&env, &insurance_addr, &caller, policy_id)
                 .map_err(|e| {
                     Self::emit_error_event(
                         &env,
                         &caller,
-                        symbol_short!("bills"),
+                        symbol_short!("insuranc"),
                         e as u32,
                         timestamp,
                     );
@@ -1015,20 +1208,32 @@ impl Orchestrator {
                 })
                 .is_ok();
 
-        // Step 7: Pay insurance premium
-        let insurance_success =
-            Self::pay_insurance_premium(&env, &insurance_addr, &caller, policy_id)
-                .map_err(|e| {
+        // Step 8: Sweep unused bills/insurance allocation into savings. If the
+        // goal no longer accepts the deposit, the leftover stays in spending.
+        let mut swept_amount = 0;
+        if let Some(config) = sweep {
+            if leftover > 0 {
+                let swept = SavingsGoalsClient::new(&env, &savings_addr)
+                    .try_add_to_goal(&caller, &config.goal_id, &leftover)
+                    .is_ok_and(|r| r.is_ok());
+                if swept {
+                    swept_amount = leftover;
+                    env.events().publish(
+                        (symbol_short!("swept"),),
+                        (caller.clone(), config.goal_id, leftover),
+                    );
+                } else {
+                    spending_amount += leftover;
                     Self::emit_error_event(
                         &env,
                         &caller,
-                        symbol_short!("insuranc"),
-                        e as u32,
+                        symbol_short!("sweep"),
+                        OrchestratorError::SavingsDepositFailed as u32,
                         timestamp,
                     );
-                    e
-                })
-                .is_ok();
+                }
+            }
+        }
 
         // Build result
         let result = RemittanceFlowResult {
@@ -1040,6 +1245,7 @@ impl Orchestrator {
             savings_success,
             bills_success,
             insurance_success,
+            swept_amount,
             timestamp,
         };
 
@@ -1049,6 +1255,103 @@ impl Orchestrator {
         Ok(result)
     }
 
+    // ============================================================================
+    // Public Functions - Leftover Sweep Configuration
+    // ============================================================================
+
+    /// Configure sweeping of unused bill and premium allocations
+    ///
+    /// When enabled, `execute_remittance_flow` deposits whatever part of the bills
+    /// and insurance allocations was not needed to pay the due bill and premium
+    /// into `goal_id` instead of leaving it idle. If the deposit fails at
+    /// execution time, the leftover is left in spending.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Address configuring its own sweep (must authorize)
+    /// * `savings_addr` - Address of the Savings Goals contract
+    /// * `enabled` - Whether the sweep is active
+    /// * `goal_id` - Top-priority savings goal receiving the leftover
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If enabling and the goal does not exist
+    /// * `GoalNotOwned` - If enabling and the goal is not the caller's
+    pub fn set_leftover_sweep(
+        env: Env,
+        caller: Address,
+        savings_addr: Address,
+        enabled: bool,
+        goal_id: u32,
+    ) -> Result<bool, OrchestratorError> {
+        caller.require_auth();
+        if enabled {
+            let goal = SavingsGoalsClient::new(&env, &savings_addr)
+                .get_goal(&goal_id)
+                .ok_or(OrchestratorError::GoalNotFound)?;
+            if goal.owner != caller {
+                return Err(OrchestratorError::GoalNotOwned);
+            }
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut configs: Map<Address, LeftoverSweepConfig> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SWEEP"))
+            .unwrap_or_else(|| Map::new(&env));
+        configs.set(caller.clone(), LeftoverSweepConfig { enabled, goal_id });
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SWEEP"), &configs);
+
+        Self::append_audit_entry(&env, &caller, symbol_short!("set_swp"), 0, true, None);
+        Ok(true)
+    }
+
+    /// Get a user's leftover sweep configuration
+    ///
+    /// # Returns
+    /// LeftoverSweepConfig if the user has configured one, None otherwise
+    pub fn get_leftover_sweep(env: Env, user: Address) -> Option<LeftoverSweepConfig> {
+        let configs: Option<Map<Address, LeftoverSweepConfig>> =
+            env.storage().instance().get(&symbol_short!("SWEEP"));
+        configs.and_then(|m| m.get(user))
+    }
+
+    /// Set the token `execute_remittance_flow` pays the caller's bills in
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Address configuring its own bill token (must authorize)
+    /// * `token` - Token bill payees are paid in
+    pub fn set_bill_token(env: Env, caller: Address, token: Address) -> bool {
+        caller.require_auth();
+        Self::extend_instance_ttl(&env);
+
+        let mut tokens: Map<Address, Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("BILL_TKN"))
+            .unwrap_or_else(|| Map::new(&env));
+        tokens.set(caller.clone(), token);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("BILL_TKN"), &tokens);
+
+        Self::append_audit_entry(&env, &caller, symbol_short!("set_btkn"), 0, true, None);
+        true
+    }
+
+    /// Get the token a user's bills are paid in
+    ///
+    /// # Returns
+    /// The token if the user has set one, None otherwise
+    pub fn get_bill_token(env: Env, user: Address) -> Option<Address> {
+        let tokens: Option<Map<Address, Address>> =
+            env.storage().instance().get(&symbol_short!("BILL_TKN"));
+        tokens.and_then(|m| m.get(user))
+    }
+
     // ============================================================================
     // Helper Functions - Audit Logging and Statistics
     // ============================================================================
@@ -1,7 +1,12 @@
 // Integration tests for the orchestrator contract
 
-use crate::{Orchestrator, OrchestratorClient, OrchestratorError};
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Vec};
+use crate::{
+    Bill, BillStatus, InsurancePolicy, Orchestrator, OrchestratorClient, OrchestratorError,
+    SavingsGoal,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, Env, String, Vec,
+};
 
 // ============================================================================
 // Mock Contract Implementations
@@ -44,14 +49,49 @@ pub struct MockSavingsGoals;
 
 #[contractimpl]
 impl MockSavingsGoals {
+    /// Store a goal for get_goal to return; goals are locked by default
+    pub fn set_goal(env: Env, goal_id: u32, owner: Address) {
+        let goal = SavingsGoal {
+            id: goal_id,
+            owner,
+            name: String::from_str(&env, "Goal"),
+            target_amount: 10000,
+            current_amount: 0,
+            target_date: 0,
+            locked: true,
+            unlock_date: None,
+            tag: None,
+        };
+        env.storage().persistent().set(&goal_id, &goal);
+    }
+
+    /// Remove a stored goal; later deposits into it fail
+    pub fn remove_goal(env: Env, goal_id: u32) {
+        env.storage().persistent().remove(&goal_id);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("removed"), goal_id), &true);
+    }
+
     /// Mock implementation of add_to_goal
-    /// Panics if goal_id == 999 (simulating goal not found)
-    pub fn add_to_goal(_env: Env, _caller: Address, goal_id: u32, amount: i128) -> i128 {
-        if goal_id == 999 {
+    /// Panics if goal_id == 999 or the goal was removed (simulating goal
+    /// not found)
+    pub fn add_to_goal(env: Env, _caller: Address, goal_id: u32, amount: i128) -> i128 {
+        let removed = env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("removed"), goal_id));
+        if goal_id == 999 || removed {
             panic!("Goal not found");
         }
         amount
     }
+
+    /// Mock implementation of get_goal
+    /// Returns goals stored with set_goal
+    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
+        env.storage().persistent().get(&goal_id)
+    }
 }
 
 /// Mock Bill Payments contract for testing
@@ -62,10 +102,34 @@ pub struct MockBillPayments;
 impl MockBillPayments {
     /// Mock implementation of pay_bill
     /// Panics if bill_id == 999 (simulating bill not found or already paid)
-    pub fn pay_bill(_env: Env, _caller: Address, bill_id: u32) {
+    pub fn pay_bill(_env: Env, _caller: Address, bill_id: u32, _token: Address) -> i128 {
         if bill_id == 999 {
             panic!("Bill not found or already paid");
         }
+        1500
+    }
+
+    /// Mock implementation of get_bill
+    /// Every bill is an unpaid 1500 bill
+    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
+        Some(Bill {
+            id: bill_id,
+            owner: env.current_contract_address(),
+            payee: env.current_contract_address(),
+            name: String::from_str(&env, "Electricity"),
+            category: symbol_short!("utilities"),
+            amount: 1500,
+            amount_paid: 0,
+            due_date: 0,
+            recurring: false,
+            frequency_days: 0,
+            paid: false,
+            created_at: 0,
+            paid_at: None,
+            schedule_id: None,
+            status: BillStatus::Open,
+            currency: String::from_str(&env, "XLM"),
+        })
     }
 }
 
@@ -80,6 +144,23 @@ impl MockInsurance {
     pub fn pay_premium(_env: Env, _caller: Address, policy_id: u32) -> bool {
         policy_id != 999
     }
+
+    /// Mock implementation of get_policy
+    /// Every policy is active with a 400 monthly premium
+    pub fn get_policy(env: Env, policy_id: u32) -> Option<InsurancePolicy> {
+        Some(InsurancePolicy {
+            id: policy_id,
+            owner: env.current_contract_address(),
+            name: String::from_str(&env, "Health"),
+            coverage_type: String::from_str(&env, "health"),
+            monthly_premium: 400,
+            coverage_amount: 10000,
+            active: true,
+            next_payment_date: 0,
+            term_end: 0,
+            schedule_id: None,
+        })
+    }
 }
 
 // ============================================================================
@@ -239,7 +320,8 @@ mod tests {
             &3000,
             &family_wallet_id,
             &bills_id,
-            &1, // bill_id
+            &Address::generate(&env), // bill token
+            &1,                       // bill_id
         );
 
         // Should succeed
@@ -268,7 +350,8 @@ mod tests {
             &3000,
             &family_wallet_id,
             &bills_id,
-            &999, // invalid bill_id
+            &Address::generate(&env), // bill token
+            &999,                     // invalid bill_id
         );
 
         // Should fail (the mock will panic, which gets caught and converted to error)
@@ -317,6 +400,7 @@ mod tests {
         ) = setup_test_env();
 
         let client = OrchestratorClient::new(&env, &orchestrator_id);
+        client.set_bill_token(&user, &Address::generate(&env));
 
         // Execute complete remittance flow with amount within limit (10000)
         let result = client.try_execute_remittance_flow(
@@ -348,6 +432,169 @@ mod tests {
         assert!(flow_result.savings_success);
         assert!(flow_result.bills_success);
         assert!(flow_result.insurance_success);
+
+        // No sweep configured
+        assert_eq!(flow_result.swept_amount, 0);
+    }
+
+    #[test]
+    fn test_remittance_flow_without_bill_token_skips_bill() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        assert_eq!(client.get_bill_token(&user), None);
+
+        let flow_result = client.execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &remittance_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1,
+            &1,
+            &1,
+        );
+        assert!(flow_result.savings_success);
+        assert!(!flow_result.bills_success);
+        assert!(flow_result.insurance_success);
+    }
+
+    #[test]
+    fn test_remittance_flow_sweeps_leftover_into_savings() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        MockSavingsGoalsClient::new(&env, &savings_id).set_goal(&7, &user);
+        client.set_leftover_sweep(&user, &savings_id, &true, &7);
+
+        let flow_result = client.execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &remittance_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1, // goal_id
+            &1, // bill_id
+            &1, // policy_id
+        );
+
+        // bills: 2000 - 1500 = 500, insurance: 1000 - 400 = 600
+        assert_eq!(flow_result.swept_amount, 1100);
+        assert_eq!(flow_result.spending_amount, 4000);
+    }
+
+    #[test]
+    fn test_leftover_sweep_validates_goal() {
+        let (env, orchestrator_id, _, _, savings_id, _, _, user) = setup_test_env();
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        let savings = MockSavingsGoalsClient::new(&env, &savings_id);
+        savings.set_goal(&7, &Address::generate(&env));
+        savings.set_goal(&8, &user);
+
+        let result = client.try_set_leftover_sweep(&user, &savings_id, &true, &6);
+        assert_eq!(result, Err(Ok(OrchestratorError::GoalNotFound)));
+        let result = client.try_set_leftover_sweep(&user, &savings_id, &true, &7);
+        assert_eq!(result, Err(Ok(OrchestratorError::GoalNotOwned)));
+        assert_eq!(client.get_leftover_sweep(&user), None);
+
+        // Locked goals still accept deposits
+        assert!(client.set_leftover_sweep(&user, &savings_id, &true, &8));
+        assert_eq!(client.get_leftover_sweep(&user).unwrap().goal_id, 8);
+    }
+
+    #[test]
+    fn test_leftover_stays_in_spending_when_sweep_fails() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        let savings = MockSavingsGoalsClient::new(&env, &savings_id);
+        savings.set_goal(&7, &user);
+        client.set_leftover_sweep(&user, &savings_id, &true, &7);
+        savings.remove_goal(&7);
+
+        let flow_result = client.execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &remittance_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1,
+            &1,
+            &1,
+        );
+        assert!(flow_result.savings_success);
+        assert_eq!(flow_result.swept_amount, 0);
+        assert_eq!(flow_result.spending_amount, 4000 + 1100);
+    }
+
+    #[test]
+    fn test_leftover_sweep_config_and_disable() {
+        let (
+            env,
+            orchestrator_id,
+            family_wallet_id,
+            remittance_split_id,
+            savings_id,
+            bills_id,
+            insurance_id,
+            user,
+        ) = setup_test_env();
+
+        let client = OrchestratorClient::new(&env, &orchestrator_id);
+        assert_eq!(client.get_leftover_sweep(&user), None);
+
+        client.set_leftover_sweep(&user, &savings_id, &false, &7);
+        let config = client.get_leftover_sweep(&user).unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.goal_id, 7);
+
+        let flow_result = client.execute_remittance_flow(
+            &user,
+            &10000,
+            &family_wallet_id,
+            &remittance_split_id,
+            &savings_id,
+            &bills_id,
+            &insurance_id,
+            &1,
+            &1,
+            &1,
+        );
+        assert_eq!(flow_result.swept_amount, 0);
     }
 
     #[test]
@@ -364,6 +611,7 @@ mod tests {
         ) = setup_test_env();
 
         let client = OrchestratorClient::new(&env, &orchestrator_id);
+        client.set_bill_token(&user, &Address::generate(&env));
 
         // Execute remittance flow with invalid bill_id (999)
         // The mock will panic, but the orchestrator catches it and returns an error
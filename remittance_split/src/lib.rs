@@ -2,8 +2,10 @@
 mod test;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, vec,
-    Address, Env, Map, Symbol, Vec,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Map, String, Symbol, Vec,
 };
 
 // Event topics
@@ -21,21 +23,48 @@ pub struct SplitInitializedEvent {
     pub timestamp: u64,
 }
 
+/// Errors returned by the remittance split contract.
+///
+/// Discriminants are part of the public interface: integrators such as the
+/// reporting contract match on them, so existing codes never change.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum RemittanceSplitError {
     AlreadyInitialized = 1,
     NotInitialized = 2,
-    PercentagesDoNotSumTo100 = 3,
+    InvalidPercentageSum = 3,
     InvalidAmount = 4,
     Overflow = 5,
-    Unauthorized = 6,
+    NotAuthorized = 6,
     InvalidNonce = 7,
     UnsupportedVersion = 8,
     ChecksumMismatch = 9,
     InvalidDueDate = 10,
     ScheduleNotFound = 11,
+    FamilyHeadNotSet = 12,
+    InvalidDuration = 13,
+    InvalidCapConfig = 14,
+    TooManyCategories = 15,
+    AmountTooSmall = 16,
+    VersionNotFound = 17,
+    InvalidFee = 18,
+    AllCategoriesPaused = 19,
+    SlippageExceeded = 20,
+    TransferFailed = 21,
+    InsufficientFunds = 22,
+    InvalidPercentage = 23,
+}
+
+/// Split category, in allocation order.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SplitCategory {
+    Spending = 0,
+    Savings = 1,
+    Bills = 2,
+    Insurance = 3,
 }
 
 #[derive(Clone)]
@@ -54,6 +83,277 @@ pub struct AccountGroup {
     pub insurance: Address,
 }
 
+/// Minimum absolute amounts reserved for each category before the
+/// percentage split is applied to the remainder.
+///
+/// Floors are honoured in priority order: bills, insurance, savings,
+/// spending. When a remittance is too small to cover every floor, the
+/// higher-priority categories are filled first and nothing is left for the
+/// percentage distribution.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CategoryFloors {
+    pub spending: i128,
+    pub savings: i128,
+    pub bills: i128,
+    pub insurance: i128,
+}
+
+/// Split result when the bills leg is earmarked for bills that are due.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EarmarkedSplit {
+    pub spending: i128,
+    pub savings: i128,
+    pub bills: i128,
+    pub insurance: i128,
+    /// IDs of the bills the bills leg is earmarked for
+    pub bill_ids: Vec<u32>,
+    /// Sum of the due bills; larger than `bills` when the remittance falls short
+    pub bills_due: i128,
+}
+
+/// A historical split configuration, kept for rollback.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitVersion {
+    pub version: u32,
+    pub spending_percent: u32,
+    pub savings_percent: u32,
+    pub bills_percent: u32,
+    pub insurance_percent: u32,
+    pub timestamp: u64,
+}
+
+/// Platform fee taken from each executed split before the percentages apply.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PlatformFee {
+    /// Fee in basis points of the remitted amount
+    pub fee_bps: u32,
+    pub collector: Address,
+}
+
+/// A platform fee charged on one `distribute_usdc` call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeePayment {
+    pub payer: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Per-category monthly maximums enforced on executed splits.
+///
+/// A cap of 0 leaves the category uncapped. Amounts above a cap are
+/// redirected to `fallback`, which must itself be uncapped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CategoryCaps {
+    pub spending: i128,
+    pub savings: i128,
+    pub bills: i128,
+    pub insurance: i128,
+    pub fallback: SplitCategory,
+}
+
+/// Amounts distributed per category in the current 30-day period.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MonthlyUsage {
+    pub period: u64,
+    pub spending: i128,
+    pub savings: i128,
+    pub bills: i128,
+    pub insurance: i128,
+}
+
+/// Active emergency override routing whole remittances to spending.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmergencyMode {
+    pub approved_by: Address,
+    pub enabled_at: u64,
+    pub expires_at: u64,
+}
+
+/// One leg of a simulated split.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitLeg {
+    pub category: Symbol,
+    /// Destination account, if one has been registered with `set_account_group`
+    pub target: Option<Address>,
+    pub amount: i128,
+    pub percent: u32,
+    /// Part of `amount` coming from the category floor
+    pub floor_amount: i128,
+    /// Rule that produced the amount: `floor`, `percent` or `remainder`
+    pub rule: Symbol,
+    /// Units added to this leg to absorb percentage rounding
+    pub rounding_adjustment: i128,
+}
+
+/// Read-only preview of how a remittance would be split.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitPlan {
+    pub user: Address,
+    pub total_amount: i128,
+    pub legs: Vec<SplitLeg>,
+    pub timestamp: u64,
+}
+
+/// Per-leg settlement assets. A leg with `None` is paid in the source token;
+/// otherwise its amount is converted through `router` during execution.
+///
+/// Each converted leg needs a reference rate: units of its asset per unit of
+/// the source token, scaled by 10^7 like `ConversionRecord::rate`. The router
+/// must pay at least that rate less `max_slippage_bps`, so its own quote never
+/// sets the floor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CategoryAssets {
+    pub spending: Option<Address>,
+    pub savings: Option<Address>,
+    pub bills: Option<Address>,
+    pub insurance: Option<Address>,
+    pub router: Address,
+    /// Maximum shortfall against the reference rate, in basis points
+    pub max_slippage_bps: u32,
+    pub spending_rate: i128,
+    pub savings_rate: i128,
+    pub bills_rate: i128,
+    pub insurance_rate: i128,
+}
+
+/// Conversion performed for one leg of an executed split.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ConversionRecord {
+    pub category: SplitCategory,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    /// `amount_out` per unit of `amount_in`, scaled by 10^7
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+// Client trait for the path-payment / AMM router used for leg conversions
+
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouterTrait {
+    fn quote(env: Env, token_in: Address, token_out: Address, amount_in: i128) -> i128;
+    fn swap(
+        env: Env,
+        sender: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
+// Client trait for reading due bills from, and earmarking the bills leg in,
+// the bill_payments contract
+
+#[contractclient(name = "BillPaymentsClient")]
+pub trait BillPaymentsTrait {
+    fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage;
+    fn deposit_earmarked(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128;
+}
+
+// Client trait for settling premiums from the insurance leg in the insurance
+// contract
+
+#[contractclient(name = "InsuranceClient")]
+pub trait InsuranceTrait {
+    fn deposit_premium_funds(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128;
+}
+
+// Client trait for delivering the spending leg into the family wallet's pool
+
+#[contractclient(name = "FamilyWalletClient")]
+pub trait FamilyWalletTrait {
+    fn receive_split_funds(env: Env, payer: Address, token: Address, amount: i128) -> i128;
+}
+
+// Client trait for crediting the savings leg to the savings account's goals
+// in the savings_goals contract
+
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsTrait {
+    fn credit_from_split(
+        env: Env,
+        user: Address,
+        token: Address,
+        amount: i128,
+    ) -> Vec<ContributionItem>;
+}
+
+// Data structure from the savings_goals contract (needed for the client trait)
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ContributionItem {
+    pub goal_id: u32,
+    pub amount: i128,
+}
+
+// Data structures from the bill_payments contract (needed for the client trait)
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Bill {
+    pub id: u32,
+    pub owner: Address,
+    pub payee: Address,
+    pub name: String,
+    pub category: Symbol,
+    pub amount: i128,
+    pub amount_paid: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub paid: bool,
+    pub created_at: u64,
+    pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub status: BillStatus,
+    pub currency: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum BillStatus {
+    Open,
+    Disputed(String),
+    Cancelled(String),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct BillPage {
+    pub items: Vec<Bill>,
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
 // Storage TTL constants
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
@@ -82,6 +382,29 @@ pub struct SplitCalculatedEvent {
     pub timestamp: u64,
 }
 
+/// Payload of the `(split, Executed, user)` event emitted once per executed
+/// split, for indexers and the reporting contract's remittance history.
+///
+/// Leg amounts are in the source token, after the platform fee and monthly
+/// caps; converted legs are reported separately by the `convert` event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitExecutedEvent {
+    /// Monotonic execution sequence number, starting at 1
+    pub execution_id: u64,
+    pub user: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub fee_amount: i128,
+    pub spending_amount: i128,
+    pub savings_amount: i128,
+    pub bills_amount: i128,
+    pub insurance_amount: i128,
+    /// Split config version the amounts were computed with
+    pub config_version: u32,
+    pub timestamp: u64,
+}
+
 /// Events emitted by the contract for audit trail
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -89,6 +412,7 @@ pub enum SplitEvent {
     Initialized,
     Updated,
     Calculated,
+    Executed,
 }
 
 /// Snapshot for data export/import (migration). Checksum is a simple numeric digest for on-chain verification.
@@ -126,6 +450,25 @@ pub struct RemittanceSchedule {
     pub missed_count: u32,
 }
 
+/// Recurring split executed permissionlessly from the owner's allowance.
+#[contracttype]
+#[derive(Clone)]
+pub struct StandingInstruction {
+    pub id: u32,
+    pub owner: Address,
+    pub token: Address,
+    pub accounts: AccountGroup,
+    pub amount: i128,
+    pub interval: u64,
+    pub next_due: u64,
+    pub active: bool,
+    pub last_executed: Option<u64>,
+    pub executed_count: u32,
+    pub missed_count: u32,
+    pub partial_count: u32,
+    pub consecutive_misses: u32,
+}
+
 /// Schedule event types
 #[contracttype]
 #[derive(Clone)]
@@ -135,11 +478,39 @@ pub enum ScheduleEvent {
     Missed,
     Modified,
     Cancelled,
+    PartiallyExecuted,
+    Suspended,
+}
+
+/// A split that stopped at a failing leg. `delivered` holds, per category,
+/// the amount of the sender's token already paid out before the failure.
+struct SplitFailure {
+    error: RemittanceSplitError,
+    delivered: [i128; 4],
+}
+
+impl From<RemittanceSplitError> for SplitFailure {
+    fn from(error: RemittanceSplitError) -> Self {
+        SplitFailure {
+            error,
+            delivered: [0; 4],
+        }
+    }
 }
 
 const SNAPSHOT_VERSION: u32 = 1;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
+const MAX_EMERGENCY_DURATION: u64 = 30 * 86400; // 30 days
+const MAX_SPLIT_HISTORY: u32 = 20;
+const MAX_FEE_BPS: u32 = 1000; // 10%
+const MAX_FEE_RECORDS: u32 = 100;
+const MAX_TRIGGER_BATCH: u32 = 20;
+const MIN_STANDING_INTERVAL: u64 = 86400; // 1 day
+const MAX_CONSECUTIVE_MISSES: u32 = 3;
+const MAX_CONVERSION_RECORDS: u32 = 100;
+const RATE_SCALE: i128 = 10_000_000;
+const CAP_PERIOD: u64 = 30 * 86400; // monthly caps reset every 30 days
 
 #[contract]
 pub struct RemittanceSplit;
@@ -157,7 +528,7 @@ impl RemittanceSplit {
     }
     fn require_not_paused(env: &Env) -> Result<(), RemittanceSplitError> {
         if Self::get_global_paused(env) {
-            Err(RemittanceSplitError::Unauthorized)
+            Err(RemittanceSplitError::NotAuthorized)
         } else {
             Ok(())
         }
@@ -175,7 +546,7 @@ impl RemittanceSplit {
             .get(&symbol_short!("CONFIG"))
             .ok_or(RemittanceSplitError::NotInitialized)?;
         if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         env.storage()
             .instance()
@@ -191,7 +562,7 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
         if admin != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         env.storage()
             .instance()
@@ -209,7 +580,7 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
         if admin != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         env.storage()
             .instance()
@@ -218,6 +589,30 @@ impl RemittanceSplit {
             .publish((symbol_short!("split"), symbol_short!("unpaused")), ());
         Ok(())
     }
+    fn get_fee_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("FEE_ADM"))
+    }
+    /// Set the platform fee admin, who alone configures fees and discounts.
+    ///
+    /// The platform claims the role once at deploy (`caller` must equal
+    /// `new_admin`); afterwards only the current fee admin can hand it over.
+    /// Split owners have no say over the fee they pay.
+    pub fn set_fee_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        match Self::get_fee_admin(&env) {
+            None if caller != new_admin => return Err(RemittanceSplitError::NotAuthorized),
+            Some(admin) if admin != caller => return Err(RemittanceSplitError::NotAuthorized),
+            _ => {}
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FEE_ADM"), &new_admin);
+        Ok(())
+    }
     pub fn is_paused(env: Env) -> bool {
         Self::get_global_paused(&env)
     }
@@ -242,7 +637,7 @@ impl RemittanceSplit {
             .get(&symbol_short!("CONFIG"))
             .ok_or(RemittanceSplitError::NotInitialized)?;
         if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         env.storage()
             .instance()
@@ -262,7 +657,7 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         let admin = Self::get_upgrade_admin(&env).unwrap_or(config.owner);
         if admin != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         let prev = Self::get_version(env.clone());
         env.storage()
@@ -312,10 +707,14 @@ impl RemittanceSplit {
             return Err(RemittanceSplitError::AlreadyInitialized);
         }
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        if let Err(e) = Self::check_percentages(&[
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+        ]) {
             Self::append_audit(&env, symbol_short!("init"), &owner, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+            return Err(e);
         }
 
         Self::extend_instance_ttl(&env);
@@ -344,6 +743,7 @@ impl RemittanceSplit {
             ],
         );
 
+        Self::record_split_version(&env, &config);
         Self::increment_nonce(&env, &owner)?;
         Self::append_audit(&env, symbol_short!("init"), &owner, true);
         env.events()
@@ -373,13 +773,17 @@ impl RemittanceSplit {
 
         if config.owner != caller {
             Self::append_audit(&env, symbol_short!("update"), &caller, false);
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
 
-        let total = spending_percent + savings_percent + bills_percent + insurance_percent;
-        if total != 100 {
+        if let Err(e) = Self::check_percentages(&[
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+        ]) {
             Self::append_audit(&env, symbol_short!("update"), &caller, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+            return Err(e);
         }
 
         Self::extend_instance_ttl(&env);
@@ -403,74 +807,1318 @@ impl RemittanceSplit {
             ],
         );
 
-        let event = SplitInitializedEvent {
-            spending_percent,
-            savings_percent,
-            bills_percent,
-            insurance_percent,
-            timestamp: env.ledger().timestamp(),
-        };
-        env.events().publish((SPLIT_INITIALIZED,), event);
-        env.events()
-            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        Self::record_split_version(&env, &config);
+
+        let event = SplitInitializedEvent {
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((SPLIT_INITIALIZED,), event);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+
+        Ok(true)
+    }
+
+    /// Restore the percentages of a previous split version.
+    ///
+    /// The rollback is itself recorded as a new version, so history is never
+    /// rewritten.
+    ///
+    /// # Arguments
+    /// * `caller` - Split owner (must authorize)
+    /// * `nonce` - Caller's transaction nonce for replay protection
+    /// * `version` - Version number from `get_split_history`
+    ///
+    /// # Returns
+    /// The new version number
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if caller is not the owner
+    /// - `VersionNotFound` if the version is not in the retained history
+    pub fn rollback_split(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        version: u32,
+    ) -> Result<u32, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &caller, nonce)?;
+
+        let mut config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("rollback"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        let target = Self::get_split_history(env.clone())
+            .iter()
+            .find(|v| v.version == version);
+        let target = match target {
+            Some(v) => v,
+            None => {
+                Self::append_audit(&env, symbol_short!("rollback"), &caller, false);
+                return Err(RemittanceSplitError::VersionNotFound);
+            }
+        };
+
+        Self::extend_instance_ttl(&env);
+        config.spending_percent = target.spending_percent;
+        config.savings_percent = target.savings_percent;
+        config.bills_percent = target.bills_percent;
+        config.insurance_percent = target.insurance_percent;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONFIG"), &config);
+        env.storage().instance().set(
+            &symbol_short!("SPLIT"),
+            &vec![
+                &env,
+                target.spending_percent,
+                target.savings_percent,
+                target.bills_percent,
+                target.insurance_percent,
+            ],
+        );
+        let new_version = Self::record_split_version(&env, &config);
+
+        Self::increment_nonce(&env, &caller)?;
+        Self::append_audit(&env, symbol_short!("rollback"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("rollback")),
+            (version, new_version),
+        );
+        Ok(new_version)
+    }
+
+    /// Get the current split version number (0 before initialization).
+    pub fn get_split_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SPLIT_VER"))
+            .unwrap_or(0)
+    }
+
+    /// Get retained split versions, oldest first (up to the last 20).
+    pub fn get_split_history(env: Env) -> Vec<SplitVersion> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SPLIT_HIS"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Validate a list of split percentages without storing anything.
+    ///
+    /// Lets integrators check user input before submitting it.
+    ///
+    /// # Errors
+    /// - `TooManyCategories` if more than four percentages are given
+    /// - `InvalidPercentageSum` if they do not sum to exactly 100
+    pub fn validate_split(_env: Env, percentages: Vec<u32>) -> Result<bool, RemittanceSplitError> {
+        if percentages.len() > 4 {
+            return Err(RemittanceSplitError::TooManyCategories);
+        }
+        let mut values = [0u32; 4];
+        for (i, p) in percentages.iter().enumerate() {
+            values[i] = p;
+        }
+        Self::check_percentages(&values)?;
+        Ok(true)
+    }
+
+    pub fn get_split(env: &Env) -> Vec<u32> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("SPLIT"))
+            .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
+    }
+
+    pub fn get_config(env: Env) -> Option<SplitConfig> {
+        env.storage().instance().get(&symbol_short!("CONFIG"))
+    }
+
+    /// Set minimum per-category amounts applied before the percentage split.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the split owner (must authorize)
+    /// * `floors` - Minimum amount per category; all values must be >= 0.
+    ///   Passing all zeros disables floors.
+    ///
+    /// # Errors
+    /// - `NotInitialized` if the split has not been configured
+    /// - `NotAuthorized` if caller is not the split owner
+    /// - `InvalidAmount` if any floor is negative
+    pub fn set_category_floors(
+        env: Env,
+        caller: Address,
+        floors: CategoryFloors,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("floors"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        if floors.spending < 0 || floors.savings < 0 || floors.bills < 0 || floors.insurance < 0 {
+            Self::append_audit(&env, symbol_short!("floors"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FLOORS"), &floors);
+
+        Self::append_audit(&env, symbol_short!("floors"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("floors")), floors);
+
+        Ok(true)
+    }
+
+    /// Get the configured category floors, if any.
+    pub fn get_category_floors(env: Env) -> Option<CategoryFloors> {
+        env.storage().instance().get(&symbol_short!("FLOORS"))
+    }
+
+    pub fn calculate_split(
+        env: Env,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let amounts = Self::calculate_split_amounts(&env, total_amount, true)?;
+        Ok(vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]])
+    }
+
+    /// Calculate a split whose bills leg covers exactly the bills due before the
+    /// next expected remittance.
+    ///
+    /// Unpaid bills of `owner` with `due_date <= next_remittance_at` are read from
+    /// the bill_payments contract and their total is earmarked for bills (capped
+    /// at `total_amount`). The remainder is shared between spending, savings and
+    /// insurance in proportion to their configured percentages; the bills
+    /// percentage and category floors are not used in this mode.
+    ///
+    /// # Arguments
+    /// * `bills_contract` - Address of the bill_payments contract
+    /// * `owner` - Owner of the bills
+    /// * `total_amount` - Amount being split (must be positive)
+    /// * `next_remittance_at` - Timestamp of the next expected remittance
+    pub fn calculate_split_with_bills(
+        env: Env,
+        bills_contract: Address,
+        owner: Address,
+        total_amount: i128,
+        next_remittance_at: u64,
+    ) -> Result<EarmarkedSplit, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        let client = BillPaymentsClient::new(&env, &bills_contract);
+        let mut bill_ids = Vec::new(&env);
+        let mut bills_due: i128 = 0;
+        let mut cursor = 0u32;
+        loop {
+            let page = client.get_unpaid_bills(&owner, &cursor, &0);
+            for bill in page.items.iter() {
+                if bill.due_date <= next_remittance_at {
+                    bills_due = bills_due
+                        .checked_add(bill.amount - bill.amount_paid)
+                        .ok_or(RemittanceSplitError::Overflow)?;
+                    bill_ids.push_back(bill.id);
+                }
+            }
+            if page.next_cursor == 0 {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        // Emergency mode routes everything to spending, due bills or not.
+        let emergency = Self::is_emergency_mode(env.clone());
+        let bills = if emergency {
+            0
+        } else {
+            bills_due.min(total_amount)
+        };
+        let remaining = total_amount - bills;
+
+        let split = Self::effective_split(&env);
+        let s0 = split[0] as i128;
+        let s1 = split[1] as i128;
+        let s3 = split[3] as i128;
+        let weight = s0 + s1 + s3;
+
+        let (spending, savings, insurance) = if weight == 0 || emergency {
+            (remaining, 0, 0)
+        } else {
+            let spending = remaining
+                .checked_mul(s0)
+                .and_then(|n| n.checked_div(weight))
+                .ok_or(RemittanceSplitError::Overflow)?;
+            let savings = remaining
+                .checked_mul(s1)
+                .and_then(|n| n.checked_div(weight))
+                .ok_or(RemittanceSplitError::Overflow)?;
+            (spending, savings, remaining - spending - savings)
+        };
+
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("earmark")),
+            (owner, bills, bill_ids.len()),
+        );
+
+        Ok(EarmarkedSplit {
+            spending,
+            savings,
+            bills,
+            insurance,
+            bill_ids,
+            bills_due,
+        })
+    }
+
+    /// Temporarily pause a split category until `resume_at`.
+    ///
+    /// While paused, the category's percentage is redistributed across the
+    /// remaining categories in proportion to their own percentages. The
+    /// pause lifts automatically once the ledger reaches `resume_at`.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if caller is not the split owner
+    /// - `InvalidDuration` if `resume_at` is not in the future
+    /// - `AllCategoriesPaused` if this would pause every category
+    pub fn pause_category(
+        env: Env,
+        caller: Address,
+        category: SplitCategory,
+        resume_at: u64,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("cat_pause"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+        if resume_at <= env.ledger().timestamp() {
+            Self::append_audit(&env, symbol_short!("cat_pause"), &caller, false);
+            return Err(RemittanceSplitError::InvalidDuration);
+        }
+
+        let mut pauses = Self::active_category_pauses(&env);
+        pauses.set(category, resume_at);
+        if pauses.len() >= 4 {
+            Self::append_audit(&env, symbol_short!("cat_pause"), &caller, false);
+            return Err(RemittanceSplitError::AllCategoriesPaused);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_PAUSE"), &pauses);
+        Self::append_audit(&env, symbol_short!("cat_pause"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("cat_pause")),
+            (category, resume_at),
+        );
+        Ok(true)
+    }
+
+    /// Resume a paused category before its scheduled resume date.
+    pub fn resume_category(
+        env: Env,
+        caller: Address,
+        category: SplitCategory,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("cat_resum"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        let mut pauses = Self::active_category_pauses(&env);
+        pauses.remove(category);
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAT_PAUSE"), &pauses);
+        Self::append_audit(&env, symbol_short!("cat_resum"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("cat_resum")),
+            category,
+        );
+        Ok(true)
+    }
+
+    /// Get currently paused categories with their resume timestamps.
+    pub fn get_paused_categories(env: Env) -> Map<SplitCategory, u64> {
+        Self::active_category_pauses(&env)
+    }
+
+    /// Split percentages after redistributing paused categories.
+    pub fn get_effective_split(env: Env) -> Vec<u32> {
+        let split = Self::effective_split(&env);
+        vec![&env, split[0], split[1], split[2], split[3]]
+    }
+
+    /// Choose the asset each split leg settles in.
+    ///
+    /// Legs with a configured asset are converted from the source token via
+    /// `router` when a split is executed, and the rate used is recorded. The
+    /// owner keeps the reference rates current, as an oracle would.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if caller is not the split owner
+    /// - `InvalidPercentage` if `max_slippage_bps` is above 10000
+    /// - `InvalidAmount` if a leg with an asset has no positive rate
+    pub fn set_category_assets(
+        env: Env,
+        caller: Address,
+        assets: CategoryAssets,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("assets"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+        if assets.max_slippage_bps > 10_000 {
+            Self::append_audit(&env, symbol_short!("assets"), &caller, false);
+            return Err(RemittanceSplitError::InvalidPercentage);
+        }
+        let legs = [
+            (&assets.spending, assets.spending_rate),
+            (&assets.savings, assets.savings_rate),
+            (&assets.bills, assets.bills_rate),
+            (&assets.insurance, assets.insurance_rate),
+        ];
+        if legs
+            .iter()
+            .any(|(asset, rate)| asset.is_some() && *rate <= 0)
+        {
+            Self::append_audit(&env, symbol_short!("assets"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ASSETS"), &assets);
+        Self::append_audit(&env, symbol_short!("assets"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("assets")), assets);
+        Ok(true)
+    }
+
+    pub fn get_category_assets(env: Env) -> Option<CategoryAssets> {
+        env.storage().instance().get(&symbol_short!("ASSETS"))
+    }
+
+    /// Route the bills leg of executed splits into `escrow`, a bill_payments
+    /// contract, as an earmarked balance of the bills account that can only
+    /// pay bills. `None` sends the bills leg straight to the account again.
+    pub fn set_bills_escrow(
+        env: Env,
+        caller: Address,
+        escrow: Option<Address>,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("escrow"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &escrow {
+            Some(escrow) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("BILL_ESC"), escrow),
+            None => env.storage().instance().remove(&symbol_short!("BILL_ESC")),
+        }
+        Self::append_audit(&env, symbol_short!("escrow"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("escrow")), escrow);
+        Ok(true)
+    }
+
+    pub fn get_bills_escrow(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("BILL_ESC"))
+    }
+
+    /// Route the insurance leg of executed splits into `escrow`, an insurance
+    /// contract, which settles the insurance account's soonest-due premiums
+    /// and keeps any surplus in its premium escrow. `None` sends the
+    /// insurance leg straight to the account again.
+    pub fn set_insurance_escrow(
+        env: Env,
+        caller: Address,
+        escrow: Option<Address>,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("ins_esc"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &escrow {
+            Some(escrow) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("INS_ESC"), escrow),
+            None => env.storage().instance().remove(&symbol_short!("INS_ESC")),
+        }
+        Self::append_audit(&env, symbol_short!("ins_esc"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("ins_esc")), escrow);
+        Ok(true)
+    }
+
+    pub fn get_insurance_escrow(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("INS_ESC"))
+    }
+
+    /// Route the spending leg of executed splits into `wallet`, a
+    /// family_wallet contract that has set this contract as its split
+    /// contract, crediting the family's spending pool. `None` sends the
+    /// spending leg straight to the account again.
+    pub fn set_spending_wallet(
+        env: Env,
+        caller: Address,
+        wallet: Option<Address>,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("spnd_wal"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &wallet {
+            Some(wallet) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("SPND_WAL"), wallet),
+            None => env.storage().instance().remove(&symbol_short!("SPND_WAL")),
+        }
+        Self::append_audit(&env, symbol_short!("spnd_wal"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("spnd_wal")), wallet);
+        Ok(true)
+    }
+
+    pub fn get_spending_wallet(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SPND_WAL"))
+    }
+
+    /// Route the savings leg of executed splits into `savings`, a
+    /// savings_goals contract that has registered this contract with
+    /// `set_split_contract`, crediting the savings account's goals. `None`
+    /// sends the savings leg straight to the account again.
+    pub fn set_savings_goals(
+        env: Env,
+        caller: Address,
+        savings: Option<Address>,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("sav_goal"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &savings {
+            Some(savings) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("SAV_GOAL"), savings),
+            None => env.storage().instance().remove(&symbol_short!("SAV_GOAL")),
+        }
+        Self::append_audit(&env, symbol_short!("sav_goal"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("sav_goal")), savings);
+        Ok(true)
+    }
+
+    pub fn get_savings_goals(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SAV_GOAL"))
+    }
+
+    /// Recent leg conversions (up to the last 100), oldest first.
+    pub fn get_conversion_history(env: Env) -> Vec<ConversionRecord> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CONVERT"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Set per-category monthly caps for executed splits.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if caller is not the split owner
+    /// - `InvalidAmount` if any cap is negative
+    /// - `InvalidCapConfig` if the fallback category is itself capped
+    pub fn set_category_caps(
+        env: Env,
+        caller: Address,
+        caps: CategoryCaps,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            Self::append_audit(&env, symbol_short!("caps"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        let limits = [caps.spending, caps.savings, caps.bills, caps.insurance];
+        if limits.iter().any(|cap| *cap < 0) {
+            Self::append_audit(&env, symbol_short!("caps"), &caller, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if limits[caps.fallback as usize] != 0 {
+            Self::append_audit(&env, symbol_short!("caps"), &caller, false);
+            return Err(RemittanceSplitError::InvalidCapConfig);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&symbol_short!("CAPS"), &caps);
+
+        Self::append_audit(&env, symbol_short!("caps"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("caps")), caps);
+        Ok(true)
+    }
+
+    pub fn get_category_caps(env: Env) -> Option<CategoryCaps> {
+        env.storage().instance().get(&symbol_short!("CAPS"))
+    }
+
+    /// Get amounts distributed per category in the current period.
+    pub fn get_monthly_usage(env: Env) -> MonthlyUsage {
+        let period = env.ledger().timestamp() / CAP_PERIOD;
+        let usage: Option<MonthlyUsage> = env.storage().instance().get(&symbol_short!("CAP_USE"));
+        usage
+            .filter(|u| u.period == period)
+            .unwrap_or(MonthlyUsage {
+                period,
+                spending: 0,
+                savings: 0,
+                bills: 0,
+                insurance: 0,
+            })
+    }
+
+    /// Set the family head whose approval is required for emergency mode.
+    pub fn set_family_head(
+        env: Env,
+        caller: Address,
+        head: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FAM_HEAD"), &head);
+        Ok(true)
+    }
+
+    pub fn get_family_head(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("FAM_HEAD"))
+    }
+
+    /// Route entire remittances to spending for `duration` seconds.
+    ///
+    /// While active, every split calculation (including `distribute_usdc`)
+    /// bypasses percentages and floors and allocates 100% to spending. The
+    /// override expires on its own at `now + duration`.
+    ///
+    /// # Arguments
+    /// * `user` - Split owner requesting the override (must authorize)
+    /// * `family_head` - Registered family head approving it (must authorize)
+    /// * `duration` - Length of the override in seconds (1 to 30 days)
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if `user` is not the owner or `family_head` does not match
+    /// - `FamilyHeadNotSet` if no family head has been registered
+    /// - `InvalidDuration` if duration is 0 or longer than 30 days
+    pub fn enable_emergency_mode(
+        env: Env,
+        user: Address,
+        family_head: Address,
+        duration: u64,
+    ) -> Result<u64, RemittanceSplitError> {
+        user.require_auth();
+        family_head.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != user {
+            Self::append_audit(&env, symbol_short!("emerg_on"), &user, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+        let head =
+            Self::get_family_head(env.clone()).ok_or(RemittanceSplitError::FamilyHeadNotSet)?;
+        if head != family_head {
+            Self::append_audit(&env, symbol_short!("emerg_on"), &user, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+        if duration == 0 || duration > MAX_EMERGENCY_DURATION {
+            Self::append_audit(&env, symbol_short!("emerg_on"), &user, false);
+            return Err(RemittanceSplitError::InvalidDuration);
+        }
+
+        let now = env.ledger().timestamp();
+        let mode = EmergencyMode {
+            approved_by: family_head,
+            enabled_at: now,
+            expires_at: now + duration,
+        };
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&symbol_short!("EMERG"), &mode);
+
+        Self::append_audit(&env, symbol_short!("emerg_on"), &user, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("emerg_on")),
+            (user, mode.expires_at),
+        );
+        Ok(mode.expires_at)
+    }
+
+    /// End emergency mode before it expires.
+    pub fn disable_emergency_mode(env: Env, user: Address) -> Result<bool, RemittanceSplitError> {
+        user.require_auth();
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != user {
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        env.storage().instance().remove(&symbol_short!("EMERG"));
+        Self::append_audit(&env, symbol_short!("emerg_off"), &user, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("emerg_off")), user);
+        Ok(true)
+    }
+
+    /// Get the emergency override if it is still active.
+    pub fn get_emergency_mode(env: Env) -> Option<EmergencyMode> {
+        let mode: Option<EmergencyMode> = env.storage().instance().get(&symbol_short!("EMERG"));
+        mode.filter(|m| m.expires_at > env.ledger().timestamp())
+    }
+
+    pub fn is_emergency_mode(env: Env) -> bool {
+        Self::get_emergency_mode(env).is_some()
+    }
+
+    /// Register the destination accounts for each category.
+    ///
+    /// Used to show targets in `simulate_split`; `distribute_usdc` still takes
+    /// its accounts explicitly.
+    pub fn set_account_group(
+        env: Env,
+        caller: Address,
+        accounts: AccountGroup,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+
+        let config: SplitConfig = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONFIG"))
+            .ok_or(RemittanceSplitError::NotInitialized)?;
+        if config.owner != caller {
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ACCOUNTS"), &accounts);
+        Ok(true)
+    }
+
+    pub fn get_account_group(env: Env) -> Option<AccountGroup> {
+        env.storage().instance().get(&symbol_short!("ACCOUNTS"))
+    }
+
+    /// Preview how `amount` would be split for `user` without changing state
+    /// or emitting events.
+    ///
+    /// Each leg reports its amount, destination, the rule that produced it and
+    /// any rounding adjustment. Insurance takes the remainder of the percentage
+    /// split, so it carries the rounding dust.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if amount is not positive
+    /// - `NotAuthorized` if the split is configured and `user` is not its owner
+    pub fn simulate_split(
+        env: Env,
+        user: Address,
+        amount: i128,
+    ) -> Result<SplitPlan, RemittanceSplitError> {
+        if let Some(config) = Self::get_config(env.clone()) {
+            if config.owner != user {
+                return Err(RemittanceSplitError::NotAuthorized);
+            }
+        }
+
+        let (reserved, shares) = Self::compute_split_parts(&env, amount)?;
+        let split = Self::effective_split(&env);
+        let accounts = Self::get_account_group(env.clone());
+        let targets = [
+            accounts.as_ref().map(|a| a.spending.clone()),
+            accounts.as_ref().map(|a| a.savings.clone()),
+            accounts.as_ref().map(|a| a.bills.clone()),
+            accounts.as_ref().map(|a| a.insurance.clone()),
+        ];
+        let categories = [
+            symbol_short!("SPENDING"),
+            symbol_short!("SAVINGS"),
+            symbol_short!("BILLS"),
+            symbol_short!("INSURANCE"),
+        ];
+
+        let remaining = amount - reserved.iter().sum::<i128>();
+        let mut legs = Vec::new(&env);
+        for (index, category) in categories.into_iter().enumerate() {
+            let percent = split[index];
+            let exact_share = remaining * percent as i128 / 100;
+            let rounding_adjustment = shares[index] - exact_share;
+            let rule = if reserved[index] > 0 {
+                symbol_short!("floor")
+            } else if rounding_adjustment != 0 {
+                symbol_short!("remainder")
+            } else {
+                symbol_short!("percent")
+            };
+            legs.push_back(SplitLeg {
+                category,
+                target: targets[index].clone(),
+                amount: reserved[index] + shares[index],
+                percent,
+                floor_amount: reserved[index],
+                rule,
+                rounding_adjustment,
+            });
+        }
+
+        Ok(SplitPlan {
+            user,
+            total_amount: amount,
+            legs,
+            timestamp: env.ledger().timestamp(),
+        })
+    }
+
+    /// Configure the platform fee deducted by `distribute_usdc`.
+    ///
+    /// Callable by the platform fee admin only. A `fee_bps` of 0 disables the
+    /// fee.
+    ///
+    /// # Errors
+    /// - `NotAuthorized` if caller is not the fee admin
+    /// - `InvalidFee` if `fee_bps` exceeds 1000 (10%)
+    pub fn set_platform_fee(
+        env: Env,
+        caller: Address,
+        fee_bps: u32,
+        collector: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_fee_admin(&env, &caller)?;
+        if fee_bps > MAX_FEE_BPS {
+            return Err(RemittanceSplitError::InvalidFee);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(
+            &symbol_short!("FEE_CFG"),
+            &PlatformFee {
+                fee_bps,
+                collector: collector.clone(),
+            },
+        );
+        Self::append_audit(&env, symbol_short!("set_fee"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("fee_set")),
+            (fee_bps, collector),
+        );
+        Ok(true)
+    }
+
+    pub fn get_platform_fee(env: Env) -> Option<PlatformFee> {
+        env.storage().instance().get(&symbol_short!("FEE_CFG"))
+    }
+
+    /// Grant a user a discount on the platform fee, in basis points of the
+    /// fee itself (10000 waives it). A discount of 0 removes it.
+    pub fn set_fee_discount(
+        env: Env,
+        caller: Address,
+        user: Address,
+        discount_bps: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_fee_admin(&env, &caller)?;
+        if discount_bps > 10_000 {
+            return Err(RemittanceSplitError::InvalidFee);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut discounts: Map<Address, u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("FEE_DISC"))
+            .unwrap_or_else(|| Map::new(&env));
+        if discount_bps == 0 {
+            discounts.remove(user.clone());
+        } else {
+            discounts.set(user.clone(), discount_bps);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FEE_DISC"), &discounts);
+        Self::append_audit(&env, symbol_short!("fee_disc"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("fee_disc")),
+            (user, discount_bps),
+        );
+        Ok(true)
+    }
+
+    pub fn get_fee_discount(env: Env, user: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, u32>>(&symbol_short!("FEE_DISC"))
+            .and_then(|d| d.get(user))
+            .unwrap_or(0)
+    }
+
+    /// Fee `distribute_usdc` would charge `user` on `amount`, after discounts.
+    pub fn quote_platform_fee(env: Env, user: Address, amount: i128) -> i128 {
+        let fee = match Self::get_platform_fee(env.clone()) {
+            Some(fee) if amount > 0 => fee,
+            _ => return 0,
+        };
+        let discount = Self::get_fee_discount(env, user) as i128;
+        let bps = fee.fee_bps as i128 * (10_000 - discount) / 10_000;
+        amount * bps / 10_000
+    }
+
+    /// Total platform fees paid by `user` between `from` and `to` (inclusive).
+    ///
+    /// Only each user's most recent 100 fee payments are retained.
+    pub fn get_fees_paid(env: Env, user: Address, from: u64, to: u64) -> i128 {
+        Self::get_fee_history(env, user)
+            .iter()
+            .filter(|p| p.timestamp >= from && p.timestamp <= to)
+            .map(|p| p.amount)
+            .sum()
+    }
+
+    /// `user`'s retained fee payments, oldest first.
+    pub fn get_fee_history(env: Env, user: Address) -> Vec<FeePayment> {
+        env.storage()
+            .persistent()
+            .get(&(symbol_short!("FEES"), user))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn distribute_usdc(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+    ) -> Result<bool, RemittanceSplitError> {
+        if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        from.require_auth();
+        Self::require_nonce(&env, &from, nonce)?;
+
+        // A failure reverts the whole call, including any delivered legs.
+        if let Err(failure) =
+            Self::transfer_split(&env, &usdc_contract, &from, &accounts, total_amount, false)
+        {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(failure.error);
+        }
+
+        Self::increment_nonce(&env, &from)?;
+        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Ok(true)
+    }
+
+    /// Create a standing instruction that splits `amount` of `token` from the
+    /// owner's account every `interval` seconds, starting at `next_due`.
+    ///
+    /// Funds are pulled with `transfer_from`, so the owner must approve this
+    /// contract as spender on `token` for the amounts to be moved. Execution
+    /// is permissionless through `trigger_due_remittances`.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if amount is not positive
+    /// - `InvalidDueDate` if `next_due` is not in the future
+    /// - `InvalidDuration` if `interval` is shorter than one day
+    pub fn create_standing_instruction(
+        env: Env,
+        owner: Address,
+        token: Address,
+        accounts: AccountGroup,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> Result<u32, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if next_due <= env.ledger().timestamp() {
+            return Err(RemittanceSplitError::InvalidDueDate);
+        }
+        if interval < MIN_STANDING_INTERVAL {
+            return Err(RemittanceSplitError::InvalidDuration);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut instructions = Self::load_standing_instructions(&env);
+        let id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_SI"))
+            .unwrap_or(0u32)
+            + 1;
+        instructions.set(
+            id,
+            StandingInstruction {
+                id,
+                owner: owner.clone(),
+                token,
+                accounts,
+                amount,
+                interval,
+                next_due,
+                active: true,
+                last_executed: None,
+                executed_count: 0,
+                missed_count: 0,
+                partial_count: 0,
+                consecutive_misses: 0,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STAND_INS"), &instructions);
+        env.storage().instance().set(&symbol_short!("NEXT_SI"), &id);
+
+        Self::append_audit(&env, symbol_short!("si_create"), &owner, true);
+        env.events().publish(
+            (symbol_short!("schedule"), ScheduleEvent::Created, owner),
+            (id, amount, next_due),
+        );
+        Ok(id)
+    }
+
+    pub fn cancel_standing_instruction(
+        env: Env,
+        caller: Address,
+        instruction_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
 
-        Ok(true)
-    }
+        let mut instructions = Self::load_standing_instructions(&env);
+        let mut instruction = instructions
+            .get(instruction_id)
+            .ok_or(RemittanceSplitError::ScheduleNotFound)?;
+        if instruction.owner != caller {
+            Self::append_audit(&env, symbol_short!("si_cancel"), &caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
 
-    pub fn get_split(env: &Env) -> Vec<u32> {
+        Self::extend_instance_ttl(&env);
+        instruction.active = false;
+        instructions.set(instruction_id, instruction);
         env.storage()
             .instance()
-            .get(&symbol_short!("SPLIT"))
-            .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
-    }
+            .set(&symbol_short!("STAND_INS"), &instructions);
 
-    pub fn get_config(env: Env) -> Option<SplitConfig> {
-        env.storage().instance().get(&symbol_short!("CONFIG"))
+        Self::append_audit(&env, symbol_short!("si_cancel"), &caller, true);
+        env.events().publish(
+            (symbol_short!("schedule"), ScheduleEvent::Cancelled, caller),
+            instruction_id,
+        );
+        Ok(true)
     }
 
-    pub fn calculate_split(
-        env: Env,
-        total_amount: i128,
-    ) -> Result<Vec<i128>, RemittanceSplitError> {
-        let amounts = Self::calculate_split_amounts(&env, total_amount, true)?;
-        Ok(vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]])
+    pub fn get_standing_instruction(env: Env, instruction_id: u32) -> Option<StandingInstruction> {
+        Self::load_standing_instructions(&env).get(instruction_id)
     }
 
-    pub fn distribute_usdc(
-        env: Env,
-        usdc_contract: Address,
-        from: Address,
-        nonce: u64,
-        accounts: AccountGroup,
-        total_amount: i128,
-    ) -> Result<bool, RemittanceSplitError> {
-        if total_amount <= 0 {
-            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
-            return Err(RemittanceSplitError::InvalidAmount);
+    pub fn get_standing_instructions(env: Env, owner: Address) -> Vec<StandingInstruction> {
+        let mut result = Vec::new(&env);
+        for (_, instruction) in Self::load_standing_instructions(&env).iter() {
+            if instruction.owner == owner {
+                result.push_back(instruction);
+            }
         }
+        result
+    }
 
-        from.require_auth();
-        Self::require_nonce(&env, &from, nonce)?;
-
-        let amounts = Self::calculate_split_amounts(&env, total_amount, false)?;
-        let token = TokenClient::new(&env, &usdc_contract);
+    /// Execute every active standing instruction that is due.
+    ///
+    /// Anyone may call this. At most 20 instructions are processed per call;
+    /// when a batch fills up, the next call resumes after the last instruction
+    /// processed so later ids are not starved. An instruction whose owner
+    /// lacks the balance or allowance, whose amount cannot be split, or whose
+    /// leg conversion or escrow deposit fails is recorded as missed and
+    /// rescheduled for its next interval; the rest of the batch still runs.
+    ///
+    /// Conversion quotes are checked before any funds move. A leg that still
+    /// fails after earlier legs were delivered is reported as
+    /// `PartiallyExecuted` with the amount delivered per category. After 3
+    /// consecutive missed or partial runs the instruction is deactivated and
+    /// `Suspended` is emitted.
+    ///
+    /// # Returns
+    /// The number of instructions executed successfully
+    pub fn trigger_due_remittances(env: Env) -> Result<u32, RemittanceSplitError> {
+        Self::require_not_paused(&env)?;
 
-        if amounts[0] > 0 {
-            token.transfer(&from, &accounts.spending, &amounts[0]);
-        }
-        if amounts[1] > 0 {
-            token.transfer(&from, &accounts.savings, &amounts[1]);
+        let now = env.ledger().timestamp();
+        let mut instructions = Self::load_standing_instructions(&env);
+        let cursor: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SI_CURSOR"))
+            .unwrap_or(0);
+        let ids = instructions.keys();
+        let mut ordered = Vec::new(&env);
+        for id in ids.iter().filter(|id| *id > cursor) {
+            ordered.push_back(id);
         }
-        if amounts[2] > 0 {
-            token.transfer(&from, &accounts.bills, &amounts[2]);
+        for id in ids.iter().filter(|id| *id <= cursor) {
+            ordered.push_back(id);
         }
-        if amounts[3] > 0 {
-            token.transfer(&from, &accounts.insurance, &amounts[3]);
+        let mut processed = 0u32;
+        let mut executed = 0u32;
+        let mut last_processed = 0u32;
+
+        for id in ordered.iter() {
+            if processed >= MAX_TRIGGER_BATCH {
+                break;
+            }
+            let mut instruction = match instructions.get(id) {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+            if !instruction.active || instruction.next_due > now {
+                continue;
+            }
+            processed += 1;
+            last_processed = id;
+
+            let token = TokenClient::new(&env, &instruction.token);
+            let funded = token.balance(&instruction.owner) >= instruction.amount
+                && token.allowance(&instruction.owner, &env.current_contract_address())
+                    >= instruction.amount;
+            let result = if funded {
+                Self::transfer_split(
+                    &env,
+                    &instruction.token,
+                    &instruction.owner,
+                    &instruction.accounts,
+                    instruction.amount,
+                    true,
+                )
+            } else {
+                Err(RemittanceSplitError::InsufficientFunds.into())
+            };
+
+            match result {
+                Ok(amounts) => {
+                    executed += 1;
+                    instruction.executed_count += 1;
+                    instruction.consecutive_misses = 0;
+                    instruction.last_executed = Some(now);
+                    Self::append_audit(&env, symbol_short!("si_exec"), &instruction.owner, true);
+                    env.events().publish(
+                        (
+                            symbol_short!("schedule"),
+                            ScheduleEvent::Executed,
+                            instruction.owner.clone(),
+                        ),
+                        (
+                            id,
+                            instruction.amount,
+                            amounts[0],
+                            amounts[1],
+                            amounts[2],
+                            amounts[3],
+                        ),
+                    );
+                }
+                Err(SplitFailure { error, delivered }) => {
+                    instruction.consecutive_misses += 1;
+                    Self::append_audit(&env, symbol_short!("si_exec"), &instruction.owner, false);
+                    if delivered.iter().any(|amount| *amount > 0) {
+                        instruction.partial_count += 1;
+                        env.events().publish(
+                            (
+                                symbol_short!("schedule"),
+                                ScheduleEvent::PartiallyExecuted,
+                                instruction.owner.clone(),
+                            ),
+                            (
+                                id,
+                                error as u32,
+                                delivered[0],
+                                delivered[1],
+                                delivered[2],
+                                delivered[3],
+                            ),
+                        );
+                    } else {
+                        instruction.missed_count += 1;
+                        env.events().publish(
+                            (
+                                symbol_short!("schedule"),
+                                ScheduleEvent::Missed,
+                                instruction.owner.clone(),
+                            ),
+                            (id, error as u32),
+                        );
+                    }
+                    if instruction.consecutive_misses >= MAX_CONSECUTIVE_MISSES {
+                        instruction.active = false;
+                        env.events().publish(
+                            (
+                                symbol_short!("schedule"),
+                                ScheduleEvent::Suspended,
+                                instruction.owner.clone(),
+                            ),
+                            (id, instruction.consecutive_misses),
+                        );
+                    }
+                }
+            }
+
+            // Skip any intervals that elapsed while nobody triggered.
+            let elapsed = now - instruction.next_due;
+            instruction.next_due += (elapsed / instruction.interval + 1) * instruction.interval;
+            instructions.set(id, instruction);
         }
 
-        Self::increment_nonce(&env, &from)?;
-        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
-        Ok(true)
+        if processed > 0 {
+            Self::extend_instance_ttl(&env);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("STAND_INS"), &instructions);
+            let cursor = if processed >= MAX_TRIGGER_BATCH {
+                last_processed
+            } else {
+                0
+            };
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SI_CURSOR"), &cursor);
+        }
+        Ok(executed)
     }
 
     pub fn get_usdc_balance(env: &Env, usdc_contract: Address, account: Address) -> i128 {
@@ -490,7 +2138,7 @@ impl RemittanceSplit {
         ];
 
         let mut result = Vec::new(env);
-        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
+        for (category, amount) in categories.into_iter().zip(amounts) {
             result.push_back(Allocation { category, amount });
         }
         Ok(result)
@@ -520,7 +2168,7 @@ impl RemittanceSplit {
             .get(&symbol_short!("CONFIG"))
             .ok_or(RemittanceSplitError::NotInitialized)?;
         if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
         let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
         Ok(Some(ExportSnapshot {
@@ -556,16 +2204,17 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::NotInitialized)?;
         if existing.owner != caller {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
 
-        let total = snapshot.config.spending_percent
-            + snapshot.config.savings_percent
-            + snapshot.config.bills_percent
-            + snapshot.config.insurance_percent;
-        if total != 100 {
+        if let Err(e) = Self::check_percentages(&[
+            snapshot.config.spending_percent,
+            snapshot.config.savings_percent,
+            snapshot.config.bills_percent,
+            snapshot.config.insurance_percent,
+        ]) {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            return Err(RemittanceSplitError::PercentagesDoNotSumTo100);
+            return Err(e);
         }
 
         Self::extend_instance_ttl(&env);
@@ -583,6 +2232,7 @@ impl RemittanceSplit {
             ],
         );
 
+        Self::record_split_version(&env, &snapshot.config);
         Self::increment_nonce(&env, &caller)?;
         Self::append_audit(&env, symbol_short!("import"), &caller, true);
         Ok(true)
@@ -635,6 +2285,472 @@ impl RemittanceSplit {
         Ok(())
     }
 
+    fn require_fee_admin(env: &Env, caller: &Address) -> Result<(), RemittanceSplitError> {
+        if Self::get_fee_admin(env).as_ref() != Some(caller) {
+            Self::append_audit(env, symbol_short!("set_fee"), caller, false);
+            return Err(RemittanceSplitError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn record_fee(env: &Env, payer: &Address, amount: i128) {
+        let mut fees = Self::get_fee_history(env.clone(), payer.clone());
+        if fees.len() >= MAX_FEE_RECORDS {
+            fees.pop_front();
+        }
+        fees.push_back(FeePayment {
+            payer: payer.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+        let key = (symbol_short!("FEES"), payer.clone());
+        env.storage().persistent().set(&key, &fees);
+        env.storage().persistent().extend_ttl(
+            &key,
+            INSTANCE_LIFETIME_THRESHOLD,
+            INSTANCE_BUMP_AMOUNT,
+        );
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("fee")),
+            (payer.clone(), amount),
+        );
+    }
+
+    fn load_standing_instructions(env: &Env) -> Map<u32, StandingInstruction> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STAND_INS"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Split `total_amount` of `token` from `from` into `accounts`, after the
+    /// platform fee, dust check and monthly caps. With `via_allowance` the
+    /// contract pulls the funds as an approved spender instead of relying on
+    /// `from`'s authorization.
+    ///
+    /// Every conversion is quoted before any funds move. A conversion or
+    /// escrow deposit that still fails returns that leg's funds to `from` and
+    /// reports the error with the amounts already delivered, instead of
+    /// panicking. Legs that call out run first, so a failure on the first of
+    /// them leaves nothing delivered.
+    fn transfer_split(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        accounts: &AccountGroup,
+        total_amount: i128,
+        via_allowance: bool,
+    ) -> Result<[i128; 4], SplitFailure> {
+        let fee = Self::quote_platform_fee(env.clone(), from.clone(), total_amount);
+        let net_amount = total_amount - fee;
+        let amounts = Self::calculate_split_amounts(env, net_amount, false)?;
+        if !Self::is_emergency_mode(env.clone()) {
+            // Refuse dust that would leave a funded category with nothing.
+            let split = Self::effective_split(env);
+            for (amount, percent) in amounts.iter().zip(split) {
+                if *amount == 0 && percent > 0 {
+                    return Err(RemittanceSplitError::AmountTooSmall.into());
+                }
+            }
+        }
+        let usage_before = Self::get_monthly_usage(env.clone());
+        let amounts = Self::apply_monthly_caps(env, amounts);
+        let token_in = token;
+        let token = TokenClient::new(env, token);
+        let spender = env.current_contract_address();
+        let send = |to: &Address, amount: i128| {
+            if amount <= 0 {
+                return;
+            }
+            if via_allowance {
+                token.transfer_from(&spender, from, to, &amount);
+            } else {
+                token.transfer(from, to, &amount);
+            }
+        };
+
+        let targets = [
+            &accounts.spending,
+            &accounts.savings,
+            &accounts.bills,
+            &accounts.insurance,
+        ];
+        let assets = Self::get_category_assets(env.clone());
+        let spending_wallet = Self::get_spending_wallet(env.clone());
+        let savings_goals = Self::get_savings_goals(env.clone());
+        let bills_escrow = Self::get_bills_escrow(env.clone());
+        let insurance_escrow = Self::get_insurance_escrow(env.clone());
+        let legs: [(Option<Address>, Option<&Address>); 4] = core::array::from_fn(|index| {
+            let amount = amounts[index];
+            let token_out = assets
+                .as_ref()
+                .and_then(|a| match index {
+                    0 => a.spending.clone(),
+                    1 => a.savings.clone(),
+                    2 => a.bills.clone(),
+                    _ => a.insurance.clone(),
+                })
+                .filter(|t| amount > 0 && t != token_in);
+            let escrow = match index {
+                0 => spending_wallet.as_ref(),
+                1 => savings_goals.as_ref(),
+                2 => bills_escrow.as_ref(),
+                3 => insurance_escrow.as_ref(),
+                _ => None,
+            }
+            .filter(|_| amount > 0);
+            (token_out, escrow)
+        });
+
+        if let Some(assets) = assets.as_ref() {
+            for (index, (token_out, _)) in legs.iter().enumerate() {
+                let quoted = match token_out {
+                    Some(token_out) => {
+                        Self::check_quote(env, assets, index, token_in, token_out, amounts[index])
+                    }
+                    None => continue,
+                };
+                if let Err(err) = quoted {
+                    env.storage()
+                        .instance()
+                        .set(&symbol_short!("CAP_USE"), &usage_before);
+                    return Err(err.into());
+                }
+            }
+        }
+
+        // Legs that call out to a router or escrow go first, so a failing
+        // call leaves as little as possible already delivered.
+        let mut order = [0usize; 4];
+        let mut next = 0;
+        for calls_out in [true, false] {
+            for (index, (token_out, escrow)) in legs.iter().enumerate() {
+                if (token_out.is_some() || escrow.is_some()) == calls_out {
+                    order[next] = index;
+                    next += 1;
+                }
+            }
+        }
+
+        let mut delivered = [0i128; 4];
+        for index in order {
+            let (target, amount) = (targets[index], amounts[index]);
+            let (token_out, escrow) = &legs[index];
+            let result = match (token_out, assets.as_ref()) {
+                (Some(token_out), Some(assets)) => {
+                    // Route the leg through the contract so the router pulls
+                    // from an address this contract can authorize.
+                    send(&spender, amount);
+                    let to = if escrow.is_some() { &spender } else { target };
+                    let converted = Self::convert_leg(
+                        env,
+                        assets,
+                        Self::category_at(index),
+                        token_in,
+                        token_out,
+                        amount,
+                        to,
+                    );
+                    match (converted, escrow) {
+                        (Ok(amount_out), Some(escrow)) => {
+                            Self::deposit_escrow(env, index, escrow, token_out, target, amount_out)
+                                .inspect_err(|_| {
+                                    Self::refund_converted(
+                                        env, assets, token_in, token_out, amount, amount_out, from,
+                                    );
+                                })
+                        }
+                        (Ok(_), None) => Ok(()),
+                        (Err(err), _) => {
+                            token.transfer(&spender, from, &amount);
+                            Err(err)
+                        }
+                    }
+                }
+                _ => match escrow {
+                    Some(escrow) => {
+                        send(&spender, amount);
+                        Self::deposit_escrow(env, index, escrow, token_in, target, amount)
+                            .inspect_err(|_| token.transfer(&spender, from, &amount))
+                    }
+                    None => {
+                        send(target, amount);
+                        Ok(())
+                    }
+                },
+            };
+            if let Err(error) = result {
+                // Only the delivered legs count against the monthly caps.
+                let mut usage = usage_before;
+                if Self::get_category_caps(env.clone()).is_some()
+                    && !Self::is_emergency_mode(env.clone())
+                {
+                    usage.spending += delivered[0];
+                    usage.savings += delivered[1];
+                    usage.bills += delivered[2];
+                    usage.insurance += delivered[3];
+                }
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("CAP_USE"), &usage);
+                return Err(SplitFailure { error, delivered });
+            }
+            delivered[index] = amount;
+        }
+
+        if fee > 0 {
+            if let Some(config) = Self::get_platform_fee(env.clone()) {
+                send(&config.collector, fee);
+                Self::record_fee(env, from, fee);
+            }
+        }
+
+        let execution_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("EXEC_SEQ"))
+            .unwrap_or(0u64)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("EXEC_SEQ"), &execution_id);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Executed, from.clone()),
+            SplitExecutedEvent {
+                execution_id,
+                user: from.clone(),
+                token: token_in.clone(),
+                total_amount,
+                fee_amount: fee,
+                spending_amount: amounts[0],
+                savings_amount: amounts[1],
+                bills_amount: amounts[2],
+                insurance_amount: amounts[3],
+                config_version: Self::get_split_version(env.clone()),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(amounts)
+    }
+
+    /// Deposit a leg, already held by this contract, into the family wallet,
+    /// savings goals or escrow on behalf of the leg's account.
+    fn deposit_escrow(
+        env: &Env,
+        index: usize,
+        escrow: &Address,
+        token: &Address,
+        owner: &Address,
+        amount: i128,
+    ) -> Result<(), RemittanceSplitError> {
+        // The escrow pulls `token` from this contract; pre-authorize that
+        // single transfer since the escrow, not this contract, invokes it.
+        let contract = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (contract.clone(), escrow.clone(), amount).into_val(env),
+                },
+                sub_invocations: Vec::new(env),
+            }),
+        ]);
+        let deposited = match index {
+            0 => FamilyWalletClient::new(env, escrow)
+                .try_receive_split_funds(&contract, token, &amount)
+                .is_ok_and(|r| r.is_ok()),
+            1 => SavingsGoalsClient::new(env, escrow)
+                .try_credit_from_split(owner, token, &amount)
+                .is_ok_and(|r| r.is_ok()),
+            2 => BillPaymentsClient::new(env, escrow)
+                .try_deposit_earmarked(&contract, owner, token, &amount)
+                .is_ok_and(|r| r.is_ok()),
+            _ => InsuranceClient::new(env, escrow)
+                .try_deposit_premium_funds(&contract, owner, token, &amount)
+                .is_ok_and(|r| r.is_ok()),
+        };
+        if deposited {
+            Ok(())
+        } else {
+            Err(RemittanceSplitError::TransferFailed)
+        }
+    }
+
+    /// Least amount of the leg's asset accepted for `amount_in`: the leg's
+    /// reference rate less `max_slippage_bps`.
+    fn leg_min_out(
+        assets: &CategoryAssets,
+        index: usize,
+        amount_in: i128,
+    ) -> Result<i128, RemittanceSplitError> {
+        let rate = [
+            assets.spending_rate,
+            assets.savings_rate,
+            assets.bills_rate,
+            assets.insurance_rate,
+        ][index];
+        amount_in
+            .checked_mul(rate)
+            .and_then(|n| n.checked_mul((10_000 - assets.max_slippage_bps) as i128))
+            .map(|n| n / RATE_SCALE / 10_000)
+            .ok_or(RemittanceSplitError::Overflow)
+    }
+
+    /// Check that the router currently quotes at least the leg's minimum.
+    fn check_quote(
+        env: &Env,
+        assets: &CategoryAssets,
+        index: usize,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+    ) -> Result<(), RemittanceSplitError> {
+        let quoted = SwapRouterClient::new(env, &assets.router)
+            .try_quote(token_in, token_out, &amount_in)
+            .ok()
+            .and_then(|r| r.ok())
+            .ok_or(RemittanceSplitError::TransferFailed)?;
+        if quoted < Self::leg_min_out(assets, index, amount_in)? {
+            return Err(RemittanceSplitError::SlippageExceeded);
+        }
+        Ok(())
+    }
+
+    /// Pre-authorize the router to pull `amount` of `token` from this
+    /// contract, since the router, not this contract, invokes the transfer.
+    fn authorize_router_pull(env: &Env, assets: &CategoryAssets, token: &Address, amount: i128) {
+        let contract = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (contract, assets.router.clone(), amount).into_val(env),
+                },
+                sub_invocations: Vec::new(env),
+            }),
+        ]);
+    }
+
+    /// Return a converted leg that could not be deposited to `from` in the
+    /// token they sent, swapping it back through the router. The swap back
+    /// must return `amount_in` less the slippage allowance on each way; if
+    /// the router refuses, the converted amount is returned as is.
+    fn refund_converted(
+        env: &Env,
+        assets: &CategoryAssets,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+        amount_out: i128,
+        from: &Address,
+    ) {
+        let keep = (10_000 - assets.max_slippage_bps) as i128;
+        let min_back = amount_in
+            .checked_mul(keep * keep)
+            .map(|n| n / 100_000_000)
+            .unwrap_or(amount_in);
+        Self::authorize_router_pull(env, assets, token_out, amount_out);
+        let contract = env.current_contract_address();
+        let swapped = SwapRouterClient::new(env, &assets.router)
+            .try_swap(&contract, token_out, token_in, &amount_out, &min_back, from)
+            .is_ok_and(|r| r.is_ok());
+        if !swapped {
+            TokenClient::new(env, token_out).transfer(&contract, from, &amount_out);
+        }
+    }
+
+    /// Swap one leg's amount into its settlement asset and record the rate.
+    fn convert_leg(
+        env: &Env,
+        assets: &CategoryAssets,
+        category: SplitCategory,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: i128,
+        to: &Address,
+    ) -> Result<i128, RemittanceSplitError> {
+        let min_out = Self::leg_min_out(assets, category as usize, amount_in)?;
+        Self::authorize_router_pull(env, assets, token_in, amount_in);
+        let contract = env.current_contract_address();
+        let amount_out = SwapRouterClient::new(env, &assets.router)
+            .try_swap(&contract, token_in, token_out, &amount_in, &min_out, to)
+            .ok()
+            .and_then(|r| r.ok())
+            .ok_or(RemittanceSplitError::TransferFailed)?;
+        if amount_out < min_out {
+            return Err(RemittanceSplitError::SlippageExceeded);
+        }
+
+        let rate = amount_out
+            .checked_mul(RATE_SCALE)
+            .map(|n| n / amount_in)
+            .ok_or(RemittanceSplitError::Overflow)?;
+        let record = ConversionRecord {
+            category,
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            amount_in,
+            amount_out,
+            rate,
+            timestamp: env.ledger().timestamp(),
+        };
+        let mut history = Self::get_conversion_history(env.clone());
+        if history.len() >= MAX_CONVERSION_RECORDS {
+            history.pop_front();
+        }
+        history.push_back(record.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONVERT"), &history);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("convert")), record);
+        Ok(amount_out)
+    }
+
+    /// Append the given configuration to the version history, dropping the
+    /// oldest entry past `MAX_SPLIT_HISTORY`. Returns the new version number.
+    fn record_split_version(env: &Env, config: &SplitConfig) -> u32 {
+        let version = Self::get_split_version(env.clone()) + 1;
+        let mut history = Self::get_split_history(env.clone());
+        if history.len() >= MAX_SPLIT_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(SplitVersion {
+            version,
+            spending_percent: config.spending_percent,
+            savings_percent: config.savings_percent,
+            bills_percent: config.bills_percent,
+            insurance_percent: config.insurance_percent,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_HIS"), &history);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_VER"), &version);
+        version
+    }
+
+    /// Percentages must sum to exactly 100; the sum is checked so oversized
+    /// inputs are rejected instead of overflowing.
+    fn check_percentages(percentages: &[u32]) -> Result<(), RemittanceSplitError> {
+        let mut total: u32 = 0;
+        for p in percentages {
+            total = total
+                .checked_add(*p)
+                .ok_or(RemittanceSplitError::InvalidPercentageSum)?;
+        }
+        if total != 100 {
+            return Err(RemittanceSplitError::InvalidPercentageSum);
+        }
+        Ok(())
+    }
+
     fn compute_checksum(version: u32, config: &SplitConfig) -> u64 {
         let v = version as u64;
         let s = config.spending_percent as u64;
@@ -678,32 +2794,11 @@ impl RemittanceSplit {
         total_amount: i128,
         emit_events: bool,
     ) -> Result<[i128; 4], RemittanceSplitError> {
-        if total_amount <= 0 {
-            return Err(RemittanceSplitError::InvalidAmount);
-        }
-
-        let split = Self::get_split(env);
-        let s0 = split.get(0).unwrap() as i128;
-        let s1 = split.get(1).unwrap() as i128;
-        let s2 = split.get(2).unwrap() as i128;
-
-        let spending = total_amount
-            .checked_mul(s0)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let savings = total_amount
-            .checked_mul(s1)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let bills = total_amount
-            .checked_mul(s2)
-            .and_then(|n| n.checked_div(100))
-            .ok_or(RemittanceSplitError::Overflow)?;
-        let insurance = total_amount
-            .checked_sub(spending)
-            .and_then(|n| n.checked_sub(savings))
-            .and_then(|n| n.checked_sub(bills))
-            .ok_or(RemittanceSplitError::Overflow)?;
+        let (reserved, shares) = Self::compute_split_parts(env, total_amount)?;
+        let spending = shares[0] + reserved[0];
+        let savings = shares[1] + reserved[1];
+        let bills = shares[2] + reserved[2];
+        let insurance = shares[3] + reserved[3];
 
         if emit_events {
             let event = SplitCalculatedEvent {
@@ -724,6 +2819,177 @@ impl RemittanceSplit {
         Ok([spending, savings, bills, insurance])
     }
 
+    /// Clamp executed amounts to the monthly caps, move the overflow to the
+    /// fallback category, and record the period's usage.
+    ///
+    /// Caps are not applied while emergency mode is active.
+    fn apply_monthly_caps(env: &Env, amounts: [i128; 4]) -> [i128; 4] {
+        let caps = match Self::get_category_caps(env.clone()) {
+            Some(caps) if !Self::is_emergency_mode(env.clone()) => caps,
+            _ => return amounts,
+        };
+
+        let mut usage = Self::get_monthly_usage(env.clone());
+        let limits = [caps.spending, caps.savings, caps.bills, caps.insurance];
+        let used = [usage.spending, usage.savings, usage.bills, usage.insurance];
+
+        let mut result = amounts;
+        let mut overflow = 0i128;
+        for i in 0..4 {
+            if limits[i] > 0 {
+                let room = (limits[i] - used[i]).max(0);
+                if result[i] > room {
+                    overflow += result[i] - room;
+                    result[i] = room;
+                }
+            }
+        }
+        result[caps.fallback as usize] += overflow;
+
+        usage.spending += result[0];
+        usage.savings += result[1];
+        usage.bills += result[2];
+        usage.insurance += result[3];
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CAP_USE"), &usage);
+
+        if overflow > 0 {
+            env.events().publish(
+                (symbol_short!("split"), symbol_short!("capped")),
+                (caps.fallback, overflow),
+            );
+        }
+        result
+    }
+
+    /// Split `total_amount` into floor reservations and percentage shares.
+    ///
+    /// Returns `(reserved, shares)`, both indexed spending, savings, bills,
+    /// insurance. Insurance's share is the remainder so the sum always equals
+    /// `total_amount`.
+    fn compute_split_parts(
+        env: &Env,
+        total_amount: i128,
+    ) -> Result<([i128; 4], [i128; 4]), RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        // Emergency override: everything goes to spending.
+        if Self::is_emergency_mode(env.clone()) {
+            return Ok(([0; 4], [total_amount, 0, 0, 0]));
+        }
+
+        // Reserve floors first, highest priority first, capped by what is left.
+        let mut reserved = [0i128; 4];
+        let mut remaining = total_amount;
+        if let Some(floors) = Self::get_category_floors(env.clone()) {
+            for (index, floor) in [
+                (2usize, floors.bills),
+                (3, floors.insurance),
+                (1, floors.savings),
+                (0, floors.spending),
+            ] {
+                let take = floor.min(remaining);
+                reserved[index] = take;
+                remaining -= take;
+            }
+        }
+
+        // Insurance absorbs the rounding dust, unless it is paused, in which
+        // case the last active category does.
+        let split = Self::effective_split(env);
+        let pauses = Self::active_category_pauses(env);
+        let dust_index = (0..4usize)
+            .rev()
+            .find(|i| !pauses.contains_key(Self::category_at(*i)))
+            .unwrap_or(3);
+
+        let mut shares = [0i128; 4];
+        for (index, percent) in split.iter().enumerate() {
+            if index != dust_index {
+                shares[index] = remaining
+                    .checked_mul(*percent as i128)
+                    .and_then(|n| n.checked_div(100))
+                    .ok_or(RemittanceSplitError::Overflow)?;
+            }
+        }
+        shares[dust_index] = shares
+            .iter()
+            .try_fold(remaining, |left, share| left.checked_sub(*share))
+            .ok_or(RemittanceSplitError::Overflow)?;
+
+        Ok((reserved, shares))
+    }
+
+    /// Paused categories whose resume date has not yet been reached.
+    fn active_category_pauses(env: &Env) -> Map<SplitCategory, u64> {
+        let now = env.ledger().timestamp();
+        let stored: Map<SplitCategory, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CAT_PAUSE"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut active = Map::new(env);
+        for (category, resume_at) in stored.iter() {
+            if resume_at > now {
+                active.set(category, resume_at);
+            }
+        }
+        active
+    }
+
+    fn category_at(index: usize) -> SplitCategory {
+        match index {
+            0 => SplitCategory::Spending,
+            1 => SplitCategory::Savings,
+            2 => SplitCategory::Bills,
+            _ => SplitCategory::Insurance,
+        }
+    }
+
+    /// Configured percentages with paused categories zeroed and their share
+    /// spread proportionally over the active ones. Integer leftovers go to the
+    /// last active category so the result always sums to 100.
+    fn effective_split(env: &Env) -> [u32; 4] {
+        let split = Self::get_split(env);
+        let mut percents = [0u32; 4];
+        for (index, percent) in percents.iter_mut().enumerate() {
+            *percent = split.get(index as u32).unwrap_or(0);
+        }
+        let pauses = Self::active_category_pauses(env);
+        if pauses.is_empty() {
+            return percents;
+        }
+
+        let mut paused_total = 0u32;
+        let mut active_total = 0u32;
+        let mut last_active = None;
+        for (index, percent) in percents.iter().enumerate() {
+            if pauses.contains_key(Self::category_at(index)) {
+                paused_total += percent;
+            } else {
+                active_total += percent;
+                last_active = Some(index);
+            }
+        }
+        let last_active = match last_active {
+            Some(index) => index,
+            None => return percents,
+        };
+
+        let mut result = [0u32; 4];
+        for (index, percent) in percents.iter().enumerate() {
+            if !pauses.contains_key(Self::category_at(index)) && active_total > 0 {
+                result[index] = percent + paused_total * percent / active_total;
+            }
+        }
+        let assigned: u32 = result.iter().sum();
+        result[last_active] += 100 - assigned;
+        result
+    }
+
     /// Extend the TTL of instance storage
     fn extend_instance_ttl(env: &Env) {
         env.storage()
@@ -825,7 +3091,7 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
 
         schedule.amount = amount;
@@ -866,7 +3132,7 @@ impl RemittanceSplit {
             .ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+            return Err(RemittanceSplitError::NotAuthorized);
         }
 
         schedule.active = false;
@@ -910,4 +3176,3 @@ impl RemittanceSplit {
         schedules.get(schedule_id)
     }
 }
-mod test;
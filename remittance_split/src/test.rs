@@ -2,8 +2,10 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
-    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+    contract, contractimpl,
+    testutils::{Address as AddressTrait, EnvTestConfig, Events, Ledger, LedgerInfo},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec,
 };
 
 fn set_time(env: &Env, timestamp: u64) {
@@ -62,10 +64,7 @@ fn test_initialize_split_invalid_sum() {
         &50, &50, &10, // Sums to 110
         &0,
     );
-    assert_eq!(
-        result,
-        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
-    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentageSum)));
 }
 
 #[test]
@@ -117,7 +116,7 @@ fn test_update_split_unauthorized() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
 }
 
 #[test]
@@ -683,3 +682,1707 @@ fn test_update_split_boundary_percentages() {
     assert_eq!(amounts.get(2).unwrap(), 250);
     assert_eq!(amounts.get(3).unwrap(), 250);
 }
+
+// ──────────────────────────────────────────────────────────────────────────
+// Category floors
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_category_floors_applied_before_percentages() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let floors = CategoryFloors {
+        spending: 0,
+        savings: 0,
+        bills: 200,
+        insurance: 0,
+    };
+    client.set_category_floors(&owner, &floors);
+    assert_eq!(client.get_category_floors(), Some(floors));
+
+    // 200 reserved for bills, remaining 800 split 50/30/15/5
+    let amounts = client.calculate_split(&1000);
+    assert_eq!(amounts.get(0).unwrap(), 400);
+    assert_eq!(amounts.get(1).unwrap(), 240);
+    assert_eq!(amounts.get(2).unwrap(), 320);
+    assert_eq!(amounts.get(3).unwrap(), 40);
+    let sum: i128 = amounts.into_iter().sum();
+    assert_eq!(sum, 1000);
+}
+
+#[test]
+fn test_category_floors_priority_when_amount_too_small() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_category_floors(
+        &owner,
+        &CategoryFloors {
+            spending: 100,
+            savings: 100,
+            bills: 50,
+            insurance: 30,
+        },
+    );
+
+    // Bills then insurance are funded first; savings takes the rest.
+    let amounts = client.calculate_split(&120);
+    assert_eq!(amounts.get(0).unwrap(), 0);
+    assert_eq!(amounts.get(1).unwrap(), 40);
+    assert_eq!(amounts.get(2).unwrap(), 50);
+    assert_eq!(amounts.get(3).unwrap(), 30);
+}
+
+#[test]
+fn test_category_floors_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let negative = CategoryFloors {
+        spending: 0,
+        savings: -1,
+        bills: 0,
+        insurance: 0,
+    };
+    let result = client.try_set_category_floors(&owner, &negative);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+
+    let valid = CategoryFloors {
+        spending: 0,
+        savings: 0,
+        bills: 10,
+        insurance: 0,
+    };
+    let result = client.try_set_category_floors(&other, &valid);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+    assert_eq!(client.get_category_floors(), None);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Earmarking due bills
+// ──────────────────────────────────────────────────────────────────────────
+
+/// Mock bill_payments contract: three unpaid bills due at 100, 200 and 900,
+/// served one per page to exercise cursor handling, and an earmarked balance
+/// per owner.
+#[contract]
+pub struct MockBillPayments;
+
+#[contractimpl]
+impl MockBillPayments {
+    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, _limit: u32) -> BillPage {
+        let bills = [(1u32, 120i128, 100u64), (2, 80, 200), (3, 500, 900)];
+        let mut items = Vec::new(&env);
+        let mut next_cursor = 0;
+        for (index, (id, amount, due_date)) in bills.iter().enumerate() {
+            if *id > cursor {
+                items.push_back(Bill {
+                    id: *id,
+                    owner: owner.clone(),
+                    payee: owner.clone(),
+                    name: String::from_str(&env, "Bill"),
+                    category: symbol_short!("utilities"),
+                    amount: *amount,
+                    amount_paid: 0,
+                    due_date: *due_date,
+                    recurring: false,
+                    frequency_days: 0,
+                    paid: false,
+                    created_at: 0,
+                    paid_at: None,
+                    schedule_id: None,
+                    status: BillStatus::Open,
+                    currency: String::from_str(&env, "XLM"),
+                });
+                if index + 1 < bills.len() {
+                    next_cursor = *id;
+                }
+                break;
+            }
+        }
+        let count = items.len();
+        BillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    pub fn deposit_earmarked(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128 {
+        payer.require_auth();
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        let earmarked = Self::get_earmarked_balance(env.clone(), owner.clone()) + amount;
+        env.storage().instance().set(&owner, &earmarked);
+        earmarked
+    }
+
+    pub fn get_earmarked_balance(env: Env, owner: Address) -> i128 {
+        env.storage().instance().get(&owner).unwrap_or(0)
+    }
+}
+
+#[contract]
+pub struct MockInsurance;
+
+#[contractimpl]
+impl MockInsurance {
+    pub fn deposit_premium_funds(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> i128 {
+        payer.require_auth();
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        let escrowed = Self::get_premium_escrow(env.clone(), owner.clone()) + amount;
+        env.storage().instance().set(&owner, &escrowed);
+        escrowed
+    }
+
+    pub fn get_premium_escrow(env: Env, owner: Address) -> i128 {
+        env.storage().instance().get(&owner).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_calculate_split_with_bills_earmarks_due_bills() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let bills_id = env.register_contract(None, MockBillPayments);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // Bills 1 and 2 are due before 500; bill 3 is not.
+    let result = client.calculate_split_with_bills(&bills_id, &owner, &1000, &500);
+    assert_eq!(result.bills, 200);
+    assert_eq!(result.bills_due, 200);
+    assert_eq!(result.bill_ids.len(), 2);
+    assert_eq!(result.bill_ids.get(0).unwrap(), 1);
+    assert_eq!(result.bill_ids.get(1).unwrap(), 2);
+
+    // Remaining 800 split 50:30:5 across spending, savings, insurance
+    assert_eq!(result.spending, 470);
+    assert_eq!(result.savings, 282);
+    assert_eq!(result.insurance, 48);
+    assert_eq!(
+        result.spending + result.savings + result.bills + result.insurance,
+        1000
+    );
+}
+
+#[test]
+fn test_calculate_split_with_bills_shortfall() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let bills_id = env.register_contract(None, MockBillPayments);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // All three bills due (700) but only 300 arrives
+    let result = client.calculate_split_with_bills(&bills_id, &owner, &300, &1000);
+    assert_eq!(result.bills, 300);
+    assert_eq!(result.bills_due, 700);
+    assert_eq!(result.bill_ids.len(), 3);
+    assert_eq!(result.spending, 0);
+    assert_eq!(result.savings, 0);
+    assert_eq!(result.insurance, 0);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Split simulation
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_simulate_split_reports_legs_and_rounding() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &25, &25, &25, &25);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.set_account_group(&owner, &accounts);
+
+    let plan = client.simulate_split(&owner, &99);
+    assert_eq!(plan.total_amount, 99);
+    assert_eq!(plan.legs.len(), 4);
+
+    let spending = plan.legs.get(0).unwrap();
+    assert_eq!(spending.amount, 24);
+    assert_eq!(spending.target, Some(accounts.spending));
+    assert_eq!(spending.rule, symbol_short!("percent"));
+    assert_eq!(spending.rounding_adjustment, 0);
+
+    let insurance = plan.legs.get(3).unwrap();
+    assert_eq!(insurance.amount, 27);
+    assert_eq!(insurance.rule, symbol_short!("remainder"));
+    assert_eq!(insurance.rounding_adjustment, 3);
+
+    // Simulation matches the real calculation
+    let amounts = client.calculate_split(&99);
+    for i in 0..4 {
+        assert_eq!(plan.legs.get(i).unwrap().amount, amounts.get(i).unwrap());
+    }
+}
+
+#[test]
+fn test_simulate_split_with_floor_and_no_state_change() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_category_floors(
+        &owner,
+        &CategoryFloors {
+            spending: 0,
+            savings: 0,
+            bills: 200,
+            insurance: 0,
+        },
+    );
+
+    let audit_before = client.get_audit_log(&0, &100).len();
+    let plan = client.simulate_split(&owner, &1000);
+    let bills = plan.legs.get(2).unwrap();
+    assert_eq!(bills.amount, 320);
+    assert_eq!(bills.floor_amount, 200);
+    assert_eq!(bills.rule, symbol_short!("floor"));
+    assert_eq!(bills.target, None);
+    assert_eq!(client.get_audit_log(&0, &100).len(), audit_before);
+
+    let result = client.try_simulate_split(&other, &1000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Emergency mode
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_emergency_mode_routes_all_to_spending_and_expires() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let head = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_family_head(&owner, &head);
+
+    let expires_at = client.enable_emergency_mode(&owner, &head, &86400);
+    assert_eq!(expires_at, 87400);
+    assert!(client.is_emergency_mode());
+
+    let amounts = client.calculate_split(&1000);
+    assert_eq!(amounts.get(0).unwrap(), 1000);
+    assert_eq!(amounts.get(1).unwrap(), 0);
+    assert_eq!(amounts.get(2).unwrap(), 0);
+    assert_eq!(amounts.get(3).unwrap(), 0);
+
+    // Auto-expiry restores the configured split
+    set_time(&env, 87400);
+    assert!(!client.is_emergency_mode());
+    let amounts = client.calculate_split(&1000);
+    assert_eq!(amounts.get(0).unwrap(), 500);
+    assert_eq!(amounts.get(3).unwrap(), 50);
+}
+
+#[test]
+fn test_emergency_mode_requires_family_head() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let head = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let result = client.try_enable_emergency_mode(&owner, &head, &3600);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::FamilyHeadNotSet)));
+
+    client.set_family_head(&owner, &head);
+    let result = client.try_enable_emergency_mode(&owner, &stranger, &3600);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    let result = client.try_enable_emergency_mode(&owner, &head, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDuration)));
+
+    client.enable_emergency_mode(&owner, &head, &3600);
+    client.disable_emergency_mode(&owner);
+    assert!(!client.is_emergency_mode());
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Monthly category caps
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_monthly_caps_redirect_overflow_to_fallback() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_category_caps(
+        &owner,
+        &CategoryCaps {
+            spending: 0,
+            savings: 0,
+            bills: 0,
+            insurance: 80,
+            fallback: SplitCategory::Savings,
+        },
+    );
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &3000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let balances = TokenClient::new(&env, &token.address());
+
+    // First remittance: insurance 50 fits under the 80 cap
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.insurance), 50);
+
+    // Second remittance: only 30 room left, 20 overflows into savings
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.insurance), 80);
+    assert_eq!(balances.balance(&accounts.savings), 620);
+
+    let usage = client.get_monthly_usage();
+    assert_eq!(usage.insurance, 80);
+    assert_eq!(usage.savings, 620);
+
+    // Next period resets usage
+    set_time(&env, 1000 + 30 * 86400);
+    assert_eq!(client.get_monthly_usage().insurance, 0);
+    client.distribute_usdc(&token.address(), &owner, &3, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.insurance), 130);
+}
+
+#[test]
+fn test_monthly_caps_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let capped_fallback = CategoryCaps {
+        spending: 0,
+        savings: 100,
+        bills: 0,
+        insurance: 80,
+        fallback: SplitCategory::Savings,
+    };
+    let result = client.try_set_category_caps(&owner, &capped_fallback);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidCapConfig)));
+
+    let negative = CategoryCaps {
+        spending: 0,
+        savings: 0,
+        bills: -1,
+        insurance: 0,
+        fallback: SplitCategory::Spending,
+    };
+    let result = client.try_set_category_caps(&owner, &negative);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+    assert_eq!(client.get_category_caps(), None);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Structured errors
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_percentage_overflow_returns_error_instead_of_panicking() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let result = client.try_initialize_split(&owner, &0, &u32::MAX, &1, &0, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentageSum)));
+}
+
+#[test]
+fn test_validate_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+
+    assert!(client.validate_split(&Vec::from_array(&env, [50, 30, 15, 5])));
+    assert!(client.validate_split(&Vec::from_array(&env, [60, 40])));
+
+    let result = client.try_validate_split(&Vec::from_array(&env, [50, 20, 10, 10, 10]));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::TooManyCategories)));
+
+    let result = client.try_validate_split(&Vec::from_array(&env, [50, 30, 15]));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentageSum)));
+}
+
+#[test]
+fn test_distribute_usdc_rejects_dust() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &100);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    // 5 units: savings (30%) and bills (15%) would round to zero
+    let result = client.try_distribute_usdc(&token.address(), &owner, &1, &accounts, &5);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::AmountTooSmall)));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Versioned configs and rollback
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_split_history_and_rollback() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.update_split(&owner, &1, &70, &10, &10, &10);
+    assert_eq!(client.get_split_version(), 2);
+
+    let history = client.get_split_history();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().spending_percent, 50);
+    assert_eq!(history.get(1).unwrap().spending_percent, 70);
+
+    let new_version = client.rollback_split(&owner, &1, &1);
+    assert_eq!(new_version, 3);
+
+    let config = client.get_config().unwrap();
+    assert_eq!(config.spending_percent, 50);
+    assert_eq!(config.savings_percent, 30);
+    assert_eq!(config.bills_percent, 15);
+    assert_eq!(config.insurance_percent, 5);
+    assert_eq!(client.get_split().get(0).unwrap(), 50);
+    assert_eq!(client.get_split_history().len(), 3);
+}
+
+#[test]
+fn test_rollback_split_errors() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let result = client.try_rollback_split(&owner, &1, &9);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::VersionNotFound)));
+
+    let result = client.try_rollback_split(&other, &0, &1);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Platform fee
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_platform_fee_deducted_before_split() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let fee_admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_fee_admin(&fee_admin, &fee_admin);
+    client.set_platform_fee(&fee_admin, &100, &collector); // 1%
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &2000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let balances = TokenClient::new(&env, &token.address());
+
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+    assert_eq!(balances.balance(&collector), 10);
+    assert_eq!(balances.balance(&accounts.spending), 495);
+    assert_eq!(balances.balance(&accounts.savings), 297);
+    assert_eq!(balances.balance(&owner), 1000);
+
+    // Half discount for this user
+    client.set_fee_discount(&fee_admin, &owner, &5000);
+    assert_eq!(client.quote_platform_fee(&owner, &1000), 5);
+    set_time(&env, 2000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&collector), 15);
+
+    assert_eq!(client.get_fees_paid(&owner, &0, &u64::MAX), 15);
+    assert_eq!(client.get_fees_paid(&owner, &1500, &u64::MAX), 5);
+    assert_eq!(client.get_fee_history(&owner).len(), 2);
+    assert_eq!(client.get_fee_history(&fee_admin).len(), 0);
+}
+
+#[test]
+fn test_platform_fee_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let fee_admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // No fee admin yet: the split owner cannot set fees
+    let result = client.try_set_platform_fee(&owner, &100, &collector);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    // The platform claims the role; the owner cannot take it over
+    let result = client.try_set_fee_admin(&owner, &fee_admin);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+    client.set_fee_admin(&fee_admin, &fee_admin);
+    let result = client.try_set_fee_admin(&owner, &owner);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+    let result = client.try_set_fee_discount(&owner, &owner, &10_000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    let result = client.try_set_platform_fee(&owner, &100, &collector);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    let result = client.try_set_platform_fee(&fee_admin, &1001, &collector);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFee)));
+
+    client.set_platform_fee(&fee_admin, &250, &collector);
+    assert_eq!(client.get_platform_fee().unwrap().fee_bps, 250);
+    assert_eq!(client.quote_platform_fee(&owner, &0), 0);
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Per-category pause
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_pause_category_redistributes_and_auto_resumes() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &40, &20, &30, &10);
+
+    // Savings' 20% is spread 40:30:10 over the others
+    client.pause_category(&owner, &SplitCategory::Savings, &5000);
+    let effective = client.get_effective_split();
+    assert_eq!(effective, vec![&env, 50, 0, 37, 13]);
+
+    let amounts = client.calculate_split(&1000);
+    assert_eq!(amounts.get(0).unwrap(), 500);
+    assert_eq!(amounts.get(1).unwrap(), 0);
+    assert_eq!(amounts.get(2).unwrap(), 370);
+    assert_eq!(amounts.get(3).unwrap(), 130);
+
+    set_time(&env, 5000);
+    assert!(client.get_paused_categories().is_empty());
+    let amounts = client.calculate_split(&1000);
+    assert_eq!(amounts.get(1).unwrap(), 200);
+}
+
+#[test]
+fn test_pause_insurance_moves_rounding_dust() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.pause_category(&owner, &SplitCategory::Insurance, &2000);
+
+    let amounts = client.calculate_split(&101);
+    assert_eq!(amounts.get(3).unwrap(), 0);
+    let total: i128 = amounts.iter().sum();
+    assert_eq!(total, 101);
+
+    client.resume_category(&owner, &SplitCategory::Insurance);
+    assert_eq!(client.get_effective_split(), client.get_split());
+}
+
+#[test]
+fn test_pause_category_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let result = client.try_pause_category(&owner, &SplitCategory::Savings, &1000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDuration)));
+
+    let result = client.try_pause_category(&other, &SplitCategory::Savings, &2000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    client.pause_category(&owner, &SplitCategory::Spending, &2000);
+    client.pause_category(&owner, &SplitCategory::Savings, &2000);
+    client.pause_category(&owner, &SplitCategory::Bills, &2000);
+    let result = client.try_pause_category(&owner, &SplitCategory::Insurance, &2000);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::AllCategoriesPaused)));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Standing instructions
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_trigger_due_remittances_executes_standing_instructions() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token_admin = Address::generate(&env);
+    let token = env.register_stellar_asset_contract_v2(token_admin);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1500);
+    let balances = TokenClient::new(&env, &token.address());
+    balances.approve(&owner, &contract_id, &5000, &1000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let id = client.create_standing_instruction(
+        &owner,
+        &token.address(),
+        &accounts,
+        &1000,
+        &2000,
+        &86400,
+    );
+
+    // Not yet due
+    assert_eq!(client.trigger_due_remittances(), 0);
+
+    set_time(&env, 2000);
+    assert_eq!(client.trigger_due_remittances(), 1);
+    assert_eq!(balances.balance(&accounts.spending), 500);
+    assert_eq!(balances.balance(&accounts.insurance), 50);
+
+    let instruction = client.get_standing_instruction(&id).unwrap();
+    assert_eq!(instruction.executed_count, 1);
+    assert_eq!(instruction.next_due, 2000 + 86400);
+
+    // Same period again does nothing
+    assert_eq!(client.trigger_due_remittances(), 0);
+
+    // Next period: only 500 left, recorded as missed and rescheduled
+    set_time(&env, 2000 + 86400);
+    assert_eq!(client.trigger_due_remittances(), 0);
+    let instruction = client.get_standing_instruction(&id).unwrap();
+    assert_eq!(instruction.missed_count, 1);
+    assert_eq!(instruction.next_due, 2000 + 2 * 86400);
+    assert_eq!(balances.balance(&owner), 500);
+}
+
+#[test]
+fn test_standing_instruction_validation_and_cancel() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    let result =
+        client.try_create_standing_instruction(&owner, &token, &accounts, &0, &2000, &86400);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+    let result =
+        client.try_create_standing_instruction(&owner, &token, &accounts, &100, &1000, &86400);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDueDate)));
+    let result = client.try_create_standing_instruction(&owner, &token, &accounts, &100, &2000, &0);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDuration)));
+    let result =
+        client.try_create_standing_instruction(&owner, &token, &accounts, &100, &2000, &3600);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDuration)));
+
+    let id = client.create_standing_instruction(&owner, &token, &accounts, &100, &2000, &86400);
+    let result = client.try_cancel_standing_instruction(&other, &id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    client.cancel_standing_instruction(&owner, &id);
+    assert!(!client.get_standing_instruction(&id).unwrap().active);
+    assert_eq!(client.get_standing_instructions(&owner).len(), 1);
+
+    set_time(&env, 3000);
+    assert_eq!(client.trigger_due_remittances(), 0);
+}
+
+/// Collect the data of every `schedule` event of the given kind.
+fn schedule_events<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+    env: &Env,
+    contract_id: &Address,
+    kind: ScheduleEvent,
+) -> Vec<T> {
+    let mut result = Vec::new(env);
+    for event in env.events().all().iter() {
+        if event.0 == *contract_id
+            && Symbol::try_from_val(env, &event.1.get(0).unwrap()) == Ok(symbol_short!("schedule"))
+        {
+            let event_kind = ScheduleEvent::try_from_val(env, &event.1.get(1).unwrap()).unwrap();
+            if event_kind as u32 == kind.clone() as u32 {
+                result.push_back(T::try_from_val(env, &event.2).unwrap());
+            }
+        }
+    }
+    result
+}
+
+#[test]
+fn test_unfunded_standing_instruction_is_suspended_after_misses() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let id = client.create_standing_instruction(
+        &owner,
+        &token.address(),
+        &accounts,
+        &1000,
+        &2000,
+        &86400,
+    );
+
+    // Triggered ten intervals late: one miss, next due in the next interval.
+    set_time(&env, 2000 + 10 * 86400 + 5);
+    assert_eq!(client.trigger_due_remittances(), 0);
+    let missed: Vec<(u32, u32)> = schedule_events(&env, &contract_id, ScheduleEvent::Missed);
+    assert_eq!(
+        missed,
+        vec![&env, (id, RemittanceSplitError::InsufficientFunds as u32)]
+    );
+    let instruction = client.get_standing_instruction(&id).unwrap();
+    assert_eq!(instruction.missed_count, 1);
+    assert_eq!(instruction.consecutive_misses, 1);
+    assert_eq!(instruction.next_due, 2000 + 11 * 86400);
+
+    for period in 11..13 {
+        set_time(&env, 2000 + period * 86400);
+        assert_eq!(client.trigger_due_remittances(), 0);
+    }
+    let instruction = client.get_standing_instruction(&id).unwrap();
+    assert_eq!(instruction.missed_count, 3);
+    assert!(!instruction.active);
+    let suspended: Vec<(u32, u32)> = schedule_events(&env, &contract_id, ScheduleEvent::Suspended);
+    assert_eq!(suspended, vec![&env, (id, 3)]);
+
+    // A suspended instruction is no longer attempted.
+    set_time(&env, 2000 + 13 * 86400);
+    assert_eq!(client.trigger_due_remittances(), 0);
+    assert_eq!(
+        client.get_standing_instruction(&id).unwrap().missed_count,
+        3
+    );
+}
+
+#[test]
+fn test_trigger_due_remittances_resumes_after_full_batch() {
+    // Twenty-one instructions would make a very large snapshot
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.budget().reset_unlimited();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    for _ in 0..21 {
+        client.create_standing_instruction(
+            &owner,
+            &token.address(),
+            &accounts,
+            &1000,
+            &2000,
+            &86400,
+        );
+    }
+
+    set_time(&env, 2000);
+    client.trigger_due_remittances();
+    assert_eq!(
+        client.get_standing_instruction(&20).unwrap().missed_count,
+        1
+    );
+    assert_eq!(
+        client.get_standing_instruction(&21).unwrap().missed_count,
+        0
+    );
+
+    // Every instruction is due again; the scan starts after id 20, so id 21
+    // runs before the batch fills and id 20 waits for the next call.
+    set_time(&env, 2000 + 86400);
+    client.trigger_due_remittances();
+    assert_eq!(
+        client.get_standing_instruction(&21).unwrap().missed_count,
+        1
+    );
+    assert_eq!(client.get_standing_instruction(&1).unwrap().missed_count, 2);
+    assert_eq!(
+        client.get_standing_instruction(&20).unwrap().missed_count,
+        1
+    );
+
+    client.trigger_due_remittances();
+    assert_eq!(
+        client.get_standing_instruction(&20).unwrap().missed_count,
+        2
+    );
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Per-category settlement assets
+// ──────────────────────────────────────────────────────────────────────────
+
+/// Router paying out two units of `token_out` per unit of `token_in`, or
+/// half a unit when swapping back into the token set with `set_source`.
+/// Amounts above the limit set with `set_slip_above` are quoted and paid one
+/// for one instead. Swaps paying less than `min_out` are rejected.
+#[contract]
+pub struct MockSwapRouter;
+
+impl MockSwapRouter {
+    fn amount_out(env: &Env, token_out: &Address, amount_in: i128) -> i128 {
+        let source: Option<Address> = env.storage().instance().get(&symbol_short!("SRC"));
+        let slip_above: Option<i128> = env.storage().instance().get(&symbol_short!("SLIP"));
+        match slip_above {
+            _ if source.as_ref() == Some(token_out) => amount_in / 2,
+            Some(limit) if amount_in > limit => amount_in,
+            _ => amount_in * 2,
+        }
+    }
+}
+
+#[contractimpl]
+impl MockSwapRouter {
+    pub fn set_slip_above(env: Env, limit: i128) {
+        env.storage().instance().set(&symbol_short!("SLIP"), &limit);
+    }
+
+    pub fn set_source(env: Env, token: Address) {
+        env.storage().instance().set(&symbol_short!("SRC"), &token);
+    }
+
+    pub fn quote(env: Env, _token_in: Address, token_out: Address, amount_in: i128) -> i128 {
+        Self::amount_out(&env, &token_out, amount_in)
+    }
+
+    pub fn swap(
+        env: Env,
+        sender: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128 {
+        let amount_out = Self::amount_out(&env, &token_out, amount_in);
+        if amount_out < min_out {
+            panic!("slippage");
+        }
+        let router = env.current_contract_address();
+        TokenClient::new(&env, &token_in).transfer(&sender, &router, &amount_in);
+        TokenClient::new(&env, &token_out).transfer(&router, &to, &amount_out);
+        amount_out
+    }
+}
+
+#[test]
+fn test_category_assets_convert_leg_and_record_rate() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let router_id = env.register_contract(None, MockSwapRouter);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let local = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &usdc.address()).mint(&owner, &1000);
+    StellarAssetClient::new(&env, &local.address()).mint(&router_id, &10_000);
+
+    client.set_category_assets(
+        &owner,
+        &CategoryAssets {
+            spending: Some(local.address()),
+            savings: Some(usdc.address()),
+            bills: None,
+            insurance: None,
+            router: router_id.clone(),
+            max_slippage_bps: 50,
+            spending_rate: 20_000_000,
+            savings_rate: 10_000_000,
+            bills_rate: 0,
+            insurance_rate: 0,
+        },
+    );
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    client.distribute_usdc(&usdc.address(), &owner, &1, &accounts, &1000);
+
+    let usdc_balances = TokenClient::new(&env, &usdc.address());
+    let local_balances = TokenClient::new(&env, &local.address());
+    assert_eq!(local_balances.balance(&accounts.spending), 1000);
+    assert_eq!(usdc_balances.balance(&accounts.spending), 0);
+    assert_eq!(usdc_balances.balance(&accounts.savings), 300);
+    assert_eq!(usdc_balances.balance(&router_id), 500);
+    assert_eq!(usdc_balances.balance(&contract_id), 0);
+
+    let history = client.get_conversion_history();
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.category, SplitCategory::Spending);
+    assert_eq!(record.amount_in, 500);
+    assert_eq!(record.amount_out, 1000);
+    assert_eq!(record.rate, 20_000_000);
+}
+
+#[test]
+fn test_trigger_due_remittances_isolates_failed_conversion() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let router_id = env.register_contract(None, MockSwapRouter);
+    let owner = Address::generate(&env);
+    let payer = Address::generate(&env);
+
+    env.mock_all_auths();
+    MockSwapRouterClient::new(&env, &router_id).set_slip_above(&400);
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let local = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let usdc_balances = TokenClient::new(&env, &usdc.address());
+    let local_balances = TokenClient::new(&env, &local.address());
+    StellarAssetClient::new(&env, &local.address()).mint(&router_id, &10_000);
+    for user in [&owner, &payer] {
+        StellarAssetClient::new(&env, &usdc.address()).mint(user, &1000);
+        usdc_balances.approve(user, &contract_id, &5000, &1000);
+    }
+    client.set_category_assets(
+        &owner,
+        &CategoryAssets {
+            spending: Some(local.address()),
+            savings: None,
+            bills: None,
+            insurance: None,
+            router: router_id,
+            max_slippage_bps: 50,
+            spending_rate: 20_000_000,
+            savings_rate: 0,
+            bills_rate: 0,
+            insurance_rate: 0,
+        },
+    );
+    let new_accounts = || AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let (slipping, converting) = (new_accounts(), new_accounts());
+
+    // Spending leg of 500 slips past the limit; the leg of 300 converts.
+    let failing = client.create_standing_instruction(
+        &owner,
+        &usdc.address(),
+        &slipping,
+        &1000,
+        &2000,
+        &86400,
+    );
+    let passing = client.create_standing_instruction(
+        &payer,
+        &usdc.address(),
+        &converting,
+        &600,
+        &2000,
+        &86400,
+    );
+
+    set_time(&env, 2000);
+    assert_eq!(client.trigger_due_remittances(), 1);
+
+    let mut missed = Vec::new(&env);
+    for event in env.events().all().iter() {
+        if event.0 == contract_id
+            && Symbol::try_from_val(&env, &event.1.get(0).unwrap()) == Ok(symbol_short!("schedule"))
+            && matches!(
+                ScheduleEvent::try_from_val(&env, &event.1.get(1).unwrap()),
+                Ok(ScheduleEvent::Missed)
+            )
+        {
+            missed.push_back(<(u32, u32)>::try_from_val(&env, &event.2).unwrap());
+        }
+    }
+    assert_eq!(
+        missed,
+        vec![
+            &env,
+            (failing, RemittanceSplitError::SlippageExceeded as u32)
+        ]
+    );
+
+    let instruction = client.get_standing_instruction(&failing).unwrap();
+    assert_eq!(instruction.missed_count, 1);
+    assert_eq!(instruction.executed_count, 0);
+    assert_eq!(usdc_balances.balance(&owner), 1000);
+    assert_eq!(usdc_balances.balance(&contract_id), 0);
+    assert_eq!(local_balances.balance(&slipping.spending), 0);
+    assert_eq!(usdc_balances.balance(&slipping.savings), 0);
+
+    let instruction = client.get_standing_instruction(&passing).unwrap();
+    assert_eq!(instruction.executed_count, 1);
+    assert_eq!(local_balances.balance(&converting.spending), 600);
+    assert_eq!(usdc_balances.balance(&converting.savings), 180);
+}
+
+#[test]
+fn test_trigger_due_remittances_reports_partial_execution() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let router_id = env.register_contract(None, MockSwapRouter);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let local = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let usdc_balances = TokenClient::new(&env, &usdc.address());
+    StellarAssetClient::new(&env, &local.address()).mint(&router_id, &10_000);
+    StellarAssetClient::new(&env, &usdc.address()).mint(&owner, &1000);
+    usdc_balances.approve(&owner, &contract_id, &5000, &1000);
+    client.set_category_assets(
+        &owner,
+        &CategoryAssets {
+            spending: Some(local.address()),
+            savings: None,
+            bills: None,
+            insurance: None,
+            router: router_id.clone(),
+            max_slippage_bps: 50,
+            spending_rate: 20_000_000,
+            savings_rate: 0,
+            bills_rate: 0,
+            insurance_rate: 0,
+        },
+    );
+    // The router has no escrow interface, so the bills deposit fails after
+    // the spending leg has been converted.
+    client.set_bills_escrow(&owner, &Some(router_id));
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let id = client.create_standing_instruction(
+        &owner,
+        &usdc.address(),
+        &accounts,
+        &1000,
+        &2000,
+        &86400,
+    );
+
+    set_time(&env, 2000);
+    assert_eq!(client.trigger_due_remittances(), 0);
+
+    let partial: Vec<(u32, u32, i128, i128, i128, i128)> =
+        schedule_events(&env, &contract_id, ScheduleEvent::PartiallyExecuted);
+    assert_eq!(
+        partial,
+        vec![
+            &env,
+            (
+                id,
+                RemittanceSplitError::TransferFailed as u32,
+                500,
+                0,
+                0,
+                0
+            )
+        ]
+    );
+    let missed: Vec<(u32, u32)> = schedule_events(&env, &contract_id, ScheduleEvent::Missed);
+    assert_eq!(missed.len(), 0);
+
+    let instruction = client.get_standing_instruction(&id).unwrap();
+    assert_eq!(instruction.partial_count, 1);
+    assert_eq!(instruction.missed_count, 0);
+    assert_eq!(instruction.consecutive_misses, 1);
+    assert_eq!(usdc_balances.balance(&owner), 500);
+    assert_eq!(usdc_balances.balance(&contract_id), 0);
+    assert_eq!(
+        TokenClient::new(&env, &local.address()).balance(&accounts.spending),
+        1000
+    );
+}
+
+#[test]
+fn test_failed_escrow_deposit_refunds_source_token() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let router_id = env.register_contract(None, MockSwapRouter);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let local = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let usdc_balances = TokenClient::new(&env, &usdc.address());
+    let local_balances = TokenClient::new(&env, &local.address());
+    MockSwapRouterClient::new(&env, &router_id).set_source(&usdc.address());
+    StellarAssetClient::new(&env, &local.address()).mint(&router_id, &10_000);
+    StellarAssetClient::new(&env, &usdc.address()).mint(&owner, &1000);
+    usdc_balances.approve(&owner, &contract_id, &5000, &1000);
+    client.set_category_assets(
+        &owner,
+        &CategoryAssets {
+            spending: Some(local.address()),
+            savings: None,
+            bills: None,
+            insurance: None,
+            router: router_id.clone(),
+            max_slippage_bps: 50,
+            spending_rate: 20_000_000,
+            savings_rate: 0,
+            bills_rate: 0,
+            insurance_rate: 0,
+        },
+    );
+    // The router is no family wallet, so the converted spending leg cannot
+    // be deposited and is swapped back.
+    client.set_spending_wallet(&owner, &Some(router_id.clone()));
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let id = client.create_standing_instruction(
+        &owner,
+        &usdc.address(),
+        &accounts,
+        &1000,
+        &2000,
+        &86400,
+    );
+
+    set_time(&env, 2000);
+    assert_eq!(client.trigger_due_remittances(), 0);
+
+    let missed: Vec<(u32, u32)> = schedule_events(&env, &contract_id, ScheduleEvent::Missed);
+    assert_eq!(
+        missed,
+        vec![&env, (id, RemittanceSplitError::TransferFailed as u32)]
+    );
+    assert_eq!(usdc_balances.balance(&owner), 1000);
+    assert_eq!(local_balances.balance(&owner), 0);
+    assert_eq!(local_balances.balance(&router_id), 10_000);
+    assert_eq!(local_balances.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_set_category_assets_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let mut assets = CategoryAssets {
+        spending: None,
+        savings: None,
+        bills: None,
+        insurance: None,
+        router: Address::generate(&env),
+        max_slippage_bps: 10_001,
+        spending_rate: 0,
+        savings_rate: 0,
+        bills_rate: 0,
+        insurance_rate: 0,
+    };
+    let result = client.try_set_category_assets(&owner, &assets);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentage)));
+
+    assets.max_slippage_bps = 100;
+    assets.bills = Some(Address::generate(&env));
+    let result = client.try_set_category_assets(&owner, &assets);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+
+    assets.bills_rate = 5_000_000;
+    let result = client.try_set_category_assets(&other, &assets);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotAuthorized)));
+
+    client.set_category_assets(&owner, &assets);
+    assert_eq!(client.get_category_assets(), Some(assets));
+}
+
+// ──────────────────────────────────────────────────────────────────────────
+// Execution events
+// ──────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_distribute_usdc_emits_execution_event() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.update_split(&owner, &1, &40, &30, &20, &10);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &2000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+
+    for nonce in 1..3u64 {
+        client.distribute_usdc(&token.address(), &owner, &nonce, &accounts, &1000);
+    }
+
+    let mut executed = Vec::new(&env);
+    for event in env.events().all().iter() {
+        if event.0 == contract_id
+            && event.1.len() == 3
+            && SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()) == Ok(SplitEvent::Executed)
+        {
+            executed.push_back(event);
+        }
+    }
+    assert_eq!(executed.len(), 2);
+
+    let (_, topics, data) = executed.last().unwrap();
+    let topic0: Symbol = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+    let user: Address = Address::try_from_val(&env, &topics.get(2).unwrap()).unwrap();
+    assert_eq!(topic0, symbol_short!("split"));
+    assert_eq!(user, owner);
+
+    let event = SplitExecutedEvent::try_from_val(&env, &data).unwrap();
+    assert_eq!(event.execution_id, 2);
+    assert_eq!(event.token, token.address());
+    assert_eq!(event.total_amount, 1000);
+    assert_eq!(event.fee_amount, 0);
+    assert_eq!(event.spending_amount, 400);
+    assert_eq!(event.savings_amount, 300);
+    assert_eq!(event.bills_amount, 200);
+    assert_eq!(event.insurance_amount, 100);
+    assert_eq!(event.config_version, 2);
+    assert_eq!(event.timestamp, 1000);
+}
+
+#[test]
+fn test_bills_escrow_earmarks_bills_leg() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let escrow_id = env.register_contract(None, MockBillPayments);
+    let escrow = MockBillPaymentsClient::new(&env, &escrow_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    assert_eq!(
+        client.try_set_bills_escrow(&other, &Some(escrow_id.clone())),
+        Err(Ok(RemittanceSplitError::NotAuthorized))
+    );
+    client.set_bills_escrow(&owner, &Some(escrow_id.clone()));
+    assert_eq!(client.get_bills_escrow(), Some(escrow_id.clone()));
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+
+    let balances = TokenClient::new(&env, &token.address());
+    assert_eq!(balances.balance(&accounts.spending), 400);
+    assert_eq!(balances.balance(&accounts.bills), 0);
+    assert_eq!(balances.balance(&escrow_id), 200);
+    assert_eq!(balances.balance(&contract_id), 0);
+    assert_eq!(escrow.get_earmarked_balance(&accounts.bills), 200);
+
+    client.set_bills_escrow(&owner, &None);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.bills), 200);
+    assert_eq!(escrow.get_earmarked_balance(&accounts.bills), 200);
+}
+
+#[test]
+fn test_insurance_escrow_funds_insurance_leg() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let escrow_id = env.register_contract(None, MockInsurance);
+    let escrow = MockInsuranceClient::new(&env, &escrow_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    assert_eq!(
+        client.try_set_insurance_escrow(&other, &Some(escrow_id.clone())),
+        Err(Ok(RemittanceSplitError::NotAuthorized))
+    );
+    client.set_insurance_escrow(&owner, &Some(escrow_id.clone()));
+    assert_eq!(client.get_insurance_escrow(), Some(escrow_id.clone()));
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+
+    let balances = TokenClient::new(&env, &token.address());
+    assert_eq!(balances.balance(&accounts.bills), 200);
+    assert_eq!(balances.balance(&accounts.insurance), 0);
+    assert_eq!(balances.balance(&escrow_id), 100);
+    assert_eq!(balances.balance(&contract_id), 0);
+    assert_eq!(escrow.get_premium_escrow(&accounts.insurance), 100);
+
+    client.set_insurance_escrow(&owner, &None);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.insurance), 100);
+    assert_eq!(escrow.get_premium_escrow(&accounts.insurance), 100);
+}
+
+/// Family wallet that accepts split funds only from its split contract.
+#[contract]
+pub struct MockFamilyWallet;
+
+#[contractimpl]
+impl MockFamilyWallet {
+    pub fn set_split_contract(env: Env, split: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_CT"), &split);
+    }
+
+    pub fn receive_split_funds(env: Env, payer: Address, token: Address, amount: i128) -> i128 {
+        payer.require_auth();
+        let split: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPLIT_CT"))
+            .unwrap();
+        assert_eq!(payer, split);
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+        let pool: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POOL"))
+            .unwrap_or(0)
+            + amount;
+        env.storage().instance().set(&symbol_short!("POOL"), &pool);
+        pool
+    }
+
+    pub fn get_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("POOL"))
+            .unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_spending_wallet_receives_spending_leg() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let wallet_id = env.register_contract(None, MockFamilyWallet);
+    let wallet = MockFamilyWalletClient::new(&env, &wallet_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    wallet.set_split_contract(&contract_id);
+    assert_eq!(
+        client.try_set_spending_wallet(&other, &Some(wallet_id.clone())),
+        Err(Ok(RemittanceSplitError::NotAuthorized))
+    );
+    client.set_spending_wallet(&owner, &Some(wallet_id.clone()));
+    assert_eq!(client.get_spending_wallet(), Some(wallet_id.clone()));
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+
+    let balances = TokenClient::new(&env, &token.address());
+    assert_eq!(balances.balance(&accounts.spending), 0);
+    assert_eq!(balances.balance(&wallet_id), 400);
+    assert_eq!(balances.balance(&contract_id), 0);
+    assert_eq!(wallet.get_pool(), 400);
+
+    client.set_spending_wallet(&owner, &None);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.spending), 400);
+    assert_eq!(wallet.get_pool(), 400);
+}
+
+mod savings_goals_mock {
+    use crate::ContributionItem;
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short, token::TokenClient, vec, Address, Env, Vec,
+    };
+
+    /// Savings goals contract crediting a single goal per user, pulling the
+    /// tokens from its split contract.
+    #[contract]
+    pub struct MockSavingsGoals;
+
+    #[contractimpl]
+    impl MockSavingsGoals {
+        pub fn set_split_contract(env: Env, split: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SPLIT_CT"), &split);
+        }
+
+        pub fn credit_from_split(
+            env: Env,
+            user: Address,
+            token: Address,
+            amount: i128,
+        ) -> Vec<ContributionItem> {
+            let split: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("SPLIT_CT"))
+                .unwrap();
+            split.require_auth();
+            TokenClient::new(&env, &token).transfer(
+                &split,
+                &env.current_contract_address(),
+                &amount,
+            );
+            let total = Self::get_saved(env.clone(), user.clone()) + amount;
+            env.storage().instance().set(&user, &total);
+            vec![&env, ContributionItem { goal_id: 1, amount }]
+        }
+
+        pub fn get_saved(env: Env, user: Address) -> i128 {
+            env.storage().instance().get(&user).unwrap_or(0)
+        }
+    }
+}
+
+use savings_goals_mock::{MockSavingsGoals, MockSavingsGoalsClient};
+
+#[test]
+fn test_savings_goals_receive_savings_leg() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let savings_id = env.register_contract(None, MockSavingsGoals);
+    let savings = MockSavingsGoalsClient::new(&env, &savings_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.initialize_split(&owner, &0, &40, &30, &20, &10);
+    savings.set_split_contract(&contract_id);
+    assert_eq!(
+        client.try_set_savings_goals(&other, &Some(savings_id.clone())),
+        Err(Ok(RemittanceSplitError::NotAuthorized))
+    );
+    client.set_savings_goals(&owner, &Some(savings_id.clone()));
+    assert_eq!(client.get_savings_goals(), Some(savings_id.clone()));
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    let accounts = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    client.distribute_usdc(&token.address(), &owner, &1, &accounts, &1000);
+
+    let balances = TokenClient::new(&env, &token.address());
+    assert_eq!(balances.balance(&accounts.savings), 0);
+    assert_eq!(balances.balance(&savings_id), 300);
+    assert_eq!(balances.balance(&contract_id), 0);
+    assert_eq!(savings.get_saved(&accounts.savings), 300);
+
+    client.set_savings_goals(&owner, &None);
+    StellarAssetClient::new(&env, &token.address()).mint(&owner, &1000);
+    client.distribute_usdc(&token.address(), &owner, &2, &accounts, &1000);
+    assert_eq!(balances.balance(&accounts.savings), 300);
+    assert_eq!(savings.get_saved(&accounts.savings), 300);
+}
@@ -13,7 +13,17 @@
 //! - Edge cases with extreme values
 
 use remittance_split::{RemittanceSplit, RemittanceSplitClient};
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig},
+    Address, Env,
+};
+
+/// Fuzz cases run many invocations; skip their ledger snapshots.
+fn fuzz_env() -> Env {
+    Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    })
+}
 
 /// Test that calculate_split preserves sum across many random inputs
 #[test]
@@ -34,7 +44,7 @@ fn fuzz_calculate_split_sum_preservation() {
     ];
 
     for (total_amount, spending_pct, savings_pct, bills_pct, insurance_pct) in test_cases {
-        let env = Env::default();
+        let env = fuzz_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -97,7 +107,7 @@ fn fuzz_calculate_split_sum_preservation() {
 /// Test edge cases with small amounts
 #[test]
 fn fuzz_calculate_split_small_amounts() {
-    let env = Env::default();
+    let env = fuzz_env();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, RemittanceSplit);
     let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -157,7 +167,7 @@ fn fuzz_rounding_behavior() {
     ];
 
     for (spending_pct, savings_pct, bills_pct, insurance_pct) in prime_percentages {
-        let env = Env::default();
+        let env = fuzz_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -204,7 +214,7 @@ fn fuzz_rounding_behavior() {
 /// Test that invalid amounts are rejected
 #[test]
 fn fuzz_invalid_amounts() {
-    let env = Env::default();
+    let env = fuzz_env();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, RemittanceSplit);
     let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -231,7 +241,7 @@ fn fuzz_invalid_percentages() {
     ];
 
     for (spending_pct, savings_pct, bills_pct, insurance_pct) in invalid_percentages {
-        let env = Env::default();
+        let env = fuzz_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -260,7 +270,7 @@ fn fuzz_invalid_percentages() {
 /// Test overflow protection with large amounts
 #[test]
 fn fuzz_large_amounts() {
-    let env = Env::default();
+    let env = fuzz_env();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, RemittanceSplit);
     let client = RemittanceSplitClient::new(&env, &contract_id);
@@ -309,7 +319,7 @@ fn fuzz_single_category_splits() {
     ];
 
     for (spending_pct, savings_pct, bills_pct, insurance_pct) in single_category_splits {
-        let env = Env::default();
+        let env = fuzz_env();
         env.mock_all_auths();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
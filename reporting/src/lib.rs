@@ -1,6 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
+    Symbol, Vec,
 };
 
 // Storage TTL constants for active data
@@ -11,6 +12,13 @@ const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
 const ARCHIVE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const ARCHIVE_BUMP_AMOUNT: u32 = 2592000; // ~180 days (6 months)
 
+// Most savings-score points a monthly savings streak can add
+const MAX_STREAK_BONUS: u32 = 5;
+
+// Recommended insurance coverage, in months of remittance income
+const RECOMMENDED_COVERAGE_MONTHS: i128 = 12;
+const MONTH_SECS: u64 = 30 * 86400;
+
 /// Category for financial breakdown
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -51,6 +59,43 @@ pub struct TrendData {
     pub change_percentage: i32, // Can be negative
 }
 
+/// A reporting period, `[start, end]`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReportPeriod {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Insurance cost and coverage over one period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumPeriod {
+    pub period_start: u64,
+    pub period_end: u64,
+    /// Premiums paid for due dates within the period
+    pub premiums_paid: i128,
+    /// Monthly premium in force at the end of the period
+    pub monthly_premium: i128,
+    /// Coverage in force at the end of the period
+    pub total_coverage: i128,
+    /// Monthly premium per unit of coverage, in basis points
+    pub premium_per_coverage_bps: u32,
+}
+
+/// How insurance costs moved between two periods relative to coverage
+#[contracttype]
+#[derive(Clone)]
+pub struct PremiumTrend {
+    pub current: PremiumPeriod,
+    pub previous: PremiumPeriod,
+    pub premiums_paid: TrendData,
+    pub monthly_premium: TrendData,
+    pub total_coverage: TrendData,
+    /// The premium paid per unit of coverage went up between the periods
+    pub cost_rising: bool,
+}
+
 /// Remittance summary report
 #[contracttype]
 #[derive(Clone)]
@@ -71,10 +116,30 @@ pub struct SavingsReport {
     pub total_target: i128,
     pub total_saved: i128,
     pub completion_percentage: u32,
+    /// Goal milestones reached so far, across all goals
+    pub milestones_reached: u32,
+    /// Amount unlocked for withdrawal by reached milestones
+    pub milestone_unlocked: i128,
+    /// Contributed to goals during the report period
+    pub period_contributions: i128,
+    /// Yield credited to goals from pools; included in `total_saved`
+    pub yield_earned: i128,
+    /// Progress per goal tag; untagged goals are not included
+    pub by_tag: Vec<TagProgress>,
     pub period_start: u64,
     pub period_end: u64,
 }
 
+/// Savings progress for one goal tag
+#[contracttype]
+#[derive(Clone)]
+pub struct TagProgress {
+    pub tag: soroban_sdk::Symbol,
+    pub goals: u32,
+    pub total_target: i128,
+    pub total_saved: i128,
+}
+
 /// Bill payment compliance report
 #[contracttype]
 #[derive(Clone)]
@@ -82,15 +147,40 @@ pub struct BillComplianceReport {
     pub total_bills: u32,
     pub paid_bills: u32,
     pub unpaid_bills: u32,
+    /// Unpaid bills with part of the amount already paid
+    pub partially_paid_bills: u32,
+    /// Unpaid bills under dispute; never counted as overdue
+    pub disputed_bills: u32,
+    /// Bills cancelled by the owner; left out of every other count and amount
+    pub cancelled_bills: u32,
+    /// Unpaid bills on an installment plan that is on schedule; never
+    /// counted as overdue, and counted as compliant
+    pub on_plan_bills: u32,
     pub overdue_bills: u32,
     pub total_amount: i128,
     pub paid_amount: i128,
     pub unpaid_amount: i128,
     pub compliance_percentage: u32,
+    /// Sub-totals per bill category, in order of first appearance
+    pub categories: Vec<CategoryCompliance>,
+    /// Bills paid in another asset during the period, with effective rates
+    pub conversions: Vec<PaymentConversion>,
     pub period_start: u64,
     pub period_end: u64,
 }
 
+/// Bill compliance sub-totals for one category, e.g. `rent` or `school`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryCompliance {
+    pub category: Symbol,
+    pub total_bills: u32,
+    pub paid_bills: u32,
+    pub overdue_bills: u32,
+    pub total_amount: i128,
+    pub unpaid_amount: i128,
+}
+
 /// Insurance coverage report
 #[contracttype]
 #[derive(Clone)]
@@ -100,10 +190,42 @@ pub struct InsuranceReport {
     pub monthly_premium: i128,
     pub annual_premium: i128,
     pub coverage_to_premium_ratio: u32,
+    /// Premiums paid for due dates within the period
+    pub premiums_paid: i128,
+    /// Unearned prepaid premiums refunded on cancellation within the period
+    pub premiums_refunded: i128,
+    /// The user's mutual pool memberships and each pool's solvency
+    pub pools: Vec<PoolSummary>,
+    /// Claims filed within the period
+    pub claims_filed: u32,
+    /// Claims approved within the period, including those since paid
+    pub claims_approved: u32,
+    /// Claims rejected within the period
+    pub claims_rejected: u32,
+    /// Claim payouts received within the period
+    pub claims_paid: i128,
+    /// Coverage held as a covered member of family group policies
+    pub group_coverage: i128,
+    /// Coverage still needed to reach the recommended level. Income is only
+    /// known to `get_financial_health_report`, so this is 0 elsewhere.
+    pub coverage_gap: i128,
     pub period_start: u64,
     pub period_end: u64,
 }
 
+/// Recommended versus actual insurance coverage for a given income
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoverageGap {
+    pub monthly_income: i128,
+    pub recommended_coverage: i128,
+    pub actual_coverage: i128,
+    /// Shortfall against the recommendation, 0 when adequately covered
+    pub gap: i128,
+    /// Actual coverage as basis points of the recommendation
+    pub coverage_bps: u32,
+}
+
 /// Family spending report
 #[contracttype]
 #[derive(Clone)]
@@ -111,6 +233,45 @@ pub struct FamilySpendingReport {
     pub total_members: u32,
     pub total_spending: i128,
     pub average_per_member: i128,
+    /// Spending recorded per category, in category order
+    pub by_category: Vec<CategorySpend>,
+    pub period_start: u64,
+    pub period_end: u64,
+}
+
+/// Family wallet budget envelopes compared with actual spending
+#[contracttype]
+#[derive(Clone)]
+pub struct FamilyBudgetReport {
+    /// Each envelope's budget, spending and remaining balance, in category order
+    pub envelopes: Vec<EnvelopeVsActual>,
+    pub total_budget: i128,
+    pub total_actual: i128,
+    /// Number of envelopes whose spending exceeded their budget
+    pub over_budget: u32,
+    pub period_start: u64,
+    pub period_end: u64,
+}
+
+/// One covered member's position across a family's group policies
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FamilyMemberCoverage {
+    pub member: Address,
+    pub coverage_amount: i128,
+    /// Claim payouts the member received within the period
+    pub claims_paid: i128,
+}
+
+/// Group insurance report for a family's policy owner
+#[contracttype]
+#[derive(Clone)]
+pub struct FamilyInsuranceReport {
+    pub group_policies: u32,
+    pub covered_members: u32,
+    pub total_coverage: i128,
+    pub monthly_premium: i128,
+    pub members: Vec<FamilyMemberCoverage>,
     pub period_start: u64,
     pub period_end: u64,
 }
@@ -124,6 +285,8 @@ pub struct FinancialHealthReport {
     pub savings_report: SavingsReport,
     pub bill_compliance: BillComplianceReport,
     pub insurance_report: InsuranceReport,
+    /// Suggested actions, such as closing a coverage gap
+    pub recommendations: Vec<String>,
     pub generated_at: u64,
 }
 
@@ -225,19 +388,50 @@ pub trait RemittanceSplitTrait {
 pub trait SavingsGoalsTrait {
     fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal>;
     fn is_goal_completed(env: Env, goal_id: u32) -> bool;
+    fn get_goal_totals(env: Env, owner: Address) -> GoalTotals;
+    fn get_period_contributions(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> i128;
+    fn get_savings_streak(env: Env, owner: Address) -> SavingsStreak;
 }
 
 #[contractclient(name = "BillPaymentsClient")]
 pub trait BillPaymentsTrait {
     fn get_unpaid_bills(env: Env, owner: Address) -> Vec<Bill>;
     fn get_total_unpaid(env: Env, owner: Address) -> i128;
-    fn get_all_bills(env: Env) -> Vec<Bill>;
+    fn get_bills_by_owner(env: Env, owner: Address, offset: u32, limit: u32) -> BillPage;
+    fn get_grace_period(env: Env, owner: Address) -> u32;
+    fn get_owner_conversions(env: Env, owner: Address) -> Vec<PaymentConversion>;
+    fn get_owner_plan_statuses(env: Env, owner: Address) -> Vec<PaymentPlanStatus>;
 }
 
 #[contractclient(name = "InsuranceClient")]
 pub trait InsuranceTrait {
-    fn get_active_policies(env: Env, owner: Address) -> Vec<InsurancePolicy>;
-    fn get_total_monthly_premium(env: Env, owner: Address) -> i128;
+    fn get_policy(env: Env, policy_id: u32) -> Option<InsurancePolicy>;
+    fn get_coverage_totals(env: Env, owner: Address) -> CoverageTotals;
+    fn get_premiums_paid(env: Env, owner: Address, period_start: u64, period_end: u64) -> i128;
+    fn get_premiums_refunded(env: Env, owner: Address, period_start: u64, period_end: u64) -> i128;
+    fn get_overdue_policies(env: Env, owner: Address) -> Vec<u32>;
+    fn get_pool_summaries(env: Env, member: Address) -> Vec<PoolSummary>;
+    fn get_claim_stats(env: Env, owner: Address, period_start: u64, period_end: u64) -> ClaimStats;
+    fn get_member_coverage(env: Env, member: Address) -> Vec<MemberCoverage>;
+    fn get_group_policies(env: Env, owner: Address) -> Vec<GroupPolicy>;
+    fn get_premium_level_at(env: Env, owner: Address, timestamp: u64) -> PremiumLevel;
+}
+
+#[contractclient(name = "FamilyWalletClient")]
+pub trait FamilyWalletTrait {
+    fn get_member_count(env: Env) -> u32;
+    fn get_family_spending(env: Env, period_start: u64, period_end: u64) -> i128;
+    fn get_category_spending(env: Env, period_start: u64, period_end: u64) -> Vec<CategorySpend>;
+    fn get_envelope_vs_actual(
+        env: Env,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<EnvelopeVsActual>;
 }
 
 // Data structures from other contracts (needed for client traits)
@@ -252,6 +446,31 @@ pub struct SavingsGoal {
     pub current_amount: i128,
     pub target_date: u64,
     pub locked: bool,
+    pub unlock_date: Option<u64>,
+    pub tag: Option<soroban_sdk::Symbol>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalTotals {
+    pub goal_count: u32,
+    pub completed_count: u32,
+    pub total_target: i128,
+    pub total_saved: i128,
+    pub milestones_reached: u32,
+    pub milestone_unlocked: i128,
+    pub yield_earned: i128,
+    pub by_tag: Vec<TagProgress>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct SavingsStreak {
+    pub current_weeks: u32,
+    pub longest_weeks: u32,
+    pub current_months: u32,
+    pub longest_months: u32,
+    pub last_contribution: u64,
 }
 
 #[contracttype]
@@ -259,14 +478,101 @@ pub struct SavingsGoal {
 pub struct Bill {
     pub id: u32,
     pub owner: Address,
+    pub payee: Address,
     pub name: soroban_sdk::String,
+    pub category: Symbol,
     pub amount: i128,
+    pub amount_paid: i128,
     pub due_date: u64,
     pub recurring: bool,
     pub frequency_days: u32,
     pub paid: bool,
     pub created_at: u64,
     pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub status: BillStatus,
+    pub currency: soroban_sdk::String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentConversion {
+    pub bill_id: u32,
+    pub source_token: Address,
+    pub target_token: Address,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolSummary {
+    pub pool_id: u32,
+    pub contributed: i128,
+    pub claimed: i128,
+    pub solvency_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimStats {
+    pub filed: u32,
+    pub approved: u32,
+    pub rejected: u32,
+    pub total_paid: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoveredMember {
+    pub member: Address,
+    pub coverage_amount: i128,
+    pub added_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupPolicy {
+    pub policy_id: u32,
+    pub family_wallet: Address,
+    pub members: Vec<CoveredMember>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberCoverage {
+    pub policy_id: u32,
+    pub owner: Address,
+    pub coverage_amount: i128,
+    pub claimed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentPlanStatus {
+    pub bill_id: u32,
+    pub installments_paid: u32,
+    pub installments_total: u32,
+    pub on_schedule: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BillPage {
+    pub items: Vec<Bill>,
+    /// Offset of the next page; 0 means no more pages
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BillStatus {
+    Open,
+    Disputed(soroban_sdk::String),
+    Cancelled(soroban_sdk::String),
 }
 
 #[contracttype]
@@ -280,6 +586,55 @@ pub struct InsurancePolicy {
     pub coverage_amount: i128,
     pub active: bool,
     pub next_payment_date: u64,
+    pub schedule_id: Option<u32>,
+    pub term_end: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoverageTotals {
+    pub active_policies: u32,
+    pub total_coverage: i128,
+    pub monthly_premium: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumLevel {
+    pub active_policies: u32,
+    pub total_coverage: i128,
+    pub monthly_premium: i128,
+    pub recorded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SpendCategory {
+    School = 1,
+    Food = 2,
+    Health = 3,
+    Transport = 4,
+    Housing = 5,
+    Utilities = 6,
+    Other = 7,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategorySpend {
+    pub category: SpendCategory,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvelopeVsActual {
+    pub category: SpendCategory,
+    pub token: Address,
+    pub budget: i128,
+    pub actual: i128,
+    pub balance: i128,
 }
 
 #[contract]
@@ -435,33 +790,27 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let savings_client = SavingsGoalsClient::new(&env, &addresses.savings_goals);
-        let goals = savings_client.get_all_goals(&user);
-
-        let mut total_target = 0i128;
-        let mut total_saved = 0i128;
-        let mut completed_count = 0u32;
-        let total_goals = goals.len();
-
-        for goal in goals.iter() {
-            total_target += goal.target_amount;
-            total_saved += goal.current_amount;
-            if goal.current_amount >= goal.target_amount {
-                completed_count += 1;
-            }
-        }
+        let totals = savings_client.get_goal_totals(&user);
+        let period_contributions =
+            savings_client.get_period_contributions(&user, &period_start, &period_end);
 
-        let completion_percentage = if total_target > 0 {
-            ((total_saved * 100) / total_target) as u32
+        let completion_percentage = if totals.total_target > 0 {
+            ((totals.total_saved * 100) / totals.total_target) as u32
         } else {
             0
         };
 
         SavingsReport {
-            total_goals,
-            completed_goals: completed_count,
-            total_target,
-            total_saved,
+            total_goals: totals.goal_count,
+            completed_goals: totals.completed_count,
+            total_target: totals.total_target,
+            total_saved: totals.total_saved,
             completion_percentage,
+            milestones_reached: totals.milestones_reached,
+            milestone_unlocked: totals.milestone_unlocked,
+            period_contributions,
+            yield_earned: totals.yield_earned,
+            by_tag: totals.by_tag,
             period_start,
             period_end,
         }
@@ -481,58 +830,125 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let bill_client = BillPaymentsClient::new(&env, &addresses.bill_payments);
-        let all_bills = bill_client.get_all_bills();
+        let mut all_bills = Vec::new(&env);
+        let mut offset = 0u32;
+        loop {
+            let page = bill_client.get_bills_by_owner(&user, &offset, &0);
+            all_bills.append(&page.items);
+            if page.next_cursor == 0 {
+                break;
+            }
+            offset = page.next_cursor;
+        }
 
         let mut total_bills = 0u32;
         let mut paid_bills = 0u32;
         let mut unpaid_bills = 0u32;
+        let mut partially_paid_bills = 0u32;
+        let mut disputed_bills = 0u32;
+        let mut cancelled_bills = 0u32;
+        let mut on_plan_bills = 0u32;
         let mut overdue_bills = 0u32;
         let mut total_amount = 0i128;
         let mut paid_amount = 0i128;
         let mut unpaid_amount = 0i128;
+        let mut categories: Vec<CategoryCompliance> = Vec::new(&env);
 
-        let current_time = env.ledger().timestamp();
+        // Bills only count as overdue once the owner's grace period has passed
+        let overdue_before = env
+            .ledger()
+            .timestamp()
+            .saturating_sub(bill_client.get_grace_period(&user) as u64 * 86400);
+        // A bill on an installment plan is overdue only when the plan is behind
+        let plans = bill_client.get_owner_plan_statuses(&user);
 
         for bill in all_bills.iter() {
-            if bill.owner != user {
+            // Filter by period
+            if bill.created_at < period_start || bill.created_at > period_end {
                 continue;
             }
 
-            // Filter by period
-            if bill.created_at < period_start || bill.created_at > period_end {
+            if let BillStatus::Cancelled(_) = bill.status {
+                cancelled_bills += 1;
                 continue;
             }
 
             total_bills += 1;
             total_amount += bill.amount;
 
+            let position = categories
+                .iter()
+                .position(|entry| entry.category == bill.category);
+            let mut entry = match position {
+                Some(i) => categories.get(i as u32).unwrap(),
+                None => CategoryCompliance {
+                    category: bill.category.clone(),
+                    total_bills: 0,
+                    paid_bills: 0,
+                    overdue_bills: 0,
+                    total_amount: 0,
+                    unpaid_amount: 0,
+                },
+            };
+            entry.total_bills += 1;
+            entry.total_amount += bill.amount;
+
             if bill.paid {
                 paid_bills += 1;
                 paid_amount += bill.amount;
+                entry.paid_bills += 1;
             } else {
-                unpaid_bills += 1;
-                unpaid_amount += bill.amount;
-                if bill.due_date < current_time {
+                if bill.amount_paid > 0 {
+                    partially_paid_bills += 1;
+                    paid_amount += bill.amount_paid;
+                } else {
+                    unpaid_bills += 1;
+                }
+                unpaid_amount += bill.amount - bill.amount_paid;
+                entry.unpaid_amount += bill.amount - bill.amount_paid;
+                let plan = plans.iter().find(|plan| plan.bill_id == bill.id);
+                if let BillStatus::Disputed(_) = bill.status {
+                    disputed_bills += 1;
+                } else if plan.as_ref().is_some_and(|plan| plan.on_schedule) {
+                    on_plan_bills += 1;
+                } else if plan.is_some() || bill.due_date < overdue_before {
                     overdue_bills += 1;
+                    entry.overdue_bills += 1;
                 }
             }
+
+            match position {
+                Some(i) => categories.set(i as u32, entry),
+                None => categories.push_back(entry),
+            }
         }
 
-        let compliance_percentage = if total_bills > 0 {
-            (paid_bills * 100) / total_bills
-        } else {
-            100
-        };
+        let compliance_percentage = ((paid_bills + on_plan_bills) * 100)
+            .checked_div(total_bills)
+            .unwrap_or(100);
+
+        let mut conversions = Vec::new(&env);
+        for conversion in bill_client.get_owner_conversions(&user).iter() {
+            if conversion.timestamp >= period_start && conversion.timestamp <= period_end {
+                conversions.push_back(conversion);
+            }
+        }
 
         BillComplianceReport {
             total_bills,
             paid_bills,
             unpaid_bills,
+            partially_paid_bills,
+            disputed_bills,
+            cancelled_bills,
+            on_plan_bills,
             overdue_bills,
             total_amount,
             paid_amount,
             unpaid_amount,
             compliance_percentage,
+            categories,
+            conversions,
             period_start,
             period_end,
         }
@@ -552,16 +968,19 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let insurance_client = InsuranceClient::new(&env, &addresses.insurance);
-        let policies = insurance_client.get_active_policies(&user);
-        let monthly_premium = insurance_client.get_total_monthly_premium(&user);
-
-        let mut total_coverage = 0i128;
-        let active_policies = policies.len();
-
-        for policy in policies.iter() {
-            total_coverage += policy.coverage_amount;
+        let totals = insurance_client.get_coverage_totals(&user);
+        let premiums_paid = insurance_client.get_premiums_paid(&user, &period_start, &period_end);
+        let premiums_refunded =
+            insurance_client.get_premiums_refunded(&user, &period_start, &period_end);
+        let pools = insurance_client.get_pool_summaries(&user);
+        let claims = insurance_client.get_claim_stats(&user, &period_start, &period_end);
+        let mut group_coverage = 0i128;
+        for coverage in insurance_client.get_member_coverage(&user).iter() {
+            group_coverage += coverage.coverage_amount;
         }
 
+        let monthly_premium = totals.monthly_premium;
+        let total_coverage = totals.total_coverage;
         let annual_premium = monthly_premium * 12;
         let coverage_to_premium_ratio = if annual_premium > 0 {
             ((total_coverage * 100) / annual_premium) as u32
@@ -570,16 +989,180 @@ impl ReportingContract {
         };
 
         InsuranceReport {
-            active_policies,
+            active_policies: totals.active_policies,
             total_coverage,
             monthly_premium,
             annual_premium,
             coverage_to_premium_ratio,
+            premiums_paid,
+            premiums_refunded,
+            pools,
+            claims_filed: claims.filed,
+            claims_approved: claims.approved,
+            claims_rejected: claims.rejected,
+            claims_paid: claims.total_paid,
+            group_coverage,
+            coverage_gap: 0,
             period_start,
             period_end,
         }
     }
 
+    /// Generate the family-level view of the group policies `owner` holds:
+    /// each covered member's coverage and the claim payouts they received
+    /// within the period.
+    pub fn get_family_insurance_report(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> FamilyInsuranceReport {
+        let addresses: ContractAddresses = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADDRS"))
+            .expect("Contract addresses not configured");
+
+        let insurance_client = InsuranceClient::new(&env, &addresses.insurance);
+        let groups = insurance_client.get_group_policies(&owner);
+
+        let mut members: Vec<FamilyMemberCoverage> = Vec::new(&env);
+        let mut total_coverage = 0i128;
+        let mut monthly_premium = 0i128;
+        for group in groups.iter() {
+            if let Some(policy) = insurance_client.get_policy(&group.policy_id) {
+                monthly_premium += policy.monthly_premium;
+            }
+            for covered in group.members.iter() {
+                total_coverage += covered.coverage_amount;
+                match members.iter().position(|m| m.member == covered.member) {
+                    Some(index) => {
+                        let index = index as u32;
+                        let mut entry = members.get(index).unwrap();
+                        entry.coverage_amount += covered.coverage_amount;
+                        members.set(index, entry);
+                    }
+                    None => members.push_back(FamilyMemberCoverage {
+                        member: covered.member.clone(),
+                        coverage_amount: covered.coverage_amount,
+                        claims_paid: insurance_client
+                            .get_claim_stats(&covered.member, &period_start, &period_end)
+                            .total_paid,
+                    }),
+                }
+            }
+        }
+
+        FamilyInsuranceReport {
+            group_policies: groups.len(),
+            covered_members: members.len(),
+            total_coverage,
+            monthly_premium,
+            members,
+            period_start,
+            period_end,
+        }
+    }
+
+    /// Generate the family wallet's spending report: every outflow in the
+    /// members' transaction histories within the period, plus the
+    /// categorized sub-balance spends broken down by category.
+    pub fn get_family_spending_report(
+        env: Env,
+        period_start: u64,
+        period_end: u64,
+    ) -> FamilySpendingReport {
+        let addresses: ContractAddresses = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADDRS"))
+            .expect("Contract addresses not configured");
+
+        let wallet_client = FamilyWalletClient::new(&env, &addresses.family_wallet);
+        let total_members = wallet_client.get_member_count();
+        let by_category = wallet_client.get_category_spending(&period_start, &period_end);
+        let total_spending = wallet_client.get_family_spending(&period_start, &period_end);
+
+        FamilySpendingReport {
+            total_members,
+            total_spending,
+            average_per_member: if total_members > 0 {
+                total_spending / total_members as i128
+            } else {
+                0
+            },
+            by_category,
+            period_start,
+            period_end,
+        }
+    }
+
+    /// Generate the family wallet's budget report: each envelope's budget
+    /// against what was spent from it within the period.
+    pub fn get_family_budget_report(
+        env: Env,
+        period_start: u64,
+        period_end: u64,
+    ) -> FamilyBudgetReport {
+        let addresses: ContractAddresses = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADDRS"))
+            .expect("Contract addresses not configured");
+
+        let wallet_client = FamilyWalletClient::new(&env, &addresses.family_wallet);
+        let envelopes = wallet_client.get_envelope_vs_actual(&period_start, &period_end);
+        let mut total_budget = 0i128;
+        let mut total_actual = 0i128;
+        let mut over_budget = 0u32;
+        for envelope in envelopes.iter() {
+            total_budget += envelope.budget;
+            total_actual += envelope.actual;
+            if envelope.actual > envelope.budget {
+                over_budget += 1;
+            }
+        }
+
+        FamilyBudgetReport {
+            envelopes,
+            total_budget,
+            total_actual,
+            over_budget,
+            period_start,
+            period_end,
+        }
+    }
+
+    /// Compare the user's active coverage with the recommended
+    /// `RECOMMENDED_COVERAGE_MONTHS` months of remittance income.
+    pub fn get_coverage_gap(env: Env, user: Address, monthly_remittance: i128) -> CoverageGap {
+        let addresses: ContractAddresses = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADDRS"))
+            .expect("Contract addresses not configured");
+
+        let insurance_client = InsuranceClient::new(&env, &addresses.insurance);
+        let actual_coverage = insurance_client.get_coverage_totals(&user).total_coverage;
+
+        let monthly_income = monthly_remittance.max(0);
+        let recommended_coverage = monthly_income * RECOMMENDED_COVERAGE_MONTHS;
+        let gap = (recommended_coverage - actual_coverage).max(0);
+        let coverage_bps = if recommended_coverage > 0 {
+            ((actual_coverage * 10_000) / recommended_coverage).min(u32::MAX as i128) as u32
+        } else {
+            10_000
+        };
+
+        CoverageGap {
+            monthly_income,
+            recommended_coverage,
+            actual_coverage,
+            gap,
+            coverage_bps,
+        }
+    }
+
     /// Calculate financial health score
     pub fn calculate_health_score(env: Env, user: Address, _total_remittance: i128) -> HealthScore {
         let addresses: ContractAddresses = env
@@ -588,17 +1171,12 @@ impl ReportingContract {
             .get(&symbol_short!("ADDRS"))
             .expect("Contract addresses not configured");
 
-        // Savings score (0-40 points)
+        // Savings score (0-40 points), plus a point per consecutive month
+        // of saving (up to 5) within the same cap
         let savings_client = SavingsGoalsClient::new(&env, &addresses.savings_goals);
-        let goals = savings_client.get_all_goals(&user);
-        let mut total_target = 0i128;
-        let mut total_saved = 0i128;
-        for goal in goals.iter() {
-            total_target += goal.target_amount;
-            total_saved += goal.current_amount;
-        }
-        let savings_score = if total_target > 0 {
-            let progress = ((total_saved * 100) / total_target) as u32;
+        let totals = savings_client.get_goal_totals(&user);
+        let progress_score = if totals.total_target > 0 {
+            let progress = ((totals.total_saved * 100) / totals.total_target) as u32;
             if progress > 100 {
                 40
             } else {
@@ -607,16 +1185,25 @@ impl ReportingContract {
         } else {
             20 // Default score if no goals
         };
+        let streak_bonus = savings_client
+            .get_savings_streak(&user)
+            .current_months
+            .min(MAX_STREAK_BONUS);
+        let savings_score = (progress_score + streak_bonus).min(40);
 
         // Bills score (0-40 points)
         let bill_client = BillPaymentsClient::new(&env, &addresses.bill_payments);
         let unpaid_bills = bill_client.get_unpaid_bills(&user);
+        let overdue_before = env
+            .ledger()
+            .timestamp()
+            .saturating_sub(bill_client.get_grace_period(&user) as u64 * 86400);
         let bills_score = if unpaid_bills.is_empty() {
             40
         } else {
             let overdue_count = unpaid_bills
                 .iter()
-                .filter(|b| b.due_date < env.ledger().timestamp())
+                .filter(|b| b.status == BillStatus::Open && b.due_date < overdue_before)
                 .count();
             if overdue_count == 0 {
                 35 // Has unpaid but none overdue
@@ -625,10 +1212,16 @@ impl ReportingContract {
             }
         };
 
-        // Insurance score (0-20 points)
+        // Insurance score (0-20 points). A premium only counts as missed
+        // once it is past the policy's grace period.
         let insurance_client = InsuranceClient::new(&env, &addresses.insurance);
-        let policies = insurance_client.get_active_policies(&user);
-        let insurance_score = if !policies.is_empty() { 20 } else { 0 };
+        let insurance_score = if insurance_client.get_coverage_totals(&user).active_policies == 0 {
+            0
+        } else if insurance_client.get_overdue_policies(&user).is_empty() {
+            20
+        } else {
+            10 // Has premiums missed past their grace period
+        };
 
         let total_score = savings_score + bills_score + insurance_score;
 
@@ -661,8 +1254,26 @@ impl ReportingContract {
             Self::get_savings_report(env.clone(), user.clone(), period_start, period_end);
         let bill_compliance =
             Self::get_bill_compliance_report(env.clone(), user.clone(), period_start, period_end);
-        let insurance_report =
-            Self::get_insurance_report(env.clone(), user, period_start, period_end);
+        let mut insurance_report =
+            Self::get_insurance_report(env.clone(), user.clone(), period_start, period_end);
+
+        // Normalise the period's remittance to a 30-day month
+        let period_len = period_end.saturating_sub(period_start);
+        let monthly_remittance = if period_len >= 86400 {
+            total_remittance * MONTH_SECS as i128 / period_len as i128
+        } else {
+            total_remittance
+        };
+        let coverage = Self::get_coverage_gap(env.clone(), user, monthly_remittance);
+        insurance_report.coverage_gap = coverage.gap;
+
+        let mut recommendations = Vec::new(&env);
+        if coverage.gap > 0 {
+            recommendations.push_back(String::from_str(
+                &env,
+                "Increase insurance coverage to 12 months of income",
+            ));
+        }
 
         let generated_at = env.ledger().timestamp();
 
@@ -677,10 +1288,62 @@ impl ReportingContract {
             savings_report,
             bill_compliance,
             insurance_report,
+            recommendations,
             generated_at,
         }
     }
 
+    /// Compare the user's insurance costs in two periods: premiums paid in
+    /// each, and the premium and coverage in force at each period's end, so
+    /// a premium creeping up without matching coverage shows.
+    pub fn get_premium_trend(
+        env: Env,
+        user: Address,
+        current_period: ReportPeriod,
+        previous_period: ReportPeriod,
+    ) -> PremiumTrend {
+        let addresses: ContractAddresses = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("ADDRS"))
+            .expect("Contract addresses not configured");
+
+        let insurance_client = InsuranceClient::new(&env, &addresses.insurance);
+        let premium_period = |period: &ReportPeriod| {
+            let level = insurance_client.get_premium_level_at(&user, &period.end);
+            let premium_per_coverage_bps = if level.total_coverage > 0 {
+                ((level.monthly_premium * 10_000) / level.total_coverage).min(u32::MAX as i128)
+                    as u32
+            } else {
+                0
+            };
+            PremiumPeriod {
+                period_start: period.start,
+                period_end: period.end,
+                premiums_paid: insurance_client.get_premiums_paid(
+                    &user,
+                    &period.start,
+                    &period.end,
+                ),
+                monthly_premium: level.monthly_premium,
+                total_coverage: level.total_coverage,
+                premium_per_coverage_bps,
+            }
+        };
+        let current = premium_period(&current_period);
+        let previous = premium_period(&previous_period);
+
+        PremiumTrend {
+            premiums_paid: Self::trend(current.premiums_paid, previous.premiums_paid),
+            monthly_premium: Self::trend(current.monthly_premium, previous.monthly_premium),
+            total_coverage: Self::trend(current.total_coverage, previous.total_coverage),
+            cost_rising: previous.total_coverage > 0
+                && current.premium_per_coverage_bps > previous.premium_per_coverage_bps,
+            current,
+            previous,
+        }
+    }
+
     /// Generate trend analysis comparing two periods
     pub fn get_trend_analysis(
         _env: Env,
@@ -688,6 +1351,10 @@ impl ReportingContract {
         current_amount: i128,
         previous_amount: i128,
     ) -> TrendData {
+        Self::trend(current_amount, previous_amount)
+    }
+
+    fn trend(current_amount: i128, previous_amount: i128) -> TrendData {
         let change_amount = current_amount - previous_amount;
         let change_percentage = if previous_amount > 0 {
             ((change_amount * 100) / previous_amount) as i32
@@ -1,6 +1,9 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractclient, contractimpl, contracttype, symbol_short,
+    token::TokenClient,
+    vec, Address, Env, IntoVal, Map, String, Symbol, Vec,
 };
 
 // Event topics
@@ -57,6 +60,8 @@ pub struct SavingsGoal {
     pub target_date: u64,
     pub locked: bool,
     pub unlock_date: Option<u64>,
+    /// Purpose of the goal, e.g. `education`, `emergency`, `housing`
+    pub tag: Option<Symbol>,
 }
 
 /// Paginated result for savings goal queries
@@ -71,6 +76,330 @@ pub struct GoalPage {
     pub count: u32,
 }
 
+/// Savings progress for one goal tag.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TagProgress {
+    pub tag: Symbol,
+    pub goals: u32,
+    pub total_target: i128,
+    pub total_saved: i128,
+}
+
+/// Aggregates over all of an owner's goals, computed in a single call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalTotals {
+    pub goal_count: u32,
+    pub completed_count: u32,
+    pub total_target: i128,
+    pub total_saved: i128,
+    /// Milestones reached across all goals
+    pub milestones_reached: u32,
+    /// Amount unlocked by reached milestones
+    pub milestone_unlocked: i128,
+    /// Yield harvested into the goals (included in `total_saved`)
+    pub yield_earned: i128,
+    /// Progress per tag; untagged goals are not included
+    pub by_tag: Vec<TagProgress>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeadlineStatus {
+    /// Due within the warning window and not yet funded
+    DeadlineApproaching = 0,
+    /// Funding lags the share of time already elapsed
+    AtRisk = 1,
+    /// Target date passed without reaching the target
+    Missed = 2,
+}
+
+/// Reminder emitted by `check_goal_deadlines` for an off-chain notifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadlineAlert {
+    pub goal_id: u32,
+    pub status: DeadlineStatus,
+    pub target_date: u64,
+    pub current_amount: i128,
+    pub target_amount: i128,
+    /// Funding progress in basis points of the target
+    pub progress_bps: u32,
+    /// Seconds until the target date (0 once passed)
+    pub seconds_remaining: u64,
+}
+
+/// Who may claim a goal if its owner stops using the contract.
+///
+/// `claim_started` is set by `start_inactivity_claim`; any activity by the
+/// owner after that point defeats the claim.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InactivityPlan {
+    pub beneficiary: Address,
+    pub inactivity_period: u64,
+    pub claim_started: Option<u64>,
+}
+
+/// A change made through `update_goal`, kept so edits can be traced later.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalEdit {
+    pub editor: Address,
+    pub old_target: i128,
+    pub new_target: i128,
+    pub old_date: u64,
+    pub new_date: u64,
+    pub old_name: String,
+    pub new_name: String,
+    pub timestamp: u64,
+}
+
+/// A sponsor's commitment to match token deposits into a goal.
+///
+/// Each qualifying deposit pulls `amount * ratio_bps / 10000` from the
+/// sponsor, until `matched` reaches `cap`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchingProgram {
+    pub sponsor: Address,
+    pub ratio_bps: u32,
+    pub cap: i128,
+    pub matched: i128,
+}
+
+/// A guardian-approved withdrawal that bypassed the goal's locks.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyWithdrawal {
+    pub guardian: Address,
+    pub amount: i128,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+/// One contribution to a goal, kept for audit even after the goal is archived.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionRecord {
+    pub contributor: Address,
+    pub amount: i128,
+    /// How the funds arrived, e.g. `add`, `deposit`, `split_crd`
+    pub operation: Symbol,
+    pub timestamp: u64,
+}
+
+/// Consecutive weeks and months in which an owner saved into their goals.
+///
+/// A streak is current while its last period is this one or the one before.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsStreak {
+    pub current_weeks: u32,
+    pub longest_weeks: u32,
+    pub current_months: u32,
+    pub longest_months: u32,
+    pub last_contribution: u64,
+}
+
+/// Where `cancel_goal` sends a goal's balance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundTarget {
+    /// The owner's own (spending) address
+    Owner,
+    /// Another goal of the same owner
+    Goal(u32),
+    /// The family wallet set with `set_family_wallet`
+    FamilyWallet,
+}
+
+/// Why and where a goal's balance went when it was cancelled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalCancellation {
+    pub refund_target: RefundTarget,
+    /// Goal balance moved to the target
+    pub amount: i128,
+    /// Custodied tokens moved to the target
+    pub tokens: i128,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+/// A goal removed from the active set by `archive_goal`.
+#[contracttype]
+#[derive(Clone)]
+pub struct ArchivedGoal {
+    pub goal: SavingsGoal,
+    /// Whether the goal had reached its target (otherwise it was cancelled)
+    pub completed: bool,
+    pub archived_at: u64,
+}
+
+/// Tokens held in custody by the contract for a goal.
+///
+/// A goal holds a single token; `balance` is always covered by the goal's
+/// `current_amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalCustody {
+    pub token: Address,
+    pub balance: i128,
+}
+
+/// Family members allowed to contribute to a goal besides its owner.
+///
+/// `family_head`, when set, may withdraw from the goal alongside the owner;
+/// the tokens always go to the owner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SharedGoal {
+    pub contributors: Vec<Address>,
+    pub family_head: Option<Address>,
+}
+
+/// Total contributed to a goal by one address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributorShare {
+    pub contributor: Address,
+    pub amount: i128,
+}
+
+/// Goal funds placed in a whitelisted yield pool.
+///
+/// `shares` are this goal's claim on everything the contract holds in the
+/// pool for `token`; `principal` is what was moved in and `harvested` the
+/// yield credited to the goal so far.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalYield {
+    pub pool: Address,
+    pub token: Address,
+    pub shares: i128,
+    pub principal: i128,
+    pub harvested: i128,
+}
+
+/// Lending pool interface (Blend-style) used for idle goal balances.
+///
+/// `deposit` pulls `amount` of `token` from `from`; `balance` reports the
+/// depositor's position including accrued interest.
+#[contractclient(name = "YieldPoolClient")]
+pub trait YieldPoolTrait {
+    fn deposit(env: Env, from: Address, token: Address, amount: i128);
+    fn withdraw(env: Env, to: Address, token: Address, amount: i128);
+    fn balance(env: Env, owner: Address, token: Address) -> i128;
+}
+
+/// Rewards program notified the first time each goal reaches its target.
+#[contractclient(name = "RewardsClient")]
+pub trait RewardsTrait {
+    fn on_goal_completed(env: Env, goal_id: u32, owner: Address, target_amount: i128);
+}
+
+/// Price feed used to value multi-asset goals.
+///
+/// `rate` returns how many units of `quote` one unit of `base` is worth,
+/// scaled by 10^7.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleTrait {
+    fn rate(env: Env, base: Address, quote: Address) -> i128;
+}
+
+/// One token held by a multi-asset goal and its value in the goal's
+/// valuation asset at the current oracle rate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetHolding {
+    pub token: Address,
+    pub amount: i128,
+    pub value: i128,
+}
+
+/// Penalty charged on custodied withdrawals taken before a goal's unlock date.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyWithdrawalPolicy {
+    /// Share of the withdrawn amount kept as penalty, in basis points
+    pub penalty_bps: u32,
+    /// Address that receives the penalty
+    pub recipient: Address,
+}
+
+/// Milestone definition supplied by the goal owner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneConfig {
+    /// Progress threshold as a percentage of the target (1-100)
+    pub percent: u32,
+    /// Portion of the target unlocked on reaching it, in basis points
+    pub unlock_bps: u32,
+}
+
+/// A goal milestone and whether it has been reached.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalMilestone {
+    pub percent: u32,
+    pub unlock_bps: u32,
+    /// Amount unlocked by this milestone (`unlock_bps` of the target)
+    pub unlock_amount: i128,
+    pub reached_at: Option<u64>,
+}
+
+/// Milestones of one goal plus what has been withdrawn against them.
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalMilestones {
+    pub milestones: Vec<GoalMilestone>,
+    pub withdrawn: i128,
+}
+
+/// How a withdrawal passed the lock checks.
+#[derive(Clone, Copy, PartialEq)]
+enum WithdrawalMode {
+    Unlocked,
+    Early,
+    Milestone,
+}
+
+/// How `credit_from_split` spreads an incoming amount over a user's goals.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AllocationRule {
+    /// Waterfall: fill each goal to its target, in priority order, before
+    /// the next one receives anything
+    Priority = 0,
+    /// Split in proportion to what each goal still needs
+    Proportional = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitAllocation {
+    pub rule: AllocationRule,
+    /// Goal IDs in priority order; goals not listed follow in ID order
+    pub priorities: Vec<u32>,
+}
+
+/// Recurring token contribution into a goal, pulled from the owner's
+/// wallet through a token allowance granted to this contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionSchedule {
+    pub goal_id: u32,
+    pub amount: i128,
+    /// Seconds between contributions
+    pub interval: u64,
+    pub next_due: u64,
+    pub last_applied: Option<u64>,
+    pub applied_count: u32,
+    pub missed_count: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsSchedule {
@@ -96,6 +425,14 @@ pub enum SavingsGoalsError {
     GoalLocked = 4,
     InsufficientBalance = 5,
     Overflow = 6,
+    TokenMismatch = 7,
+    InvalidMilestone = 8,
+    PoolNotAllowed = 9,
+    GoalNotLocked = 10,
+    CannotArchive = 11,
+    OracleUnavailable = 12,
+    ClaimNotReady = 13,
+    CannotCancel = 14,
 }
 
 impl From<SavingsGoalsError> for soroban_sdk::Error {
@@ -125,6 +462,38 @@ impl From<SavingsGoalsError> for soroban_sdk::Error {
                 soroban_sdk::xdr::ScErrorType::Contract,
                 soroban_sdk::xdr::ScErrorCode::InvalidInput,
             )),
+            SavingsGoalsError::TokenMismatch => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            SavingsGoalsError::InvalidMilestone => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidInput,
+            )),
+            SavingsGoalsError::PoolNotAllowed => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            SavingsGoalsError::GoalNotLocked => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            SavingsGoalsError::CannotArchive => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            SavingsGoalsError::OracleUnavailable => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::MissingValue,
+            )),
+            SavingsGoalsError::ClaimNotReady => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
+            SavingsGoalsError::CannotCancel => soroban_sdk::Error::from((
+                soroban_sdk::xdr::ScErrorType::Contract,
+                soroban_sdk::xdr::ScErrorCode::InvalidAction,
+            )),
         }
     }
 }
@@ -155,6 +524,34 @@ pub enum SavingsEvent {
     ScheduleMissed,
     ScheduleModified,
     ScheduleCancelled,
+    TokensDeposited,
+    TokensWithdrawn,
+    EarlyWithdrawal,
+    MilestoneReached,
+    SplitCredited,
+    GoalShared,
+    YieldDeposited,
+    YieldHarvested,
+    YieldWithdrawn,
+    GoalRewarded,
+    ContributionApplied,
+    ContributionMissed,
+    TransferProposed,
+    GoalTransferred,
+    GoalArchived,
+    DeadlineAlert,
+    GoalUpdated,
+    GuardianSet,
+    EmergencyWithdrawal,
+    AssetDeposited,
+    AssetWithdrawn,
+    GoalRevalued,
+    MatchSet,
+    MatchApplied,
+    BeneficiarySet,
+    InactivityClaimStarted,
+    InactivityClaimed,
+    GoalCancelled,
 }
 
 #[contracttype]
@@ -179,6 +576,17 @@ const SNAPSHOT_VERSION: u32 = 1;
 const MAX_AUDIT_ENTRIES: u32 = 100;
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+const MAX_MILESTONES: u32 = 10;
+const MAX_GOAL_HISTORY: u32 = 100;
+/// Fixed-point scale of oracle rates (1.0 = 10_000_000).
+const RATE_SCALE: i128 = 10_000_000;
+/// Streak periods, in seconds; a month is counted as 30 days.
+const STREAK_WEEK: u64 = 7 * 86400;
+const STREAK_MONTH: u64 = 30 * 86400;
+/// Goals due within this many seconds get a `DeadlineApproaching` alert.
+const DEADLINE_WARNING_WINDOW: u64 = 7 * 86400;
+/// Time an owner has to show activity after an inactivity claim is started.
+const INACTIVITY_CHALLENGE_WINDOW: u64 = 7 * 86400;
 
 pub mod pause_functions {
     use soroban_sdk::{symbol_short, Symbol};
@@ -196,6 +604,15 @@ pub struct ContributionItem {
     pub amount: i128,
 }
 
+/// One goal to create with `create_goals`.
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalInput {
+    pub name: String,
+    pub target_amount: i128,
+    pub target_date: u64,
+}
+
 #[contractimpl]
 impl SavingsGoalContract {
     const STORAGE_NEXT_ID: Symbol = symbol_short!("NEXT_ID");
@@ -260,7 +677,7 @@ impl SavingsGoalContract {
     }
 
     pub fn set_pause_admin(env: Env, caller: Address, new_admin: Address) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let current = Self::get_pause_admin(&env);
         match current {
             None => {
@@ -277,7 +694,7 @@ impl SavingsGoalContract {
     }
 
     pub fn pause(env: Env, caller: Address) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let admin = Self::get_pause_admin(&env).expect("No pause admin set");
         if admin != caller {
             panic!("Unauthorized");
@@ -290,7 +707,7 @@ impl SavingsGoalContract {
     }
 
     pub fn unpause(env: Env, caller: Address) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let admin = Self::get_pause_admin(&env).expect("No pause admin set");
         if admin != caller {
             panic!("Unauthorized");
@@ -310,7 +727,7 @@ impl SavingsGoalContract {
     }
 
     pub fn pause_function(env: Env, caller: Address, func: Symbol) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let admin = Self::get_pause_admin(&env).expect("No pause admin set");
         if admin != caller {
             panic!("Unauthorized");
@@ -327,7 +744,7 @@ impl SavingsGoalContract {
     }
 
     pub fn unpause_function(env: Env, caller: Address, func: Symbol) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let admin = Self::get_pause_admin(&env).expect("No pause admin set");
         if admin != caller {
             panic!("Unauthorized");
@@ -359,7 +776,7 @@ impl SavingsGoalContract {
     }
 
     pub fn set_upgrade_admin(env: Env, caller: Address, new_admin: Address) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let current = Self::get_upgrade_admin(&env);
         match current {
             None => {
@@ -376,7 +793,7 @@ impl SavingsGoalContract {
     }
 
     pub fn set_version(env: Env, caller: Address, new_version: u32) {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
         if admin != caller {
             panic!("Unauthorized");
@@ -391,6 +808,48 @@ impl SavingsGoalContract {
         );
     }
 
+    /// Set the early-withdrawal penalty (upgrade admin only).
+    ///
+    /// With a non-zero `penalty_bps`, `withdraw` may release custodied tokens
+    /// from a time-locked goal before its unlock date; the penalty share goes
+    /// to `recipient`. With 0, such withdrawals fail with `GoalLocked`.
+    ///
+    /// Goals are created locked and `unlock_goal` is refused during a
+    /// time-lock, so the penalty only applies to goals the owner unlocked
+    /// before calling `set_time_lock`. A goal still locked stays closed.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidAmount` - If `penalty_bps` is above 10000
+    pub fn set_early_withdrawal_policy(
+        env: Env,
+        caller: Address,
+        penalty_bps: u32,
+        recipient: Address,
+    ) -> Result<(), SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        if Self::get_upgrade_admin(&env) != Some(caller) {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if penalty_bps > 10_000 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let policy = EarlyWithdrawalPolicy {
+            penalty_bps,
+            recipient,
+        };
+        env.storage()
+            .instance()
+            .set(&symbol_short!("EARLY_PEN"), &policy);
+        env.events()
+            .publish((symbol_short!("savings"), symbol_short!("penalty")), policy);
+        Ok(())
+    }
+
+    pub fn get_early_withdrawal_policy(env: Env) -> Option<EarlyWithdrawalPolicy> {
+        env.storage().instance().get(&symbol_short!("EARLY_PEN"))
+    }
+
     // -----------------------------------------------------------------------
     // Core goal operations
     // -----------------------------------------------------------------------
@@ -402,7 +861,7 @@ impl SavingsGoalContract {
         target_amount: i128,
         target_date: u64,
     ) -> Result<u32, SavingsGoalsError> {
-        owner.require_auth();
+        Self::require_caller(&env, &owner);
         Self::require_not_paused(&env, pause_functions::CREATE_GOAL);
 
         if target_amount <= 0 {
@@ -411,12 +870,59 @@ impl SavingsGoalContract {
         }
 
         Self::extend_instance_ttl(&env);
+        Ok(Self::insert_goal(
+            &env,
+            &owner,
+            name,
+            target_amount,
+            target_date,
+        ))
+    }
+
+    /// Create several goals for `owner` in one transaction.
+    ///
+    /// All inputs are validated before any goal is created.
+    ///
+    /// # Returns
+    /// The new goal IDs, in input order
+    pub fn create_goals(env: Env, owner: Address, goals: Vec<GoalInput>) -> Vec<u32> {
+        Self::require_caller(&env, &owner);
+        Self::require_not_paused(&env, pause_functions::CREATE_GOAL);
+        if goals.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
+        }
+        for input in goals.iter() {
+            if input.target_amount <= 0 {
+                panic!("Target amount must be positive");
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut ids = Vec::new(&env);
+        for input in goals.iter() {
+            ids.push_back(Self::insert_goal(
+                &env,
+                &owner,
+                input.name,
+                input.target_amount,
+                input.target_date,
+            ));
+        }
+        ids
+    }
 
+    fn insert_goal(
+        env: &Env,
+        owner: &Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+    ) -> u32 {
         let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .unwrap_or_else(|| Map::new(env));
 
         let next_id = env
             .storage()
@@ -434,6 +940,7 @@ impl SavingsGoalContract {
             target_date,
             locked: true,
             unlock_date: None,
+            tag: None,
         };
 
         goals.set(next_id, goal.clone());
@@ -443,7 +950,16 @@ impl SavingsGoalContract {
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
-        Self::append_owner_goal_id(&env, &owner, next_id);
+        Self::append_owner_goal_id(env, owner, next_id);
+        let mut created: Map<u32, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOAL_CRT"))
+            .unwrap_or_else(|| Map::new(env));
+        created.set(next_id, env.ledger().timestamp());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOAL_CRT"), &created);
 
         let event = GoalCreatedEvent {
             goal_id: next_id,
@@ -455,10 +971,10 @@ impl SavingsGoalContract {
         env.events().publish((GOAL_CREATED,), event);
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::GoalCreated),
-            (next_id, owner),
+            (next_id, owner.clone()),
         );
 
-        Ok(next_id)
+        next_id
     }
 
     /// Adds funds to an existing savings goal.
@@ -474,7 +990,7 @@ impl SavingsGoalContract {
     /// # Errors
     /// * `InvalidAmount` - If amount ≤ 0
     /// * `GoalNotFound` - If goal_id does not exist
-    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `Unauthorized` - If caller is neither the owner nor a shared contributor
     /// * `Overflow` - If adding amount would overflow i128
     ///
     /// # Panics
@@ -485,7 +1001,7 @@ impl SavingsGoalContract {
         goal_id: u32,
         amount: i128,
     ) -> Result<i128, SavingsGoalsError> {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
 
         if amount <= 0 {
@@ -509,11 +1025,125 @@ impl SavingsGoalContract {
             }
         };
 
-        if goal.owner != caller {
+        if !Self::can_contribute(&env, &goal, &caller) {
             Self::append_audit(&env, symbol_short!("add"), &caller, false);
             return Err(SavingsGoalsError::Unauthorized);
         }
 
+        Self::credit_goal(
+            &env,
+            &mut goals,
+            &mut goal,
+            &caller,
+            amount,
+            symbol_short!("add"),
+        )
+    }
+
+    /// Edit a goal's target, target date and name.
+    ///
+    /// Every edit is appended to the goal's change log (`get_goal_edits`), so
+    /// sender and recipient can trace who changed what.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If new_target ≤ 0 or below the amount already saved
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `GoalLocked` - If the goal is locked or time-locked
+    pub fn update_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        new_target: i128,
+        new_date: u64,
+        new_name: String,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if goal.locked || Self::is_time_locked(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        if new_target <= 0 || new_target < goal.current_amount {
+            Self::append_audit(&env, symbol_short!("update"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let edit = GoalEdit {
+            editor: caller.clone(),
+            old_target: goal.target_amount,
+            new_target,
+            old_date: goal.target_date,
+            new_date,
+            old_name: goal.name.clone(),
+            new_name: new_name.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        goal.target_amount = new_target;
+        goal.target_date = new_date;
+        goal.name = new_name;
+        goals.set(goal_id, goal.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        let mut edits: Map<u32, Vec<GoalEdit>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOAL_EDIT"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut log = edits.get(goal_id).unwrap_or_else(|| Vec::new(&env));
+        if log.len() >= MAX_GOAL_HISTORY {
+            log.pop_front();
+        }
+        log.push_back(edit.clone());
+        edits.set(goal_id, log);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOAL_EDIT"), &edits);
+
+        Self::update_milestones(&env, &goal);
+        if goal.current_amount >= goal.target_amount {
+            Self::reward_completion(&env, &goal);
+        }
+
+        Self::append_audit(&env, symbol_short!("update"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalUpdated),
+            (goal_id, edit),
+        );
+        Ok(true)
+    }
+
+    /// The goal's change log, oldest first (last 100 edits).
+    pub fn get_goal_edits(env: Env, goal_id: u32) -> Vec<GoalEdit> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Vec<GoalEdit>>>(&symbol_short!("GOAL_EDIT"))
+            .and_then(|edits| edits.get(goal_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Add `amount` to `goal`, persist it and emit the contribution events.
+    fn credit_goal(
+        env: &Env,
+        goals: &mut Map<u32, SavingsGoal>,
+        goal: &mut SavingsGoal,
+        caller: &Address,
+        amount: i128,
+        operation: Symbol,
+    ) -> Result<i128, SavingsGoalsError> {
+        let goal_id = goal.id;
         goal.current_amount = goal
             .current_amount
             .checked_add(amount)
@@ -523,9 +1153,9 @@ impl SavingsGoalContract {
         let previously_completed = (new_total - amount) >= goal.target_amount;
 
         goals.set(goal_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        env.storage().instance().set(&symbol_short!("GOALS"), goals);
+        Self::update_milestones(env, goal);
+        Self::record_contribution(env, goal_id, &goal.owner, caller, amount, operation.clone());
 
         let funds_event = FundsAddedEvent {
             goal_id,
@@ -545,7 +1175,7 @@ impl SavingsGoalContract {
             env.events().publish((GOAL_COMPLETED,), completed_event);
         }
 
-        Self::append_audit(&env, symbol_short!("add"), &caller, true);
+        Self::append_audit(env, operation, caller, true);
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::FundsAdded),
             (goal_id, caller.clone(), amount),
@@ -554,8 +1184,9 @@ impl SavingsGoalContract {
         if was_completed {
             env.events().publish(
                 (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                (goal_id, caller),
+                (goal_id, caller.clone()),
             );
+            Self::reward_completion(env, goal);
         }
 
         Ok(new_total)
@@ -566,7 +1197,7 @@ impl SavingsGoalContract {
         caller: Address,
         contributions: Vec<ContributionItem>,
     ) -> u32 {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
         if contributions.len() > MAX_BATCH_SIZE {
             panic!("Batch too large");
@@ -605,6 +1236,14 @@ impl SavingsGoalContract {
             let was_completed = new_total >= goal.target_amount;
             let previously_completed = (new_total - item.amount) >= goal.target_amount;
             goals.set(item.goal_id, goal.clone());
+            Self::record_contribution(
+                &env,
+                item.goal_id,
+                &goal.owner,
+                &caller,
+                item.amount,
+                symbol_short!("batch_add"),
+            );
             let funds_event = FundsAddedEvent {
                 goal_id: item.goal_id,
                 amount: item.amount,
@@ -630,6 +1269,7 @@ impl SavingsGoalContract {
                     (symbol_short!("savings"), SavingsEvent::GoalCompleted),
                     (item.goal_id, caller.clone()),
                 );
+                Self::reward_completion(&env, &goal);
             }
             count += 1;
         }
@@ -656,7 +1296,7 @@ impl SavingsGoalContract {
     /// # Errors
     /// * `InvalidAmount` - If amount ≤ 0
     /// * `GoalNotFound` - If goal_id does not exist
-    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `Unauthorized` - If caller is neither the owner nor the family head
     /// * `GoalLocked` - If goal is locked or time-locked
     /// * `InsufficientBalance` - If amount > current_amount
     /// * `Overflow` - If subtraction would underflow i128
@@ -669,7 +1309,7 @@ impl SavingsGoalContract {
         goal_id: u32,
         amount: i128,
     ) -> Result<i128, SavingsGoalsError> {
-        caller.require_auth();
+        Self::require_caller(&env, &caller);
         Self::require_not_paused(&env, pause_functions::WITHDRAW);
 
         if amount <= 0 {
@@ -693,27 +1333,22 @@ impl SavingsGoalContract {
             }
         };
 
-        if goal.owner != caller {
+        let mode = match Self::ensure_withdrawable(&env, &goal, &caller, amount, false) {
+            Ok(mode) => mode,
+            Err(err) => {
+                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+                return Err(err);
+            }
+        };
+
+        // Tokens held in custody, a yield pool or as other assets must be
+        // released through `withdraw` or `withdraw_asset`.
+        let held = Self::get_goal_custody(env.clone(), goal_id).map_or(0, |c| c.balance)
+            + Self::get_goal_yield(env.clone(), goal_id).map_or(0, |y| y.principal)
+            + Self::load_valued(&env).get(goal_id).unwrap_or(0);
+        if amount > goal.current_amount - held {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::Unauthorized);
-        }
-
-        if goal.locked {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::GoalLocked);
-        }
-
-        if let Some(unlock_date) = goal.unlock_date {
-            let current_time = env.ledger().timestamp();
-            if current_time < unlock_date {
-                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalLocked);
-            }
-        }
-
-        if amount > goal.current_amount {
-            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::InsufficientBalance);
+            return Err(SavingsGoalsError::InsufficientBalance);
         }
 
         goal.current_amount = goal
@@ -726,6 +1361,9 @@ impl SavingsGoalContract {
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
+        if mode == WithdrawalMode::Milestone {
+            Self::record_milestone_withdrawal(&env, goal_id, amount);
+        }
 
         Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
         env.events().publish(
@@ -736,9 +1374,32 @@ impl SavingsGoalContract {
         Ok(new_amount)
     }
 
-    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::LOCK);
+    /// Deposits tokens into the contract's custody and credits them to a goal.
+    ///
+    /// The first deposit fixes the goal's token; later deposits must use the
+    /// same token. `current_amount` grows by `amount`, exactly as with
+    /// `add_to_goal`, plus any sponsor match (see `set_goal_match`).
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is neither the owner nor a shared contributor
+    /// * `TokenMismatch` - If the goal already holds a different token
+    pub fn deposit(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("deposit"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
         Self::extend_instance_ttl(&env);
 
         let mut goals: Map<u32, SavingsGoal> = env
@@ -746,38 +1407,157 @@ impl SavingsGoalContract {
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
-
         let mut goal = match goals.get(goal_id) {
             Some(g) => g,
             None => {
-                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-                panic!("Goal not found");
+                Self::append_audit(&env, symbol_short!("deposit"), &caller, false);
+                return Err(SavingsGoalsError::GoalNotFound);
             }
         };
+        if !Self::can_contribute(&env, &goal, &caller) {
+            Self::append_audit(&env, symbol_short!("deposit"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
 
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-            panic!("Only the goal owner can lock this goal");
+        let mut custody = Self::load_custody(&env);
+        let mut held = custody.get(goal_id).unwrap_or(GoalCustody {
+            token: token.clone(),
+            balance: 0,
+        });
+        if held.token != token {
+            Self::append_audit(&env, symbol_short!("deposit"), &caller, false);
+            return Err(SavingsGoalsError::TokenMismatch);
         }
+        held.balance = held
+            .balance
+            .checked_add(amount)
+            .ok_or(SavingsGoalsError::Overflow)?;
 
-        goal.locked = true;
-        goals.set(goal_id, goal);
+        TokenClient::new(&env, &token).transfer(&caller, &env.current_contract_address(), &amount);
+        custody.set(goal_id, held);
         env.storage()
             .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+            .set(&symbol_short!("CUSTODY"), &custody);
+        Self::credit_deposit(&env, &mut goals, &mut goal, &caller, &token, amount)
+    }
 
-        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+    /// Deposit one token into several goals with a single transfer.
+    ///
+    /// All items are validated before any tokens move: amounts must be
+    /// positive, the caller must be able to contribute to every goal, and
+    /// goals that already hold tokens must hold `token`. Each goal is then
+    /// credited as with `deposit`, including sponsor matches.
+    ///
+    /// # Returns
+    /// Each goal's new total, in input order
+    pub fn deposit_many(
+        env: Env,
+        caller: Address,
+        token: Address,
+        deposits: Vec<ContributionItem>,
+    ) -> Vec<i128> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        if deposits.len() > MAX_BATCH_SIZE {
+            panic!("Batch too large");
+        }
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let custody = Self::load_custody(&env);
+        let mut total = 0i128;
+        for item in deposits.iter() {
+            if item.amount <= 0 {
+                panic!("Amount must be positive");
+            }
+            let goal = goals.get(item.goal_id).expect("Goal not found");
+            if !Self::can_contribute(&env, &goal, &caller) {
+                panic!("Not a contributor to all goals");
+            }
+            if custody
+                .get(item.goal_id)
+                .is_some_and(|held| held.token != token)
+            {
+                panic!("Token mismatch");
+            }
+            total = total.checked_add(item.amount).expect("overflow");
+        }
+
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&caller, &env.current_contract_address(), &total);
+        let mut totals = Vec::new(&env);
+        for item in deposits.iter() {
+            let mut custody = Self::load_custody(&env);
+            let mut held = custody.get(item.goal_id).unwrap_or(GoalCustody {
+                token: token.clone(),
+                balance: 0,
+            });
+            held.balance += item.amount;
+            custody.set(item.goal_id, held);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CUSTODY"), &custody);
+
+            let mut goal = goals.get(item.goal_id).expect("Goal not found");
+            match Self::credit_deposit(&env, &mut goals, &mut goal, &caller, &token, item.amount) {
+                Ok(new_total) => totals.push_back(new_total),
+                Err(err) => env.panic_with_error(err),
+            }
+        }
+        totals
+    }
+
+    /// Credit tokens already placed in custody to `goal`, then apply any
+    /// sponsor match.
+    fn credit_deposit(
+        env: &Env,
+        goals: &mut Map<u32, SavingsGoal>,
+        goal: &mut SavingsGoal,
+        caller: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalLocked),
-            (goal_id, caller),
+            (symbol_short!("savings"), SavingsEvent::TokensDeposited),
+            (goal.id, token.clone(), amount),
         );
-
-        true
+        let new_total =
+            Self::credit_goal(env, goals, goal, caller, amount, symbol_short!("deposit"))?;
+        Ok(Self::apply_match(env, goals, goal, caller, token, amount).unwrap_or(new_total))
     }
 
-    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::UNLOCK);
+    /// Releases custodied tokens from a goal back to its owner.
+    ///
+    /// The family head of a shared goal may approve the withdrawal as
+    /// caller, but the tokens are still paid to the owner. Subject to the
+    /// same lock rules as `withdraw_from_goal`; the amount is
+    /// removed from both the custody balance and `current_amount`. Before a
+    /// time-lock's unlock date the withdrawal is allowed only when an
+    /// early-withdrawal penalty is configured; the penalty is sent to the
+    /// policy recipient and the owner receives the rest.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is neither the owner nor the family head
+    /// * `GoalLocked` - If goal is locked or time-locked
+    /// * `InsufficientBalance` - If amount exceeds the tokens held for the goal
+    pub fn withdraw(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
         Self::extend_instance_ttl(&env);
 
         let mut goals: Map<u32, SavingsGoal> = env
@@ -785,320 +1565,486 @@ impl SavingsGoalContract {
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
-
         let mut goal = match goals.get(goal_id) {
             Some(g) => g,
             None => {
-                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-                panic!("Goal not found");
+                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+                return Err(SavingsGoalsError::GoalNotFound);
+            }
+        };
+        let mode = match Self::ensure_withdrawable(&env, &goal, &caller, amount, true) {
+            Ok(mode) => mode,
+            Err(err) => {
+                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+                return Err(err);
             }
         };
 
-        if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-            panic!("Only the goal owner can unlock this goal");
+        let mut custody = Self::load_custody(&env);
+        let mut held = match custody.get(goal_id) {
+            Some(held) if held.balance >= amount && goal.current_amount >= amount => held,
+            _ => {
+                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
+                return Err(SavingsGoalsError::InsufficientBalance);
+            }
+        };
+
+        held.balance -= amount;
+        goal.current_amount -= amount;
+        let new_amount = goal.current_amount;
+
+        let token = TokenClient::new(&env, &held.token);
+        let contract = env.current_contract_address();
+        let owner = goal.owner.clone();
+        let mut payout = amount;
+        if mode == WithdrawalMode::Early {
+            if let Some(policy) = Self::get_early_withdrawal_policy(env.clone()) {
+                let penalty = amount * policy.penalty_bps as i128 / 10_000;
+                if penalty > 0 {
+                    token.transfer(&contract, &policy.recipient, &penalty);
+                }
+                payout -= penalty;
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::EarlyWithdrawal),
+                    (goal_id, owner.clone(), amount, penalty),
+                );
+            }
+        }
+        if payout > 0 {
+            token.transfer(&contract, &owner, &payout);
         }
 
-        goal.locked = false;
+        custody.set(goal_id, held.clone());
         goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CUSTODY"), &custody);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
+        if mode == WithdrawalMode::Milestone {
+            Self::record_milestone_withdrawal(&env, goal_id, amount);
+        }
 
-        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
+        Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
-            (goal_id, caller),
+            (symbol_short!("savings"), SavingsEvent::TokensWithdrawn),
+            (goal_id, held.token, amount),
+        );
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsWithdrawn),
+            (goal_id, caller, amount),
         );
 
-        true
+        Ok(new_amount)
     }
 
-    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        goals.get(goal_id)
+    /// Get the token and balance held in custody for a goal.
+    pub fn get_goal_custody(env: Env, goal_id: u32) -> Option<GoalCustody> {
+        Self::load_custody(&env).get(goal_id)
     }
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES
-    // -----------------------------------------------------------------------
-
-    /// Get a page of savings goals for `owner`.
+    /// Designate (or remove) the guardian who must co-sign emergency
+    /// withdrawals, e.g. the remitter abroad.
     ///
-    /// # Arguments
-    /// * `owner`  – whose goals to return
-    /// * `cursor` – start after this goal ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// Replacing or removing an existing guardian also needs that guardian's
+    /// authorization, so the owner cannot swap in an address they control.
     ///
-    /// # Returns
-    /// `GoalPage { items, next_cursor, count }`.
-    /// `next_cursor == 0` means no more pages.
-    pub fn get_goals(env: Env, owner: Address, cursor: u32, limit: u32) -> GoalPage {
-        let limit = Self::clamp_limit(limit);
-        let goals: Map<u32, SavingsGoal> = env
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_guardian(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        guardian: Option<Address>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("guardian"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut guardians: Map<u32, Address> = env
             .storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
+            .get(&symbol_short!("GUARDIAN"))
             .unwrap_or_else(|| Map::new(&env));
-
-        let mut result = Vec::new(&env);
-        let mut next_cursor: u32 = 0;
-        let mut collected: u32 = 0;
-
-        for (id, goal) in goals.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if goal.owner != owner {
-                continue;
-            }
-            if collected < limit {
-                result.push_back(goal);
-                collected += 1;
-                next_cursor = id; // track last returned ID
-            } else {
-                break;
-            }
+        if let Some(current) = guardians.get(goal_id) {
+            current.require_auth();
         }
-
-        // If we didn't fill the page, there are no more items
-        if collected < limit {
-            next_cursor = 0;
+        match guardian.clone() {
+            Some(guardian) => guardians.set(goal_id, guardian),
+            None => {
+                guardians.remove(goal_id);
+            }
         }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GUARDIAN"), &guardians);
 
-        GoalPage {
-            items: result,
-            next_cursor,
-            count: collected,
-        }
+        Self::append_audit(&env, symbol_short!("guardian"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GuardianSet),
+            (goal_id, guardian),
+        );
+        Ok(true)
     }
 
-    /// Backward-compatible: returns ALL goals for owner in one Vec.
-    /// Prefer the paginated `get_goals` for production use.
-    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
+    pub fn get_goal_guardian(env: Env, goal_id: u32) -> Option<Address> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, goal) in goals.iter() {
-            if goal.owner == owner {
-                result.push_back(goal);
-            }
-        }
-        result
+            .get::<_, Map<u32, Address>>(&symbol_short!("GUARDIAN"))
+            .and_then(|guardians| guardians.get(goal_id))
     }
 
-    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
-        let storage = env.storage().instance();
-        let goals: Map<u32, SavingsGoal> = storage
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or(Map::new(&env));
-        if let Some(goal) = goals.get(goal_id) {
-            goal.current_amount >= goal.target_amount
+    /// Commit `sponsor` to matching token deposits into a goal.
+    ///
+    /// Each deposit by someone other than the sponsor is matched at
+    /// `ratio_bps` (10000 = 1:1) until `cap` has been matched in total. The
+    /// match is pulled from the sponsor with `transfer_from`, so the sponsor
+    /// must approve this contract; a deposit that cannot be matched in full
+    /// is matched with whatever balance and allowance remain. A goal has one
+    /// program at a time; the sponsor can replace it or end it with
+    /// `ratio_bps` 0. Amounts already matched carry over to a replacement.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `InvalidAmount` - If the ratio is set and cap ≤ 0
+    /// * `Unauthorized` - If another sponsor already matches the goal
+    pub fn set_goal_match(
+        env: Env,
+        sponsor: Address,
+        goal_id: u32,
+        ratio_bps: u32,
+        cap: i128,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &sponsor);
+        Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if ratio_bps > 0 && cap <= 0 {
+            Self::append_audit(&env, symbol_short!("match"), &sponsor, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut programs = Self::load_matches(&env);
+        let matched = match programs.get(goal_id) {
+            Some(current) if current.sponsor != sponsor => {
+                Self::append_audit(&env, symbol_short!("match"), &sponsor, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+            Some(current) => current.matched,
+            None => 0,
+        };
+        if ratio_bps == 0 {
+            programs.remove(goal_id);
         } else {
-            false
+            programs.set(
+                goal_id,
+                MatchingProgram {
+                    sponsor: sponsor.clone(),
+                    ratio_bps,
+                    cap,
+                    matched,
+                },
+            );
         }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MATCH"), &programs);
+
+        Self::append_audit(&env, symbol_short!("match"), &sponsor, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::MatchSet),
+            (goal_id, sponsor, ratio_bps, cap),
+        );
+        Ok(true)
     }
 
-    // -----------------------------------------------------------------------
-    // Snapshot, audit, schedule
-    // -----------------------------------------------------------------------
+    pub fn get_goal_match(env: Env, goal_id: u32) -> Option<MatchingProgram> {
+        Self::load_matches(&env).get(goal_id)
+    }
 
-    pub fn get_nonce(env: Env, address: Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address))
+    /// Total matched into a goal by its current sponsor.
+    pub fn get_matched_amount(env: Env, goal_id: u32) -> i128 {
+        Self::load_matches(&env)
+            .get(goal_id)
+            .map(|program| program.matched)
             .unwrap_or(0)
     }
 
-    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
-        caller.require_auth();
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let next_id = env
-            .storage()
+    fn load_matches(env: &Env) -> Map<u32, MatchingProgram> {
+        env.storage()
             .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-        let mut list = Vec::new(&env);
-        for i in 1..=next_id {
-            if let Some(g) = goals.get(i) {
-                list.push_back(g);
-            }
+            .get(&symbol_short!("MATCH"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Pull the sponsor's match for a deposit of `amount` into custody and
+    /// credit it to the goal. Returns the goal's new total if anything was
+    /// matched.
+    fn apply_match(
+        env: &Env,
+        goals: &mut Map<u32, SavingsGoal>,
+        goal: &mut SavingsGoal,
+        depositor: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> Option<i128> {
+        let mut programs = Self::load_matches(env);
+        let mut program = programs.get(goal.id)?;
+        if &program.sponsor == depositor {
+            return None;
         }
-        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
-        GoalsExportSnapshot {
-            version: SNAPSHOT_VERSION,
-            checksum,
-            next_id,
-            goals: list,
+
+        let contract = env.current_contract_address();
+        let client = TokenClient::new(env, token);
+        let mut due = amount
+            .checked_mul(program.ratio_bps as i128)?
+            .checked_div(10_000)?
+            .min(program.cap - program.matched);
+        due = due
+            .min(client.balance(&program.sponsor))
+            .min(client.allowance(&program.sponsor, &contract));
+        if due <= 0 {
+            return None;
+        }
+
+        client.transfer_from(&contract, &program.sponsor, &contract, &due);
+        Self::adjust_custody(env, goal.id, due);
+        program.matched += due;
+        programs.set(goal.id, program.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("MATCH"), &programs);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::MatchApplied),
+            (goal.id, program.sponsor.clone(), due),
+        );
+        match Self::credit_goal(
+            env,
+            goals,
+            goal,
+            &program.sponsor,
+            due,
+            symbol_short!("match"),
+        ) {
+            Ok(total) => Some(total),
+            Err(err) => env.panic_with_error(err),
         }
     }
 
-    pub fn import_snapshot(
+    /// Withdraw from a locked or time-locked goal in an emergency.
+    ///
+    /// Requires authorization from both the owner and the goal's guardian.
+    /// No early-withdrawal penalty applies. Goals holding tokens pay out of
+    /// custody; otherwise only the bookkeeping amount is reduced. The reason
+    /// is recorded and returned by `get_emergency_withdrawals`.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the owner or the goal has no guardian
+    /// * `InsufficientBalance` - If amount exceeds the available balance
+    pub fn emergency_withdraw(
         env: Env,
         caller: Address,
-        nonce: u64,
-        snapshot: GoalsExportSnapshot,
-    ) -> bool {
-        caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce);
-
-        if snapshot.version != SNAPSHOT_VERSION {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            panic!("Unsupported snapshot version");
-        }
-        let expected =
-            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
-        if snapshot.checksum != expected {
-            Self::append_audit(&env, symbol_short!("import"), &caller, false);
-            panic!("Snapshot checksum mismatch");
+        goal_id: u32,
+        amount: i128,
+        reason: String,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
         }
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let guardian = match Self::get_goal_guardian(env.clone(), goal_id) {
+            Some(guardian) if goal.owner == caller => guardian,
+            _ => {
+                Self::append_audit(&env, symbol_short!("emergency"), &caller, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+        };
+        Self::require_caller(&env, &guardian);
 
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = Map::new(&env);
-        for g in snapshot.goals.iter() {
-            goals.set(g.id, g.clone());
-            let mut ids = owner_goal_ids
-                .get(g.owner.clone())
-                .unwrap_or_else(|| Vec::new(&env));
-            ids.push_back(g.id);
-            owner_goal_ids.set(g.owner.clone(), ids);
+        let mut custody = Self::load_custody(&env);
+        match custody.get(goal_id) {
+            Some(mut held) => {
+                if held.balance < amount || goal.current_amount < amount {
+                    return Err(SavingsGoalsError::InsufficientBalance);
+                }
+                held.balance -= amount;
+                TokenClient::new(&env, &held.token).transfer(
+                    &env.current_contract_address(),
+                    &caller,
+                    &amount,
+                );
+                custody.set(goal_id, held);
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("CUSTODY"), &custody);
+            }
+            None => {
+                if goal.current_amount < amount {
+                    return Err(SavingsGoalsError::InsufficientBalance);
+                }
+            }
         }
+        goal.current_amount -= amount;
+        let remaining = goal.current_amount;
+        goals.set(goal_id, goal);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
-        env.storage()
+
+        let record = EmergencyWithdrawal {
+            guardian,
+            amount,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        };
+        let mut all: Map<u32, Vec<EmergencyWithdrawal>> = env
+            .storage()
             .instance()
-            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
+            .get(&symbol_short!("EMERGENCY"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut records = all.get(goal_id).unwrap_or_else(|| Vec::new(&env));
+        if records.len() >= MAX_GOAL_HISTORY {
+            records.pop_front();
+        }
+        records.push_back(record.clone());
+        all.set(goal_id, records);
         env.storage()
             .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
-
-        Self::increment_nonce(&env, &caller);
-        Self::append_audit(&env, symbol_short!("import"), &caller, true);
-        true
-    }
-
-    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
-        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
-        let log = log.unwrap_or_else(|| Vec::new(&env));
-        let len = log.len();
-        let cap = MAX_AUDIT_ENTRIES.min(limit);
-        let mut out = Vec::new(&env);
-        if from_index >= len {
-            return out;
-        }
-        let end = (from_index + cap).min(len);
-        for i in from_index..end {
-            if let Some(entry) = log.get(i) {
-                out.push_back(entry);
-            }
-        }
-        out
-    }
+            .set(&symbol_short!("EMERGENCY"), &all);
 
-    fn require_nonce(env: &Env, address: &Address, expected: u64) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        if expected != current {
-            panic!("Invalid nonce: expected {}, got {}", current, expected);
-        }
+        Self::append_audit(&env, symbol_short!("emergency"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::EmergencyWithdrawal),
+            (goal_id, record),
+        );
+        Ok(remaining)
     }
 
-    fn increment_nonce(env: &Env, address: &Address) {
-        let current = Self::get_nonce(env.clone(), address.clone());
-        let next = current.checked_add(1).expect("nonce overflow");
-        let mut nonces: Map<Address, u64> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
+    pub fn get_emergency_withdrawals(env: Env, goal_id: u32) -> Vec<EmergencyWithdrawal> {
         env.storage()
             .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
+            .get::<_, Map<u32, Vec<EmergencyWithdrawal>>>(&symbol_short!("EMERGENCY"))
+            .and_then(|all| all.get(goal_id))
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
-    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
-        let mut c = version as u64 + next_id as u64;
-        for i in 0..goals.len() {
-            if let Some(g) = goals.get(i) {
-                c = c
-                    .wrapping_add(g.id as u64)
-                    .wrapping_add(g.target_amount as u64)
-                    .wrapping_add(g.current_amount as u64);
+    /// Defines progress milestones for a goal, replacing any existing ones.
+    ///
+    /// Each milestone is reached once `current_amount` hits `percent` of the
+    /// target, and from then on unlocks `unlock_bps` of the target for
+    /// withdrawal even while the goal is locked. Time-locks still apply.
+    /// Milestones already covered by the current amount are reached at once.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `InvalidMilestone` - If percents are not strictly increasing within
+    ///   1-100, total unlocks exceed 100%, or more than 10 are given
+    pub fn set_goal_milestones(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        milestones: Vec<MilestoneConfig>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::LOCK);
+
+        let goal = match Self::get_goal(env.clone(), goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+                return Err(SavingsGoalsError::GoalNotFound);
             }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
         }
-        c.wrapping_mul(31)
-    }
 
-    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
-        let timestamp = env.ledger().timestamp();
-        let mut log: Vec<AuditEntry> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("AUDIT"))
-            .unwrap_or_else(|| Vec::new(env));
-        if log.len() >= MAX_AUDIT_ENTRIES {
-            let mut new_log = Vec::new(env);
-            for i in 1..log.len() {
-                if let Some(entry) = log.get(i) {
-                    new_log.push_back(entry);
-                }
+        let mut previous = 0u32;
+        let mut total_bps = 0u32;
+        let mut valid = milestones.len() <= MAX_MILESTONES;
+        let mut list = Vec::new(&env);
+        for config in milestones.iter() {
+            total_bps = total_bps.saturating_add(config.unlock_bps);
+            if config.percent <= previous || config.percent > 100 || total_bps > 10_000 {
+                valid = false;
+                break;
             }
-            log = new_log;
+            previous = config.percent;
+            list.push_back(GoalMilestone {
+                percent: config.percent,
+                unlock_bps: config.unlock_bps,
+                unlock_amount: goal.target_amount * config.unlock_bps as i128 / 10_000,
+                reached_at: None,
+            });
+        }
+        if !valid {
+            Self::append_audit(&env, symbol_short!("milestone"), &caller, false);
+            return Err(SavingsGoalsError::InvalidMilestone);
         }
-        log.push_back(AuditEntry {
-            operation,
-            caller: caller.clone(),
-            timestamp,
-            success,
-        });
-        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
-    }
-
-    fn get_owner_goal_ids_map(env: &Env) -> Option<Map<Address, Vec<u32>>> {
-        env.storage().instance().get(&Self::STORAGE_OWNER_GOAL_IDS)
-    }
 
-    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
-            .storage()
-            .instance()
-            .get(&Self::STORAGE_OWNER_GOAL_IDS)
-            .unwrap_or_else(|| Map::new(env));
-        let mut ids = owner_goal_ids
-            .get(owner.clone())
-            .unwrap_or_else(|| Vec::new(env));
-        ids.push_back(goal_id);
-        owner_goal_ids.set(owner.clone(), ids);
+        Self::extend_instance_ttl(&env);
+        let mut all = Self::load_milestones(&env);
+        let withdrawn = all.get(goal_id).map_or(0, |entry| entry.withdrawn);
+        all.set(
+            goal_id,
+            GoalMilestones {
+                milestones: list,
+                withdrawn,
+            },
+        );
         env.storage()
             .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+            .set(&symbol_short!("MILESTONE"), &all);
+        Self::update_milestones(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("milestone"), &caller, true);
+        Ok(true)
     }
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    /// Get a goal's milestones in ascending order.
+    pub fn get_goal_milestones(env: Env, goal_id: u32) -> Vec<GoalMilestone> {
+        Self::load_milestones(&env)
+            .get(goal_id)
+            .map(|entry| entry.milestones)
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
-    /// Set time-lock on a goal
-    pub fn set_time_lock(env: Env, caller: Address, goal_id: u32, unlock_date: u64) -> bool {
-        caller.require_auth();
+    /// Amount still withdrawable from a locked goal through reached milestones.
+    pub fn get_milestone_withdrawable(env: Env, goal_id: u32) -> i128 {
+        let entry = match Self::load_milestones(&env).get(goal_id) {
+            Some(entry) => entry,
+            None => return 0,
+        };
+        let unlocked: i128 = entry
+            .milestones
+            .iter()
+            .filter(|m| m.reached_at.is_some())
+            .map(|m| m.unlock_amount)
+            .sum();
+        let balance = Self::get_goal(env, goal_id).map_or(0, |g| g.current_amount);
+        (unlocked - entry.withdrawn).max(0).min(balance)
+    }
+
+    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::LOCK);
         Self::extend_instance_ttl(&env);
 
         let mut goals: Map<u32, SavingsGoal> = env
@@ -1110,437 +2056,4776 @@ impl SavingsGoalContract {
         let mut goal = match goals.get(goal_id) {
             Some(g) => g,
             None => {
-                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+                Self::append_audit(&env, symbol_short!("lock"), &caller, false);
                 panic!("Goal not found");
             }
         };
 
         if goal.owner != caller {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Only the goal owner can set time-lock");
-        }
-
-        let current_time = env.ledger().timestamp();
-        if unlock_date <= current_time {
-            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Unlock date must be in the future");
+            Self::append_audit(&env, symbol_short!("lock"), &caller, false);
+            panic!("Only the goal owner can lock this goal");
         }
 
-        goal.unlock_date = Some(unlock_date);
+        goal.locked = true;
         goals.set(goal_id, goal);
         env.storage()
             .instance()
             .set(&symbol_short!("GOALS"), &goals);
 
-        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
+        Self::append_audit(&env, symbol_short!("lock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalLocked),
+            (goal_id, caller),
+        );
+
         true
     }
 
-    pub fn create_savings_schedule(
-        env: Env,
-        owner: Address,
-        goal_id: u32,
-        amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> u32 {
-        owner.require_auth();
-
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
+    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::UNLOCK);
+        Self::extend_instance_ttl(&env);
 
-        let goals: Map<u32, SavingsGoal> = env
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
             .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
 
-        let goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = match goals.get(goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
 
-        if goal.owner != owner {
-            panic!("Only the goal owner can create schedules");
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            panic!("Only the goal owner can unlock this goal");
         }
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            panic!("Next due date must be in the future");
+        if Self::is_time_locked(&env, &goal) {
+            Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
+            panic!("Goal is time-locked");
         }
 
-        Self::extend_instance_ttl(&env);
+        goal.locked = false;
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
+        Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalUnlocked),
+            (goal_id, caller),
+        );
+
+        true
+    }
+
+    pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
+        let goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
+            .get(&symbol_short!("GOALS"))
             .unwrap_or_else(|| Map::new(&env));
+        goals.get(goal_id)
+    }
 
-        let next_schedule_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_SSCH"))
-            .unwrap_or(0u32)
-            + 1;
+    // -----------------------------------------------------------------------
+    // Ownership transfer
+    // -----------------------------------------------------------------------
 
-        let schedule = SavingsSchedule {
-            id: next_schedule_id,
-            owner: owner.clone(),
-            goal_id,
-            amount,
-            next_due,
-            interval,
-            recurring: interval > 0,
-            active: true,
-            created_at: current_time,
-            last_executed: None,
-            missed_count: 0,
-        };
+    /// Propose handing a goal to `new_owner`, who must accept it with
+    /// `accept_goal_transfer`. A new proposal replaces any pending one.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn transfer_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        new_owner: Address,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("xfer"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
 
-        schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::extend_instance_ttl(&env);
+        let mut pending = Self::load_pending_transfers(&env);
+        pending.set(goal_id, new_owner.clone());
         env.storage()
             .instance()
-            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
+            .set(&symbol_short!("GOAL_XFER"), &pending);
 
+        Self::append_audit(&env, symbol_short!("xfer"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
-            (next_schedule_id, owner),
+            (symbol_short!("savings"), SavingsEvent::TransferProposed),
+            (goal_id, caller, new_owner),
         );
-
-        next_schedule_id
+        Ok(true)
     }
 
-    pub fn modify_savings_schedule(
+    /// Withdraw a pending transfer proposal (current owner only).
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn cancel_goal_transfer(
         env: Env,
         caller: Address,
-        schedule_id: u32,
-        amount: i128,
-        next_due: u64,
-        interval: u64,
-    ) -> bool {
-        caller.require_auth();
-
-        if amount <= 0 {
-            panic!("Amount must be positive");
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalsError::Unauthorized);
         }
+        let mut pending = Self::load_pending_transfers(&env);
+        pending.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOAL_XFER"), &pending);
+        Ok(true)
+    }
 
-        let current_time = env.ledger().timestamp();
-        if next_due <= current_time {
-            panic!("Next due date must be in the future");
+    /// Accept a proposed goal transfer.
+    ///
+    /// The goal keeps its ID, balance, custody, milestones and contribution
+    /// history. Contribution schedules funded by the previous owner are
+    /// cancelled.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist or has no pending transfer
+    /// * `Unauthorized` - If caller is not the proposed new owner
+    pub fn accept_goal_transfer(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let new_owner = Self::load_pending_transfers(&env)
+            .get(goal_id)
+            .ok_or(SavingsGoalsError::GoalNotFound)?;
+        if new_owner != caller {
+            Self::append_audit(&env, symbol_short!("xfer_acc"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
         }
 
         Self::extend_instance_ttl(&env);
+        let previous_owner = Self::reassign_goal(&env, goal_id, &new_owner)?;
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
+        Self::append_audit(&env, symbol_short!("xfer_acc"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalTransferred),
+            (goal_id, previous_owner, new_owner),
+        );
+        Ok(true)
+    }
+
+    pub fn get_pending_goal_transfer(env: Env, goal_id: u32) -> Option<Address> {
+        Self::load_pending_transfers(&env).get(goal_id)
+    }
+
+    /// Hand a goal to `new_owner`, clearing the previous owner's pending
+    /// transfer, inactivity plan and contribution schedules.
+    fn reassign_goal(
+        env: &Env,
+        goal_id: u32,
+        new_owner: &Address,
+    ) -> Result<Address, SavingsGoalsError> {
+        let mut goals: Map<u32, SavingsGoal> = env
             .storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let previous_owner = goal.owner.clone();
+        goal.owner = new_owner.clone();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        Self::move_owner_goal_id(env, &previous_owner, new_owner, goal_id);
 
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        let mut pending = Self::load_pending_transfers(env);
+        pending.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOAL_XFER"), &pending);
 
-        if schedule.owner != caller {
-            panic!("Only the schedule owner can modify it");
-        }
+        let mut plans = Self::load_inactivity_plans(env);
+        plans.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("INACTIVE"), &plans);
 
-        schedule.amount = amount;
-        schedule.next_due = next_due;
-        schedule.interval = interval;
-        schedule.recurring = interval > 0;
+        let mut contribution_schedules = Self::load_contribution_schedules(env);
+        contribution_schedules.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONT_SCH"), &contribution_schedules);
+        Self::deactivate_goal_schedules(env, goal_id);
+        Ok(previous_owner)
+    }
 
-        schedules.set(schedule_id, schedule);
+    // -----------------------------------------------------------------------
+    // Inactivity beneficiary
+    // -----------------------------------------------------------------------
+
+    /// Name a beneficiary who can claim the goal once the owner has been
+    /// inactive for `inactivity_days`. Activity is any call the owner
+    /// authorizes on this contract. Pass `None` to remove the plan.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `InvalidAmount` - If a beneficiary is given with `inactivity_days` 0
+    pub fn set_inactivity_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        beneficiary: Option<Address>,
+        inactivity_days: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if beneficiary.is_some() && inactivity_days == 0 {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut plans = Self::load_inactivity_plans(&env);
+        match beneficiary.clone() {
+            Some(beneficiary) => plans.set(
+                goal_id,
+                InactivityPlan {
+                    beneficiary,
+                    inactivity_period: inactivity_days as u64 * 86400,
+                    claim_started: None,
+                },
+            ),
+            None => {
+                plans.remove(goal_id);
+            }
+        }
         env.storage()
             .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+            .set(&symbol_short!("INACTIVE"), &plans);
 
+        Self::append_audit(&env, symbol_short!("benefic"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
-            (schedule_id, caller),
+            (symbol_short!("savings"), SavingsEvent::BeneficiarySet),
+            (goal_id, beneficiary, inactivity_days),
         );
+        Ok(true)
+    }
 
-        true
+    pub fn get_inactivity_plan(env: Env, goal_id: u32) -> Option<InactivityPlan> {
+        Self::load_inactivity_plans(&env).get(goal_id)
     }
 
-    pub fn cancel_savings_schedule(env: Env, caller: Address, schedule_id: u32) -> bool {
-        caller.require_auth();
+    /// Timestamp of the last call `address` authorized on this contract.
+    pub fn get_last_activity(env: Env, address: Address) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, u64>>(&symbol_short!("ACTIVITY"))
+            .and_then(|activity| activity.get(address))
+    }
+
+    /// Start the beneficiary's claim on an inactive owner's goal.
+    ///
+    /// Opens a seven-day challenge window; any activity by the owner during
+    /// it defeats the claim.
+    ///
+    /// # Returns
+    /// The time from which `finalize_inactivity_claim` can be called
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist or has no beneficiary
+    /// * `Unauthorized` - If caller is not the beneficiary
+    /// * `ClaimNotReady` - If the owner has been active within the period
+    pub fn start_inactivity_claim(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<u64, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let mut plans = Self::load_inactivity_plans(&env);
+        let mut plan = plans.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if plan.beneficiary != caller {
+            Self::append_audit(&env, symbol_short!("inact_clm"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let last_active = Self::get_last_activity(env.clone(), goal.owner.clone()).unwrap_or(0);
+        if now < last_active.saturating_add(plan.inactivity_period) {
+            Self::append_audit(&env, symbol_short!("inact_clm"), &caller, false);
+            return Err(SavingsGoalsError::ClaimNotReady);
+        }
 
         Self::extend_instance_ttl(&env);
+        plan.claim_started = Some(now);
+        plans.set(goal_id, plan);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("INACTIVE"), &plans);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
+        let claimable_at = now + INACTIVITY_CHALLENGE_WINDOW;
+        Self::append_audit(&env, symbol_short!("inact_clm"), &caller, true);
+        env.events().publish(
+            (
+                symbol_short!("savings"),
+                SavingsEvent::InactivityClaimStarted,
+            ),
+            (goal_id, goal.owner, caller, claimable_at),
+        );
+        Ok(claimable_at)
+    }
+
+    /// Complete an inactivity claim once the challenge window has passed
+    /// without owner activity. The goal, with its balance, custody and
+    /// history, passes to the beneficiary as with `accept_goal_transfer`.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist or has no beneficiary
+    /// * `Unauthorized` - If caller is not the beneficiary
+    /// * `ClaimNotReady` - If no claim is open, the window has not passed, or
+    ///   the owner has been active since the claim started
+    pub fn finalize_inactivity_claim(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let plan = Self::load_inactivity_plans(&env)
+            .get(goal_id)
+            .ok_or(SavingsGoalsError::GoalNotFound)?;
+        if plan.beneficiary != caller {
+            Self::append_audit(&env, symbol_short!("inact_fin"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let last_active = Self::get_last_activity(env.clone(), goal.owner.clone()).unwrap_or(0);
+        let ready = match plan.claim_started {
+            Some(started) => now >= started + INACTIVITY_CHALLENGE_WINDOW && last_active < started,
+            None => false,
+        };
+        if !ready {
+            Self::append_audit(&env, symbol_short!("inact_fin"), &caller, false);
+            return Err(SavingsGoalsError::ClaimNotReady);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let previous_owner = Self::reassign_goal(&env, goal_id, &caller)?;
+
+        Self::append_audit(&env, symbol_short!("inact_fin"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InactivityClaimed),
+            (goal_id, previous_owner, caller),
+        );
+        Ok(true)
+    }
+
+    fn load_inactivity_plans(env: &Env) -> Map<u32, InactivityPlan> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("INACTIVE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Require `caller`'s authorization and record it as activity for
+    /// inactivity plans.
+    fn require_caller(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let mut activity: Map<Address, u64> = env
             .storage()
             .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+            .get(&symbol_short!("ACTIVITY"))
+            .unwrap_or_else(|| Map::new(env));
+        activity.set(caller.clone(), env.ledger().timestamp());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ACTIVITY"), &activity);
+    }
 
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+    fn load_pending_transfers(env: &Env) -> Map<u32, Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("GOAL_XFER"))
+            .unwrap_or_else(|| Map::new(env))
+    }
 
-        if schedule.owner != caller {
-            panic!("Only the schedule owner can cancel it");
-        }
+    // -----------------------------------------------------------------------
+    // Shared family goals
+    // -----------------------------------------------------------------------
 
-        schedule.active = false;
+    /// Let family members contribute to a goal, replacing any previous sharing.
+    ///
+    /// `contributors` may call `add_to_goal` and `deposit` on the goal;
+    /// `family_head`, if given, may also withdraw from it, paying the owner.
+    /// Pass an empty list and `None` to stop sharing.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn share_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        contributors: Vec<Address>,
+        family_head: Option<Address>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("share"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
 
-        schedules.set(schedule_id, schedule);
+        Self::extend_instance_ttl(&env);
+        let mut shared: Map<u32, SharedGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SHARED"))
+            .unwrap_or_else(|| Map::new(&env));
+        if contributors.is_empty() && family_head.is_none() {
+            shared.remove(goal_id);
+        } else {
+            shared.set(
+                goal_id,
+                SharedGoal {
+                    contributors: contributors.clone(),
+                    family_head: family_head.clone(),
+                },
+            );
+        }
         env.storage()
             .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+            .set(&symbol_short!("SHARED"), &shared);
 
+        Self::append_audit(&env, symbol_short!("share"), &caller, true);
         env.events().publish(
-            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
+            (symbol_short!("savings"), SavingsEvent::GoalShared),
+            (goal_id, contributors.len(), family_head),
+        );
+        Ok(true)
+    }
+
+    pub fn get_shared_goal(env: Env, goal_id: u32) -> Option<SharedGoal> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, SharedGoal>>(&symbol_short!("SHARED"))
+            .and_then(|shared| shared.get(goal_id))
+    }
+
+    /// Per-contributor totals for a goal, in order of first contribution.
+    pub fn get_goal_contributions(env: Env, goal_id: u32) -> Vec<ContributorShare> {
+        let totals = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, Vec<ContributorShare>>>(&symbol_short!("CONTRIBS"))
+            .and_then(|all| all.get(goal_id));
+        totals.unwrap_or_else(|| Vec::new(&env))
+    }
+
+    fn can_contribute(env: &Env, goal: &SavingsGoal, caller: &Address) -> bool {
+        goal.owner == *caller
+            || Self::get_shared_goal(env.clone(), goal.id)
+                .is_some_and(|shared| shared.contributors.contains(caller))
+    }
+
+    /// Add to the contributor's running total and the goal's history, and
+    /// extend the owner's savings streak when they made the contribution.
+    fn record_contribution(
+        env: &Env,
+        goal_id: u32,
+        owner: &Address,
+        contributor: &Address,
+        amount: i128,
+        operation: Symbol,
+    ) {
+        if contributor == owner && operation != symbol_short!("cancel") {
+            Self::extend_streak(env, owner);
+        }
+
+        let mut history: Map<u32, Vec<ContributionRecord>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONT_HIS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut records = history.get(goal_id).unwrap_or_else(|| Vec::new(env));
+        if records.len() >= MAX_GOAL_HISTORY {
+            records.pop_front();
+        }
+        records.push_back(ContributionRecord {
+            contributor: contributor.clone(),
+            amount,
+            operation,
+            timestamp: env.ledger().timestamp(),
+        });
+        history.set(goal_id, records);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONT_HIS"), &history);
+
+        let mut all: Map<u32, Vec<ContributorShare>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CONTRIBS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut totals = all.get(goal_id).unwrap_or_else(|| Vec::new(env));
+        let position = totals
+            .iter()
+            .position(|share| share.contributor == *contributor);
+        match position {
+            Some(index) => {
+                let mut share = totals.get(index as u32).unwrap();
+                share.amount = share.amount.saturating_add(amount);
+                totals.set(index as u32, share);
+            }
+            None => totals.push_back(ContributorShare {
+                contributor: contributor.clone(),
+                amount,
+            }),
+        }
+        all.set(goal_id, totals);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONTRIBS"), &all);
+    }
+
+    fn extend_streak(env: &Env, owner: &Address) {
+        let now = env.ledger().timestamp();
+        let mut streaks: Map<Address, SavingsStreak> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("STREAK"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut streak = streaks.get(owner.clone()).unwrap_or(SavingsStreak {
+            current_weeks: 0,
+            longest_weeks: 0,
+            current_months: 0,
+            longest_months: 0,
+            last_contribution: 0,
+        });
+        let last = streak.last_contribution;
+        let first = streak.current_weeks == 0;
+
+        let (week, last_week) = (now / STREAK_WEEK, last / STREAK_WEEK);
+        if first || week > last_week + 1 {
+            streak.current_weeks = 1;
+        } else if week == last_week + 1 {
+            streak.current_weeks += 1;
+        }
+        let (month, last_month) = (now / STREAK_MONTH, last / STREAK_MONTH);
+        if first || month > last_month + 1 {
+            streak.current_months = 1;
+        } else if month == last_month + 1 {
+            streak.current_months += 1;
+        }
+        streak.longest_weeks = streak.longest_weeks.max(streak.current_weeks);
+        streak.longest_months = streak.longest_months.max(streak.current_months);
+        streak.last_contribution = now;
+
+        streaks.set(owner.clone(), streak);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STREAK"), &streaks);
+    }
+
+    /// The owner's savings streak. Contributions the owner makes to any of
+    /// their goals count; sponsor matches, others' contributions and
+    /// balances moved by `cancel_goal` do not. Current streaks read 0 once a
+    /// full week or month has passed without saving.
+    pub fn get_savings_streak(env: Env, owner: Address) -> SavingsStreak {
+        let mut streak = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, SavingsStreak>>(&symbol_short!("STREAK"))
+            .and_then(|streaks| streaks.get(owner))
+            .unwrap_or(SavingsStreak {
+                current_weeks: 0,
+                longest_weeks: 0,
+                current_months: 0,
+                longest_months: 0,
+                last_contribution: 0,
+            });
+        let now = env.ledger().timestamp();
+        let last = streak.last_contribution;
+        if now / STREAK_WEEK > last / STREAK_WEEK + 1 {
+            streak.current_weeks = 0;
+        }
+        if now / STREAK_MONTH > last / STREAK_MONTH + 1 {
+            streak.current_months = 0;
+        }
+        streak
+    }
+
+    /// The goal's most recent contributions, oldest first.
+    ///
+    /// `limit` of 0 returns DEFAULT_PAGE_LIMIT entries; at most MAX_PAGE_LIMIT
+    /// are returned. Up to 100 contributions are kept per goal, including for
+    /// archived goals.
+    pub fn get_contributions(env: Env, goal_id: u32, limit: u32) -> Vec<ContributionRecord> {
+        let records = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, Vec<ContributionRecord>>>(&symbol_short!("CONT_HIS"))
+            .and_then(|history| history.get(goal_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let limit = Self::clamp_limit(limit);
+        let start = records.len().saturating_sub(limit);
+        records.slice(start..)
+    }
+
+    // -----------------------------------------------------------------------
+    // Deadline reminders
+    // -----------------------------------------------------------------------
+
+    /// Check `owner`'s unfinished goals against their target dates.
+    ///
+    /// Permissionless. Each goal gets at most one alert, in order of
+    /// severity: `Missed` once the target date has passed, `AtRisk` when the
+    /// funded share is below the share of time elapsed since creation, and
+    /// `DeadlineApproaching` within seven days of the target date. Every
+    /// alert is also published as a `DeadlineAlert` event.
+    pub fn check_goal_deadlines(env: Env, owner: Address) -> Vec<DeadlineAlert> {
+        let now = env.ledger().timestamp();
+        let created: Map<u32, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOAL_CRT"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut alerts = Vec::new(&env);
+        for goal in Self::get_all_goals(env.clone(), owner.clone()).iter() {
+            if goal.current_amount >= goal.target_amount {
+                continue;
+            }
+            let progress_bps = (goal.current_amount.max(0) * 10_000 / goal.target_amount) as u32;
+            let seconds_remaining = goal.target_date.saturating_sub(now);
+
+            let behind_schedule = created.get(goal.id).is_some_and(|start| {
+                goal.target_date > start && {
+                    let elapsed = now.saturating_sub(start).min(goal.target_date - start);
+                    let elapsed_bps = elapsed as u128 * 10_000 / (goal.target_date - start) as u128;
+                    (progress_bps as u128) < elapsed_bps
+                }
+            });
+            let status = if now > goal.target_date {
+                DeadlineStatus::Missed
+            } else if behind_schedule {
+                DeadlineStatus::AtRisk
+            } else if seconds_remaining <= DEADLINE_WARNING_WINDOW {
+                DeadlineStatus::DeadlineApproaching
+            } else {
+                continue;
+            };
+
+            let alert = DeadlineAlert {
+                goal_id: goal.id,
+                status,
+                target_date: goal.target_date,
+                current_amount: goal.current_amount,
+                target_amount: goal.target_amount,
+                progress_bps,
+                seconds_remaining,
+            };
+            env.events().publish(
+                (
+                    symbol_short!("savings"),
+                    SavingsEvent::DeadlineAlert,
+                    owner.clone(),
+                ),
+                alert.clone(),
+            );
+            alerts.push_back(alert);
+        }
+        alerts
+    }
+
+    /// Total contributed to a goal between `period_start` and `period_end`
+    /// (inclusive), from its contribution history.
+    pub fn get_goal_period_contributions(
+        env: Env,
+        goal_id: u32,
+        period_start: u64,
+        period_end: u64,
+    ) -> i128 {
+        let records = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, Vec<ContributionRecord>>>(&symbol_short!("CONT_HIS"))
+            .and_then(|history| history.get(goal_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut total = 0i128;
+        for record in records.iter() {
+            if record.timestamp >= period_start && record.timestamp <= period_end {
+                total += record.amount;
+            }
+        }
+        total
+    }
+
+    /// Total contributed to all of `owner`'s goals, including archived ones,
+    /// between `period_start` and `period_end` (inclusive).
+    ///
+    /// Based on the last 100 contributions kept per goal.
+    pub fn get_period_contributions(
+        env: Env,
+        owner: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> i128 {
+        let mut goal_ids = Self::owner_goal_ids(&env, &owner);
+        for archived in Self::get_archived_goals(env.clone(), owner).iter() {
+            goal_ids.push_back(archived.goal.id);
+        }
+        let mut total = 0i128;
+        for goal_id in goal_ids.iter() {
+            total +=
+                Self::get_goal_period_contributions(env.clone(), goal_id, period_start, period_end);
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // Archiving
+    // -----------------------------------------------------------------------
+
+    /// Move a completed or cancelled goal out of the active set.
+    ///
+    /// A goal can be archived once it reached its target, or when it is
+    /// empty (cancelled). Tokens must be withdrawn from custody and any yield
+    /// pool first. Archived goals no longer appear in `get_goal` or
+    /// `get_all_goals`; their contribution history is kept.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `CannotArchive` - If the goal is in progress or still holds tokens
+    pub fn archive_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("archive"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let completed = goal.current_amount >= goal.target_amount;
+        let holds_tokens = Self::get_goal_custody(env.clone(), goal_id)
+            .is_some_and(|c| c.balance > 0)
+            || Self::get_goal_yield(env.clone(), goal_id).is_some_and(|y| y.shares > 0)
+            || Self::load_holdings(&env, goal_id)
+                .values()
+                .iter()
+                .any(|amount| amount > 0);
+        if (!completed && goal.current_amount != 0) || holds_tokens {
+            Self::append_audit(&env, symbol_short!("archive"), &caller, false);
+            return Err(SavingsGoalsError::CannotArchive);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::move_to_archive(&env, &mut goals, goal, completed);
+
+        Self::append_audit(&env, symbol_short!("archive"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalArchived),
+            (goal_id, caller, completed),
+        );
+        Ok(true)
+    }
+
+    /// Remove `goal` from the active set and its schedules, and keep it in
+    /// the archive.
+    fn move_to_archive(
+        env: &Env,
+        goals: &mut Map<u32, SavingsGoal>,
+        goal: SavingsGoal,
+        completed: bool,
+    ) {
+        let goal_id = goal.id;
+        goals.remove(goal_id);
+        env.storage().instance().set(&symbol_short!("GOALS"), goals);
+        Self::remove_owner_goal_id(env, &goal.owner, goal_id);
+
+        let mut pending = Self::load_pending_transfers(env);
+        pending.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOAL_XFER"), &pending);
+        let mut contribution_schedules = Self::load_contribution_schedules(env);
+        contribution_schedules.remove(goal_id);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONT_SCH"), &contribution_schedules);
+        Self::deactivate_goal_schedules(env, goal_id);
+
+        let mut archive = Self::load_archive(env);
+        archive.set(
+            goal_id,
+            ArchivedGoal {
+                goal,
+                completed,
+                archived_at: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ARCHIVE"), &archive);
+    }
+
+    /// Set the family wallet that `cancel_goal` can refund to (upgrade admin
+    /// only).
+    pub fn set_family_wallet(env: Env, caller: Address, wallet: Address) {
+        Self::require_caller(&env, &caller);
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("FAM_WAL"), &wallet);
+    }
+
+    pub fn get_family_wallet(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("FAM_WAL"))
+    }
+
+    /// Cancel an unfinished goal and route its balance to `refund_target`.
+    ///
+    /// The goal must be unlocked and past any time-lock. Custodied tokens are
+    /// sent to the owner or the family wallet, or moved into the target
+    /// goal's custody; the target goal is also credited with the cancelled
+    /// goal's balance. Tokens in a yield pool or multi-asset holdings must be
+    /// withdrawn first. The cancelled goal is archived and the reason kept
+    /// for `get_goal_cancellation`.
+    ///
+    /// # Returns
+    /// The balance moved to the target
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id or the target goal does not exist
+    /// * `Unauthorized` - If caller does not own both goals
+    /// * `GoalLocked` - If the goal is locked or time-locked
+    /// * `TokenMismatch` - If the target goal holds a different token
+    /// * `CannotCancel` - If the goal is completed, still has tokens in a
+    ///   pool or holdings, targets itself, or no family wallet is set
+    pub fn cancel_goal(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        refund_target: RefundTarget,
+        reason: String,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let checked = Self::check_cancellable(&env, &goals, &goal, &caller, &refund_target);
+        if let Err(err) = checked {
+            Self::append_audit(&env, symbol_short!("cancel"), &caller, false);
+            return Err(err);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let contract = env.current_contract_address();
+        let mut custody = Self::load_custody(&env);
+        let held = custody.get(goal_id);
+        let tokens = held.as_ref().map(|held| held.balance).unwrap_or(0);
+        if let Some(held) = held.filter(|held| held.balance > 0) {
+            let token = TokenClient::new(&env, &held.token);
+            match &refund_target {
+                RefundTarget::Owner => token.transfer(&contract, &caller, &held.balance),
+                RefundTarget::FamilyWallet => {
+                    let wallet = Self::get_family_wallet(env.clone())
+                        .ok_or(SavingsGoalsError::CannotCancel)?;
+                    token.transfer(&contract, &wallet, &held.balance)
+                }
+                RefundTarget::Goal(target_id) => {
+                    let mut target = custody.get(*target_id).unwrap_or(GoalCustody {
+                        token: held.token.clone(),
+                        balance: 0,
+                    });
+                    target.balance += held.balance;
+                    custody.set(*target_id, target);
+                }
+            }
+            custody.remove(goal_id);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CUSTODY"), &custody);
+        }
+
+        let amount = goal.current_amount;
+        goal.current_amount = 0;
+        Self::move_to_archive(&env, &mut goals, goal.clone(), false);
+        if let RefundTarget::Goal(target_id) = refund_target {
+            if amount > 0 {
+                let mut target = goals
+                    .get(target_id)
+                    .ok_or(SavingsGoalsError::GoalNotFound)?;
+                Self::credit_goal(
+                    &env,
+                    &mut goals,
+                    &mut target,
+                    &caller,
+                    amount,
+                    symbol_short!("cancel"),
+                )?;
+            }
+        }
+
+        let mut cancellations: Map<u32, GoalCancellation> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("CANCELS"))
+            .unwrap_or_else(|| Map::new(&env));
+        cancellations.set(
+            goal_id,
+            GoalCancellation {
+                refund_target: refund_target.clone(),
+                amount,
+                tokens,
+                reason,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CANCELS"), &cancellations);
+
+        Self::append_audit(&env, symbol_short!("cancel"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalCancelled),
+            (goal_id, caller, amount, refund_target),
+        );
+        Ok(amount)
+    }
+
+    pub fn get_goal_cancellation(env: Env, goal_id: u32) -> Option<GoalCancellation> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, GoalCancellation>>(&symbol_short!("CANCELS"))
+            .and_then(|cancellations| cancellations.get(goal_id))
+    }
+
+    fn check_cancellable(
+        env: &Env,
+        goals: &Map<u32, SavingsGoal>,
+        goal: &SavingsGoal,
+        caller: &Address,
+        refund_target: &RefundTarget,
+    ) -> Result<(), SavingsGoalsError> {
+        if goal.owner != *caller {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if goal.locked || Self::is_time_locked(env, goal) {
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        let invested = Self::get_goal_yield(env.clone(), goal.id).is_some_and(|y| y.shares > 0)
+            || Self::load_holdings(env, goal.id)
+                .values()
+                .iter()
+                .any(|amount| amount > 0);
+        if goal.current_amount >= goal.target_amount || invested {
+            return Err(SavingsGoalsError::CannotCancel);
+        }
+        match refund_target {
+            RefundTarget::Owner => {}
+            RefundTarget::FamilyWallet => {
+                if Self::get_family_wallet(env.clone()).is_none() {
+                    return Err(SavingsGoalsError::CannotCancel);
+                }
+            }
+            RefundTarget::Goal(target_id) => {
+                if *target_id == goal.id {
+                    return Err(SavingsGoalsError::CannotCancel);
+                }
+                let target = goals
+                    .get(*target_id)
+                    .ok_or(SavingsGoalsError::GoalNotFound)?;
+                if target.owner != *caller {
+                    return Err(SavingsGoalsError::Unauthorized);
+                }
+                let custody = Self::load_custody(env);
+                if let (Some(from), Some(to)) = (custody.get(goal.id), custody.get(*target_id)) {
+                    if from.balance > 0 && from.token != to.token {
+                        return Err(SavingsGoalsError::TokenMismatch);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_archived_goals(env: Env, owner: Address) -> Vec<ArchivedGoal> {
+        let mut result = Vec::new(&env);
+        for (_, archived) in Self::load_archive(&env).iter() {
+            if archived.goal.owner == owner {
+                result.push_back(archived);
+            }
+        }
+        result
+    }
+
+    fn load_archive(env: &Env) -> Map<u32, ArchivedGoal> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ARCHIVE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn deactivate_goal_schedules(env: &Env, goal_id: u32) {
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(env));
+        for (schedule_id, mut schedule) in schedules.iter() {
+            if schedule.goal_id == goal_id && schedule.active {
+                schedule.active = false;
+                schedules.set(schedule_id, schedule);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+    }
+
+    // -----------------------------------------------------------------------
+    // Multi-asset goals
+    // -----------------------------------------------------------------------
+
+    /// Set the price oracle used to value multi-asset goals (upgrade admin only).
+    pub fn set_price_oracle(env: Env, caller: Address, oracle: Address) {
+        Self::require_caller(&env, &caller);
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ORACLE"), &oracle);
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("ORACLE"))
+    }
+
+    /// Denominate a goal in `asset` (e.g. a local-currency token), so tokens
+    /// added with `deposit_asset` count towards it at oracle rates.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `TokenMismatch` - If the goal already holds assets valued in another asset
+    pub fn set_valuation_asset(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        asset: Address,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let mut assets = Self::load_valuation_assets(&env);
+        let held = Self::load_holdings(&env, goal_id)
+            .values()
+            .iter()
+            .any(|amount| amount > 0);
+        if held && assets.get(goal_id).is_some_and(|current| current != asset) {
+            return Err(SavingsGoalsError::TokenMismatch);
+        }
+
+        Self::extend_instance_ttl(&env);
+        assets.set(goal_id, asset);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("VAL_ASSET"), &assets);
+        Ok(true)
+    }
+
+    pub fn get_valuation_asset(env: Env, goal_id: u32) -> Option<Address> {
+        Self::load_valuation_assets(&env).get(goal_id)
+    }
+
+    /// Deposit any token into a goal that has a valuation asset.
+    ///
+    /// The token is held by the contract and the goal is credited with its
+    /// value at the current oracle rate.
+    ///
+    /// # Returns
+    /// The goal's new `current_amount`
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is neither the owner nor a shared contributor
+    /// * `OracleUnavailable` - If no valuation asset, oracle or rate is available
+    pub fn deposit_asset(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if !Self::can_contribute(&env, &goal, &caller) {
+            Self::append_audit(&env, symbol_short!("dep_asset"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let value = Self::asset_value(&env, goal_id, &token, amount)?;
+
+        Self::extend_instance_ttl(&env);
+        TokenClient::new(&env, &token).transfer(&caller, &env.current_contract_address(), &amount);
+        let mut holdings = Self::load_holdings(&env, goal_id);
+        holdings.set(
+            token.clone(),
+            holdings.get(token.clone()).unwrap_or(0) + amount,
+        );
+        Self::save_holdings(&env, goal_id, &holdings);
+        Self::add_valued(&env, goal_id, value);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::AssetDeposited),
+            (goal_id, token, amount, value),
+        );
+        Self::credit_goal(
+            &env,
+            &mut goals,
+            &mut goal,
+            &caller,
+            value,
+            symbol_short!("dep_asset"),
+        )
+    }
+
+    /// Withdraw a token held by a multi-asset goal to its owner, subject to
+    /// the same lock rules as `withdraw_from_goal`. The goal is debited with
+    /// the token's current value.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is neither the owner nor the family head
+    /// * `GoalLocked` - If the goal is locked or time-locked
+    /// * `InsufficientBalance` - If the goal holds less than amount of the token
+    /// * `OracleUnavailable` - If the token cannot be valued
+    pub fn withdraw_asset(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        let mut holdings = Self::load_holdings(&env, goal_id);
+        let held = holdings.get(token.clone()).unwrap_or(0);
+        if held < amount {
+            return Err(SavingsGoalsError::InsufficientBalance);
+        }
+        let value = Self::asset_value(&env, goal_id, &token, amount)?.min(goal.current_amount);
+        let mode = match Self::ensure_withdrawable(&env, &goal, &caller, value, false) {
+            Ok(mode) => mode,
+            Err(err) => {
+                Self::append_audit(&env, symbol_short!("wd_asset"), &caller, false);
+                return Err(err);
+            }
+        };
+
+        Self::extend_instance_ttl(&env);
+        holdings.set(token.clone(), held - amount);
+        Self::save_holdings(&env, goal_id, &holdings);
+        Self::add_valued(&env, goal_id, -value);
+        goal.current_amount -= value;
+        let remaining = goal.current_amount;
+        let owner = goal.owner.clone();
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        if mode == WithdrawalMode::Milestone {
+            Self::record_milestone_withdrawal(&env, goal_id, value);
+        }
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &owner, &amount);
+
+        Self::append_audit(&env, symbol_short!("wd_asset"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::AssetWithdrawn),
+            (goal_id, token, amount, value),
+        );
+        Ok(remaining)
+    }
+
+    /// Re-price a multi-asset goal's holdings at current oracle rates and
+    /// adjust `current_amount` by the change in their value. Permissionless.
+    ///
+    /// # Returns
+    /// The goal's new `current_amount`
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `OracleUnavailable` - If a holding cannot be valued
+    pub fn revalue_goal(env: Env, goal_id: u32) -> Result<i128, SavingsGoalsError> {
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+
+        let mut value = 0i128;
+        for holding in Self::get_goal_holdings(env.clone(), goal_id)?.iter() {
+            value += holding.value;
+        }
+        let mut valued = Self::load_valued(&env);
+        let previous = valued.get(goal_id).unwrap_or(0);
+        let change = value - previous;
+        if change == 0 {
+            return Ok(goal.current_amount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        goal.current_amount = (goal.current_amount + change).max(0);
+        goals.set(goal_id, goal.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        valued.set(goal_id, value);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("VALUED"), &valued);
+        Self::update_milestones(&env, &goal);
+        if goal.current_amount >= goal.target_amount {
+            Self::reward_completion(&env, &goal);
+        }
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalRevalued),
+            (goal_id, previous, value),
+        );
+        Ok(goal.current_amount)
+    }
+
+    /// Tokens held by a multi-asset goal with their current values.
+    ///
+    /// # Errors
+    /// * `OracleUnavailable` - If a holding cannot be valued
+    pub fn get_goal_holdings(
+        env: Env,
+        goal_id: u32,
+    ) -> Result<Vec<AssetHolding>, SavingsGoalsError> {
+        let mut result = Vec::new(&env);
+        for (token, amount) in Self::load_holdings(&env, goal_id).iter() {
+            if amount == 0 {
+                continue;
+            }
+            let value = Self::asset_value(&env, goal_id, &token, amount)?;
+            result.push_back(AssetHolding {
+                token,
+                amount,
+                value,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Value `amount` of `token` in the goal's valuation asset.
+    fn asset_value(
+        env: &Env,
+        goal_id: u32,
+        token: &Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        let asset = Self::load_valuation_assets(env)
+            .get(goal_id)
+            .ok_or(SavingsGoalsError::OracleUnavailable)?;
+        if *token == asset {
+            return Ok(amount);
+        }
+        let oracle =
+            Self::get_price_oracle(env.clone()).ok_or(SavingsGoalsError::OracleUnavailable)?;
+        let rate = match PriceOracleClient::new(env, &oracle).try_rate(token, &asset) {
+            Ok(Ok(rate)) if rate > 0 => rate,
+            _ => return Err(SavingsGoalsError::OracleUnavailable),
+        };
+        amount
+            .checked_mul(rate)
+            .map(|scaled| scaled / RATE_SCALE)
+            .ok_or(SavingsGoalsError::Overflow)
+    }
+
+    fn load_valuation_assets(env: &Env) -> Map<u32, Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("VAL_ASSET"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn load_holdings(env: &Env, goal_id: u32) -> Map<Address, i128> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Map<Address, i128>>>(&symbol_short!("HOLDINGS"))
+            .and_then(|all| all.get(goal_id))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_holdings(env: &Env, goal_id: u32, holdings: &Map<Address, i128>) {
+        let mut all: Map<u32, Map<Address, i128>> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("HOLDINGS"))
+            .unwrap_or_else(|| Map::new(env));
+        all.set(goal_id, holdings.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("HOLDINGS"), &all);
+    }
+
+    /// Value of the goal's holdings as last counted in `current_amount`.
+    fn load_valued(env: &Env) -> Map<u32, i128> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("VALUED"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn add_valued(env: &Env, goal_id: u32, delta: i128) {
+        let mut valued = Self::load_valued(env);
+        valued.set(goal_id, valued.get(goal_id).unwrap_or(0) + delta);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("VALUED"), &valued);
+    }
+
+    // -----------------------------------------------------------------------
+    // Yield on idle goal balances
+    // -----------------------------------------------------------------------
+
+    /// Add or remove a yield pool from the whitelist (upgrade admin only).
+    pub fn set_yield_pool(env: Env, caller: Address, pool: Address, allowed: bool) {
+        Self::require_caller(&env, &caller);
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        let mut pools = Self::get_yield_pools(env.clone());
+        match pools.first_index_of(&pool) {
+            Some(index) if !allowed => {
+                pools.remove(index);
+            }
+            None if allowed => pools.push_back(pool.clone()),
+            _ => {}
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("YLD_POOLS"), &pools);
+        env.events().publish(
+            (symbol_short!("savings"), symbol_short!("yld_pool")),
+            (pool, allowed),
+        );
+    }
+
+    pub fn get_yield_pools(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("YLD_POOLS"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Move custodied tokens of a locked goal into a whitelisted yield pool.
+    ///
+    /// The funds stay part of `current_amount`; they return to custody through
+    /// `withdraw_from_yield`. A goal uses one pool at a time.
+    ///
+    /// # Returns
+    /// The goal's principal in the pool
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `GoalNotLocked` - If the goal is unlocked
+    /// * `PoolNotAllowed` - If the pool is not whitelisted or differs from the goal's current pool
+    /// * `InsufficientBalance` - If amount exceeds the tokens held for the goal
+    pub fn deposit_to_yield(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        pool: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("yld_dep"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        if !goal.locked {
+            return Err(SavingsGoalsError::GoalNotLocked);
+        }
+        if !Self::get_yield_pools(env.clone()).contains(&pool) {
+            return Err(SavingsGoalsError::PoolNotAllowed);
+        }
+
+        let mut custody = Self::load_custody(&env);
+        let mut held = match custody.get(goal_id) {
+            Some(held) if held.balance >= amount => held,
+            _ => return Err(SavingsGoalsError::InsufficientBalance),
+        };
+        let mut positions = Self::load_yield_positions(&env);
+        let mut position = match positions.get(goal_id) {
+            Some(position) if position.shares > 0 && position.pool != pool => {
+                return Err(SavingsGoalsError::PoolNotAllowed);
+            }
+            Some(mut position) => {
+                position.pool = pool.clone();
+                position.token = held.token.clone();
+                position
+            }
+            None => GoalYield {
+                pool: pool.clone(),
+                token: held.token.clone(),
+                shares: 0,
+                principal: 0,
+                harvested: 0,
+            },
+        };
+
+        Self::extend_instance_ttl(&env);
+        let client = YieldPoolClient::new(&env, &pool);
+        let contract = env.current_contract_address();
+        let pool_balance = client.balance(&contract, &held.token);
+        let total_shares = Self::pool_shares(&env, &pool, &held.token);
+        let minted = if total_shares == 0 || pool_balance == 0 {
+            amount
+        } else {
+            amount
+                .checked_mul(total_shares)
+                .ok_or(SavingsGoalsError::Overflow)?
+                / pool_balance
+        };
+
+        // The pool pulls the tokens from this contract.
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: held.token.clone(),
+                    fn_name: Symbol::new(&env, "transfer"),
+                    args: (contract.clone(), pool.clone(), amount).into_val(&env),
+                },
+                sub_invocations: Vec::new(&env),
+            }),
+        ]);
+        client.deposit(&contract, &held.token, &amount);
+
+        held.balance -= amount;
+        custody.set(goal_id, held.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CUSTODY"), &custody);
+        position.shares += minted;
+        position.principal += amount;
+        positions.set(goal_id, position.clone());
+        Self::save_yield_positions(&env, &positions);
+        Self::set_pool_shares(&env, &pool, &held.token, total_shares + minted);
+
+        Self::append_audit(&env, symbol_short!("yld_dep"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::YieldDeposited),
+            (goal_id, pool, amount),
+        );
+        Ok(position.principal)
+    }
+
+    /// Credit the yield accrued on a goal's pool position to the goal.
+    ///
+    /// Anyone may call this; the yield is pulled back into custody and added
+    /// to `current_amount`, but is not counted as anyone's contribution.
+    ///
+    /// # Returns
+    /// The amount harvested (0 if nothing accrued)
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist or has no pool position
+    pub fn harvest(env: Env, goal_id: u32) -> Result<i128, SavingsGoalsError> {
+        let mut positions = Self::load_yield_positions(&env);
+        let mut position = positions
+            .get(goal_id)
+            .ok_or(SavingsGoalsError::GoalNotFound)?;
+
+        let gain = Self::harvest_position(&env, goal_id, &mut position)?;
+        positions.set(goal_id, position);
+        Self::save_yield_positions(&env, &positions);
+        Ok(gain)
+    }
+
+    /// Harvest and return a goal's whole pool position to custody.
+    ///
+    /// # Returns
+    /// The principal returned to custody
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist or has no pool position
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn withdraw_from_yield(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<i128, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("yld_wd"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let mut positions = Self::load_yield_positions(&env);
+        let mut position = positions
+            .get(goal_id)
+            .ok_or(SavingsGoalsError::GoalNotFound)?;
+        Self::harvest_position(&env, goal_id, &mut position)?;
+
+        let client = YieldPoolClient::new(&env, &position.pool);
+        let contract = env.current_contract_address();
+        let total_shares = Self::pool_shares(&env, &position.pool, &position.token);
+        let returned = if total_shares == 0 {
+            0
+        } else {
+            position.shares * client.balance(&contract, &position.token) / total_shares
+        };
+        if returned > 0 {
+            client.withdraw(&contract, &position.token, &returned);
+        }
+        Self::set_pool_shares(
+            &env,
+            &position.pool,
+            &position.token,
+            total_shares - position.shares,
+        );
+
+        // A pool that lost value leaves the goal short of its principal.
+        let loss = position.principal - returned;
+        if loss > 0 {
+            let mut goals: Map<u32, SavingsGoal> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("GOALS"))
+                .unwrap_or_else(|| Map::new(&env));
+            let mut goal = goal;
+            goal.current_amount -= loss;
+            goals.set(goal_id, goal);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("GOALS"), &goals);
+        }
+        Self::adjust_custody(&env, goal_id, returned);
+        position.shares = 0;
+        position.principal = 0;
+        positions.set(goal_id, position.clone());
+        Self::save_yield_positions(&env, &positions);
+
+        Self::append_audit(&env, symbol_short!("yld_wd"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::YieldWithdrawn),
+            (goal_id, position.pool, returned),
+        );
+        Ok(returned)
+    }
+
+    pub fn get_goal_yield(env: Env, goal_id: u32) -> Option<GoalYield> {
+        Self::load_yield_positions(&env).get(goal_id)
+    }
+
+    /// Total yield credited to a goal, kept apart from user contributions.
+    pub fn get_yield_earned(env: Env, goal_id: u32) -> i128 {
+        Self::get_goal_yield(env, goal_id).map_or(0, |position| position.harvested)
+    }
+
+    fn harvest_position(
+        env: &Env,
+        goal_id: u32,
+        position: &mut GoalYield,
+    ) -> Result<i128, SavingsGoalsError> {
+        let total_shares = Self::pool_shares(env, &position.pool, &position.token);
+        if position.shares == 0 || total_shares == 0 {
+            return Ok(0);
+        }
+        let client = YieldPoolClient::new(env, &position.pool);
+        let contract = env.current_contract_address();
+        let pool_balance = client.balance(&contract, &position.token);
+        let value = position
+            .shares
+            .checked_mul(pool_balance)
+            .ok_or(SavingsGoalsError::Overflow)?
+            / total_shares;
+        let gain = value - position.principal;
+        if gain <= 0 {
+            return Ok(0);
+        }
+
+        Self::extend_instance_ttl(env);
+        client.withdraw(&contract, &position.token, &gain);
+        let burned = gain * total_shares / pool_balance;
+        position.shares -= burned;
+        position.harvested += gain;
+        Self::set_pool_shares(env, &position.pool, &position.token, total_shares - burned);
+        Self::adjust_custody(env, goal_id, gain);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(gain)
+            .ok_or(SavingsGoalsError::Overflow)?;
+        goals.set(goal_id, goal.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        Self::update_milestones(env, &goal);
+        if goal.current_amount >= goal.target_amount {
+            Self::reward_completion(env, &goal);
+        }
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::YieldHarvested),
+            (goal_id, gain, goal.current_amount),
+        );
+        Ok(gain)
+    }
+
+    fn adjust_custody(env: &Env, goal_id: u32, amount: i128) {
+        let mut custody = Self::load_custody(env);
+        if let Some(mut held) = custody.get(goal_id) {
+            held.balance += amount;
+            custody.set(goal_id, held);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CUSTODY"), &custody);
+        }
+    }
+
+    fn load_yield_positions(env: &Env) -> Map<u32, GoalYield> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("YIELD"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn save_yield_positions(env: &Env, positions: &Map<u32, GoalYield>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("YIELD"), positions);
+    }
+
+    fn pool_shares(env: &Env, pool: &Address, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<(Address, Address), i128>>(&symbol_short!("YLD_SHR"))
+            .and_then(|shares| shares.get((pool.clone(), token.clone())))
+            .unwrap_or(0)
+    }
+
+    fn set_pool_shares(env: &Env, pool: &Address, token: &Address, total: i128) {
+        let mut shares: Map<(Address, Address), i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("YLD_SHR"))
+            .unwrap_or_else(|| Map::new(env));
+        shares.set((pool.clone(), token.clone()), total);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("YLD_SHR"), &shares);
+    }
+
+    // -----------------------------------------------------------------------
+    // Completion rewards
+    // -----------------------------------------------------------------------
+
+    /// Set (or clear) the rewards contract notified on goal completion
+    /// (upgrade admin only).
+    pub fn set_rewards_contract(env: Env, caller: Address, rewards: Option<Address>) {
+        Self::require_caller(&env, &caller);
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        match rewards {
+            Some(rewards) => env
+                .storage()
+                .instance()
+                .set(&symbol_short!("REWARDS"), &rewards),
+            None => env.storage().instance().remove(&symbol_short!("REWARDS")),
+        }
+    }
+
+    pub fn get_rewards_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("REWARDS"))
+    }
+
+    /// When the goal's completion was rewarded, if it has been.
+    pub fn get_goal_rewarded_at(env: Env, goal_id: u32) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, u64>>(&symbol_short!("REWARDED"))
+            .and_then(|rewarded| rewarded.get(goal_id))
+    }
+
+    /// Reward a completed goal once. Later contributions, or completing the
+    /// goal again after a withdrawal, do not reward it a second time. A
+    /// failing rewards contract never blocks the contribution itself.
+    fn reward_completion(env: &Env, goal: &SavingsGoal) {
+        let mut rewarded: Map<u32, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("REWARDED"))
+            .unwrap_or_else(|| Map::new(env));
+        if rewarded.contains_key(goal.id) {
+            return;
+        }
+        let now = env.ledger().timestamp();
+        rewarded.set(goal.id, now);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("REWARDED"), &rewarded);
+
+        let notified = match Self::get_rewards_contract(env.clone()) {
+            Some(rewards) => RewardsClient::new(env, &rewards)
+                .try_on_goal_completed(&goal.id, &goal.owner, &goal.target_amount)
+                .is_ok(),
+            None => false,
+        };
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalRewarded),
+            (goal.id, goal.owner.clone(), notified),
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Remittance split hook
+    // -----------------------------------------------------------------------
+
+    /// Set the remittance_split contract allowed to call `credit_from_split`
+    /// (upgrade admin only).
+    pub fn set_split_contract(env: Env, caller: Address, split_contract: Address) {
+        Self::require_caller(&env, &caller);
+        let admin = Self::get_upgrade_admin(&env).expect("No upgrade admin set");
+        if admin != caller {
+            panic!("Unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_CTR"), &split_contract);
+    }
+
+    pub fn get_split_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SPLIT_CTR"))
+    }
+
+    /// Choose how split funds are spread over the caller's goals.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If a prioritised goal does not exist
+    /// * `Unauthorized` - If a prioritised goal belongs to someone else
+    pub fn set_split_allocation(
+        env: Env,
+        caller: Address,
+        rule: AllocationRule,
+        priorities: Vec<u32>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        for goal_id in priorities.iter() {
+            let goal =
+                Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+            if goal.owner != caller {
+                Self::append_audit(&env, symbol_short!("split_cfg"), &caller, false);
+                return Err(SavingsGoalsError::Unauthorized);
+            }
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut allocations: Map<Address, SplitAllocation> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPLIT_ALC"))
+            .unwrap_or_else(|| Map::new(&env));
+        allocations.set(caller.clone(), SplitAllocation { rule, priorities });
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_ALC"), &allocations);
+        Self::append_audit(&env, symbol_short!("split_cfg"), &caller, true);
+        Ok(true)
+    }
+
+    /// Set the owner's goal priorities and switch split funding to waterfall
+    /// mode (`AllocationRule::Priority`).
+    ///
+    /// Goals left out of `ordering` come after it, in ID order.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If a listed goal does not exist
+    /// * `Unauthorized` - If a listed goal belongs to someone else
+    pub fn set_goal_priority(
+        env: Env,
+        owner: Address,
+        ordering: Vec<u32>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::set_split_allocation(env, owner, AllocationRule::Priority, ordering)
+    }
+
+    /// The order in which the owner's goals are funded in waterfall mode.
+    pub fn get_goal_priority(env: Env, owner: Address) -> Vec<u32> {
+        let mut ordering = Vec::new(&env);
+        for goal in Self::goals_by_priority(&env, &owner).iter() {
+            ordering.push_back(goal.id);
+        }
+        ordering
+    }
+
+    /// The owner's goals with prioritised ones first, each listed once.
+    fn goals_by_priority(env: &Env, owner: &Address) -> Vec<SavingsGoal> {
+        let allocation = Self::get_split_allocation(env.clone(), owner.clone());
+        let mut ordered: Vec<SavingsGoal> = Vec::new(env);
+        let mut seen: Vec<u32> = Vec::new(env);
+        for goal_id in allocation.priorities.iter() {
+            if seen.contains(goal_id) {
+                continue;
+            }
+            if let Some(goal) = Self::get_goal(env.clone(), goal_id) {
+                if goal.owner == *owner {
+                    seen.push_back(goal_id);
+                    ordered.push_back(goal);
+                }
+            }
+        }
+        for goal in Self::get_all_goals(env.clone(), owner.clone()).iter() {
+            if !seen.contains(goal.id) {
+                ordered.push_back(goal);
+            }
+        }
+        ordered
+    }
+
+    /// Get the caller's split allocation (proportional over all goals if unset).
+    pub fn get_split_allocation(env: Env, user: Address) -> SplitAllocation {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, SplitAllocation>>(&symbol_short!("SPLIT_ALC"))
+            .and_then(|allocations| allocations.get(user))
+            .unwrap_or(SplitAllocation {
+                rule: AllocationRule::Proportional,
+                priorities: Vec::new(&env),
+            })
+    }
+
+    /// Credit the savings leg of a remittance split to `user`'s goals.
+    ///
+    /// Only the configured remittance_split contract may call this. `amount`
+    /// of `token` is transferred from it into custody and spread using the
+    /// user's `SplitAllocation`; with `Priority`, anything left once every
+    /// goal is full goes to the top-priority goal.
+    ///
+    /// # Returns
+    /// The amount credited to each goal
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If no split contract is configured
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `GoalNotFound` - If the user has no goals
+    /// * `TokenMismatch` - If a goal to credit already holds a different token
+    pub fn credit_from_split(
+        env: Env,
+        user: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<Vec<ContributionItem>, SavingsGoalsError> {
+        let split_contract =
+            Self::get_split_contract(env.clone()).ok_or(SavingsGoalsError::Unauthorized)?;
+        split_contract.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+
+        if amount <= 0 {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+
+        let allocation = Self::get_split_allocation(env.clone(), user.clone());
+        let ordered = Self::goals_by_priority(&env, &user);
+        if ordered.is_empty() {
+            return Err(SavingsGoalsError::GoalNotFound);
+        }
+
+        let shares = match allocation.rule {
+            AllocationRule::Priority => Self::allocate_by_priority(&env, &ordered, amount),
+            AllocationRule::Proportional => Self::allocate_proportionally(&env, &ordered, amount),
+        };
+
+        let mut custody = Self::load_custody(&env);
+        for (goal, share) in ordered.iter().zip(shares.iter()) {
+            let held_token = custody.get(goal.id).map(|held| held.token);
+            if share > 0 && held_token.is_some_and(|held| held != token) {
+                return Err(SavingsGoalsError::TokenMismatch);
+            }
+        }
+        TokenClient::new(&env, &token).transfer(
+            &split_contract,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        Self::extend_instance_ttl(&env);
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut credited: Map<u32, i128> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SPLIT_CRD"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut items = Vec::new(&env);
+        for (mut goal, share) in ordered.iter().zip(shares.iter()) {
+            if share <= 0 {
+                continue;
+            }
+            let mut held = custody.get(goal.id).unwrap_or(GoalCustody {
+                token: token.clone(),
+                balance: 0,
+            });
+            held.balance = held
+                .balance
+                .checked_add(share)
+                .ok_or(SavingsGoalsError::Overflow)?;
+            custody.set(goal.id, held);
+            Self::credit_goal(
+                &env,
+                &mut goals,
+                &mut goal,
+                &user,
+                share,
+                symbol_short!("split_crd"),
+            )?;
+            credited.set(goal.id, credited.get(goal.id).unwrap_or(0) + share);
+            items.push_back(ContributionItem {
+                goal_id: goal.id,
+                amount: share,
+            });
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SPLIT_CRD"), &credited);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CUSTODY"), &custody);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::SplitCredited),
+            (user, amount, items.len()),
+        );
+        Ok(items)
+    }
+
+    /// Total credited to a goal through `credit_from_split`.
+    pub fn get_split_contributions(env: Env, goal_id: u32) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, i128>>(&symbol_short!("SPLIT_CRD"))
+            .and_then(|credited| credited.get(goal_id))
+            .unwrap_or(0)
+    }
+
+    fn allocate_by_priority(env: &Env, goals: &Vec<SavingsGoal>, amount: i128) -> Vec<i128> {
+        let mut shares = Vec::new(env);
+        let mut remaining = amount;
+        for goal in goals.iter() {
+            let need = (goal.target_amount - goal.current_amount).max(0);
+            let take = need.min(remaining);
+            shares.push_back(take);
+            remaining -= take;
+        }
+        if remaining > 0 {
+            let first = shares.get(0).unwrap_or(0);
+            shares.set(0, first + remaining);
+        }
+        shares
+    }
+
+    fn allocate_proportionally(env: &Env, goals: &Vec<SavingsGoal>, amount: i128) -> Vec<i128> {
+        // Weight by what is still needed; once everything is full, by target.
+        let mut weights = Vec::new(env);
+        let mut total = 0i128;
+        for goal in goals.iter() {
+            let need = (goal.target_amount - goal.current_amount).max(0);
+            weights.push_back(need);
+            total += need;
+        }
+        if total == 0 {
+            weights = Vec::new(env);
+            for goal in goals.iter() {
+                weights.push_back(goal.target_amount);
+                total += goal.target_amount;
+            }
+        }
+
+        let mut shares = Vec::new(env);
+        let mut assigned = 0i128;
+        let mut largest = 0u32;
+        for (index, weight) in weights.iter().enumerate() {
+            let share = amount * weight / total;
+            shares.push_back(share);
+            assigned += share;
+            if weight > weights.get(largest).unwrap_or(0) {
+                largest = index as u32;
+            }
+        }
+        // Rounding dust goes to the goal with the largest weight.
+        let dust = amount - assigned;
+        shares.set(largest, shares.get(largest).unwrap_or(0) + dust);
+        shares
+    }
+
+    // -----------------------------------------------------------------------
+    // PAGINATED LIST QUERIES
+    // -----------------------------------------------------------------------
+
+    /// Get a page of savings goals for `owner`.
+    ///
+    /// # Arguments
+    /// * `owner`  – whose goals to return
+    /// * `cursor` – start after this goal ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// * `include_completed` – when false, goals at or above target are skipped
+    ///
+    /// # Returns
+    /// `GoalPage { items, next_cursor, count }`.
+    /// `next_cursor == 0` means no more pages.
+    pub fn get_goals(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+        include_completed: bool,
+    ) -> GoalPage {
+        let limit = Self::clamp_limit(limit);
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let mut collected: u32 = 0;
+
+        // Walk the owner's index rather than every goal in the contract.
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            if id <= cursor {
+                continue;
+            }
+            let goal = match goals.get(id) {
+                Some(goal) => goal,
+                None => continue,
+            };
+            if !include_completed && goal.current_amount >= goal.target_amount {
+                continue;
+            }
+            if collected < limit {
+                result.push_back(goal);
+                collected += 1;
+                next_cursor = id; // track last returned ID
+            } else {
+                break;
+            }
+        }
+
+        // If we didn't fill the page, there are no more items
+        if collected < limit {
+            next_cursor = 0;
+        }
+
+        GoalPage {
+            items: result,
+            next_cursor,
+            count: collected,
+        }
+    }
+
+    /// Totals over all of `owner`'s goals, so callers such as the reporting
+    /// contract don't have to fetch and iterate every goal.
+    pub fn get_goal_totals(env: Env, owner: Address) -> GoalTotals {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let milestones = Self::load_milestones(&env);
+        let positions = Self::load_yield_positions(&env);
+
+        let mut totals = GoalTotals {
+            goal_count: 0,
+            completed_count: 0,
+            total_target: 0,
+            total_saved: 0,
+            milestones_reached: 0,
+            milestone_unlocked: 0,
+            yield_earned: 0,
+            by_tag: Vec::new(&env),
+        };
+        for id in Self::owner_goal_ids(&env, &owner).iter() {
+            let goal = match goals.get(id) {
+                Some(goal) => goal,
+                None => continue,
+            };
+            totals.goal_count += 1;
+            totals.total_target += goal.target_amount;
+            totals.total_saved += goal.current_amount;
+            if goal.current_amount >= goal.target_amount {
+                totals.completed_count += 1;
+            }
+            if let Some(entry) = milestones.get(id) {
+                for milestone in entry.milestones.iter() {
+                    if milestone.reached_at.is_some() {
+                        totals.milestones_reached += 1;
+                        totals.milestone_unlocked += milestone.unlock_amount;
+                    }
+                }
+            }
+            if let Some(position) = positions.get(id) {
+                totals.yield_earned += position.harvested;
+            }
+            if let Some(tag) = goal.tag {
+                let position = totals.by_tag.iter().position(|p| p.tag == tag);
+                let mut progress = match position {
+                    Some(index) => totals.by_tag.get(index as u32).unwrap(),
+                    None => TagProgress {
+                        tag,
+                        goals: 0,
+                        total_target: 0,
+                        total_saved: 0,
+                    },
+                };
+                progress.goals += 1;
+                progress.total_target += goal.target_amount;
+                progress.total_saved += goal.current_amount;
+                match position {
+                    Some(index) => totals.by_tag.set(index as u32, progress),
+                    None => totals.by_tag.push_back(progress),
+                }
+            }
+        }
+        totals
+    }
+
+    /// Backward-compatible: returns ALL goals for owner in one Vec.
+    /// Prefer the paginated `get_goals` for production use.
+    pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for (_, goal) in goals.iter() {
+            if goal.owner == owner {
+                result.push_back(goal);
+            }
+        }
+        result
+    }
+
+    /// Returns the owner's goals carrying `tag`.
+    pub fn get_goals_by_tag(env: Env, owner: Address, tag: Symbol) -> Vec<SavingsGoal> {
+        let mut result = Vec::new(&env);
+        for goal in Self::get_all_goals(env.clone(), owner).iter() {
+            if goal.tag.as_ref() == Some(&tag) {
+                result.push_back(goal);
+            }
+        }
+        result
+    }
+
+    pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
+        let storage = env.storage().instance();
+        let goals: Map<u32, SavingsGoal> = storage
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or(Map::new(&env));
+        if let Some(goal) = goals.get(goal_id) {
+            goal.current_amount >= goal.target_amount
+        } else {
+            false
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Snapshot, audit, schedule
+    // -----------------------------------------------------------------------
+
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address))
+            .unwrap_or(0)
+    }
+
+    pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
+        Self::require_caller(&env, &caller);
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32);
+        let mut list = Vec::new(&env);
+        for i in 1..=next_id {
+            if let Some(g) = goals.get(i) {
+                list.push_back(g);
+            }
+        }
+        let checksum = Self::compute_goals_checksum(SNAPSHOT_VERSION, next_id, &list);
+        GoalsExportSnapshot {
+            version: SNAPSHOT_VERSION,
+            checksum,
+            next_id,
+            goals: list,
+        }
+    }
+
+    pub fn import_snapshot(
+        env: Env,
+        caller: Address,
+        nonce: u64,
+        snapshot: GoalsExportSnapshot,
+    ) -> bool {
+        Self::require_caller(&env, &caller);
+        Self::require_nonce(&env, &caller, nonce);
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Unsupported snapshot version");
+        }
+        let expected =
+            Self::compute_goals_checksum(snapshot.version, snapshot.next_id, &snapshot.goals);
+        if snapshot.checksum != expected {
+            Self::append_audit(&env, symbol_short!("import"), &caller, false);
+            panic!("Snapshot checksum mismatch");
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
+        let mut owner_goal_ids: Map<Address, Vec<u32>> = Map::new(&env);
+        for g in snapshot.goals.iter() {
+            goals.set(g.id, g.clone());
+            let mut ids = owner_goal_ids
+                .get(g.owner.clone())
+                .unwrap_or_else(|| Vec::new(&env));
+            ids.push_back(g.id);
+            owner_goal_ids.set(g.owner.clone(), ids);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+
+        Self::increment_nonce(&env, &caller);
+        Self::append_audit(&env, symbol_short!("import"), &caller, true);
+        true
+    }
+
+    pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
+        let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
+        let log = log.unwrap_or_else(|| Vec::new(&env));
+        let len = log.len();
+        let cap = MAX_AUDIT_ENTRIES.min(limit);
+        let mut out = Vec::new(&env);
+        if from_index >= len {
+            return out;
+        }
+        let end = (from_index + cap).min(len);
+        for i in from_index..end {
+            if let Some(entry) = log.get(i) {
+                out.push_back(entry);
+            }
+        }
+        out
+    }
+
+    fn require_nonce(env: &Env, address: &Address, expected: u64) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        if expected != current {
+            panic!("Invalid nonce: expected {}, got {}", current, expected);
+        }
+    }
+
+    fn increment_nonce(env: &Env, address: &Address) {
+        let current = Self::get_nonce(env.clone(), address.clone());
+        let next = current.checked_add(1).expect("nonce overflow");
+        let mut nonces: Map<Address, u64> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NONCES"))
+            .unwrap_or_else(|| Map::new(env));
+        nonces.set(address.clone(), next);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NONCES"), &nonces);
+    }
+
+    fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
+        let mut c = version as u64 + next_id as u64;
+        for i in 0..goals.len() {
+            if let Some(g) = goals.get(i) {
+                c = c
+                    .wrapping_add(g.id as u64)
+                    .wrapping_add(g.target_amount as u64)
+                    .wrapping_add(g.current_amount as u64);
+            }
+        }
+        c.wrapping_mul(31)
+    }
+
+    fn append_audit(env: &Env, operation: Symbol, caller: &Address, success: bool) {
+        let timestamp = env.ledger().timestamp();
+        let mut log: Vec<AuditEntry> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("AUDIT"))
+            .unwrap_or_else(|| Vec::new(env));
+        if log.len() >= MAX_AUDIT_ENTRIES {
+            let mut new_log = Vec::new(env);
+            for i in 1..log.len() {
+                if let Some(entry) = log.get(i) {
+                    new_log.push_back(entry);
+                }
+            }
+            log = new_log;
+        }
+        log.push_back(AuditEntry {
+            operation,
+            caller: caller.clone(),
+            timestamp,
+            success,
+        });
+        env.storage().instance().set(&symbol_short!("AUDIT"), &log);
+    }
+
+    fn get_owner_goal_ids_map(env: &Env) -> Option<Map<Address, Vec<u32>>> {
+        env.storage().instance().get(&Self::STORAGE_OWNER_GOAL_IDS)
+    }
+
+    fn load_custody(env: &Env) -> Map<u32, GoalCustody> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CUSTODY"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn is_time_locked(env: &Env, goal: &SavingsGoal) -> bool {
+        goal.unlock_date
+            .is_some_and(|unlock_date| env.ledger().timestamp() < unlock_date)
+    }
+
+    /// Owner or family head, and lock checks shared by every withdrawal path.
+    /// The family head only approves; callers pay out to `goal.owner`.
+    ///
+    /// A manually locked goal only releases what its reached milestones have
+    /// unlocked, and nothing while it is also time-locked. A time-locked goal
+    /// that is not manually locked allows penalised early withdrawals (when
+    /// `allow_early` is set and a penalty policy exists).
+    fn ensure_withdrawable(
+        env: &Env,
+        goal: &SavingsGoal,
+        caller: &Address,
+        amount: i128,
+        allow_early: bool,
+    ) -> Result<WithdrawalMode, SavingsGoalsError> {
+        let is_family_head = Self::get_shared_goal(env.clone(), goal.id)
+            .and_then(|shared| shared.family_head)
+            .is_some_and(|head| head == *caller);
+        if goal.owner != *caller && !is_family_head {
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+        let time_locked = Self::is_time_locked(env, goal);
+        if goal.locked {
+            if !time_locked && amount <= Self::get_milestone_withdrawable(env.clone(), goal.id) {
+                return Ok(WithdrawalMode::Milestone);
+            }
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        if time_locked {
+            let penalised = Self::get_early_withdrawal_policy(env.clone())
+                .is_some_and(|policy| policy.penalty_bps > 0);
+            if allow_early && penalised {
+                return Ok(WithdrawalMode::Early);
+            }
+            return Err(SavingsGoalsError::GoalLocked);
+        }
+        Ok(WithdrawalMode::Unlocked)
+    }
+
+    fn load_milestones(env: &Env) -> Map<u32, GoalMilestones> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("MILESTONE"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Mark milestones reached by the goal's current amount.
+    fn update_milestones(env: &Env, goal: &SavingsGoal) {
+        let mut all = Self::load_milestones(env);
+        let mut entry = match all.get(goal.id) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let mut changed = false;
+        let mut updated = Vec::new(env);
+        for mut milestone in entry.milestones.iter() {
+            let threshold = goal.target_amount * milestone.percent as i128 / 100;
+            if milestone.reached_at.is_none() && goal.current_amount >= threshold {
+                milestone.reached_at = Some(env.ledger().timestamp());
+                changed = true;
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::MilestoneReached),
+                    (goal.id, milestone.percent, milestone.unlock_amount),
+                );
+            }
+            updated.push_back(milestone);
+        }
+        if changed {
+            entry.milestones = updated;
+            all.set(goal.id, entry);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("MILESTONE"), &all);
+        }
+    }
+
+    fn record_milestone_withdrawal(env: &Env, goal_id: u32, amount: i128) {
+        let mut all = Self::load_milestones(env);
+        if let Some(mut entry) = all.get(goal_id) {
+            entry.withdrawn += amount;
+            all.set(goal_id, entry);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("MILESTONE"), &all);
+        }
+    }
+
+    fn owner_goal_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        Self::get_owner_goal_ids_map(env)
+            .and_then(|index| index.get(owner.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn remove_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_OWNER_GOAL_IDS)
+            .unwrap_or_else(|| Map::new(env));
+        let mut ids = owner_goal_ids
+            .get(owner.clone())
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(index) = ids.first_index_of(goal_id) {
+            ids.remove(index);
+        }
+        owner_goal_ids.set(owner.clone(), ids);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+    }
+
+    /// Move a goal between owners in the index, keeping each list sorted so
+    /// cursor pagination stays correct.
+    fn move_owner_goal_id(env: &Env, from: &Address, to: &Address, goal_id: u32) {
+        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_OWNER_GOAL_IDS)
+            .unwrap_or_else(|| Map::new(env));
+        let mut from_ids = owner_goal_ids
+            .get(from.clone())
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(index) = from_ids.first_index_of(goal_id) {
+            from_ids.remove(index);
+        }
+        owner_goal_ids.set(from.clone(), from_ids);
+
+        let mut to_ids = owner_goal_ids
+            .get(to.clone())
+            .unwrap_or_else(|| Vec::new(env));
+        let position = to_ids
+            .iter()
+            .position(|id| id > goal_id)
+            .map_or(to_ids.len(), |index| index as u32);
+        to_ids.insert(position, goal_id);
+        owner_goal_ids.set(to.clone(), to_ids);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+    }
+
+    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_OWNER_GOAL_IDS)
+            .unwrap_or_else(|| Map::new(env));
+        let mut ids = owner_goal_ids
+            .get(owner.clone())
+            .unwrap_or_else(|| Vec::new(env));
+        ids.push_back(goal_id);
+        owner_goal_ids.set(owner.clone(), ids);
+        env.storage()
+            .instance()
+            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+    }
+
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Set or clear the goal's tag (category).
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    pub fn set_goal_tag(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        tag: Option<Symbol>,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut goal = goals.get(goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("tag"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        goal.tag = tag;
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("tag"), &caller, true);
+        Ok(true)
+    }
+
+    /// Set time-lock on a goal
+    pub fn set_time_lock(env: Env, caller: Address, goal_id: u32, unlock_date: u64) -> bool {
+        Self::require_caller(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goal = match goals.get(goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+                panic!("Goal not found");
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            panic!("Only the goal owner can set time-lock");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if unlock_date <= current_time {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            panic!("Unlock date must be in the future");
+        }
+
+        if goal
+            .unlock_date
+            .is_some_and(|existing| unlock_date < existing)
+            && Self::is_time_locked(&env, &goal)
+        {
+            Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
+            panic!("Cannot shorten an active time-lock");
+        }
+
+        goal.unlock_date = Some(unlock_date);
+        goals.set(goal_id, goal);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
+        true
+    }
+
+    pub fn create_savings_schedule(
+        env: Env,
+        owner: Address,
+        goal_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> u32 {
+        Self::require_caller(&env, &owner);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let goal = goals.get(goal_id).expect("Goal not found");
+
+        if goal.owner != owner {
+            panic!("Only the goal owner can create schedules");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            panic!("Next due date must be in the future");
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let next_schedule_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_SSCH"))
+            .unwrap_or(0u32)
+            + 1;
+
+        let schedule = SavingsSchedule {
+            id: next_schedule_id,
+            owner: owner.clone(),
+            goal_id,
+            amount,
+            next_due,
+            interval,
+            recurring: interval > 0,
+            active: true,
+            created_at: current_time,
+            last_executed: None,
+            missed_count: 0,
+        };
+
+        schedules.set(next_schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
+            (next_schedule_id, owner),
+        );
+
+        next_schedule_id
+    }
+
+    pub fn modify_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> bool {
+        Self::require_caller(&env, &caller);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if next_due <= current_time {
+            panic!("Next due date must be in the future");
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+
+        if schedule.owner != caller {
+            panic!("Only the schedule owner can modify it");
+        }
+
+        schedule.amount = amount;
+        schedule.next_due = next_due;
+        schedule.interval = interval;
+        schedule.recurring = interval > 0;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleModified),
+            (schedule_id, caller),
+        );
+
+        true
+    }
+
+    pub fn cancel_savings_schedule(env: Env, caller: Address, schedule_id: u32) -> bool {
+        Self::require_caller(&env, &caller);
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+
+        if schedule.owner != caller {
+            panic!("Only the schedule owner can cancel it");
+        }
+
+        schedule.active = false;
+
+        schedules.set(schedule_id, schedule);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
             (schedule_id, caller),
         );
 
-        true
+        true
+    }
+
+    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
+        Self::extend_instance_ttl(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut executed = Vec::new(&env);
+
+        let mut schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut goals: Map<u32, SavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        for (schedule_id, mut schedule) in schedules.iter() {
+            if !schedule.active || schedule.next_due > current_time {
+                continue;
+            }
+
+            if let Some(mut goal) = goals.get(schedule.goal_id) {
+                goal.current_amount = goal
+                    .current_amount
+                    .checked_add(schedule.amount)
+                    .expect("overflow");
+
+                let is_completed = goal.current_amount >= goal.target_amount;
+                goals.set(schedule.goal_id, goal.clone());
+                Self::update_milestones(&env, &goal);
+                Self::record_contribution(
+                    &env,
+                    schedule.goal_id,
+                    &goal.owner,
+                    &goal.owner,
+                    schedule.amount,
+                    symbol_short!("schedule"),
+                );
+
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
+                );
+
+                if is_completed {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+                        (schedule.goal_id, goal.owner.clone()),
+                    );
+                    Self::reward_completion(&env, &goal);
+                }
+            }
+
+            schedule.last_executed = Some(current_time);
+
+            if schedule.recurring && schedule.interval > 0 {
+                let mut missed = 0u32;
+                let mut next = schedule.next_due + schedule.interval;
+                while next <= current_time {
+                    missed += 1;
+                    next += schedule.interval;
+                }
+                schedule.missed_count += missed;
+                schedule.next_due = next;
+
+                if missed > 0 {
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
+                        (schedule_id, missed),
+                    );
+                }
+            } else {
+                schedule.active = false;
+            }
+
+            schedules.set(schedule_id, schedule);
+            executed.push_back(schedule_id);
+
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
+                schedule_id,
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_SCH"), &schedules);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("GOALS"), &goals);
+
+        executed
+    }
+
+    /// Contribute `amount` of the goal's custody token every `interval_days`,
+    /// starting one interval from now. An `amount` of 0 removes the schedule.
+    ///
+    /// The owner must approve this contract to spend the token; see
+    /// `apply_due_contributions`.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount < 0, or interval_days is 0
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `TokenMismatch` - If the goal has no custody token yet (`deposit` first)
+    pub fn set_contribution_schedule(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        amount: i128,
+        interval_days: u32,
+    ) -> Result<bool, SavingsGoalsError> {
+        Self::require_caller(&env, &caller);
+        if amount < 0 || (amount > 0 && interval_days == 0) {
+            return Err(SavingsGoalsError::InvalidAmount);
+        }
+        let goal = Self::get_goal(env.clone(), goal_id).ok_or(SavingsGoalsError::GoalNotFound)?;
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("cont_sch"), &caller, false);
+            return Err(SavingsGoalsError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut schedules = Self::load_contribution_schedules(&env);
+        if amount == 0 {
+            schedules.remove(goal_id);
+        } else {
+            if Self::get_goal_custody(env.clone(), goal_id).is_none() {
+                return Err(SavingsGoalsError::TokenMismatch);
+            }
+            let interval = interval_days as u64 * 86400;
+            schedules.set(
+                goal_id,
+                ContributionSchedule {
+                    goal_id,
+                    amount,
+                    interval,
+                    next_due: env.ledger().timestamp() + interval,
+                    last_applied: None,
+                    applied_count: 0,
+                    missed_count: 0,
+                },
+            );
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONT_SCH"), &schedules);
+
+        Self::append_audit(&env, symbol_short!("cont_sch"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_contribution_schedule(env: Env, goal_id: u32) -> Option<ContributionSchedule> {
+        Self::load_contribution_schedules(&env).get(goal_id)
+    }
+
+    /// Apply every due contribution schedule on `owner`'s goals.
+    ///
+    /// Permissionless. Each due schedule pulls its amount from the owner into
+    /// custody with `transfer_from`; if the owner's balance or allowance is
+    /// short, the contribution is recorded as missed instead. Either way the
+    /// schedule moves to its next due date after now.
+    ///
+    /// # Returns
+    /// IDs of the goals that received a contribution
+    pub fn apply_due_contributions(env: Env, owner: Address) -> Vec<u32> {
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        let contract = env.current_contract_address();
+        let mut schedules = Self::load_contribution_schedules(&env);
+        let mut applied = Vec::new(&env);
+
+        for goal_id in Self::owner_goal_ids(&env, &owner).iter() {
+            let mut schedule = match schedules.get(goal_id) {
+                Some(schedule) if schedule.next_due <= now => schedule,
+                _ => continue,
+            };
+            while schedule.next_due <= now {
+                schedule.next_due += schedule.interval;
+            }
+
+            let mut custody = Self::load_custody(&env);
+            let held = custody.get(goal_id);
+            let funded = held.as_ref().is_some_and(|held| {
+                let token = TokenClient::new(&env, &held.token);
+                token.balance(&owner) >= schedule.amount
+                    && token.allowance(&owner, &contract) >= schedule.amount
+            });
+            let mut held = match held {
+                Some(held) if funded => held,
+                _ => {
+                    schedule.missed_count += 1;
+                    schedules.set(goal_id, schedule.clone());
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::ContributionMissed),
+                        (goal_id, owner.clone(), schedule.amount),
+                    );
+                    continue;
+                }
+            };
+
+            let token = held.token.clone();
+            TokenClient::new(&env, &token).transfer_from(
+                &contract,
+                &owner,
+                &contract,
+                &schedule.amount,
+            );
+            held.balance += schedule.amount;
+            custody.set(goal_id, held);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("CUSTODY"), &custody);
+
+            let mut goals: Map<u32, SavingsGoal> = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("GOALS"))
+                .unwrap_or_else(|| Map::new(&env));
+            if let Some(mut goal) = goals.get(goal_id) {
+                if let Err(err) = Self::credit_goal(
+                    &env,
+                    &mut goals,
+                    &mut goal,
+                    &owner,
+                    schedule.amount,
+                    symbol_short!("cont_app"),
+                ) {
+                    env.panic_with_error(err);
+                }
+                Self::apply_match(&env, &mut goals, &mut goal, &owner, &token, schedule.amount);
+            }
+
+            schedule.last_applied = Some(now);
+            schedule.applied_count += 1;
+            schedules.set(goal_id, schedule.clone());
+            applied.push_back(goal_id);
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::ContributionApplied),
+                (goal_id, owner.clone(), schedule.amount),
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("CONT_SCH"), &schedules);
+        applied
+    }
+
+    fn load_contribution_schedules(env: &Env) -> Map<u32, ContributionSchedule> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("CONT_SCH"))
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (_, schedule) in schedules.iter() {
+            if schedule.owner == owner {
+                result.push_back(schedule);
+            }
+        }
+        result
+    }
+
+    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
+        let schedules: Map<u32, SavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("SAV_SCH"))
+            .unwrap_or_else(|| Map::new(&env));
+        schedules.get(schedule_id)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, token::TokenClient, Env, String};
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    fn setup_goals(env: &Env, client: &SavingsGoalContractClient, owner: &Address, count: u32) {
+        for i in 0..count {
+            client.create_goal(
+                owner,
+                &String::from_str(env, "Goal"),
+                &(1000i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+            );
+        }
+    }
+
+    // --- get_goals ---
+
+    #[test]
+    fn test_get_goals_empty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let page = client.get_goals(&owner, &0, &0, &true);
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+        assert_eq!(page.items.len(), 0);
+    }
+
+    #[test]
+    fn test_get_goals_single_page() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 5);
+
+        let page = client.get_goals(&owner, &0, &10, &true);
+        assert_eq!(page.count, 5);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_goals_multiple_pages() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 9);
+
+        // Page 1
+        let page1 = client.get_goals(&owner, &0, &4, &true);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        // Page 2
+        let page2 = client.get_goals(&owner, &page1.next_cursor, &4, &true);
+        assert_eq!(page2.count, 4);
+        assert!(page2.next_cursor > 0);
+
+        // Page 3 (last)
+        let page3 = client.get_goals(&owner, &page2.next_cursor, &4, &true);
+        assert_eq!(page3.count, 1);
+        assert_eq!(page3.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_goals_multi_owner_isolation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner_a, 3);
+        setup_goals(&env, &client, &owner_b, 4);
+
+        let page_a = client.get_goals(&owner_a, &0, &20, &true);
+        assert_eq!(page_a.count, 3);
+        for g in page_a.items.iter() {
+            assert_eq!(g.owner, owner_a);
+        }
+
+        let page_b = client.get_goals(&owner_b, &0, &20, &true);
+        assert_eq!(page_b.count, 4);
+    }
+
+    #[test]
+    fn test_get_goals_cursor_is_exclusive() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 4);
+
+        let first = client.get_goals(&owner, &0, &2, &true);
+        assert_eq!(first.count, 2);
+        let last_id = first.items.get(1).unwrap().id;
+
+        // cursor should be exclusive — next page should NOT include `last_id`
+        let second = client.get_goals(&owner, &last_id, &2, &true);
+        for g in second.items.iter() {
+            assert!(g.id > last_id, "cursor should be exclusive");
+        }
+    }
+
+    #[test]
+    fn test_limit_zero_uses_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3);
+        let page = client.get_goals(&owner, &0, &0, &true);
+        assert_eq!(page.count, 3); // 3 < DEFAULT_PAGE_LIMIT so all returned
+    }
+
+    #[test]
+    fn test_get_goals_excludes_completed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 4);
+        client.add_to_goal(&owner, &1, &1000);
+        client.add_to_goal(&owner, &3, &3000);
+
+        let page = client.get_goals(&owner, &0, &10, &false);
+        assert_eq!(page.count, 2);
+        assert_eq!(page.items.get(0).unwrap().id, 2);
+        assert_eq!(page.items.get(1).unwrap().id, 4);
+
+        let first = client.get_goals(&owner, &0, &1, &false);
+        assert_eq!(first.items.get(0).unwrap().id, 2);
+        let second = client.get_goals(&owner, &first.next_cursor, &1, &false);
+        assert_eq!(second.items.get(0).unwrap().id, 4);
+    }
+
+    #[test]
+    fn test_get_goal_totals() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 3); // targets 1000, 2000, 3000
+        setup_goals(&env, &client, &other, 1);
+        client.set_goal_milestones(&owner, &2, &quarter_milestones(&env));
+        client.set_goal_tag(&owner, &1, &Some(symbol_short!("housing")));
+        client.set_goal_tag(&owner, &3, &Some(symbol_short!("housing")));
+        client.add_to_goal(&owner, &1, &1000);
+        client.add_to_goal(&owner, &2, &1000);
+        client.add_to_goal(&other, &4, &500);
+
+        let totals = client.get_goal_totals(&owner);
+        assert_eq!(totals.goal_count, 3);
+        assert_eq!(totals.completed_count, 1);
+        assert_eq!(totals.total_target, 6000);
+        assert_eq!(totals.total_saved, 2000);
+        assert_eq!(totals.milestones_reached, 2);
+        assert_eq!(totals.milestone_unlocked, 500);
+        assert_eq!(totals.by_tag.len(), 1);
+        let housing = totals.by_tag.get(0).unwrap();
+        assert_eq!(housing.goals, 2);
+        assert_eq!(housing.total_target, 4000);
+        assert_eq!(housing.total_saved, 1000);
+    }
+
+    // --- token custody ---
+
+    fn setup_token(env: &Env, holder: &Address, amount: i128) -> Address {
+        let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+        soroban_sdk::token::StellarAssetClient::new(env, &token.address()).mint(holder, &amount);
+        token.address()
+    }
+
+    #[test]
+    fn test_deposit_and_withdraw_move_tokens() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert_eq!(client.deposit(&owner, &1, &token, &600), 600);
+        assert_eq!(balances.balance(&owner), 400);
+        assert_eq!(balances.balance(&id), 600);
+
+        // Goals start locked
+        assert!(client.try_withdraw(&owner, &1, &100).is_err());
+
+        client.unlock_goal(&owner, &1);
+        assert_eq!(client.withdraw(&owner, &1, &250), 350);
+        assert_eq!(balances.balance(&owner), 650);
+
+        let custody = client.get_goal_custody(&1).unwrap();
+        assert_eq!(custody.token, token);
+        assert_eq!(custody.balance, 350);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 350);
+    }
+
+    #[test]
+    fn test_custody_reconciles_with_bookkeeping() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let other_token = setup_token(&env, &owner, 1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &500);
+        client.add_to_goal(&owner, &1, &200);
+        client.unlock_goal(&owner, &1);
+
+        // A goal holds one token
+        assert!(client.try_deposit(&owner, &1, &other_token, &10).is_err());
+        // Custodied funds can't leave through the bookkeeping-only path
+        assert!(client.try_withdraw_from_goal(&owner, &1, &201).is_err());
+        assert_eq!(client.withdraw_from_goal(&owner, &1, &200), 500);
+        // Nor can more than is in custody be released
+        assert!(client.try_withdraw(&owner, &1, &501).is_err());
+        assert_eq!(client.withdraw(&owner, &1, &500), 0);
+    }
+
+    // --- time locks and early withdrawal ---
+
+    fn set_time(env: &Env, timestamp: u64) {
+        use soroban_sdk::testutils::Ledger;
+        env.ledger().with_mut(|li| li.timestamp = timestamp);
+    }
+
+    #[test]
+    #[should_panic(expected = "Goal is time-locked")]
+    fn test_unlock_goal_refused_during_time_lock() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.set_time_lock(&owner, &1, &10_000);
+        client.unlock_goal(&owner, &1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot shorten an active time-lock")]
+    fn test_time_lock_cannot_be_shortened() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.set_time_lock(&owner, &1, &10_000);
+        client.set_time_lock(&owner, &1, &20_000);
+        client.set_time_lock(&owner, &1, &5_000);
+    }
+
+    #[test]
+    fn test_early_withdrawal_pays_penalty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &1000);
+        // The goal must be unlocked before the time-lock starts; unlocking
+        // is refused once it is active.
+        client.unlock_goal(&owner, &1);
+        client.set_time_lock(&owner, &1, &10_000);
+
+        // Without a penalty policy the time lock simply blocks withdrawals
+        assert!(client.try_withdraw(&owner, &1, &100).is_err());
+
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_early_withdrawal_policy(&admin, &1000, &treasury); // 10%
+        assert_eq!(client.withdraw(&owner, &1, &400), 600);
+        assert_eq!(balances.balance(&treasury), 40);
+        assert_eq!(balances.balance(&owner), 360);
+
+        // After the unlock date no penalty applies
+        set_time(&env, 10_000);
+        client.withdraw(&owner, &1, &100);
+        assert_eq!(balances.balance(&treasury), 40);
+        assert_eq!(balances.balance(&owner), 460);
+    }
+
+    #[test]
+    fn test_manual_lock_blocks_early_withdrawal_during_time_lock() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+
+        setup_goals(&env, &client, &owner, 1); // locked by default
+        client.deposit(&owner, &1, &token, &1000);
+        client.set_time_lock(&owner, &1, &10_000);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_early_withdrawal_policy(&admin, &1000, &treasury);
+
+        let goal = client.get_goal(&1).unwrap();
+        assert!(goal.locked);
+        let mode = env.as_contract(&id, || {
+            SavingsGoalContract::ensure_withdrawable(&env, &goal, &owner, 100, true)
+        });
+        assert!(matches!(mode, Err(SavingsGoalsError::GoalLocked)));
+        assert!(client.try_withdraw(&owner, &1, &100).is_err());
+        assert_eq!(TokenClient::new(&env, &token).balance(&treasury), 0);
+    }
+
+    #[test]
+    fn test_early_withdrawal_policy_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let admin = Address::generate(&env);
+        let other = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        client.set_upgrade_admin(&admin, &admin);
+        let set = |caller: &Address, penalty_bps: u32| {
+            env.as_contract(&id, || {
+                SavingsGoalContract::set_early_withdrawal_policy(
+                    env.clone(),
+                    caller.clone(),
+                    penalty_bps,
+                    treasury.clone(),
+                )
+            })
+        };
+        assert!(matches!(
+            set(&other, 1000),
+            Err(SavingsGoalsError::Unauthorized)
+        ));
+        assert!(matches!(
+            set(&admin, 10_001),
+            Err(SavingsGoalsError::InvalidAmount)
+        ));
+        assert!(client.get_early_withdrawal_policy().is_none());
+
+        assert!(set(&admin, 1000).is_ok());
+        assert_eq!(
+            client.get_early_withdrawal_policy().unwrap().penalty_bps,
+            1000
+        );
+    }
+
+    // --- milestones ---
+
+    fn quarter_milestones(env: &Env) -> soroban_sdk::Vec<MilestoneConfig> {
+        let mut milestones = soroban_sdk::Vec::new(env);
+        for (percent, unlock_bps) in [(25u32, 1000u32), (50, 1500), (75, 2500)] {
+            milestones.push_back(MilestoneConfig {
+                percent,
+                unlock_bps,
+            });
+        }
+        milestones
+    }
+
+    #[test]
+    fn test_milestones_unlock_portions_of_locked_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1); // target 1000, locked
+        client.set_goal_milestones(&owner, &1, &quarter_milestones(&env));
+        assert_eq!(client.get_milestone_withdrawable(&1), 0);
+
+        client.add_to_goal(&owner, &1, &300);
+        let milestones = client.get_goal_milestones(&1);
+        assert!(milestones.get(0).unwrap().reached_at.is_some());
+        assert!(milestones.get(1).unwrap().reached_at.is_none());
+        assert_eq!(client.get_milestone_withdrawable(&1), 100);
+
+        client.add_to_goal(&owner, &1, &250); // 55%: +150 unlocked
+        assert_eq!(client.get_milestone_withdrawable(&1), 250);
+
+        assert!(client.try_withdraw_from_goal(&owner, &1, &251).is_err());
+        assert_eq!(client.withdraw_from_goal(&owner, &1, &200), 350);
+        assert_eq!(client.get_milestone_withdrawable(&1), 50);
+
+        // Falling back below a threshold keeps reached milestones reached
+        assert!(client
+            .get_goal_milestones(&1)
+            .get(1)
+            .unwrap()
+            .reached_at
+            .is_some());
+    }
+
+    #[test]
+    fn test_set_goal_milestones_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert!(client
+            .try_set_goal_milestones(&other, &1, &quarter_milestones(&env))
+            .is_err());
+
+        let mut unordered = quarter_milestones(&env);
+        unordered.push_back(MilestoneConfig {
+            percent: 50,
+            unlock_bps: 0,
+        });
+        assert!(client
+            .try_set_goal_milestones(&owner, &1, &unordered)
+            .is_err());
+
+        let mut too_generous = soroban_sdk::Vec::new(&env);
+        too_generous.push_back(MilestoneConfig {
+            percent: 50,
+            unlock_bps: 10_001,
+        });
+        assert!(client
+            .try_set_goal_milestones(&owner, &1, &too_generous)
+            .is_err());
+
+        // Milestones already covered are reached immediately
+        client.add_to_goal(&owner, &1, &600);
+        client.set_goal_milestones(&owner, &1, &quarter_milestones(&env));
+        assert_eq!(client.get_milestone_withdrawable(&1), 250);
+    }
+
+    // --- remittance split hook ---
+
+    fn setup_split_hook(env: &Env, client: &SavingsGoalContractClient) -> Address {
+        let admin = Address::generate(env);
+        let split_contract = Address::generate(env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_split_contract(&admin, &split_contract);
+        split_contract
+    }
+
+    #[test]
+    fn test_credit_from_split_proportional_by_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let split = setup_split_hook(&env, &client);
+        let token = setup_token(&env, &split, 1001);
+
+        setup_goals(&env, &client, &owner, 2); // targets 1000 and 2000
+        client.add_to_goal(&owner, &2, &500); // needs 1000 and 1500
+
+        let items = client.credit_from_split(&owner, &token, &1001);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items.get(0).unwrap().amount, 400);
+        assert_eq!(items.get(1).unwrap().amount, 601);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 400);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 1101);
+        assert_eq!(client.get_split_contributions(&2), 601);
+
+        // The credited amounts are held in custody
+        let balances = TokenClient::new(&env, &token);
+        assert_eq!(balances.balance(&split), 0);
+        assert_eq!(balances.balance(&id), 1001);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 400);
+        assert_eq!(client.get_goal_custody(&2).unwrap().balance, 601);
+    }
+
+    #[test]
+    fn test_credit_from_split_priority_rule() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let split = setup_split_hook(&env, &client);
+        let token = setup_token(&env, &split, 3200);
+
+        setup_goals(&env, &client, &owner, 2); // targets 1000 and 2000
+        setup_goals(&env, &client, &other, 1);
+
+        let mut priorities = soroban_sdk::Vec::new(&env);
+        priorities.push_back(3u32);
+        assert!(client
+            .try_set_split_allocation(&owner, &AllocationRule::Priority, &priorities)
+            .is_err());
+
+        let mut priorities = soroban_sdk::Vec::new(&env);
+        priorities.push_back(2u32);
+        client.set_split_allocation(&owner, &AllocationRule::Priority, &priorities);
+
+        client.credit_from_split(&owner, &token, &2500);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 2000);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 500);
+
+        // Everything full: overflow goes to the top-priority goal
+        client.credit_from_split(&owner, &token, &700);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 1000);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 2200);
+    }
+
+    #[test]
+    fn test_goal_priority_waterfall() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let split = setup_split_hook(&env, &client);
+        let token = setup_token(&env, &split, 3500);
+
+        setup_goals(&env, &client, &owner, 3); // targets 1000, 2000, 3000
+        let mut ordering = soroban_sdk::Vec::new(&env);
+        ordering.push_back(3u32);
+        ordering.push_back(1u32);
+        ordering.push_back(3u32);
+        client.set_goal_priority(&owner, &ordering);
+
+        let expected = soroban_sdk::vec![&env, 3u32, 1u32, 2u32];
+        assert_eq!(client.get_goal_priority(&owner), expected);
+        assert_eq!(
+            client.get_split_allocation(&owner).rule,
+            AllocationRule::Priority
+        );
+
+        let items = client.credit_from_split(&owner, &token, &1500);
+        assert_eq!(items.len(), 1);
+        assert_eq!(client.get_goal(&3).unwrap().current_amount, 1500);
+
+        client.credit_from_split(&owner, &token, &2000);
+        assert_eq!(client.get_goal(&3).unwrap().current_amount, 3000);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 500);
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 0);
+    }
+
+    #[test]
+    fn test_credit_from_split_requires_split_contract() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        let token = setup_token(&env, &owner, 100);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert!(client.try_credit_from_split(&owner, &token, &100).is_err());
+
+        let split = setup_split_hook(&env, &client);
+        let stranger = Address::generate(&env);
+        assert!(client
+            .try_credit_from_split(&stranger, &token, &100)
+            .is_err());
+
+        // A goal already holding another token is not credited
+        client.deposit(&owner, &1, &token, &100);
+        let other_token = setup_token(&env, &split, 100);
+        let result = env.as_contract(&id, || {
+            SavingsGoalContract::credit_from_split(env.clone(), owner.clone(), other_token, 100)
+        });
+        assert!(matches!(result, Err(SavingsGoalsError::TokenMismatch)));
+    }
+
+    // --- shared family goals ---
+
+    #[test]
+    fn test_shared_goal_contributions_and_breakdown() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert!(client.try_add_to_goal(&sibling, &1, &100).is_err());
+
+        let mut contributors = soroban_sdk::Vec::new(&env);
+        contributors.push_back(sibling.clone());
+        assert!(client
+            .try_share_goal(&sibling, &1, &contributors, &None)
+            .is_err());
+        client.share_goal(&owner, &1, &contributors, &None);
+
+        client.add_to_goal(&owner, &1, &200);
+        client.add_to_goal(&sibling, &1, &150);
+        client.add_to_goal(&sibling, &1, &50);
+        assert!(client.try_add_to_goal(&stranger, &1, &10).is_err());
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 400);
+
+        let breakdown = client.get_goal_contributions(&1);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown.get(0).unwrap().contributor, owner);
+        assert_eq!(breakdown.get(0).unwrap().amount, 200);
+        assert_eq!(breakdown.get(1).unwrap().contributor, sibling);
+        assert_eq!(breakdown.get(1).unwrap().amount, 200);
+    }
+
+    #[test]
+    fn test_shared_goal_withdrawal_by_owner_or_family_head() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let head = Address::generate(&env);
+        let token = setup_token(&env, &sibling, 1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        let mut contributors = soroban_sdk::Vec::new(&env);
+        contributors.push_back(sibling.clone());
+        client.share_goal(&owner, &1, &contributors, &Some(head.clone()));
+
+        client.deposit(&sibling, &1, &token, &600);
+        client.unlock_goal(&owner, &1);
+
+        assert!(client.try_withdraw(&sibling, &1, &100).is_err());
+        // The head approves, but the owner is paid
+        let balances = TokenClient::new(&env, &token);
+        assert_eq!(client.withdraw(&head, &1, &100), 500);
+        assert_eq!(balances.balance(&head), 0);
+        assert_eq!(balances.balance(&owner), 100);
+        assert_eq!(client.withdraw(&owner, &1, &100), 400);
+        assert_eq!(balances.balance(&owner), 200);
+    }
+
+    // --- yield on idle balances ---
+
+    mod yield_pool {
+        use soroban_sdk::{contract, contractimpl, token::TokenClient, Address, Env, Map};
+
+        /// Book-keeps deposits per (owner, token); `accrue` simulates interest.
+        #[contract]
+        pub struct MockYieldPool;
+
+        #[contractimpl]
+        impl MockYieldPool {
+            pub fn deposit(env: Env, from: Address, token: Address, amount: i128) {
+                TokenClient::new(&env, &token).transfer(
+                    &from,
+                    &env.current_contract_address(),
+                    &amount,
+                );
+                Self::add(&env, from, token, amount);
+            }
+
+            pub fn withdraw(env: Env, to: Address, token: Address, amount: i128) {
+                Self::add(&env, to.clone(), token.clone(), -amount);
+                TokenClient::new(&env, &token).transfer(
+                    &env.current_contract_address(),
+                    &to,
+                    &amount,
+                );
+            }
+
+            pub fn balance(env: Env, owner: Address, token: Address) -> i128 {
+                Self::positions(&env).get((owner, token)).unwrap_or(0)
+            }
+
+            pub fn accrue(env: Env, owner: Address, token: Address, amount: i128) {
+                Self::add(&env, owner, token, amount);
+            }
+
+            fn positions(env: &Env) -> Map<(Address, Address), i128> {
+                env.storage()
+                    .instance()
+                    .get(&0u32)
+                    .unwrap_or_else(|| Map::new(env))
+            }
+
+            fn add(env: &Env, owner: Address, token: Address, amount: i128) {
+                let mut positions = Self::positions(env);
+                let key = (owner, token);
+                positions.set(key.clone(), positions.get(key).unwrap_or(0) + amount);
+                env.storage().instance().set(&0u32, &positions);
+            }
+        }
+    }
+
+    #[test]
+    fn test_yield_deposit_harvest_and_withdraw() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let pool_id = env.register_contract(None, yield_pool::MockYieldPool);
+        let pool = yield_pool::MockYieldPoolClient::new(&env, &pool_id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        client.set_upgrade_admin(&admin, &admin);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &1000);
+        assert!(client
+            .try_deposit_to_yield(&owner, &1, &pool_id, &800)
+            .is_err());
+
+        client.set_yield_pool(&admin, &pool_id, &true);
+        assert_eq!(client.deposit_to_yield(&owner, &1, &pool_id, &800), 800);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 200);
+        assert_eq!(TokenClient::new(&env, &token).balance(&pool_id), 800);
+        // Pooled funds can't leave through bookkeeping withdrawals either
+        client.unlock_goal(&owner, &1);
+        assert!(client.try_withdraw_from_goal(&owner, &1, &100).is_err());
+        client.lock_goal(&owner, &1);
+
+        // Pool earns 80 in interest
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&pool_id, &80);
+        pool.accrue(&id, &token, &80);
+
+        assert_eq!(client.harvest(&1), 80);
+        assert_eq!(client.harvest(&1), 0);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 1080);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 280);
+        assert_eq!(client.get_yield_earned(&1), 80);
+        // Yield is not attributed to any contributor
+        assert_eq!(
+            client.get_goal_contributions(&1).get(0).unwrap().amount,
+            1000
+        );
+
+        assert_eq!(client.withdraw_from_yield(&owner, &1), 800);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 1080);
+        assert_eq!(TokenClient::new(&env, &token).balance(&id), 1080);
+        assert_eq!(client.get_goal_yield(&1).unwrap().principal, 0);
+    }
+
+    #[test]
+    fn test_yield_requires_locked_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let pool_id = env.register_contract(None, yield_pool::MockYieldPool);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_yield_pool(&admin, &pool_id, &true);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &500);
+        client.unlock_goal(&owner, &1);
+        assert!(client
+            .try_deposit_to_yield(&owner, &1, &pool_id, &100)
+            .is_err());
+        client.lock_goal(&owner, &1);
+        assert!(client
+            .try_deposit_to_yield(&owner, &1, &pool_id, &600)
+            .is_err());
+    }
+
+    // --- completion rewards ---
+
+    mod rewards {
+        use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+        /// Counts completion notifications; panics once `set_failing` is on.
+        #[contract]
+        pub struct MockRewards;
+
+        #[contractimpl]
+        impl MockRewards {
+            pub fn on_goal_completed(env: Env, goal_id: u32, _owner: Address, _target: i128) {
+                if env.storage().instance().has(&symbol_short!("fail")) {
+                    panic!("rewards unavailable");
+                }
+                let count: u32 = env.storage().instance().get(&goal_id).unwrap_or(0);
+                env.storage().instance().set(&goal_id, &(count + 1));
+            }
+
+            pub fn set_failing(env: Env) {
+                env.storage().instance().set(&symbol_short!("fail"), &true);
+            }
+
+            pub fn count(env: Env, goal_id: u32) -> u32 {
+                env.storage().instance().get(&goal_id).unwrap_or(0)
+            }
+        }
+    }
+
+    #[test]
+    fn test_completion_rewarded_once() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let rewards_id = env.register_contract(None, rewards::MockRewards);
+        let rewards = rewards::MockRewardsClient::new(&env, &rewards_id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        client.set_rewards_contract(&admin, &Some(rewards_id.clone()));
+
+        setup_goals(&env, &client, &owner, 1); // target 1000
+        client.add_to_goal(&owner, &1, &600);
+        assert_eq!(rewards.count(&1), 0);
+        assert_eq!(client.get_goal_rewarded_at(&1), None);
+
+        set_time(&env, 5_000);
+        client.add_to_goal(&owner, &1, &400);
+        assert_eq!(rewards.count(&1), 1);
+        assert_eq!(client.get_goal_rewarded_at(&1), Some(5_000));
+
+        // Further contributions, and re-completing after a withdrawal, don't
+        // reward again.
+        client.add_to_goal(&owner, &1, &100);
+        client.unlock_goal(&owner, &1);
+        client.withdraw_from_goal(&owner, &1, &500);
+        client.add_to_goal(&owner, &1, &500);
+        assert_eq!(rewards.count(&1), 1);
+
+        // A failing rewards contract doesn't block contributions.
+        rewards.set_failing();
+        client.create_goal(
+            &owner,
+            &String::from_str(&env, "Goal"),
+            &1000,
+            &(env.ledger().timestamp() + 86400),
+        );
+        assert_eq!(client.add_to_goal(&owner, &2, &1000), 1000);
+        assert!(client.get_goal_rewarded_at(&2).is_some());
+    }
+
+    #[test]
+    fn test_completion_without_rewards_contract() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        set_time(&env, 7_000);
+        client.add_to_goal(&owner, &1, &1000);
+        assert_eq!(client.get_goal_rewarded_at(&1), Some(7_000));
+    }
+
+    // --- contribution schedules ---
+
+    #[test]
+    fn test_apply_due_contributions_moves_tokens() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        setup_goals(&env, &client, &owner, 1);
+        assert!(client
+            .try_set_contribution_schedule(&owner, &1, &100, &7)
+            .is_err());
+        client.deposit(&owner, &1, &token, &100);
+        client.set_contribution_schedule(&owner, &1, &100, &7);
+        balances.approve(&owner, &id, &150, &1_000);
+
+        assert_eq!(client.apply_due_contributions(&owner).len(), 0);
+
+        set_time(&env, 7 * 86400);
+        let applied = client.apply_due_contributions(&owner);
+        assert_eq!(applied, soroban_sdk::vec![&env, 1u32]);
+        assert_eq!(balances.balance(&owner), 800);
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 200);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 200);
+        // Not due again until next week
+        assert_eq!(client.apply_due_contributions(&owner).len(), 0);
+
+        // Only 50 of allowance left: recorded as missed, schedule advances
+        set_time(&env, 14 * 86400);
+        assert_eq!(client.apply_due_contributions(&owner).len(), 0);
+        let schedule = client.get_contribution_schedule(&1).unwrap();
+        assert_eq!(schedule.applied_count, 1);
+        assert_eq!(schedule.missed_count, 1);
+        assert_eq!(schedule.next_due, 21 * 86400);
+
+        client.set_contribution_schedule(&owner, &1, &0, &0);
+        assert!(client.get_contribution_schedule(&1).is_none());
+    }
+
+    // --- ownership transfer ---
+
+    #[test]
+    fn test_transfer_goal_requires_acceptance() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let migrant = Address::generate(&env);
+        let parent = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let token = setup_token(&env, &migrant, 1000);
+
+        setup_goals(&env, &client, &migrant, 2);
+        setup_goals(&env, &client, &parent, 2); // goals 3 and 4
+        client.deposit(&migrant, &1, &token, &400);
+        client.set_contribution_schedule(&migrant, &1, &100, &30);
+
+        assert!(client.try_transfer_goal(&stranger, &1, &parent).is_err());
+        client.transfer_goal(&migrant, &1, &parent);
+        assert_eq!(client.get_pending_goal_transfer(&1), Some(parent.clone()));
+        assert!(client.try_accept_goal_transfer(&stranger, &1).is_err());
+        // Nothing changes until the new owner accepts
+        assert_eq!(client.get_goal(&1).unwrap().owner, migrant);
+
+        client.accept_goal_transfer(&parent, &1);
+        let goal = client.get_goal(&1).unwrap();
+        assert_eq!(goal.owner, parent);
+        assert_eq!(goal.current_amount, 400);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 400);
+        assert_eq!(
+            client
+                .get_goal_contributions(&1)
+                .get(0)
+                .unwrap()
+                .contributor,
+            migrant
+        );
+        assert!(client.get_contribution_schedule(&1).is_none());
+        assert!(client.get_pending_goal_transfer(&1).is_none());
+
+        let parent_goals = client.get_goals(&parent, &0, &10, &true);
+        assert_eq!(parent_goals.count, 3);
+        assert_eq!(parent_goals.items.get(0).unwrap().id, 1);
+        assert_eq!(client.get_goals(&migrant, &0, &10, &true).count, 1);
+
+        // The old owner lost control; the new owner has it
+        assert!(client.try_add_to_goal(&migrant, &1, &10).is_err());
+        client.add_to_goal(&parent, &1, &10);
+    }
+
+    #[test]
+    fn test_cancel_goal_transfer() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.transfer_goal(&owner, &1, &other);
+        client.cancel_goal_transfer(&owner, &1);
+        assert!(client.try_accept_goal_transfer(&other, &1).is_err());
+        assert_eq!(client.get_goal(&1).unwrap().owner, owner);
     }
 
-    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
-        Self::extend_instance_ttl(&env);
-
-        let current_time = env.ledger().timestamp();
-        let mut executed = Vec::new(&env);
+    // --- archiving and contribution history ---
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    #[test]
+    fn test_archive_goal() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        setup_goals(&env, &client, &owner, 3); // targets 1000, 2000, 3000
+        client.add_to_goal(&owner, &1, &1000);
+        client.add_to_goal(&owner, &2, &500);
 
-        for (schedule_id, mut schedule) in schedules.iter() {
-            if !schedule.active || schedule.next_due > current_time {
-                continue;
-            }
+        assert!(client.try_archive_goal(&owner, &2).is_err());
+        client.archive_goal(&owner, &1);
+        client.archive_goal(&owner, &3); // empty: cancelled
 
-            if let Some(mut goal) = goals.get(schedule.goal_id) {
-                goal.current_amount = goal
-                    .current_amount
-                    .checked_add(schedule.amount)
-                    .expect("overflow");
+        let active = client.get_all_goals(&owner);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active.get(0).unwrap().id, 2);
+        assert!(client.get_goal(&1).is_none());
 
-                let is_completed = goal.current_amount >= goal.target_amount;
-                goals.set(schedule.goal_id, goal.clone());
+        let archived = client.get_archived_goals(&owner);
+        assert_eq!(archived.len(), 2);
+        assert!(archived.get(0).unwrap().completed);
+        assert!(!archived.get(1).unwrap().completed);
+        assert_eq!(client.get_contributions(&1, &10).len(), 1);
+    }
 
-                env.events().publish(
-                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
-                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
-                );
+    #[test]
+    fn test_archive_requires_empty_custody() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &1000);
+        assert!(client.try_archive_goal(&owner, &1).is_err());
+        client.unlock_goal(&owner, &1);
+        client.withdraw(&owner, &1, &1000);
+        client.archive_goal(&owner, &1);
+    }
 
-                if is_completed {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                        (schedule.goal_id, goal.owner),
-                    );
-                }
-            }
+    #[test]
+    fn test_get_contributions_history() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
 
-            schedule.last_executed = Some(current_time);
+        setup_goals(&env, &client, &owner, 1);
+        for i in 1..=5 {
+            set_time(&env, i * 100);
+            client.add_to_goal(&owner, &1, &(i as i128 * 10));
+        }
 
-            if schedule.recurring && schedule.interval > 0 {
-                let mut missed = 0u32;
-                let mut next = schedule.next_due + schedule.interval;
-                while next <= current_time {
-                    missed += 1;
-                    next += schedule.interval;
-                }
-                schedule.missed_count += missed;
-                schedule.next_due = next;
+        let recent = client.get_contributions(&1, &2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent.get(0).unwrap().amount, 40);
+        assert_eq!(recent.get(1).unwrap().amount, 50);
+        assert_eq!(recent.get(1).unwrap().timestamp, 500);
+        assert_eq!(recent.get(1).unwrap().operation, symbol_short!("add"));
+        assert_eq!(client.get_contributions(&1, &0).len(), 5);
+    }
 
-                if missed > 0 {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
-                        (schedule_id, missed),
-                    );
-                }
-            } else {
-                schedule.active = false;
-            }
+    // --- deadline reminders ---
 
-            schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+    #[test]
+    fn test_check_goal_deadlines() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let name = String::from_str(&env, "Goal");
 
-            env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
-                schedule_id,
-            );
+        // Created at t=0, each due after 100 days
+        let due = 100 * 86400u64;
+        for _ in 0..4 {
+            client.create_goal(&owner, &name, &1000, &due);
         }
+        client.add_to_goal(&owner, &1, &1000); // complete: never alerted
+        client.add_to_goal(&owner, &2, &600);
+        client.add_to_goal(&owner, &3, &300);
+
+        // Day 50: goal 3 (30%) and 4 (0%) lag the 50% of time elapsed
+        set_time(&env, 50 * 86400);
+        let alerts = client.check_goal_deadlines(&owner);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts.get(0).unwrap().goal_id, 3);
+        assert_eq!(alerts.get(0).unwrap().status, DeadlineStatus::AtRisk);
+        assert_eq!(alerts.get(0).unwrap().progress_bps, 3000);
+
+        // Day 95: goal 2 is on track but its deadline is close
+        client.add_to_goal(&owner, &2, &360);
+        set_time(&env, 95 * 86400);
+        let alerts = client.check_goal_deadlines(&owner);
+        let first = alerts.get(0).unwrap();
+        assert_eq!(first.goal_id, 2);
+        assert_eq!(first.status, DeadlineStatus::DeadlineApproaching);
+        assert_eq!(first.seconds_remaining, 5 * 86400);
+
+        // Past the date: everything unfinished is missed
+        set_time(&env, 101 * 86400);
+        let alerts = client.check_goal_deadlines(&owner);
+        assert_eq!(alerts.len(), 3);
+        for alert in alerts.iter() {
+            assert_eq!(alert.status, DeadlineStatus::Missed);
+        }
+    }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+    // --- emergency withdrawals ---
 
-        executed
+    #[test]
+    fn test_emergency_withdraw_with_guardian() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let reason = String::from_str(&env, "Medical bill");
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &800);
+        client.set_time_lock(&owner, &1, &(env.ledger().timestamp() + 86400 * 365));
+        assert!(client.try_withdraw(&owner, &1, &100).is_err());
+        // No guardian designated yet
+        assert!(client
+            .try_emergency_withdraw(&owner, &1, &300, &reason)
+            .is_err());
+
+        client.set_goal_guardian(&owner, &1, &Some(guardian.clone()));
+        assert!(client
+            .try_emergency_withdraw(&guardian, &1, &300, &reason)
+            .is_err());
+        assert_eq!(client.emergency_withdraw(&owner, &1, &300, &reason), 500);
+        assert_eq!(TokenClient::new(&env, &token).balance(&owner), 500);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 500);
+
+        let records = client.get_emergency_withdrawals(&1);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records.get(0).unwrap().guardian, guardian);
+        assert_eq!(records.get(0).unwrap().reason, reason);
     }
 
-    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    #[test]
+    fn test_emergency_withdraw_requires_both_signatures() {
+        use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+        use soroban_sdk::IntoVal;
 
-        let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
-                result.push_back(schedule);
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let reason = String::from_str(&env, "Rent");
+
+        setup_goals(&env, &client, &owner, 1);
+        client.add_to_goal(&owner, &1, &500);
+        client.set_goal_guardian(&owner, &1, &Some(guardian.clone()));
+
+        // Only the owner signs: the guardian's auth is missing
+        let args = (owner.clone(), 1u32, 100i128, reason.clone()).into_val(&env);
+        env.mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &id,
+                fn_name: "emergency_withdraw",
+                args,
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(client
+            .try_emergency_withdraw(&owner, &1, &100, &reason)
+            .is_err());
+        assert_eq!(client.get_goal(&1).unwrap().current_amount, 500);
+    }
+
+    // --- multi-asset goals ---
+
+    mod oracle {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        #[contract]
+        pub struct MockOracle;
+
+        #[contractimpl]
+        impl MockOracle {
+            pub fn set_rate(env: Env, base: Address, quote: Address, rate: i128) {
+                env.storage().instance().set(&(base, quote), &rate);
+            }
+
+            pub fn rate(env: Env, base: Address, quote: Address) -> i128 {
+                env.storage()
+                    .instance()
+                    .get(&(base, quote))
+                    .expect("no rate")
             }
         }
-        result
     }
 
-    pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-        schedules.get(schedule_id)
+    #[test]
+    fn test_multi_asset_goal_valued_by_oracle() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let oracle_id = env.register_contract(None, oracle::MockOracle);
+        let oracle = oracle::MockOracleClient::new(&env, &oracle_id);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let kes = Address::generate(&env);
+        let usdc = setup_token(&env, &owner, 100);
+        let eurc = setup_token(&env, &owner, 100);
+        client.set_upgrade_admin(&admin, &admin);
+
+        // Target: 5000 KES
+        client.create_goal(&owner, &String::from_str(&env, "Rent"), &5000, &86400);
+        assert!(client.try_deposit_asset(&owner, &1, &usdc, &10).is_err());
+        client.set_valuation_asset(&owner, &1, &kes);
+        assert!(client.try_deposit_asset(&owner, &1, &usdc, &10).is_err());
+
+        client.set_price_oracle(&admin, &oracle_id);
+        oracle.set_rate(&usdc, &kes, &(130 * RATE_SCALE));
+        oracle.set_rate(&eurc, &kes, &(140 * RATE_SCALE));
+        assert_eq!(client.deposit_asset(&owner, &1, &usdc, &10), 1300);
+        assert_eq!(client.deposit_asset(&owner, &1, &eurc, &10), 2700);
+
+        // USDC appreciates: progress follows once revalued
+        oracle.set_rate(&usdc, &kes, &(150 * RATE_SCALE));
+        assert_eq!(client.revalue_goal(&1), 2900);
+        let holdings = client.get_goal_holdings(&1);
+        assert_eq!(holdings.len(), 2);
+
+        client.unlock_goal(&owner, &1);
+        assert_eq!(client.withdraw_asset(&owner, &1, &usdc, &4), 2300);
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 94);
+        assert!(client.try_withdraw_asset(&owner, &1, &usdc, &7).is_err());
+        assert!(client.try_withdraw_from_goal(&owner, &1, &100).is_err());
+        assert!(client.try_archive_goal(&owner, &1).is_err());
     }
-}
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env, String};
+    // --- goal edits ---
 
-    fn make_env() -> Env {
-        Env::default()
+    #[test]
+    fn test_update_goal_records_change_log() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let new_name = String::from_str(&env, "School fees");
+
+        setup_goals(&env, &client, &owner, 1); // target 1000
+        client.add_to_goal(&owner, &1, &400);
+
+        // Goals start locked
+        assert!(client
+            .try_update_goal(&owner, &1, &2000, &5000, &new_name)
+            .is_err());
+        client.unlock_goal(&owner, &1);
+        assert!(client
+            .try_update_goal(&other, &1, &2000, &5000, &new_name)
+            .is_err());
+        assert!(client
+            .try_update_goal(&owner, &1, &300, &5000, &new_name)
+            .is_err());
+
+        set_time(&env, 1_000);
+        client.update_goal(&owner, &1, &2000, &5000, &new_name);
+        let goal = client.get_goal(&1).unwrap();
+        assert_eq!(goal.target_amount, 2000);
+        assert_eq!(goal.target_date, 5000);
+        assert_eq!(goal.name, new_name);
+        assert_eq!(goal.current_amount, 400);
+
+        let edits = client.get_goal_edits(&1);
+        assert_eq!(edits.len(), 1);
+        let edit = edits.get(0).unwrap();
+        assert_eq!(edit.editor, owner);
+        assert_eq!(edit.old_target, 1000);
+        assert_eq!(edit.new_target, 2000);
+        assert_eq!(edit.old_name, String::from_str(&env, "Goal"));
+        assert_eq!(edit.timestamp, 1_000);
     }
 
-    fn setup_goals(env: &Env, client: &SavingsGoalContractClient, owner: &Address, count: u32) {
-        for i in 0..count {
-            client.create_goal(
-                owner,
-                &String::from_str(env, "Goal"),
-                &(1000i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-            );
-        }
+    #[test]
+    fn test_period_contributions() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+
+        setup_goals(&env, &client, &owner, 2);
+        set_time(&env, 100);
+        client.add_to_goal(&owner, &1, &1000);
+        set_time(&env, 200);
+        client.add_to_goal(&owner, &2, &300);
+        set_time(&env, 300);
+        client.add_to_goal(&owner, &2, &200);
+        client.archive_goal(&owner, &1);
+
+        assert_eq!(client.get_goal_period_contributions(&2, &150, &300), 500);
+        assert_eq!(client.get_goal_period_contributions(&2, &250, &1000), 200);
+        assert_eq!(client.get_period_contributions(&owner, &0, &200), 1300);
+        assert_eq!(client.get_period_contributions(&owner, &301, &400), 0);
     }
 
-    // --- get_goals ---
+    // --- sponsor matching ---
 
     #[test]
-    fn test_get_goals_empty() {
+    fn test_sponsor_match_pulls_until_cap() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
         let owner = Address::generate(&env);
-
-        let page = client.get_goals(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
-        assert_eq!(page.items.len(), 0);
+        let sponsor = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sponsor, &1000);
+        let balances = TokenClient::new(&env, &token);
+        balances.approve(&sponsor, &id, &1000, &1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.set_goal_match(&sponsor, &1, &5000, &150);
+
+        assert_eq!(client.deposit(&owner, &1, &token, &200), 300);
+        assert_eq!(client.deposit(&owner, &1, &token, &200), 550);
+        assert_eq!(client.get_matched_amount(&1), 150);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 550);
+        assert_eq!(balances.balance(&sponsor), 850);
+
+        let history = client.get_contributions(&1, &10);
+        assert_eq!(history.len(), 4);
+        let matched = history.get(3).unwrap();
+        assert_eq!(matched.contributor, sponsor);
+        assert_eq!(matched.operation, symbol_short!("match"));
+        assert_eq!(matched.amount, 50);
     }
 
     #[test]
-    fn test_get_goals_single_page() {
+    fn test_sponsor_match_limited_by_allowance_and_owner_only() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
         let owner = Address::generate(&env);
-
-        setup_goals(&env, &client, &owner, 5);
-
-        let page = client.get_goals(&owner, &0, &10);
-        assert_eq!(page.count, 5);
-        assert_eq!(page.next_cursor, 0);
+        let sponsor = Address::generate(&env);
+        let other = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sponsor, &1000);
+        TokenClient::new(&env, &token).approve(&sponsor, &id, &40, &1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.set_goal_match(&sponsor, &1, &10_000, &500);
+        assert!(client
+            .try_set_goal_match(&other, &1, &10_000, &500)
+            .is_err());
+        assert!(client
+            .try_set_goal_match(&sponsor, &1, &10_000, &0)
+            .is_err());
+
+        assert_eq!(client.deposit(&owner, &1, &token, &100), 140);
+        assert_eq!(client.deposit(&owner, &1, &token, &100), 240);
+        assert_eq!(client.get_matched_amount(&1), 40);
+
+        client.set_goal_match(&sponsor, &1, &0, &0);
+        assert!(client.get_goal_match(&1).is_none());
+        client.set_goal_match(&other, &1, &10_000, &500);
+        assert_eq!(client.get_goal_match(&1).unwrap().sponsor, other);
     }
 
+    // --- inactivity beneficiary ---
+
     #[test]
-    fn test_get_goals_multiple_pages() {
+    fn test_inactivity_claim_hands_goal_to_beneficiary() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
         let owner = Address::generate(&env);
+        let heir = Address::generate(&env);
 
-        setup_goals(&env, &client, &owner, 9);
+        set_time(&env, 1000);
+        setup_goals(&env, &client, &owner, 1);
+        client.set_inactivity_beneficiary(&owner, &1, &Some(heir.clone()), &30);
+        assert_eq!(client.get_last_activity(&owner), Some(1000));
 
-        // Page 1
-        let page1 = client.get_goals(&owner, &0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
+        set_time(&env, 1000 + 29 * 86400);
+        assert!(client.try_start_inactivity_claim(&heir, &1).is_err());
 
-        // Page 2
-        let page2 = client.get_goals(&owner, &page1.next_cursor, &4);
-        assert_eq!(page2.count, 4);
-        assert!(page2.next_cursor > 0);
+        set_time(&env, 1000 + 30 * 86400);
+        let claimable_at = client.start_inactivity_claim(&heir, &1);
+        assert!(client.try_finalize_inactivity_claim(&heir, &1).is_err());
 
-        // Page 3 (last)
-        let page3 = client.get_goals(&owner, &page2.next_cursor, &4);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
+        set_time(&env, claimable_at);
+        client.finalize_inactivity_claim(&heir, &1);
+        assert_eq!(client.get_goal(&1).unwrap().owner, heir);
+        assert!(client.get_inactivity_plan(&1).is_none());
+        assert_eq!(client.get_all_goals(&owner).len(), 0);
     }
 
     #[test]
-    fn test_get_goals_multi_owner_isolation() {
+    fn test_owner_activity_defeats_inactivity_claim() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        setup_goals(&env, &client, &owner_a, 3);
-        setup_goals(&env, &client, &owner_b, 4);
+        let owner = Address::generate(&env);
+        let heir = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        set_time(&env, 1000);
+        setup_goals(&env, &client, &owner, 1);
+        client.set_inactivity_beneficiary(&owner, &1, &Some(heir.clone()), &30);
+        set_time(&env, 1000 + 30 * 86400);
+        assert!(client.try_start_inactivity_claim(&stranger, &1).is_err());
+        client.start_inactivity_claim(&heir, &1);
+
+        set_time(&env, 1000 + 31 * 86400);
+        client.add_to_goal(&owner, &1, &10);
+
+        set_time(&env, 1000 + 40 * 86400);
+        assert!(client.try_finalize_inactivity_claim(&heir, &1).is_err());
+        assert_eq!(client.get_goal(&1).unwrap().owner, owner);
+    }
 
-        let page_a = client.get_goals(&owner_a, &0, &20);
-        assert_eq!(page_a.count, 3);
-        for g in page_a.items.iter() {
-            assert_eq!(g.owner, owner_a);
-        }
+    // --- goal cancellation ---
 
-        let page_b = client.get_goals(&owner_b, &0, &20);
-        assert_eq!(page_b.count, 4);
+    #[test]
+    fn test_cancel_goal_refunds_owner_and_archives() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let balances = TokenClient::new(&env, &token);
+
+        setup_goals(&env, &client, &owner, 1);
+        client.deposit(&owner, &1, &token, &600);
+        let reason = String::from_str(&env, "Moved abroad");
+        assert!(client
+            .try_cancel_goal(&owner, &1, &RefundTarget::Owner, &reason)
+            .is_err());
+
+        client.unlock_goal(&owner, &1);
+        assert_eq!(
+            client.cancel_goal(&owner, &1, &RefundTarget::Owner, &reason),
+            600
+        );
+        assert_eq!(balances.balance(&owner), 1000);
+        assert!(client.get_goal(&1).is_none());
+        assert!(!client.get_archived_goals(&owner).get(0).unwrap().completed);
+
+        let cancellation = client.get_goal_cancellation(&1).unwrap();
+        assert_eq!(cancellation.reason, reason);
+        assert_eq!(cancellation.amount, 600);
+        assert_eq!(cancellation.tokens, 600);
     }
 
     #[test]
-    fn test_get_goals_cursor_is_exclusive() {
+    fn test_cancel_goal_into_another_goal() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
         let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+        let reason = String::from_str(&env, "Merge");
+
+        setup_goals(&env, &client, &owner, 2);
+        client.deposit(&owner, &1, &token, &300);
+        client.add_to_goal(&owner, &1, &50);
+        client.unlock_goal(&owner, &1);
+        assert!(client
+            .try_cancel_goal(&owner, &1, &RefundTarget::Goal(1), &reason)
+            .is_err());
+        assert!(client
+            .try_cancel_goal(&owner, &1, &RefundTarget::FamilyWallet, &reason)
+            .is_err());
+
+        assert_eq!(
+            client.cancel_goal(&owner, &1, &RefundTarget::Goal(2), &reason),
+            350
+        );
+        assert_eq!(client.get_goal(&2).unwrap().current_amount, 350);
+        assert_eq!(client.get_goal_custody(&2).unwrap().balance, 300);
+        assert!(client.get_goal_custody(&1).is_none());
+        let history = client.get_contributions(&2, &10);
+        assert_eq!(history.get(0).unwrap().operation, symbol_short!("cancel"));
+    }
 
-        setup_goals(&env, &client, &owner, 4);
+    // --- savings streak ---
 
-        let first = client.get_goals(&owner, &0, &2);
-        assert_eq!(first.count, 2);
-        let last_id = first.items.get(1).unwrap().id;
+    #[test]
+    fn test_savings_streak_counts_consecutive_periods() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let relative = Address::generate(&env);
+        let week = 7 * 86400;
+
+        setup_goals(&env, &client, &owner, 2);
+        client.share_goal(&owner, &1, &vec![&env, relative.clone()], &None);
+        client.add_to_goal(&owner, &1, &10);
+        set_time(&env, week);
+        client.add_to_goal(&owner, &2, &10);
+        client.add_to_goal(&owner, &1, &10);
+        set_time(&env, 2 * week);
+        client.add_to_goal(&relative, &1, &10);
+
+        let streak = client.get_savings_streak(&owner);
+        assert_eq!(streak.current_weeks, 2);
+        assert_eq!(streak.current_months, 1);
+
+        set_time(&env, 3 * week);
+        client.add_to_goal(&owner, &1, &10);
+        let streak = client.get_savings_streak(&owner);
+        assert_eq!(streak.current_weeks, 1);
+        assert_eq!(streak.longest_weeks, 2);
+
+        set_time(&env, 5 * week);
+        assert_eq!(client.get_savings_streak(&owner).current_weeks, 0);
+        assert_eq!(client.get_savings_streak(&owner).current_months, 1);
+        assert_eq!(client.get_savings_streak(&relative).longest_weeks, 0);
+    }
 
-        // cursor should be exclusive — next page should NOT include `last_id`
-        let second = client.get_goals(&owner, &last_id, &2);
-        for g in second.items.iter() {
-            assert!(g.id > last_id, "cursor should be exclusive");
+    // --- bulk operations ---
+
+    #[test]
+    fn test_create_goals_and_deposit_many() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+
+        let mut inputs = Vec::new(&env);
+        for target in [500i128, 800, 1200] {
+            inputs.push_back(GoalInput {
+                name: String::from_str(&env, "Bulk"),
+                target_amount: target,
+                target_date: 86400,
+            });
         }
+        let ids = client.create_goals(&owner, &inputs);
+        assert_eq!(ids, vec![&env, 1, 2, 3]);
+        assert_eq!(client.get_all_goals(&owner).len(), 3);
+
+        let deposits = vec![
+            &env,
+            ContributionItem {
+                goal_id: 1,
+                amount: 100,
+            },
+            ContributionItem {
+                goal_id: 3,
+                amount: 250,
+            },
+            ContributionItem {
+                goal_id: 1,
+                amount: 50,
+            },
+        ];
+        assert_eq!(
+            client.deposit_many(&owner, &token, &deposits),
+            vec![&env, 100, 250, 150]
+        );
+        assert_eq!(TokenClient::new(&env, &token).balance(&owner), 600);
+        assert_eq!(client.get_goal_custody(&1).unwrap().balance, 150);
+        assert_eq!(client.get_goal_custody(&3).unwrap().balance, 250);
     }
 
     #[test]
-    fn test_limit_zero_uses_default() {
+    fn test_deposit_many_rejects_whole_batch() {
+        let env = make_env();
+        env.mock_all_auths();
+        let id = env.register_contract(None, SavingsGoalContract);
+        let client = SavingsGoalContractClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1000);
+
+        setup_goals(&env, &client, &owner, 1);
+        setup_goals(&env, &client, &stranger, 1);
+        let deposits = vec![
+            &env,
+            ContributionItem {
+                goal_id: 1,
+                amount: 100,
+            },
+            ContributionItem {
+                goal_id: 2,
+                amount: 100,
+            },
+        ];
+        assert!(client.try_deposit_many(&owner, &token, &deposits).is_err());
+        assert_eq!(TokenClient::new(&env, &token).balance(&owner), 1000);
+        assert!(client.get_goal_custody(&1).is_none());
+    }
+
+    // --- goal tags ---
+
+    #[test]
+    fn test_get_goals_by_tag() {
         let env = make_env();
         env.mock_all_auths();
         let id = env.register_contract(None, SavingsGoalContract);
         let client = SavingsGoalContractClient::new(&env, &id);
         let owner = Address::generate(&env);
+        let other = Address::generate(&env);
 
         setup_goals(&env, &client, &owner, 3);
-        let page = client.get_goals(&owner, &0, &0);
-        assert_eq!(page.count, 3); // 3 < DEFAULT_PAGE_LIMIT so all returned
+        setup_goals(&env, &client, &other, 1);
+        let education = symbol_short!("education");
+        client.set_goal_tag(&owner, &1, &Some(education.clone()));
+        client.set_goal_tag(&owner, &3, &Some(education.clone()));
+        client.set_goal_tag(&owner, &2, &Some(symbol_short!("emergency")));
+        client.set_goal_tag(&other, &4, &Some(education.clone()));
+
+        let tagged = client.get_goals_by_tag(&owner, &education);
+        assert_eq!(tagged.len(), 2);
+        assert_eq!(tagged.get(0).unwrap().id, 1);
+        assert_eq!(tagged.get(1).unwrap().id, 3);
+
+        client.set_goal_tag(&owner, &3, &None);
+        assert_eq!(client.get_goals_by_tag(&owner, &education).len(), 1);
+        assert!(client
+            .try_set_goal_tag(&other, &1, &Some(symbol_short!("housing")))
+            .is_err());
     }
 
     #[test]
@@ -77,9 +77,9 @@ invoke "$SAVINGS_GOALS_ID" create_goal --owner "$OWNER_ADDRESS" --name "Home Ren
 # ---- Bill Payments: deterministic bills ----
 echo "Creating bills (deterministic)..."
 # Due dates: unix timestamps (e.g. 1735689600 = 2025-01-01), amounts in stroops/smallest unit
-invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "Electricity"    --amount 150000000 --due_date 1735689600 --recurring true  --frequency_days 30
-invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "School Fees"    --amount 500000000 --due_date 1738368000 --recurring false --frequency_days 0
-invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "Internet"       --amount 75000000  --due_date 1735689600 --recurring true  --frequency_days 30
+invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "Electricity"    --payee "$OWNER_ADDRESS" --amount 150000000 --due_date 1735689600 --recurring true  --frequency_days 30 --currency "XLM" --category utilities
+invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "School Fees"    --payee "$OWNER_ADDRESS" --amount 500000000 --due_date 1738368000 --recurring false --frequency_days 0 --currency "XLM" --category school
+invoke "$BILL_PAYMENTS_ID" create_bill --owner "$OWNER_ADDRESS" --name "Internet"       --payee "$OWNER_ADDRESS" --amount 75000000  --due_date 1735689600 --recurring true  --frequency_days 30 --currency "XLM" --category utilities
 
 # ---- Insurance: deterministic policies ----
 echo "Creating insurance policies (deterministic)..."